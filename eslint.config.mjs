@@ -7,6 +7,6 @@ export default ts.config(
   ts.configs['recommended'],
   vue.configs['flat/recommended'],
   {
-    ignores: ['dist/', 'pkg/'],
+    ignores: ['dist/', 'pkg/', 'pkg-node/'],
   },
 )
@@ -0,0 +1,83 @@
+/*
+ * C interface of strust, built with the `capi` feature. See `wasm/capi.rs` for the details of
+ * each function.
+ *
+ * Models and sections are opaque handles released by the matching `_free`. Functions return a
+ * strust_status, and copy arrays into buffers of the caller after writing the number of values to
+ * `required`; a null or short buffer returns STRUST_BUFFER_TOO_SMALL without copying. A panic
+ * inside the library returns STRUST_PANICKED instead of unwinding into the caller.
+ */
+#ifndef STRUST_H
+#define STRUST_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Define STRUST_F32 for a library built with the `f32` feature. */
+#ifdef STRUST_F32
+typedef float strust_float;
+#else
+typedef double strust_float;
+#endif
+
+typedef enum strust_status {
+    STRUST_OK = 0,
+    STRUST_NULL_POINTER = 1,
+    STRUST_INVALID_NODE = 2,
+    STRUST_INVALID_MEMBER = 3,
+    STRUST_ZERO_LENGTH = 4,
+    STRUST_UNSTABLE = 5,
+    STRUST_NOT_SOLVED = 6,
+    STRUST_BUFFER_TOO_SMALL = 7,
+    STRUST_CANCELLED = 8,
+    STRUST_PANICKED = 9,
+} strust_status;
+
+typedef struct StrustModel StrustModel;
+typedef struct StrustSection StrustSection;
+
+const char *strust_status_message(strust_status status);
+
+StrustModel *strust_model_new(void);
+void strust_model_free(StrustModel *model);
+strust_status strust_model_add_node(StrustModel *model, strust_float x, strust_float y,
+                                    size_t *index);
+strust_status strust_model_add_member(StrustModel *model, size_t a, size_t b,
+                                      strust_float elasticity, strust_float area,
+                                      strust_float inertia, size_t *index);
+strust_status strust_model_add_support(StrustModel *model, size_t node, bool x, bool y,
+                                       bool rotation);
+strust_status strust_model_add_load(StrustModel *model, size_t node, strust_float fx,
+                                    strust_float fy, strust_float moment);
+strust_status strust_model_add_member_load(StrustModel *model, size_t member, strust_float wx,
+                                           strust_float wy);
+strust_status strust_model_solve(StrustModel *model);
+/* Three values per node. */
+strust_status strust_model_displacements(const StrustModel *model, strust_float *buffer,
+                                         size_t len, size_t *required);
+/* Three values per node. */
+strust_status strust_model_reactions(const StrustModel *model, strust_float *buffer, size_t len,
+                                     size_t *required);
+/* Six values per member, in its local axes. */
+strust_status strust_model_member_forces(const StrustModel *model, strust_float *buffer,
+                                         size_t len, size_t *required);
+
+StrustSection *strust_section_new(void);
+void strust_section_free(StrustSection *section);
+strust_status strust_section_add_rectangle(StrustSection *section, strust_float width,
+                                           strust_float height, strust_float x, strust_float y,
+                                           strust_float angle);
+strust_status strust_section_add_circle(StrustSection *section, strust_float radius,
+                                        strust_float x, strust_float y);
+/* Area, centroid x and y, moments of inertia about x and y, product of inertia. */
+strust_status strust_section_properties(const StrustSection *section, strust_float *properties);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
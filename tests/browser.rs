@@ -0,0 +1,7 @@
+//! The wasm API in a page, with `wasm-pack test --chrome --headless --test browser`.
+
+mod common;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+common::suite!();
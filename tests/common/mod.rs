@@ -0,0 +1,71 @@
+//! Checks of the wasm API shared by the suites of each JavaScript environment, which differ only
+//! in their `wasm_bindgen_test_configure!`. Besides the wasm memory, the API reads only the
+//! `console` and `performance` globals, common to pages, workers and Node.js.
+
+/// Defines the tests of the suite in the calling crate.
+macro_rules! suite {
+    () => {
+        use strust::api::{Model, Section};
+        use wasm_bindgen_test::*;
+
+        fn cantilever() -> Model {
+            let mut model = Model::new();
+            let a = model.add_node(0.0, 0.0);
+            let b = model.add_node(2.0, 0.0);
+            model.add_member(a, b, 1.0, 1.0, 1.0).unwrap();
+            model.add_support(a, true, true, true).unwrap();
+            model.add_load(b, 0.0, -1.0, 0.0).unwrap();
+            model
+        }
+
+        #[wasm_bindgen_test]
+        fn section() {
+            let mut section = Section::new();
+            section.add_rectangle(3.0, 5.0, 0.0, 0.0, 0.0);
+            assert_eq!(section.area(), 15.0);
+        }
+
+        /// Thrown as a JavaScript `Error`.
+        #[wasm_bindgen_test]
+        fn invalid_node() {
+            let mut model = cantilever();
+            assert!(model.add_member(0, 2, 1.0, 1.0, 1.0).is_err());
+            assert!(model.add_support(2, true, true, true).is_err());
+            assert!(model.add_load(2, 0.0, -1.0, 0.0).is_err());
+            assert!(model.add_member_load(1, 0.0, -1.0).is_err());
+            assert!(model.add_nodes(&[1.0, 2.0, 3.0]).is_err());
+            model.solve().unwrap();
+        }
+
+        /// Timed by `performance.now()`.
+        #[wasm_bindgen_test]
+        fn analysis() {
+            let mut model = cantilever();
+            model.solve().unwrap();
+            let reaction = model.reaction(0).unwrap();
+            assert!((reaction[1] - 1.0).abs() < 1e-4 && (reaction[2].abs() - 2.0).abs() < 1e-4);
+            let times = model.stats().unwrap().times();
+            assert!(times.iter().all(|&t| t >= 0.0), "{times:?}");
+        }
+
+        #[cfg(feature = "serde")]
+        #[wasm_bindgen_test]
+        fn model_value() {
+            let model = cantilever();
+            let mut copy = Model::from_value(model.to_value().unwrap()).unwrap();
+            assert!(copy.solution().unwrap().is_undefined());
+            copy.solve().unwrap();
+            assert!(copy.solution().unwrap().is_object());
+        }
+
+        /// Logged with `console.log`.
+        #[cfg(feature = "tracing")]
+        #[wasm_bindgen_test]
+        fn tracing() {
+            strust::api::enable_tracing("debug").unwrap();
+            cantilever().solve().unwrap();
+        }
+    };
+}
+
+pub(crate) use suite;
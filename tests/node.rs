@@ -0,0 +1,6 @@
+//! The wasm API in Node.js, where tests run unless configured otherwise, with
+//! `wasm-pack test --node --test node`.
+
+mod common;
+
+common::suite!();
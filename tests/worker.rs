@@ -0,0 +1,8 @@
+//! The wasm API in a dedicated worker, without a DOM, with
+//! `wasm-pack test --chrome --headless --test worker`.
+
+mod common;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_dedicated_worker);
+
+common::suite!();
@@ -0,0 +1,1568 @@
+//! Interface exported to JavaScript by `wasm-bindgen`. Points cross the boundary as flat arrays
+//! of coordinates `[x0, y0, x1, y1, ...]`, and lengths and angles are in the units of the
+//! underlying modules.
+//!
+//! Bulk results are returned as flat arrays of [`Float`], which arrive as a `Float64Array`, or a
+//! `Float32Array` with the `f32` feature, copied once out of the wasm memory. Views into the
+//! memory itself would be detached whenever it grows.
+//!
+//! Nothing here touches the DOM: besides its memory, the module imports only `console.log` and
+//! `performance.now()`, so it runs alike in pages, workers and Node.js 16 or later. The `web`
+//! target of `wasm-pack build` is initialized by awaiting its default export `init()`, or with
+//! `initSync({ module })` in a worker handed the compiled module, and the `nodejs` target as it
+//! is required.
+
+use crate::{
+    cancel,
+    export::dxf::Dxf,
+    geometry::path::{self, FillRule, LineCap, LineJoin},
+    import::ifc::{self, IfcOptions, Plane},
+    math::{complex::Complex, sparse::direct::Storage},
+    model::{
+        beam::section::{
+            circle::CircleSection, combined::CombinedSection, principal_axis,
+            rectangle::RectangleSection, rotated::RotatedSection, translated::TranslatedSection,
+            Section as _,
+        },
+        frame::{
+            self,
+            diagram::{DiagramOptions, Quantity},
+            generators::{Generated, Grid, Portal, Truss, Web},
+            gltf::GltfOptions,
+            history::{Command, History},
+            incremental::{Incremental, Reuse},
+            Analysis, Load, Member, Node, Solution, Spring, Support,
+        },
+        soil, tributary,
+    },
+    Float,
+};
+use wasm_bindgen::prelude::*;
+
+fn points(coordinates: &[Float]) -> Vec<Complex<Float>> {
+    coordinates
+        .chunks_exact(2)
+        .map(|p| Complex::new(p[0], p[1]))
+        .collect()
+}
+
+fn coordinates(points: impl IntoIterator<Item = Complex<Float>>) -> Vec<Float> {
+    points.into_iter().flat_map(|p| [p.re, p.im]).collect()
+}
+
+/// Beam section combined from basic shapes.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Section {
+    section: CombinedSection,
+}
+
+#[wasm_bindgen]
+impl Section {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rectangle with a corner at `(x, y)`, rotated by `angle` in radians around it.
+    pub fn add_rectangle(&mut self, width: Float, height: Float, x: Float, y: Float, angle: Float) {
+        let rectangle = RotatedSection::new(RectangleSection::new([width, height]), angle);
+        self.section.push(TranslatedSection::new(rectangle, [x, y]));
+    }
+
+    /// Adds a circle centered at `(x, y)`.
+    pub fn add_circle(&mut self, radius: Float, x: Float, y: Float) {
+        self.section
+            .push(TranslatedSection::new(CircleSection::new(radius), [x, y]));
+    }
+
+    pub fn area(&self) -> Float {
+        self.section.area()
+    }
+
+    pub fn centroid(&self) -> Vec<Float> {
+        self.section.centroid().to_vec()
+    }
+
+    /// Moments of inertia about the axes through the origin.
+    pub fn moment_of_inertia(&self) -> Vec<Float> {
+        self.section.moment_of_inertia().to_vec()
+    }
+
+    pub fn product_of_inertia(&self) -> Float {
+        self.section.product_of_inertia()
+    }
+
+    /// Angle of the principal axis in radians.
+    pub fn principal_axis(&self) -> Float {
+        principal_axis(&self.section)
+    }
+}
+
+/// Path of lines and curves, as the SVG path data it displays as.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Path {
+    path: path::Path<Complex<Float>, Float>,
+}
+
+#[wasm_bindgen]
+impl Path {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path through `points` by [`path::Path::catmull_rom`].
+    pub fn catmull_rom(points: &[Float], tension: Float, closed: bool) -> Self {
+        path::Path::catmull_rom(&self::points(points), tension, closed).into()
+    }
+
+    pub fn move_to(&mut self, x: Float, y: Float) {
+        self.path.move_to(Complex::new(x, y));
+    }
+
+    pub fn line_to(&mut self, x: Float, y: Float) {
+        self.path.line_to(Complex::new(x, y));
+    }
+
+    pub fn cubic_bezier(&mut self, x1: Float, y1: Float, x2: Float, y2: Float, x: Float, y: Float) {
+        self.path.cubic_bezier(
+            Complex::new(x1, y1),
+            Complex::new(x2, y2),
+            Complex::new(x, y),
+        );
+    }
+
+    pub fn square_bezier(&mut self, x1: Float, y1: Float, x: Float, y: Float) {
+        self.path
+            .square_bezier(Complex::new(x1, y1), Complex::new(x, y));
+    }
+
+    /// Elliptical arc as the SVG `A` command, with the axis rotation in degrees.
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc(
+        &mut self,
+        rx: Float,
+        ry: Float,
+        axis_rotation: Float,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        x: Float,
+        y: Float,
+    ) {
+        self.path.arc(
+            Complex::new(rx, ry),
+            axis_rotation,
+            large_arc_flag,
+            sweep_flag,
+            Complex::new(x, y),
+        );
+    }
+
+    pub fn close_path(&mut self) {
+        self.path.close_path();
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_svg(&self) -> String {
+        self.path.to_string()
+    }
+
+    /// DXF drawing of the path flattened within `tolerance` into polylines on the `layer`.
+    pub fn dxf(&self, layer: &str, tolerance: Float) -> String {
+        Dxf::new().path(layer, &self.path, tolerance).to_string()
+    }
+
+    /// Opcodes and arguments of the canvas drawing methods, as [`path::Path::canvas_commands`].
+    pub fn canvas_commands(&self) -> Vec<Float> {
+        self.path.canvas_commands()
+    }
+
+    /// Signed area of the closed subpaths, positive if counterclockwise.
+    pub fn area(&self) -> Float {
+        self.path.signed_area()
+    }
+
+    /// Whether `(x, y)` is inside by the nonzero rule, or by the even-odd rule if `even_odd`.
+    pub fn contains(&self, x: Float, y: Float, even_odd: bool) -> bool {
+        let rule = if even_odd {
+            FillRule::EvenOdd
+        } else {
+            FillRule::NonZero
+        };
+        self.path.contains(&Complex::new(x, y), rule)
+    }
+
+    /// Points of the path flattened into lines within `tolerance`.
+    pub fn flatten(&self, tolerance: Float) -> Vec<Float> {
+        coordinates(self.path.flatten(tolerance))
+    }
+
+    pub fn translate(&self, dx: Float, dy: Float) -> Self {
+        self.path.translate(Complex::new(dx, dy)).into()
+    }
+
+    /// Rotated by `angle` in radians around the origin.
+    pub fn rotate(&self, angle: Float) -> Self {
+        self.path.rotate(angle).into()
+    }
+
+    pub fn scale(&self, sx: Float, sy: Float) -> Self {
+        self.path.scale(sx, sy).into()
+    }
+
+    pub fn fillet(&self, radius: Float) -> Self {
+        self.path.fillet(radius).into()
+    }
+
+    pub fn chamfer(&self, setback: Float) -> Self {
+        self.path.chamfer(setback).into()
+    }
+
+    /// Outline of the stroke of `width` with butt caps and miter joins.
+    pub fn stroke(&self, width: Float) -> Self {
+        self.path
+            .stroke(width, LineCap::default(), LineJoin::default())
+            .into()
+    }
+}
+
+impl From<path::Path<Complex<Float>, Float>> for Path {
+    fn from(path: path::Path<Complex<Float>, Float>) -> Self {
+        Self { path }
+    }
+}
+
+/// Plane frame analyzed by [`frame::Model::solve`], or step by step by [`Analysis`] to keep the
+/// page responsive, as in
+///
+/// ```js
+/// model.start_analysis()
+/// while (!model.step_analysis()) {
+///   onProgress(model.analysis_phase(), model.analysis_progress())
+///   await new Promise((resolve) => setTimeout(resolve))
+/// }
+/// ```
+///
+/// Editing the model discards the results and any analysis in progress. Once
+/// [`Self::record_history`] is called, the edits can be undone and redone:
+///
+/// ```js
+/// model.record_history()
+/// model.set_node(1, 4, 0)
+/// model.undo() // true, back where the node was
+/// model.redo()
+/// ```
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Model {
+    model: frame::Model,
+    solution: Option<Solution>,
+    analysis: Option<Analysis<frame::Model>>,
+    history: Option<History>,
+}
+
+#[wasm_bindgen]
+impl Model {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Model read from the structural analysis domain of an IFC file, in elevation if
+    /// `elevation` and in plan otherwise, with the same properties for all members.
+    pub fn from_ifc(
+        source: &str,
+        elevation: bool,
+        elasticity: Float,
+        area: Float,
+        inertia: Float,
+    ) -> Result<Model, JsError> {
+        let options = IfcOptions {
+            plane: if elevation { Plane::XZ } else { Plane::XY },
+            elasticity,
+            area,
+            inertia,
+        };
+        Ok(Self {
+            model: ifc::read(source, &options)?,
+            ..Self::default()
+        })
+    }
+
+    /// Portal frame of `bays` bays, as generated by [`Portal`] with the other parameters default
+    /// and the dead load applied.
+    pub fn portal(span: Float, height: Float, bays: usize) -> Model {
+        Self::generated(
+            Portal {
+                span,
+                height,
+                bays,
+                ..Portal::default()
+            }
+            .generate(),
+        )
+    }
+
+    /// Frame of `stories` stories of `bays` bays, as generated by [`Grid`].
+    pub fn grid(span: Float, height: Float, bays: usize, stories: usize) -> Model {
+        Self::generated(
+            Grid {
+                span,
+                height,
+                bays,
+                stories,
+                ..Grid::default()
+            }
+            .generate(),
+        )
+    }
+
+    /// Warren truss if `warren` and Pratt truss otherwise of `panels` panels, as generated by
+    /// [`Truss`].
+    pub fn truss(warren: bool, span: Float, depth: Float, panels: usize) -> Model {
+        Self::generated(
+            Truss {
+                web: if warren { Web::Warren } else { Web::Pratt },
+                span,
+                depth,
+                panels,
+                ..Truss::default()
+            }
+            .generate(),
+        )
+    }
+
+    /// Adds a node and returns its index.
+    pub fn add_node(&mut self, x: Float, y: Float) -> usize {
+        self.extend(frame::Model {
+            nodes: vec![Node { position: [x, y] }],
+            ..frame::Model::new()
+        });
+        self.model.nodes.len() - 1
+    }
+
+    /// Adds a member between the nodes `a` and `b` and returns its index. Throws if a node does
+    /// not exist.
+    pub fn add_member(
+        &mut self,
+        a: usize,
+        b: usize,
+        elasticity: Float,
+        area: Float,
+        inertia: Float,
+    ) -> Result<usize, JsError> {
+        self.check_nodes(&[a, b])?;
+        self.extend(frame::Model {
+            members: vec![Member {
+                nodes: [a, b],
+                elasticity,
+                area,
+                inertia,
+            }],
+            ..frame::Model::new()
+        });
+        Ok(self.model.members.len() - 1)
+    }
+
+    /// Restrains the x and y displacements and the rotation of the `node` as flagged. Throws if
+    /// it does not exist.
+    pub fn add_support(
+        &mut self,
+        node: usize,
+        x: bool,
+        y: bool,
+        rotation: bool,
+    ) -> Result<(), JsError> {
+        self.check_nodes(&[node])?;
+        self.extend(frame::Model {
+            supports: vec![Support {
+                node,
+                fixed: [x, y, rotation],
+            }],
+            ..frame::Model::new()
+        });
+        Ok(())
+    }
+
+    /// Adds the forces along the global axes and the moment counterclockwise at the `node`.
+    /// Throws if it does not exist.
+    pub fn add_load(
+        &mut self,
+        node: usize,
+        fx: Float,
+        fy: Float,
+        moment: Float,
+    ) -> Result<(), JsError> {
+        self.check_nodes(&[node])?;
+        self.extend(frame::Model {
+            loads: vec![Load::Node {
+                node,
+                force: [fx, fy, moment],
+            }],
+            ..frame::Model::new()
+        });
+        Ok(())
+    }
+
+    /// Adds a uniform load per length along the global axes on the `member`. Throws if it does
+    /// not exist.
+    pub fn add_member_load(&mut self, member: usize, wx: Float, wy: Float) -> Result<(), JsError> {
+        self.check_members(&[member])?;
+        self.extend(frame::Model {
+            loads: vec![Load::Member {
+                member,
+                load: [wx, wy],
+            }],
+            ..frame::Model::new()
+        });
+        Ok(())
+    }
+
+    /// Adds a spring of the stiffnesses of the x and y displacements and the rotation of the
+    /// `node` to the ground.
+    pub fn add_spring(&mut self, node: usize, kx: Float, ky: Float, rotation: Float) {
+        self.add_springs(vec![Spring {
+            node,
+            stiffness: [kx, ky, rotation],
+        }]);
+    }
+
+    /// Adds the springs of a soil of the modulus of subgrade reaction `modulus` under the footing
+    /// `members` of the `width`, as [`soil::subgrade`]. Throws if a member does not exist.
+    pub fn add_subgrade(
+        &mut self,
+        members: &[usize],
+        modulus: Float,
+        width: Float,
+    ) -> Result<(), JsError> {
+        self.check_members(members)?;
+        self.add_springs(soil::subgrade(&self.model, members, modulus, width));
+        Ok(())
+    }
+
+    /// Adds the springs of the soil layers from the ground surface at `surface` down along the
+    /// `members` of the `width`, such as a pile, with the `layers` each as `[thickness, modulus]`,
+    /// as [`soil::SoilProfile::springs`]. Throws if a member does not exist.
+    pub fn add_soil_springs(
+        &mut self,
+        members: &[usize],
+        surface: Float,
+        layers: &[Float],
+        width: Float,
+    ) -> Result<(), JsError> {
+        batch(layers, 2, layers, 2)?;
+        self.check_members(members)?;
+        let profile = soil::SoilProfile {
+            surface,
+            layers: (layers.chunks_exact(2))
+                .map(|l| soil::Layer {
+                    thickness: l[0],
+                    modulus: l[1],
+                })
+                .collect(),
+        };
+        self.add_springs(profile.springs(&self.model, members, width));
+        Ok(())
+    }
+
+    /// Adds the spring of a rigid pile cap at the `node`, with the `piles` each as
+    /// `[offset, axial, lateral, rotational]`, as [`soil::pile_cap`].
+    pub fn add_pile_cap(&mut self, node: usize, piles: &[Float]) -> Result<(), JsError> {
+        batch(piles, 4, piles, 4)?;
+        let piles: Vec<_> = (piles.chunks_exact(4))
+            .map(|p| soil::Pile {
+                offset: p[0],
+                axial: p[1],
+                lateral: p[2],
+                rotational: p[3],
+            })
+            .collect();
+        self.add_springs(vec![soil::pile_cap(node, &piles)]);
+        Ok(())
+    }
+
+    /// Moves the `node` to `(x, y)`. Throws if it does not exist.
+    pub fn set_node(&mut self, node: usize, x: Float, y: Float) -> Result<(), JsError> {
+        if node >= self.model.nodes.len() {
+            return Err(frame::ModelError::InvalidNode { node }.into());
+        }
+        self.apply(Command::SetNode {
+            node,
+            position: [x, y],
+        });
+        Ok(())
+    }
+
+    /// Changes the properties of the `member`. Throws if it does not exist.
+    pub fn set_member(
+        &mut self,
+        member: usize,
+        elasticity: Float,
+        area: Float,
+        inertia: Float,
+    ) -> Result<(), JsError> {
+        let Some(&previous) = self.model.members.get(member) else {
+            return Err(frame::ModelError::InvalidMember { member }.into());
+        };
+        self.apply(Command::SetMember {
+            index: member,
+            member: Member {
+                elasticity,
+                area,
+                inertia,
+                ..previous
+            },
+        });
+        Ok(())
+    }
+
+    pub fn clear_loads(&mut self) {
+        self.apply(Command::SetLoads(vec![]));
+    }
+
+    /// Starts recording the edits to be undone, forgetting any recorded before.
+    pub fn record_history(&mut self) {
+        self.history = Some(History::new());
+    }
+
+    /// Stops recording the edits and forgets those recorded.
+    pub fn stop_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Reverts the last edit recorded, returning whether there was one.
+    pub fn undo(&mut self) -> bool {
+        let Some(history) = &mut self.history else {
+            return false;
+        };
+        let undone = history.undo(&mut self.model);
+        if undone {
+            self.discard();
+        }
+        undone
+    }
+
+    /// Applies again the last edit undone, returning whether there was one.
+    pub fn redo(&mut self) -> bool {
+        let Some(history) = &mut self.history else {
+            return false;
+        };
+        let redone = history.redo(&mut self.model);
+        if redone {
+            self.discard();
+        }
+        redone
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.as_ref().is_some_and(History::can_undo)
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.as_ref().is_some_and(History::can_redo)
+    }
+
+    /// Empty model with room for `nodes` nodes and `members` members, to be built in batches.
+    pub fn with_capacity(nodes: usize, members: usize) -> Model {
+        Self {
+            model: frame::Model::with_capacity(nodes, members),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a batch of nodes at the `coordinates`, `[x0, y0, x1, ...]`, and returns the index of
+    /// the first. Throws if their number is odd.
+    pub fn add_nodes(&mut self, coordinates: &[Float]) -> Result<usize, JsError> {
+        batch(coordinates, 2, coordinates, 2)?;
+        let start = self.model.nodes.len();
+        let nodes = coordinates.chunks_exact(2).map(|p| Node {
+            position: [p[0], p[1]],
+        });
+        self.extend(frame::Model {
+            nodes: nodes.collect(),
+            ..frame::Model::new()
+        });
+        Ok(start)
+    }
+
+    /// Adds a batch of members between the pairs of `nodes`, with the `properties` of each as
+    /// `[elasticity, area, inertia]`, and returns the index of the first.
+    pub fn add_members(&mut self, nodes: &[usize], properties: &[Float]) -> Result<usize, JsError> {
+        batch(nodes, 2, properties, 3)?;
+        let start = self.model.members.len();
+        let members = nodes
+            .chunks_exact(2)
+            .zip(properties.chunks_exact(3))
+            .map(|(n, p)| Member {
+                nodes: [n[0], n[1]],
+                elasticity: p[0],
+                area: p[1],
+                inertia: p[2],
+            });
+        self.extend(frame::Model {
+            members: members.collect(),
+            ..frame::Model::new()
+        });
+        Ok(start)
+    }
+
+    /// Adds a batch of supports of the `nodes`, with the x, y and rotation of each restrained
+    /// where the flags in `fixed` are nonzero.
+    pub fn add_supports(&mut self, nodes: &[usize], fixed: &[u8]) -> Result<(), JsError> {
+        batch(nodes, 1, fixed, 3)?;
+        let supports = nodes
+            .iter()
+            .zip(fixed.chunks_exact(3))
+            .map(|(&node, f)| Support {
+                node,
+                fixed: [f[0] != 0, f[1] != 0, f[2] != 0],
+            });
+        self.extend(frame::Model {
+            supports: supports.collect(),
+            ..frame::Model::new()
+        });
+        Ok(())
+    }
+
+    /// Adds a batch of loads at the `nodes`, with the `forces` of each as `[fx, fy, moment]`.
+    pub fn add_loads(&mut self, nodes: &[usize], forces: &[Float]) -> Result<(), JsError> {
+        batch(nodes, 1, forces, 3)?;
+        let loads = nodes
+            .iter()
+            .zip(forces.chunks_exact(3))
+            .map(|(&node, f)| Load::Node {
+                node,
+                force: [f[0], f[1], f[2]],
+            });
+        self.extend(frame::Model {
+            loads: loads.collect(),
+            ..frame::Model::new()
+        });
+        Ok(())
+    }
+
+    /// Adds a batch of uniform loads on the `members`, with the `loads` of each as `[wx, wy]`.
+    pub fn add_member_loads(&mut self, members: &[usize], loads: &[Float]) -> Result<(), JsError> {
+        batch(members, 1, loads, 2)?;
+        let loads = members
+            .iter()
+            .zip(loads.chunks_exact(2))
+            .map(|(&member, w)| Load::Member {
+                member,
+                load: [w[0], w[1]],
+            });
+        self.extend(frame::Model {
+            loads: loads.collect(),
+            ..frame::Model::new()
+        });
+        Ok(())
+    }
+
+    /// Checks the model once it is built, as the batches are not, and releases the capacity left
+    /// unused.
+    pub fn finalize(&mut self) -> Result<(), JsError> {
+        self.model.validate()?;
+        let frame::Model {
+            nodes,
+            members,
+            supports,
+            loads,
+            springs,
+        } = &mut self.model;
+        nodes.shrink_to_fit();
+        members.shrink_to_fit();
+        supports.shrink_to_fit();
+        loads.shrink_to_fit();
+        springs.shrink_to_fit();
+        Ok(())
+    }
+
+    pub fn solve(&mut self) -> Result<(), JsError> {
+        self.analysis = None;
+        self.solution = Some(self.model.solve()?);
+        Ok(())
+    }
+
+    /// [`Self::solve`] throwing once the `cancel` token is set, with the phase and progress the
+    /// analysis had reached in the message.
+    pub fn solve_with_cancel(&mut self, cancel: &CancelToken) -> Result<(), JsError> {
+        self.analysis = None;
+        self.solution = Some(self.model.solve_with_cancel(&cancel.token)?);
+        Ok(())
+    }
+
+    /// Size, cost and peak memory of [`Self::solve`] estimated from the connectivity alone, to
+    /// warn before a model exhausts the memory of the page. Throws if the model is invalid.
+    pub fn estimate_requirements(&self) -> Result<Requirements, JsError> {
+        Ok(Requirements {
+            requirements: self.model.estimate_requirements()?,
+        })
+    }
+
+    /// Starts an analysis of a copy of the model, replacing any in progress and the results.
+    pub fn start_analysis(&mut self) -> Result<(), JsError> {
+        self.discard();
+        self.analysis = Some(Analysis::new(self.model.clone())?);
+        Ok(())
+    }
+
+    /// [`Self::start_analysis`] whose steps throw once the `cancel` token is set, including
+    /// within a long factorization when the token is set from another worker.
+    pub fn start_analysis_with_cancel(&mut self, cancel: &CancelToken) -> Result<(), JsError> {
+        self.discard();
+        self.analysis = Some(Analysis::with_cancel(
+            self.model.clone(),
+            cancel.token.clone(),
+        )?);
+        Ok(())
+    }
+
+    /// Performs the next step of the analysis started by [`Self::start_analysis`], returning
+    /// whether it is done with the results available, as by [`Self::solve`].
+    ///
+    /// Throws if no analysis is in progress.
+    pub fn step_analysis(&mut self) -> Result<bool, JsError> {
+        let analysis = self
+            .analysis
+            .as_mut()
+            .ok_or_else(|| JsError::new("no analysis in progress"))?;
+        let step = analysis.step();
+        if matches!(step, Ok(Some(_)) | Err(_)) {
+            self.analysis = None;
+        }
+        self.solution = step?;
+        Ok(self.solution.is_some())
+    }
+
+    /// Phase of the analysis in progress, `assembly`, `factorization` or `recovery`, or
+    /// `undefined` if there is none.
+    pub fn analysis_phase(&self) -> Option<String> {
+        Some(self.analysis.as_ref()?.progress().phase.to_string())
+    }
+
+    /// Fraction of the phase of the analysis in progress done.
+    pub fn analysis_progress(&self) -> Float {
+        self.analysis.as_ref().map_or(0.0, |analysis| {
+            let progress = analysis.progress();
+            progress.done as Float / progress.total.max(1) as Float
+        })
+    }
+
+    /// SVG diagram of the `quantity`, `axial`, `shear`, `moment` or `deflection`, as
+    /// [`frame::Model::diagram_svg`] with the drawn length per unit `scale`, or automatic if not
+    /// positive, or `undefined` before solving.
+    ///
+    /// Throws if the quantity is unknown.
+    pub fn diagram_svg(
+        &self,
+        quantity: &str,
+        scale: Float,
+        samples: usize,
+    ) -> Result<Option<String>, JsError> {
+        let quantity = match quantity {
+            "axial" => Quantity::Axial,
+            "shear" => Quantity::Shear,
+            "moment" => Quantity::Moment,
+            "deflection" => Quantity::Deflection,
+            _ => return Err(JsError::new(&format!("unknown quantity {quantity}"))),
+        };
+        let options = DiagramOptions {
+            scale: (scale > 0.0).then_some(scale),
+            samples,
+            ..DiagramOptions::default()
+        };
+        Ok(self
+            .solution
+            .as_ref()
+            .map(|solution| self.model.diagram_svg(solution, quantity, &options)))
+    }
+
+    /// glTF asset of the members extruded with the `outline`, in the plane of their local y axis
+    /// and the global z axis, with the deflected shape magnified by `scale` as a morph target
+    /// once solved, as [`frame::Model::gltf`].
+    pub fn gltf(&self, outline: &[Float], samples: usize, scale: Float) -> String {
+        let options = GltfOptions {
+            outline: points(outline),
+            samples,
+            scale,
+        };
+        self.model.gltf(self.solution.as_ref(), &options)
+    }
+
+    /// DXF drawing of the members, nodes and member labels of `label_height`, as [`Dxf::model`].
+    pub fn dxf(&self, label_height: Float) -> String {
+        Dxf::new().model(&self.model, label_height).to_string()
+    }
+
+    /// Lines along the members, to be drawn by [`Path::canvas_commands`].
+    pub fn wireframe(&self) -> Path {
+        self.model.wireframe().into()
+    }
+
+    /// Displacements and rotations of all nodes, `[ux0, uy0, rz0, ux1, ...]`, or `undefined`
+    /// before solving.
+    pub fn displacements(&self) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.displacements.concat())
+    }
+
+    /// Reactions of all nodes laid out as [`Self::displacements`], or `undefined` before solving.
+    pub fn reactions(&self) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.reactions.concat())
+    }
+
+    /// Axial force, shear force and moment at `samples` points evenly spaced along each member
+    /// from its first node to its second, `[n, v, m]` for each point of the first member, then
+    /// each of the second and so on, or `undefined` before solving.
+    pub fn diagrams(&self, samples: usize) -> Option<Vec<Float>> {
+        let solution = self.solution.as_ref()?;
+        let step = 1.0 / samples.saturating_sub(1).max(1) as Float;
+        Some(
+            (0..self.model.members.len())
+                .flat_map(|member| {
+                    let length = self.model.length(member);
+                    (0..samples).flat_map(move |i| {
+                        self.model
+                            .internal_forces(solution, member, length * step * i as Float)
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Size and cost of the last analysis, or `undefined` before solving.
+    pub fn stats(&self) -> Option<Stats> {
+        Some(Stats {
+            stats: self.solution.as_ref()?.stats,
+        })
+    }
+
+    /// Hash of the results of the last analysis rounded to `digits` significant digits, as
+    /// [`Solution::fingerprint`], in 16 hexadecimal digits, or `undefined` before solving.
+    pub fn fingerprint(&self, digits: u32) -> Option<String> {
+        Some(format!(
+            "{:016x}",
+            self.solution.as_ref()?.fingerprint(digits)
+        ))
+    }
+
+    /// Displacements and rotation of the `node`, or `undefined` before solving.
+    pub fn displacement(&self, node: usize) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.displacements.get(node)?.to_vec())
+    }
+
+    /// Reactions of the `node`, or `undefined` before solving.
+    pub fn reaction(&self, node: usize) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.reactions.get(node)?.to_vec())
+    }
+
+    /// End forces of the `member` in its local axes, the axial and shear forces and the moment at
+    /// its first node then its second, or `undefined` before solving.
+    pub fn member_forces(&self, member: usize) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.member_forces.get(member)?.to_vec())
+    }
+}
+
+/// Scalar of the plain objects declared from the Rust types, `Float` in their declarations.
+#[cfg(feature = "serde")]
+#[wasm_bindgen(typescript_custom_section)]
+const FLOAT: &str = "export type Float = number;";
+
+/// Models and results as plain objects of the shapes `FrameModel` and `Solution`, declared from
+/// the Rust types with `tsify`.
+#[cfg(feature = "serde")]
+#[wasm_bindgen]
+impl Model {
+    pub fn from_value(
+        #[wasm_bindgen(unchecked_param_type = "FrameModel")] value: JsValue,
+    ) -> Result<Model, JsError> {
+        Ok(Self {
+            model: serde_wasm_bindgen::from_value(value)?,
+            ..Self::default()
+        })
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "FrameModel")]
+    pub fn to_value(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.model)?)
+    }
+
+    /// Results of the last analysis, or `undefined` before solving.
+    #[wasm_bindgen(unchecked_return_type = "Solution | undefined")]
+    pub fn solution(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.solution)?)
+    }
+
+    /// Largest deviations of the results of the last analysis from the `expected` ones within
+    /// the tolerance `absolute + relative * scale`, as [`Solution::compare`], or `undefined`
+    /// before solving. Throws if the results are of different numbers of nodes or members.
+    #[wasm_bindgen(unchecked_return_type = "Comparison | undefined")]
+    pub fn compare(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "Solution")] expected: JsValue,
+        relative: Float,
+        absolute: Float,
+    ) -> Result<JsValue, JsError> {
+        let Some(solution) = &self.solution else {
+            return Ok(JsValue::UNDEFINED);
+        };
+        let expected: Solution = serde_wasm_bindgen::from_value(expected)?;
+        let tolerance = frame::snapshot::Tolerance { relative, absolute };
+        let comparison = solution.compare(&expected, &tolerance)?;
+        Ok(serde_wasm_bindgen::to_value(&comparison)?)
+    }
+
+    /// Results of the structure under each of the load `cases`, whose loads replace those of the
+    /// model, factorizing the stiffness once, as [`frame::Model::solve_static_many`]. The results
+    /// of the last analysis are kept.
+    #[wasm_bindgen(unchecked_return_type = "Solution[]")]
+    pub fn solve_static_many(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "LoadCase[]")] cases: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let cases: Vec<frame::LoadCase> = serde_wasm_bindgen::from_value(cases)?;
+        let solutions = self.model.solve_static_many(&cases)?;
+        Ok(serde_wasm_bindgen::to_value(&solutions)?)
+    }
+
+    /// Statistics of the `limit_states` and probabilities of exceeding them when the loads and
+    /// member properties are multiplied by the random `variables`, estimated by analyzing the
+    /// samples of the `options`, as [`frame::Model::reliability`].
+    #[wasm_bindgen(unchecked_return_type = "Reliability")]
+    pub fn reliability(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "Variable[]")] variables: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "LimitState[]")] limit_states: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "ReliabilityOptions")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let variables: Vec<frame::reliability::Variable> =
+            serde_wasm_bindgen::from_value(variables)?;
+        let limit_states: Vec<frame::reliability::LimitState> =
+            serde_wasm_bindgen::from_value(limit_states)?;
+        let options = serde_wasm_bindgen::from_value(options)?;
+        let reliability = self
+            .model
+            .reliability(&variables, &limit_states, &options)?;
+        Ok(serde_wasm_bindgen::to_value(&reliability)?)
+    }
+
+    /// Results and their derivatives with respect to factors of the `inputs`, the loads and
+    /// member properties, as [`frame::Model::sensitivities`].
+    #[wasm_bindgen(unchecked_return_type = "Sensitivities")]
+    pub fn sensitivities(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "Input[]")] inputs: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let inputs: Vec<frame::reliability::Input> = serde_wasm_bindgen::from_value(inputs)?;
+        let sensitivities = self.model.sensitivities(&inputs)?;
+        Ok(serde_wasm_bindgen::to_value(&sensitivities)?)
+    }
+
+    /// Sections of the `catalog` for the members of each of the `groups` under the envelope of
+    /// the load `cases`, with the resized model, as [`frame::Model::size`]. The model itself is
+    /// not changed.
+    #[wasm_bindgen(unchecked_return_type = "Sizing")]
+    pub fn size(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "LoadCase[]")] cases: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "number[][]")] groups: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "Profile[]")] catalog: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "SizingOptions")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let cases: Vec<frame::LoadCase> = serde_wasm_bindgen::from_value(cases)?;
+        let groups: Vec<Vec<usize>> = serde_wasm_bindgen::from_value(groups)?;
+        let catalog: Vec<frame::sizing::Profile> = serde_wasm_bindgen::from_value(catalog)?;
+        let options = serde_wasm_bindgen::from_value(options)?;
+        let sizing = self.model.size(&cases, &groups, &catalog, &options)?;
+        Ok(serde_wasm_bindgen::to_value(&sizing)?)
+    }
+
+    /// Cases named after `name` of the load `(wx, wy)` per length over patterns of the spans of
+    /// the chains of members `beams`, split as [`frame::Model::spans`], each with the loads of
+    /// the model, as [`frame::patterns::Patterns::cases`]. Throws if a member does not exist.
+    #[wasm_bindgen(unchecked_return_type = "LoadCase[]")]
+    pub fn pattern_cases(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "number[][]")] beams: JsValue,
+        wx: Float,
+        wy: Float,
+        name: &str,
+    ) -> Result<JsValue, JsError> {
+        let beams: Vec<Vec<usize>> = serde_wasm_bindgen::from_value(beams)?;
+        let members = self.model.members.len();
+        if let Some(&member) = beams.iter().flatten().find(|&&m| m >= members) {
+            return Err(frame::ModelError::InvalidMember { member }.into());
+        }
+        let patterns = frame::patterns::Patterns {
+            beams: beams.iter().map(|beam| self.model.spans(beam)).collect(),
+            load: [wx, wy],
+            permanent: self.model.loads.clone(),
+        };
+        Ok(serde_wasm_bindgen::to_value(&patterns.cases(name))?)
+    }
+
+    /// Loads of the `wind` of EN 1991-1-4 on the envelope of the model, as
+    /// [`crate::model::wind::en_1991_1_4::Wind::loads`], for the frames `spacing` apart on a
+    /// building `breadth` across the wind, blowing to the right, or to the left if `from_right`.
+    /// The loads of the model are kept, so that these can make a load case.
+    #[wasm_bindgen(unchecked_return_type = "Load[]")]
+    pub fn wind_loads(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "Wind")] wind: JsValue,
+        spacing: Float,
+        breadth: Float,
+        from_right: bool,
+    ) -> Result<JsValue, JsError> {
+        let wind: crate::model::wind::en_1991_1_4::Wind = serde_wasm_bindgen::from_value(wind)?;
+        let mut envelope = crate::model::wind::Envelope::of(&self.model, spacing, breadth);
+        if from_right {
+            std::mem::swap(&mut envelope.windward, &mut envelope.leeward);
+        }
+        Ok(serde_wasm_bindgen::to_value(
+            &wind.loads(&self.model, &envelope),
+        )?)
+    }
+}
+
+impl Model {
+    fn discard(&mut self) {
+        self.solution = None;
+        self.analysis = None;
+    }
+
+    /// Applies the `command` to the model, recording it if the history is.
+    fn apply(&mut self, command: Command) {
+        self.discard();
+        match &mut self.history {
+            Some(history) => history.apply(&mut self.model, command),
+            None => {
+                command.apply(&mut self.model);
+            }
+        }
+    }
+
+    fn generated(generated: Generated) -> Self {
+        Self {
+            model: generated.model,
+            ..Self::default()
+        }
+    }
+
+    fn add_springs(&mut self, springs: Vec<Spring>) {
+        self.extend(frame::Model {
+            springs,
+            ..frame::Model::new()
+        });
+    }
+
+    /// Checks that the `nodes` exist.
+    fn check_nodes(&self, nodes: &[usize]) -> Result<(), JsError> {
+        match nodes.iter().find(|&&n| n >= self.model.nodes.len()) {
+            Some(&node) => Err(frame::ModelError::InvalidNode { node }.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the `members` exist.
+    fn check_members(&self, members: &[usize]) -> Result<(), JsError> {
+        match members.iter().find(|&&m| m >= self.model.members.len()) {
+            Some(&member) => Err(frame::ModelError::InvalidMember { member }.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Appends the nodes, members, supports, loads and springs of the `items`.
+    fn extend(&mut self, items: frame::Model) {
+        self.apply(Command::Extend(items));
+    }
+}
+
+/// Checks that a batch has as many items in `a`, of `a_width` values each, as in `b`, of
+/// `b_width` values each.
+fn batch<T, U>(a: &[T], a_width: usize, b: &[U], b_width: usize) -> Result<(), JsError> {
+    if a.len().is_multiple_of(a_width) && a.len() / a_width * b_width == b.len() {
+        Ok(())
+    } else {
+        Err(JsError::new("batch arrays have different lengths"))
+    }
+}
+
+/// Sets the number of threads of the `rayon` pool that assembles and recovers members and
+/// multiplies sparse matrices, before its first use.
+///
+/// Threads in wasm require a build with the `atomics` target feature and the page to provide
+/// workers to the pool, for example with `wasm-bindgen-rayon`. Throws if the pool is already
+/// running.
+#[cfg(feature = "rayon")]
+#[wasm_bindgen]
+pub fn set_thread_count(count: usize) -> Result<(), JsError> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(count)
+        .build_global()?;
+    Ok(())
+}
+
+/// Logs the spans of analyses, with their durations, and events such as the convergence of
+/// iterative solvers at `level`, one of `error`, `warn`, `info`, `debug` and `trace`, to the
+/// console. Throws if the level is unknown or logging is already enabled.
+#[cfg(feature = "tracing")]
+#[wasm_bindgen]
+pub fn enable_tracing(level: &str) -> Result<(), JsError> {
+    tracing::subscriber::set_global_default(crate::trace::console(level.parse()?))?;
+    Ok(())
+}
+
+/// Flag stopping the analyses it is passed to, shared by the model and the page.
+///
+/// Analyses stepped by [`Model::step_analysis`] stop at the next step once it is set. A
+/// synchronous [`Model::solve_with_cancel`] can only be stopped by setting it from another worker,
+/// which requires a build with shared memory as for `set_thread_count`.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct CancelToken {
+    token: cancel::CancelToken,
+}
+
+#[wasm_bindgen]
+impl CancelToken {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the analyses at their next check. It cannot be undone.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// Size and cost of an analysis, from [`Model::stats`].
+#[wasm_bindgen]
+pub struct Stats {
+    stats: frame::Stats,
+}
+
+#[wasm_bindgen]
+impl Stats {
+    /// Number of members.
+    #[wasm_bindgen(getter)]
+    pub fn elements(&self) -> usize {
+        self.stats.elements
+    }
+
+    /// Number of unrestrained degrees of freedom.
+    #[wasm_bindgen(getter)]
+    pub fn dofs(&self) -> usize {
+        self.stats.dofs
+    }
+
+    /// Number of stored elements of the stiffness.
+    #[wasm_bindgen(getter)]
+    pub fn nnz(&self) -> usize {
+        self.stats.nnz
+    }
+
+    /// Number of elements of the factor of the stiffness below the diagonal.
+    #[wasm_bindgen(getter)]
+    pub fn factor_nnz(&self) -> usize {
+        self.stats.factor_nnz
+    }
+
+    /// Estimate of the floating-point operations of the factorization.
+    #[wasm_bindgen(getter)]
+    pub fn flops(&self) -> Float {
+        self.stats.flops
+    }
+
+    /// Estimate of the peak bytes allocated.
+    #[wasm_bindgen(getter)]
+    pub fn memory(&self) -> usize {
+        self.stats.memory
+    }
+
+    /// Milliseconds spent assembling, factorizing and recovering, from `performance.now()`.
+    #[wasm_bindgen(getter)]
+    pub fn times(&self) -> Vec<f64> {
+        let times = self.stats.times;
+        vec![times.assembly, times.factorization, times.recovery]
+    }
+}
+
+/// Estimated size and cost of an analysis, from [`Model::estimate_requirements`].
+#[wasm_bindgen]
+pub struct Requirements {
+    requirements: frame::Requirements,
+}
+
+#[wasm_bindgen]
+impl Requirements {
+    /// Number of unrestrained degrees of freedom.
+    #[wasm_bindgen(getter)]
+    pub fn dofs(&self) -> usize {
+        self.requirements.dofs
+    }
+
+    /// Number of stored elements of the stiffness.
+    #[wasm_bindgen(getter)]
+    pub fn nnz(&self) -> usize {
+        self.requirements.nnz
+    }
+
+    /// Storage of the factor, `skyline` or `sparse`, or `undefined` if every degree of freedom
+    /// is restrained.
+    #[wasm_bindgen(getter)]
+    pub fn storage(&self) -> Option<String> {
+        self.requirements.storage.map(|storage| {
+            match storage {
+                Storage::Skyline => "skyline",
+                Storage::Sparse => "sparse",
+            }
+            .to_owned()
+        })
+    }
+
+    /// Number of elements of the factor of the stiffness below the diagonal, with the fill-in.
+    #[wasm_bindgen(getter)]
+    pub fn factor_nnz(&self) -> usize {
+        self.requirements.factor_nnz
+    }
+
+    /// Floating-point operations of the factorization.
+    #[wasm_bindgen(getter)]
+    pub fn flops(&self) -> Float {
+        self.requirements.flops
+    }
+
+    /// Peak bytes allocated by the analysis.
+    #[wasm_bindgen(getter)]
+    pub fn memory(&self) -> usize {
+        self.requirements.memory
+    }
+}
+
+/// Copy of a [`Model`] for what-if edits, re-analyzed reusing the factors of its stiffness: edits
+/// of the loads only back-substitute, and edits of a few members or nodes update the factors
+/// rather than recompute them.
+///
+/// ```js
+/// const what_if = new IncrementalModel(model)
+/// what_if.solve()
+/// what_if.set_member(3, 2.05e8, 0.012, 2.4e-4)
+/// what_if.solve() // "low-rank"
+/// ```
+#[wasm_bindgen]
+pub struct IncrementalModel {
+    incremental: Incremental,
+    solution: Option<Solution>,
+}
+
+#[wasm_bindgen]
+impl IncrementalModel {
+    #[wasm_bindgen(constructor)]
+    pub fn new(model: &Model) -> Self {
+        Self {
+            incremental: Incremental::new(model.model.clone()),
+            solution: None,
+        }
+    }
+
+    /// Moves the `node` to `(x, y)`. Throws if it does not exist.
+    pub fn set_node(&mut self, node: usize, x: Float, y: Float) -> Result<(), JsError> {
+        if node >= self.incremental.model().nodes.len() {
+            return Err(frame::ModelError::InvalidNode { node }.into());
+        }
+        self.solution = None;
+        self.incremental.set_node(node, [x, y]);
+        Ok(())
+    }
+
+    /// Changes the properties of the `member`. Throws if it does not exist.
+    pub fn set_member(
+        &mut self,
+        member: usize,
+        elasticity: Float,
+        area: Float,
+        inertia: Float,
+    ) -> Result<(), JsError> {
+        let Some(&previous) = self.incremental.model().members.get(member) else {
+            return Err(frame::ModelError::InvalidMember { member }.into());
+        };
+        self.solution = None;
+        self.incremental.set_member(
+            member,
+            Member {
+                elasticity,
+                area,
+                inertia,
+                ..previous
+            },
+        );
+        Ok(())
+    }
+
+    pub fn clear_loads(&mut self) {
+        self.solution = None;
+        self.incremental.set_loads(vec![]);
+    }
+
+    /// Adds forces along the global axes and a moment counterclockwise at the `node`.
+    pub fn add_load(&mut self, node: usize, fx: Float, fy: Float, moment: Float) {
+        self.solution = None;
+        self.incremental.add_load(Load::Node {
+            node,
+            force: [fx, fy, moment],
+        });
+    }
+
+    /// Adds a uniform load per length along the global axes to the `member`.
+    pub fn add_member_load(&mut self, member: usize, wx: Float, wy: Float) {
+        self.solution = None;
+        self.incremental.add_load(Load::Member {
+            member,
+            load: [wx, wy],
+        });
+    }
+
+    /// Analyzes the model as edited, returning how the previous analysis was reused: `none`,
+    /// `factors`, `low-rank` or `reassembled`.
+    pub fn solve(&mut self) -> Result<String, JsError> {
+        self.solution = Some(self.incremental.solve()?);
+        let reuse = match self.incremental.reuse() {
+            Some(Reuse::Factors) => "factors",
+            Some(Reuse::LowRank { .. }) => "low-rank",
+            Some(Reuse::Reassembled { .. }) => "reassembled",
+            Some(Reuse::None) | None => "none",
+        };
+        Ok(reuse.to_owned())
+    }
+
+    /// Displacements and rotations of all nodes as [`Model::displacements`], or `undefined`
+    /// before solving.
+    pub fn displacements(&self) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.displacements.concat())
+    }
+
+    /// Reactions of all nodes as [`Model::reactions`], or `undefined` before solving.
+    pub fn reactions(&self) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.reactions.concat())
+    }
+
+    /// End forces of all members in their local axes, six for each, or `undefined` before
+    /// solving.
+    pub fn member_forces(&self) -> Option<Vec<Float>> {
+        Some(self.solution.as_ref()?.member_forces.concat())
+    }
+}
+
+/// Share of a floor panel carried by a beam, from [`tributary`].
+#[wasm_bindgen]
+pub struct Tributary {
+    tributary: tributary::Tributary,
+}
+
+#[wasm_bindgen]
+impl Tributary {
+    /// Index of the edge of the panel along the beam.
+    #[wasm_bindgen(getter)]
+    pub fn edge(&self) -> usize {
+        self.tributary.edge
+    }
+
+    /// Vertices of the area carried.
+    #[wasm_bindgen(getter)]
+    pub fn area(&self) -> Vec<Float> {
+        coordinates(self.tributary.area.iter().copied())
+    }
+
+    /// Distributed load as flat pairs of the distance along the edge and the load per length.
+    #[wasm_bindgen(getter)]
+    pub fn load(&self) -> Vec<Float> {
+        self.tributary.load.concat()
+    }
+}
+
+/// Splits the convex floor panel with the vertices `panel` among the beams along the `edges`,
+/// as [`tributary::tributary`].
+#[wasm_bindgen]
+pub fn tributary(panel: &[Float], edges: &[usize], pressure: Float) -> Vec<Tributary> {
+    tributary::tributary(&points(panel), edges, pressure)
+        .into_iter()
+        .map(|tributary| Tributary { tributary })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn section() {
+        let mut section = Section::new();
+        section.add_rectangle(2.0, 4.0, -1.0, -2.0, 0.0);
+        assert_eq!(section.area(), 8.0);
+        assert_eq!(section.centroid(), [0.0, 0.0]);
+        assert_eq!(section.product_of_inertia(), 0.0);
+        // Taller than wide, so the principal axis is the vertical one.
+        let [iy, ix] = section.moment_of_inertia()[..] else {
+            unreachable!()
+        };
+        assert!(iy < ix);
+    }
+
+    #[test]
+    fn path() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0);
+        path.line_to(2.0, 0.0);
+        path.line_to(2.0, 2.0);
+        path.line_to(0.0, 2.0);
+        path.close_path();
+        assert_eq!(path.to_svg(), "M0,0 L2,0 L2,2 L0,2 Z");
+        assert_eq!(path.area(), 4.0);
+        assert!(path.contains(1.0, 1.0, false));
+        assert!(path
+            .dxf("SECTIONS", 0.1)
+            .contains("\nPOLYLINE\n8\nSECTIONS\n"));
+        assert!(!path.translate(3.0, 0.0).contains(1.0, 1.0, true));
+        assert_eq!(path.canvas_commands()[..6], [0.0, 0.0, 0.0, 1.0, 2.0, 0.0]);
+        assert_eq!(
+            path.flatten(0.1),
+            [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn model() {
+        let mut model = Model::new();
+        let a = model.add_node(0.0, 0.0);
+        let b = model.add_node(2.0, 0.0);
+        model.add_member(a, b, 1.0, 1.0, 1.0).unwrap();
+        model.add_support(a, true, true, true).unwrap();
+        model.add_load(b, 0.0, -1.0, 0.0).unwrap();
+        assert_eq!(model.wireframe().to_svg(), "M0,0 L2,0");
+        assert!(model.dxf(0.1).contains("\nLINE\n8\nMEMBERS\n"));
+        assert_eq!(model.reaction(a), None);
+        assert!(model.solve().is_ok());
+        let reaction = model.reaction(a).unwrap();
+        assert!((reaction[2] - 2.0).abs() < 1e-6);
+        assert!(model.displacement(b).unwrap()[1] < 0.0);
+        assert_eq!(model.member_forces(1), None);
+        let stats = model.stats().unwrap();
+        assert_eq!((stats.elements(), stats.dofs(), stats.nnz()), (1, 3, 9));
+        assert_eq!(stats.times().len(), 3);
+        assert_eq!(model.displacements().unwrap().len(), 6);
+        assert_eq!(model.reactions().unwrap()[..3], reaction);
+        let svg = model.diagram_svg("moment", 0.0, 5).ok().flatten().unwrap();
+        assert!(svg.contains(">-2.00</text>"));
+        let square = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        assert!(model.gltf(&square, 2, 1.0).contains(r#""targets""#));
+        let diagrams = model.diagrams(3).unwrap();
+        assert_eq!(diagrams.len(), 9);
+        // The moment from the root to the tip.
+        assert!((diagrams[2] + 2.0).abs() < 1e-6 && (diagrams[5] + 1.0).abs() < 1e-6);
+        assert!(diagrams[8].abs() < 1e-6);
+        model.add_member_load(0, 0.0, -1.0).unwrap();
+        assert_eq!(model.displacement(b), None);
+        assert!(model.start_analysis().is_ok());
+        assert_eq!(model.analysis_phase().as_deref(), Some("assembly"));
+        let mut steps = 0;
+        while !model.step_analysis().unwrap_or(true) {
+            steps += 1;
+        }
+        assert_eq!(steps, 2);
+        assert_eq!(model.analysis_phase(), None);
+        assert!(model.displacement(b).unwrap()[1] < 0.0);
+    }
+
+    #[test]
+    fn batches() {
+        let mut model = Model::with_capacity(3, 2);
+        assert_eq!(model.add_nodes(&[0.0, 0.0, 2.0, 0.0]).ok(), Some(0));
+        assert_eq!(model.add_nodes(&[4.0, 0.0]).ok(), Some(2));
+        let members = model.add_members(&[0, 1, 1, 2], &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(members.ok(), Some(0));
+        assert!(model.add_supports(&[0], &[1, 1, 1]).is_ok());
+        assert!(model.add_loads(&[2], &[0.0, -1.0, 0.0]).is_ok());
+        assert!(model.add_member_loads(&[1], &[0.0, -1.0]).is_ok());
+        assert!(model.finalize().is_ok());
+        assert!(model.solve().is_ok());
+        // The moment of the tip force and the load on the outer member about the root.
+        let reaction = model.reaction(0).unwrap();
+        assert!((reaction[2] - 4.0 - 2.0 * 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn springs() {
+        // A footing on the soil with a column on a pile cap at its right end.
+        let mut model = Model::new();
+        model
+            .add_nodes(&[0.0, 0.0, 1.0, 0.0, 2.0, 0.0, 2.0, 3.0])
+            .unwrap();
+        model
+            .add_members(&[0, 1, 1, 2, 2, 3], &[1e7, 0.1, 1e-2].repeat(3))
+            .unwrap();
+        model.add_support(0, true, false, false).unwrap();
+        assert!(model.add_subgrade(&[0, 1], 1e4, 1.0).is_ok());
+        assert!(model
+            .add_pile_cap(2, &[-0.5, 1e4, 1e3, 0.0, 0.5, 1e4, 1e3, 0.0])
+            .is_ok());
+        assert!(model.add_soil_springs(&[2], 2.0, &[1.0, 1e3], 0.5).is_ok());
+        assert_eq!(model.model.springs.len(), 3 + 1 + 2);
+        model.add_load(3, 0.0, -10.0, 0.0).unwrap();
+        assert!(model.solve().is_ok());
+        let lifted: Float = (0..4).map(|node| model.reaction(node).unwrap()[1]).sum();
+        assert_relative_eq!(lifted, 10.0, max_relative = Float::EPSILON * 1e2);
+    }
+
+    #[test]
+    fn history() {
+        let mut model = Model::new();
+        model.add_node(0.0, 0.0);
+        model.record_history();
+        assert!(!model.can_undo());
+        assert_eq!(model.add_nodes(&[2.0, 0.0]).ok(), Some(1));
+        model.add_member(0, 1, 1.0, 1.0, 1.0).unwrap();
+        model.add_support(0, true, true, true).unwrap();
+        model.add_load(1, 0.0, -1.0, 0.0).unwrap();
+        assert!(model.set_node(1, 3.0, 0.0).is_ok());
+        assert!(model.solve().is_ok());
+        assert!(model.undo());
+        assert_eq!(model.displacements(), None);
+        assert!(model.solve().is_ok());
+        // The moment of the tip load about the root, with the node back in place.
+        assert!((model.reaction(0).unwrap()[2] - 2.0).abs() < 1e-6);
+        while model.undo() {}
+        assert_eq!(model.model.nodes.len(), 1);
+        assert!(model.redo() && model.redo());
+        assert_eq!(model.model.members.len(), 1);
+        model.clear_loads();
+        assert!(!model.can_redo());
+        model.stop_history();
+        assert!(!model.undo());
+    }
+
+    #[test]
+    fn generators() {
+        let mut model = Model::grid(6.0, 3.5, 2, 3);
+        assert_eq!(model.model.members.len(), 15);
+        assert!(model.solve().is_ok());
+        assert_eq!(Model::portal(6.0, 4.0, 1).model.nodes.len(), 4);
+        assert_eq!(Model::truss(true, 12.0, 1.5, 4).model.nodes.len(), 9);
+    }
+
+    #[test]
+    fn tributary() {
+        let panel = [0.0, 0.0, 6.0, 0.0, 6.0, 4.0, 0.0, 4.0];
+        let shares = super::tributary(&panel, &[0, 2], 1.0);
+        assert_eq!(shares[1].edge(), 2);
+        assert_eq!(shares[0].load(), [0.0, 2.0, 6.0, 2.0]);
+        assert_eq!(shares[0].area().len(), 8);
+    }
+}
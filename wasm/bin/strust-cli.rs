@@ -0,0 +1,425 @@
+//! Batch analysis of plane frames read from the JSON model format, writing the results as JSON or
+//! CSV.
+
+use serde::Serialize;
+use std::{
+    fs,
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+use strust::{
+    model::frame::{
+        snapshot::{Comparison, Tolerance},
+        Model, Solution,
+    },
+    Float,
+};
+
+const USAGE: &str = "\
+Usage: strust-cli [OPTIONS] [MODEL]
+
+Analyzes the frame in the JSON file MODEL, or standard input if it is omitted or -.
+
+Options:
+  -o, --output FILE       Write the results to FILE instead of standard output
+  -f, --format FORMAT     json or csv, by default csv if FILE ends with .csv and json otherwise
+  -d, --diagrams SAMPLES  Add the axial force, shear force and moment at SAMPLES points along
+                          each member
+      --fingerprint DIGITS
+                          Write a hash of the results rounded to DIGITS significant digits
+                          instead of the results
+  -c, --compare FILE      Compare the results with those in the JSON file FILE written earlier,
+                          writing the largest deviations and failing if they exceed the
+                          tolerance
+  -t, --tolerance RELATIVE
+                          Relative tolerance of --compare, by default 1e-4
+  -h, --help              Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Args {
+    input: Option<String>,
+    output: Option<String>,
+    format: Option<Format>,
+    diagrams: Option<usize>,
+    fingerprint: Option<u32>,
+    compare: Option<String>,
+    tolerance: Option<Float>,
+    help: bool,
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} requires a value"));
+        match arg.as_str() {
+            "-o" | "--output" => parsed.output = Some(value()?),
+            "-f" | "--format" => {
+                parsed.format = Some(match value()?.as_str() {
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    format => return Err(format!("unknown format {format}")),
+                })
+            }
+            "-d" | "--diagrams" => {
+                let samples = value()?;
+                parsed.diagrams = Some(
+                    samples
+                        .parse()
+                        .map_err(|_| format!("invalid number of samples {samples}"))?,
+                );
+            }
+            "--fingerprint" => {
+                let digits = value()?;
+                parsed.fingerprint = Some(
+                    digits
+                        .parse()
+                        .map_err(|_| format!("invalid number of digits {digits}"))?,
+                );
+            }
+            "-c" | "--compare" => parsed.compare = Some(value()?),
+            "-t" | "--tolerance" => {
+                let tolerance = value()?;
+                parsed.tolerance = Some(
+                    tolerance
+                        .parse()
+                        .map_err(|_| format!("invalid tolerance {tolerance}"))?,
+                );
+            }
+            "-h" | "--help" => parsed.help = true,
+            _ if arg.starts_with('-') && arg != "-" => return Err(format!("unknown option {arg}")),
+            _ if parsed.input.is_some() => return Err(format!("unexpected argument {arg}")),
+            _ => parsed.input = Some(arg),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Results written as JSON.
+#[derive(Serialize)]
+struct Results<'a> {
+    solution: &'a Solution,
+    /// `[n, v, m]` at the samples along each member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagrams: Option<Vec<Vec<[Float; 3]>>>,
+}
+
+/// Positions along the member and `[n, v, m]` at `samples` points evenly spaced from its first
+/// node to its second.
+fn diagram(
+    model: &Model,
+    solution: &Solution,
+    member: usize,
+    samples: usize,
+) -> Vec<(Float, [Float; 3])> {
+    let length = model.length(member);
+    let step = length / samples.saturating_sub(1).max(1) as Float;
+    (0..samples)
+        .map(|i| {
+            let x = step * i as Float;
+            (x, model.internal_forces(solution, member, x))
+        })
+        .collect()
+}
+
+/// Tables of the displacements, reactions, member end forces and diagrams separated by blank
+/// lines.
+fn csv(model: &Model, solution: &Solution, diagrams: Option<usize>) -> String {
+    let mut csv = String::new();
+    let mut table = |header: &str, rows: &mut dyn Iterator<Item = Vec<String>>| {
+        if !csv.is_empty() {
+            csv.push('\n');
+        }
+        csv.push_str(header);
+        csv.push('\n');
+        for row in rows {
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    };
+    let row = |i: usize, values: &[Float]| {
+        let mut row = vec![i.to_string()];
+        // Adding zero turns negative zeros, as of an unloaded axial force, into zeros.
+        row.extend(values.iter().map(|v| (v + 0.0).to_string()));
+        row
+    };
+    table(
+        "node,ux,uy,rz",
+        &mut solution
+            .displacements
+            .iter()
+            .enumerate()
+            .map(|(i, u)| row(i, u)),
+    );
+    table(
+        "node,rx,ry,mz",
+        &mut solution
+            .reactions
+            .iter()
+            .enumerate()
+            .map(|(i, r)| row(i, r)),
+    );
+    table(
+        "member,n1,v1,m1,n2,v2,m2",
+        &mut solution
+            .member_forces
+            .iter()
+            .enumerate()
+            .map(|(i, f)| row(i, f)),
+    );
+    if let Some(samples) = diagrams {
+        table(
+            "member,x,n,v,m",
+            &mut (0..model.members.len()).flat_map(|member| {
+                diagram(model, solution, member, samples)
+                    .into_iter()
+                    .map(move |(x, [n, v, m])| row(member, &[x, n, v, m]))
+            }),
+        );
+    }
+    csv
+}
+
+/// Reads and analyzes the JSON `model`.
+fn analyze(model: &str) -> Result<(Model, Solution), String> {
+    let model: Model = serde_json::from_str(model).map_err(|e| format!("invalid model: {e}"))?;
+    let solution = model.solve().map_err(|e| format!("analysis failed: {e}"))?;
+    Ok((model, solution))
+}
+
+/// Formats the results.
+fn results(
+    model: &Model,
+    solution: &Solution,
+    format: Format,
+    diagrams: Option<usize>,
+) -> Result<String, String> {
+    Ok(match format {
+        Format::Json => {
+            let diagrams = diagrams.map(|samples| {
+                (0..model.members.len())
+                    .map(|member| {
+                        diagram(model, solution, member, samples)
+                            .into_iter()
+                            .map(|(_, forces)| forces)
+                            .collect()
+                    })
+                    .collect()
+            });
+            let results = Results { solution, diagrams };
+            let mut json = serde_json::to_string_pretty(&results).map_err(|e| e.to_string())?;
+            json.push('\n');
+            json
+        }
+        Format::Csv => csv(model, solution, diagrams),
+    })
+}
+
+/// Compares the `solution` with the one in the JSON `expected` results.
+fn compare(
+    solution: &Solution,
+    expected: &str,
+    tolerance: &Tolerance,
+) -> Result<Comparison, String> {
+    let mut expected: serde_json::Value =
+        serde_json::from_str(expected).map_err(|e| format!("invalid results: {e}"))?;
+    let expected: Solution = serde_json::from_value(expected["solution"].take())
+        .map_err(|e| format!("invalid results: {e}"))?;
+    solution
+        .compare(&expected, tolerance)
+        .map_err(|e| format!("cannot compare results: {e}"))
+}
+
+/// Table of the largest deviation of each quantity.
+fn report(comparison: &Comparison) -> String {
+    let mut report = "quantity,index,component,expected,actual,difference,allowed\n".to_owned();
+    for d in &comparison.deviations {
+        report.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            d.quantity,
+            d.index,
+            d.component,
+            d.expected,
+            d.actual,
+            d.difference(),
+            d.allowed
+        ));
+    }
+    report
+}
+
+fn main() -> ExitCode {
+    let result = parse(std::env::args().skip(1)).and_then(|args| {
+        if args.help {
+            println!("{USAGE}");
+            return Ok(true);
+        }
+        let model = match args.input.as_deref() {
+            None | Some("-") => {
+                let mut model = String::new();
+                io::stdin()
+                    .read_to_string(&mut model)
+                    .map(|_| model)
+                    .map_err(|e| format!("cannot read standard input: {e}"))
+            }
+            Some(path) => fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}")),
+        }?;
+        let format = args.format.unwrap_or(match &args.output {
+            Some(path) if path.ends_with(".csv") => Format::Csv,
+            _ => Format::Json,
+        });
+        let (model, solution) = analyze(&model)?;
+        let mut passed = true;
+        let output = if let Some(digits) = args.fingerprint {
+            format!("{:016x}\n", solution.fingerprint(digits))
+        } else if let Some(path) = &args.compare {
+            let expected =
+                fs::read_to_string(path).map_err(|e| format!("cannot read {path}: {e}"))?;
+            let tolerance = Tolerance {
+                relative: args.tolerance.unwrap_or(Tolerance::default().relative),
+                ..Tolerance::default()
+            };
+            let comparison = compare(&solution, &expected, &tolerance)?;
+            passed = comparison.passed();
+            report(&comparison)
+        } else {
+            results(&model, &solution, format, args.diagrams)?
+        };
+        match &args.output {
+            Some(path) => fs::write(path, output).map_err(|e| format!("cannot write {path}: {e}")),
+            None => io::stdout()
+                .write_all(output.as_bytes())
+                .map_err(|e| format!("cannot write standard output: {e}")),
+        }?;
+        Ok(passed)
+    });
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("strust-cli: results deviate beyond the tolerance");
+            ExitCode::FAILURE
+        }
+        Err(message) => {
+            eprintln!("strust-cli: {message}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANTILEVER: &str = r#"{
+        "nodes": [{"position": [0, 0]}, {"position": [2, 0]}],
+        "members": [{"nodes": [0, 1], "elasticity": 1, "area": 1, "inertia": 1}],
+        "supports": [{"node": 0, "fixed": [true, true, true]}],
+        "loads": [{"Node": {"node": 1, "force": [0, -1, 0]}}]
+    }"#;
+
+    fn run(model: &str, format: Format, diagrams: Option<usize>) -> Result<String, String> {
+        let (model, solution) = analyze(model)?;
+        results(&model, &solution, format, diagrams)
+    }
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        super::parse(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            args(&["model.json", "-f", "csv", "--diagrams", "5", "-o", "out"]),
+            Ok(Args {
+                input: Some("model.json".to_owned()),
+                output: Some("out".to_owned()),
+                format: Some(Format::Csv),
+                diagrams: Some(5),
+                help: false,
+                ..Args::default()
+            })
+        );
+        assert_eq!(
+            args(&["--fingerprint", "6", "-c", "old.json", "-t", "1e-3"]),
+            Ok(Args {
+                fingerprint: Some(6),
+                compare: Some("old.json".to_owned()),
+                tolerance: Some(1e-3),
+                ..Args::default()
+            })
+        );
+        assert_eq!(
+            args(&["-t", "small"]),
+            Err("invalid tolerance small".to_owned())
+        );
+        assert_eq!(args(&["-"]).unwrap().input.as_deref(), Some("-"));
+        assert!(args(&["-h"]).unwrap().help);
+        assert_eq!(args(&["-f", "xml"]), Err("unknown format xml".to_owned()));
+        assert_eq!(args(&["-o"]), Err("-o requires a value".to_owned()));
+        assert_eq!(args(&["--quiet"]), Err("unknown option --quiet".to_owned()));
+        assert_eq!(args(&["a", "b"]), Err("unexpected argument b".to_owned()));
+    }
+
+    #[test]
+    fn json() {
+        let json = run(CANTILEVER, Format::Json, Some(3)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let reaction = value["solution"]["reactions"][0][2].as_f64().unwrap();
+        assert!((reaction - 2.0).abs() < 1e-4);
+        assert_eq!(value["diagrams"][0].as_array().unwrap().len(), 3);
+        let moment = value["diagrams"][0][0][2].as_f64().unwrap();
+        assert!((moment + 2.0).abs() < 1e-4);
+        let json = run(CANTILEVER, Format::Json, None).unwrap();
+        assert!(!json.contains("diagrams"));
+    }
+
+    #[test]
+    fn csv() {
+        let csv = run(CANTILEVER, Format::Csv, Some(2)).unwrap();
+        let tables: Vec<_> = csv.split("\n\n").collect();
+        assert_eq!(tables.len(), 4);
+        assert!(tables[0].starts_with("node,ux,uy,rz\n0,0,0,0\n1,"));
+        assert!(tables[2].starts_with("member,n1,v1,m1,n2,v2,m2\n0,"));
+        assert_eq!(tables[3].lines().count(), 3);
+        assert!(tables[3].lines().nth(2).unwrap().starts_with("0,2,0,"));
+    }
+
+    #[test]
+    fn compare() {
+        let (_, solution) = analyze(CANTILEVER).unwrap();
+        let expected = run(CANTILEVER, Format::Json, None).unwrap();
+        let comparison = super::compare(&solution, &expected, &Tolerance::default()).unwrap();
+        assert!(comparison.passed());
+        let stiffer = CANTILEVER.replace(r#""inertia": 1"#, r#""inertia": 2"#);
+        let (_, stiffer) = analyze(&stiffer).unwrap();
+        let comparison = super::compare(&stiffer, &expected, &Tolerance::default()).unwrap();
+        assert!(!comparison.passed());
+        let report = report(&comparison);
+        assert_eq!(report.lines().count(), 4);
+        assert!(report
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("displacements,1,"));
+        assert!(super::compare(&solution, "{}", &Tolerance::default())
+            .unwrap_err()
+            .starts_with("invalid results: "));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(run("{", Format::Json, None)
+            .unwrap_err()
+            .starts_with("invalid model: "));
+        let unsupported = CANTILEVER.replace("true, true, true", "false, false, false");
+        assert_eq!(
+            run(&unsupported, Format::Json, None),
+            Err("analysis failed: structure is unstable".to_owned())
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Cooperative cancellation of long computations, which check a shared flag at safe points and
+//! stop with [`Cancelled`] once it is set.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Flag shared by its clones, set by [`Self::cancel`] to stop the computations it was passed to.
+/// A token never cancelled costs a relaxed load per check.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the computations to stop at their next check. It cannot be undone.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fails with the progress `done` out of `total` if cancelled.
+    pub(crate) fn check(&self, done: usize, total: usize) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled { done, total })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Computation stopped through its [`CancelToken`], with how far it got in the units of its
+/// loop, such as the columns of a factorization or the iterations of a solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cancelled {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "cancelled after {} of {}", self.done, self.total)
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Result of `run` with a token no one else holds, which cannot be cancelled.
+pub(crate) fn uncancelled<T>(run: impl FnOnce(&CancelToken) -> Result<T, Cancelled>) -> T {
+    run(&CancelToken::new()).unwrap_or_else(|_| unreachable!("cancelled without a clone"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert_eq!(token.check(1, 2), Ok(()));
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(token.check(1, 2), Err(Cancelled { done: 1, total: 2 }));
+        assert!(!CancelToken::new().is_cancelled());
+    }
+}
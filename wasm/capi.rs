@@ -0,0 +1,570 @@
+//! C interface for embedding the solver outside JavaScript, declared in `include/strust.h`.
+//!
+//! Models and sections are opaque handles created by `strust_*_new` and released by
+//! `strust_*_free`. Functions return a [`StrustStatus`], with their results written through
+//! pointers, and copy arrays of results into buffers provided by the caller: the number of values
+//! is written to `required`, and a null buffer or one too short returns
+//! [`StrustStatus::BufferTooSmall`] without copying, so that it can be queried first. `strust_float`
+//! is `double`, or `float` with the `f32` feature. A panic is returned as
+//! [`StrustStatus::Panicked`] instead of unwinding into the caller.
+
+use crate::{
+    model::{
+        beam::section::{
+            circle::CircleSection, combined::CombinedSection, rectangle::RectangleSection,
+            rotated::RotatedSection, translated::TranslatedSection, Section as _,
+        },
+        frame::{self, Load, Member, ModelError, Solution, Support},
+    },
+    Float,
+};
+use std::{
+    ffi::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+/// Result of a function of the C interface.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrustStatus {
+    Ok = 0,
+    /// A handle or output pointer is null.
+    NullPointer = 1,
+    /// A member, support or load refers to a node that does not exist.
+    InvalidNode = 2,
+    /// A load refers to a member that does not exist.
+    InvalidMember = 3,
+    /// A member connects coincident nodes.
+    ZeroLength = 4,
+    /// The structure is a mechanism.
+    Unstable = 5,
+    /// The model has not been solved since it last changed.
+    NotSolved = 6,
+    /// The buffer is null or shorter than the number of values, written to `required`.
+    BufferTooSmall = 7,
+    /// The analysis was cancelled.
+    Cancelled = 8,
+    /// The call panicked, and the handles it was given may be partly updated.
+    Panicked = 9,
+}
+
+impl From<ModelError> for StrustStatus {
+    fn from(error: ModelError) -> Self {
+        match error {
+            ModelError::InvalidNode { .. } => StrustStatus::InvalidNode,
+            ModelError::InvalidMember { .. } => StrustStatus::InvalidMember,
+            ModelError::ZeroLength { .. } => StrustStatus::ZeroLength,
+            ModelError::Unstable => StrustStatus::Unstable,
+            ModelError::Cancelled { .. } => StrustStatus::Cancelled,
+        }
+    }
+}
+
+/// Description of a status as a static null-terminated string.
+#[no_mangle]
+pub extern "C" fn strust_status_message(status: StrustStatus) -> *const c_char {
+    let message: &'static [u8] = match status {
+        StrustStatus::Ok => b"ok\0",
+        StrustStatus::NullPointer => b"null pointer\0",
+        StrustStatus::InvalidNode => b"node does not exist\0",
+        StrustStatus::InvalidMember => b"member does not exist\0",
+        StrustStatus::ZeroLength => b"member has no length\0",
+        StrustStatus::Unstable => b"structure is unstable\0",
+        StrustStatus::NotSolved => b"model is not solved\0",
+        StrustStatus::BufferTooSmall => b"buffer is too small\0",
+        StrustStatus::Cancelled => b"analysis was cancelled\0",
+        StrustStatus::Panicked => b"call panicked\0",
+    };
+    message.as_ptr().cast()
+}
+
+/// Plane frame with the results of its last analysis.
+pub struct StrustModel {
+    model: frame::Model,
+    solution: Option<Solution>,
+}
+
+/// Section combined from rectangles and circles.
+pub struct StrustSection {
+    section: CombinedSection,
+}
+
+/// Runs `f`, returning [`StrustStatus::Panicked`] if it panics instead of unwinding into the
+/// caller.
+fn guard(f: impl FnOnce() -> StrustStatus) -> StrustStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(StrustStatus::Panicked)
+}
+
+/// Writes `value` through `out` unless it is null.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn write<T>(out: *mut T, value: T) {
+    if let Some(out) = out.as_mut() {
+        *out = value;
+    }
+}
+
+/// Copies `values` into the `buffer` of `len` elements if they fit, writing their number to
+/// `required`.
+///
+/// # Safety
+///
+/// `buffer` must be null or valid for `len` writes, and `required` null or valid for a write.
+unsafe fn copy(
+    values: impl ExactSizeIterator<Item = Float>,
+    buffer: *mut Float,
+    len: usize,
+    required: *mut usize,
+) -> StrustStatus {
+    write(required, values.len());
+    if buffer.is_null() || len < values.len() {
+        return StrustStatus::BufferTooSmall;
+    }
+    for (i, value) in values.enumerate() {
+        *buffer.add(i) = value;
+    }
+    StrustStatus::Ok
+}
+
+/// Applies `f` to the model behind the handle, discarding its results.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle.
+unsafe fn edit(model: *mut StrustModel, f: impl FnOnce(&mut frame::Model)) -> StrustStatus {
+    match model.as_mut() {
+        Some(model) => {
+            model.solution = None;
+            f(&mut model.model);
+            StrustStatus::Ok
+        }
+        None => StrustStatus::NullPointer,
+    }
+}
+
+/// Empty model, to be released by [`strust_model_free`].
+#[no_mangle]
+pub extern "C" fn strust_model_new() -> *mut StrustModel {
+    Box::into_raw(Box::new(StrustModel {
+        model: frame::Model::new(),
+        solution: None,
+    }))
+}
+
+/// Releases a model. Null is ignored.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle, which is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_free(model: *mut StrustModel) {
+    if !model.is_null() {
+        drop(Box::from_raw(model));
+    }
+}
+
+/// Adds a node, writing its index to `index` unless it is null.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle, and `index` null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_add_node(
+    model: *mut StrustModel,
+    x: Float,
+    y: Float,
+    index: *mut usize,
+) -> StrustStatus {
+    guard(|| edit(model, |model| write(index, model.add_node([x, y]))))
+}
+
+/// Adds a member between the nodes `a` and `b`, writing its index to `index` unless it is null.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle, and `index` null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_add_member(
+    model: *mut StrustModel,
+    a: usize,
+    b: usize,
+    elasticity: Float,
+    area: Float,
+    inertia: Float,
+    index: *mut usize,
+) -> StrustStatus {
+    guard(|| {
+        edit(model, |model| {
+            let member = model.add_member(Member {
+                nodes: [a, b],
+                elasticity,
+                area,
+                inertia,
+            });
+            write(index, member)
+        })
+    })
+}
+
+/// Restrains the x and y displacements and the rotation of the `node` as flagged.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_add_support(
+    model: *mut StrustModel,
+    node: usize,
+    x: bool,
+    y: bool,
+    rotation: bool,
+) -> StrustStatus {
+    guard(|| {
+        edit(model, |model| {
+            model.add_support(Support {
+                node,
+                fixed: [x, y, rotation],
+            })
+        })
+    })
+}
+
+/// Adds the forces along the global axes and the moment counterclockwise at the `node`.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_add_load(
+    model: *mut StrustModel,
+    node: usize,
+    fx: Float,
+    fy: Float,
+    moment: Float,
+) -> StrustStatus {
+    guard(|| {
+        edit(model, |model| {
+            model.add_load(Load::Node {
+                node,
+                force: [fx, fy, moment],
+            })
+        })
+    })
+}
+
+/// Adds a uniform load per length along the global axes on the `member`.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_add_member_load(
+    model: *mut StrustModel,
+    member: usize,
+    wx: Float,
+    wy: Float,
+) -> StrustStatus {
+    guard(|| {
+        edit(model, |model| {
+            model.add_load(Load::Member {
+                member,
+                load: [wx, wy],
+            })
+        })
+    })
+}
+
+/// Analyzes the model, keeping the results for the functions reading them.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_solve(model: *mut StrustModel) -> StrustStatus {
+    guard(|| {
+        let Some(model) = model.as_mut() else {
+            return StrustStatus::NullPointer;
+        };
+        match model.model.solve() {
+            Ok(solution) => {
+                model.solution = Some(solution);
+                StrustStatus::Ok
+            }
+            Err(error) => error.into(),
+        }
+    })
+}
+
+/// Copies the results selected by `f` from the last analysis into the buffer.
+///
+/// # Safety
+///
+/// As [`copy`], with `model` null or a live handle.
+unsafe fn results<const N: usize>(
+    model: *const StrustModel,
+    f: impl FnOnce(&Solution) -> &[[Float; N]],
+    buffer: *mut Float,
+    len: usize,
+    required: *mut usize,
+) -> StrustStatus {
+    match model.as_ref() {
+        Some(StrustModel {
+            solution: Some(solution),
+            ..
+        }) => copy(
+            f(solution).as_flattened().iter().copied(),
+            buffer,
+            len,
+            required,
+        ),
+        Some(_) => StrustStatus::NotSolved,
+        None => StrustStatus::NullPointer,
+    }
+}
+
+/// Copies the displacements and rotation of each node, three values per node.
+///
+/// # Safety
+///
+/// `model` must be null or a live handle, `buffer` null or valid for `len` writes, and
+/// `required` null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_displacements(
+    model: *const StrustModel,
+    buffer: *mut Float,
+    len: usize,
+    required: *mut usize,
+) -> StrustStatus {
+    guard(|| results(model, |s| &s.displacements, buffer, len, required))
+}
+
+/// Copies the reactions of each node, three values per node.
+///
+/// # Safety
+///
+/// As [`strust_model_displacements`].
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_reactions(
+    model: *const StrustModel,
+    buffer: *mut Float,
+    len: usize,
+    required: *mut usize,
+) -> StrustStatus {
+    guard(|| results(model, |s| &s.reactions, buffer, len, required))
+}
+
+/// Copies the end forces of each member in its local axes, six values per member.
+///
+/// # Safety
+///
+/// As [`strust_model_displacements`].
+#[no_mangle]
+pub unsafe extern "C" fn strust_model_member_forces(
+    model: *const StrustModel,
+    buffer: *mut Float,
+    len: usize,
+    required: *mut usize,
+) -> StrustStatus {
+    guard(|| results(model, |s| &s.member_forces, buffer, len, required))
+}
+
+/// Empty section, to be released by [`strust_section_free`].
+#[no_mangle]
+pub extern "C" fn strust_section_new() -> *mut StrustSection {
+    Box::into_raw(Box::new(StrustSection {
+        section: CombinedSection::default(),
+    }))
+}
+
+/// Releases a section. Null is ignored.
+///
+/// # Safety
+///
+/// `section` must be null or a live handle, which is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn strust_section_free(section: *mut StrustSection) {
+    if !section.is_null() {
+        drop(Box::from_raw(section));
+    }
+}
+
+/// Adds a rectangle with a corner at `(x, y)`, rotated by `angle` in radians around it.
+///
+/// # Safety
+///
+/// `section` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn strust_section_add_rectangle(
+    section: *mut StrustSection,
+    width: Float,
+    height: Float,
+    x: Float,
+    y: Float,
+    angle: Float,
+) -> StrustStatus {
+    guard(|| {
+        let Some(section) = section.as_mut() else {
+            return StrustStatus::NullPointer;
+        };
+        let rectangle = RotatedSection::new(RectangleSection::new([width, height]), angle);
+        section
+            .section
+            .push(TranslatedSection::new(rectangle, [x, y]));
+        StrustStatus::Ok
+    })
+}
+
+/// Adds a circle centered at `(x, y)`.
+///
+/// # Safety
+///
+/// `section` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn strust_section_add_circle(
+    section: *mut StrustSection,
+    radius: Float,
+    x: Float,
+    y: Float,
+) -> StrustStatus {
+    guard(|| {
+        let Some(section) = section.as_mut() else {
+            return StrustStatus::NullPointer;
+        };
+        section
+            .section
+            .push(TranslatedSection::new(CircleSection::new(radius), [x, y]));
+        StrustStatus::Ok
+    })
+}
+
+/// Writes the area, the centroid, the moments of inertia about the axes through the origin and
+/// the product of inertia, six values, to `properties`.
+///
+/// # Safety
+///
+/// `section` must be null or a live handle, and `properties` null or valid for six writes.
+#[no_mangle]
+pub unsafe extern "C" fn strust_section_properties(
+    section: *const StrustSection,
+    properties: *mut Float,
+) -> StrustStatus {
+    guard(|| {
+        let (Some(section), false) = (section.as_ref(), properties.is_null()) else {
+            return StrustStatus::NullPointer;
+        };
+        let s = &section.section;
+        let [cx, cy] = s.centroid();
+        let [iy, ix] = s.moment_of_inertia();
+        let values = [s.area(), cx, cy, ix, iy, s.product_of_inertia()];
+        ptr::copy_nonoverlapping(values.as_ptr(), properties, values.len());
+        StrustStatus::Ok
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn model() {
+        unsafe {
+            let model = strust_model_new();
+            let (mut a, mut b) = (usize::MAX, usize::MAX);
+            assert_eq!(
+                strust_model_add_node(model, 0.0, 0.0, &mut a),
+                StrustStatus::Ok
+            );
+            assert_eq!(
+                strust_model_add_node(model, 2.0, 0.0, &mut b),
+                StrustStatus::Ok
+            );
+            assert_eq!((a, b), (0, 1));
+            let member = strust_model_add_member(model, a, b, 1.0, 1.0, 1.0, ptr::null_mut());
+            assert_eq!(member, StrustStatus::Ok);
+            assert_eq!(
+                strust_model_solve(model),
+                StrustStatus::Unstable,
+                "{:?}",
+                CStr::from_ptr(strust_status_message(StrustStatus::Unstable))
+            );
+            strust_model_add_support(model, a, true, true, true);
+            strust_model_add_load(model, b, 0.0, -1.0, 0.0);
+            let mut required = 0;
+            assert_eq!(
+                strust_model_reactions(model, ptr::null_mut(), 0, &mut required),
+                StrustStatus::NotSolved
+            );
+            assert_eq!(strust_model_solve(model), StrustStatus::Ok);
+            // Queried first, then copied.
+            assert_eq!(
+                strust_model_reactions(model, ptr::null_mut(), 0, &mut required),
+                StrustStatus::BufferTooSmall
+            );
+            assert_eq!(required, 6);
+            let mut reactions = vec![0.0; required];
+            let status = strust_model_reactions(model, reactions.as_mut_ptr(), 6, &mut required);
+            assert_eq!(status, StrustStatus::Ok);
+            assert!((reactions[1] - 1.0).abs() < 1e-4 && (reactions[2] - 2.0).abs() < 1e-4);
+            let mut forces = [0.0; 6];
+            let status = strust_model_member_forces(model, forces.as_mut_ptr(), 6, ptr::null_mut());
+            assert_eq!(status, StrustStatus::Ok);
+            let mut displacements = [0.0; 6];
+            let status =
+                strust_model_displacements(model, displacements.as_mut_ptr(), 6, ptr::null_mut());
+            assert_eq!(status, StrustStatus::Ok);
+            assert!(displacements[4] < 0.0);
+            strust_model_add_member_load(model, 0, 0.0, -1.0);
+            assert_eq!(
+                strust_model_displacements(model, displacements.as_mut_ptr(), 6, ptr::null_mut()),
+                StrustStatus::NotSolved
+            );
+            strust_model_add_member(model, b, b, 1.0, 1.0, 1.0, ptr::null_mut());
+            assert_eq!(strust_model_solve(model), StrustStatus::ZeroLength);
+            strust_model_free(model);
+            assert_eq!(
+                strust_model_solve(ptr::null_mut()),
+                StrustStatus::NullPointer
+            );
+            strust_model_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn section() {
+        unsafe {
+            let section = strust_section_new();
+            strust_section_add_rectangle(section, 2.0, 4.0, 0.0, 0.0, 0.0);
+            let mut properties = [0.0; 6];
+            let status = strust_section_properties(section, properties.as_mut_ptr());
+            assert_eq!(status, StrustStatus::Ok);
+            assert_eq!(properties[..3], [8.0, 1.0, 2.0]);
+            // About x from the height, about y from the width.
+            assert!((properties[3] - 128.0 / 3.0).abs() < 1e-4);
+            assert!((properties[4] - 32.0 / 3.0).abs() < 1e-4);
+            strust_section_add_circle(section, 1.0, 10.0, 0.0);
+            strust_section_properties(section, properties.as_mut_ptr());
+            assert!(properties[0] > 8.0);
+            assert_eq!(
+                strust_section_properties(section, ptr::null_mut()),
+                StrustStatus::NullPointer
+            );
+            strust_section_free(section);
+        }
+    }
+
+    #[test]
+    fn panic() {
+        assert_eq!(guard(|| panic!("solver bug")), StrustStatus::Panicked);
+        assert_eq!(guard(|| StrustStatus::Ok), StrustStatus::Ok);
+    }
+
+    #[test]
+    fn messages() {
+        let message = unsafe { CStr::from_ptr(strust_status_message(StrustStatus::Unstable)) };
+        assert_eq!(message.to_str(), Ok("structure is unstable"));
+    }
+}
@@ -0,0 +1,24 @@
+//! Wall clock for timing analyses, reading `performance.now()` in wasm, where
+//! [`std::time::Instant`] panics. The global `performance` is that of pages, workers and Node.js
+//! alike.
+
+/// Milliseconds since an arbitrary origin.
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> f64 {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        fn performance_now() -> f64;
+    }
+    performance_now()
+}
+
+/// Milliseconds since an arbitrary origin.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> f64 {
+    use std::{sync::OnceLock, time::Instant};
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed().as_secs_f64() * 1e3
+}
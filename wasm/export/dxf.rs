@@ -0,0 +1,202 @@
+//! Drawings in the ASCII Drawing Exchange Format of AutoCAD R12, the version most CAD software
+//! reads.
+
+use crate::{geometry::path::Path, math::complex::Complex, model::frame::Model, Float};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Layer of the members drawn by [`Dxf::model`].
+pub const MEMBERS: &str = "MEMBERS";
+/// Layer of the nodes drawn by [`Dxf::model`].
+pub const NODES: &str = "NODES";
+/// Layer of the member labels drawn by [`Dxf::model`].
+pub const LABELS: &str = "LABELS";
+/// Layer suggested for the outlines of sections.
+pub const SECTIONS: &str = "SECTIONS";
+
+/// DXF drawing of entities on named layers, written out by its [`Display`] implementation.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Dxf {
+    /// Layers in the order of their first use.
+    layers: Vec<String>,
+    /// Group codes and values of the entities.
+    entities: String,
+}
+
+impl Dxf {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entity(&mut self, kind: &str, layer: &str) -> &mut String {
+        if !self.layers.iter().any(|l| l == layer) {
+            self.layers.push(layer.to_owned());
+        }
+        write!(self.entities, "0\n{kind}\n8\n{layer}\n").unwrap();
+        &mut self.entities
+    }
+
+    pub fn line(&mut self, layer: &str, from: Complex<Float>, to: Complex<Float>) -> &mut Self {
+        let e = self.entity("LINE", layer);
+        write!(e, "10\n{}\n20\n{}\n30\n0\n", from.re, from.im).unwrap();
+        write!(e, "11\n{}\n21\n{}\n31\n0\n", to.re, to.im).unwrap();
+        self
+    }
+
+    pub fn point(&mut self, layer: &str, at: Complex<Float>) -> &mut Self {
+        let e = self.entity("POINT", layer);
+        write!(e, "10\n{}\n20\n{}\n30\n0\n", at.re, at.im).unwrap();
+        self
+    }
+
+    /// Single line of `text` with its baseline centered at `at`.
+    pub fn text(
+        &mut self,
+        layer: &str,
+        at: Complex<Float>,
+        height: Float,
+        text: &str,
+    ) -> &mut Self {
+        let e = self.entity("TEXT", layer);
+        write!(
+            e,
+            "10\n{}\n20\n{}\n30\n0\n40\n{height}\n1\n{text}\n",
+            at.re, at.im
+        )
+        .unwrap();
+        write!(e, "72\n1\n11\n{}\n21\n{}\n31\n0\n", at.re, at.im).unwrap();
+        self
+    }
+
+    /// Polyline through the `points`, back to the first if `closed`.
+    pub fn polyline(&mut self, layer: &str, points: &[Complex<Float>], closed: bool) -> &mut Self {
+        let e = self.entity("POLYLINE", layer);
+        write!(e, "66\n1\n10\n0\n20\n0\n30\n0\n70\n{}\n", u8::from(closed)).unwrap();
+        for p in points {
+            let e = self.entity("VERTEX", layer);
+            write!(e, "10\n{}\n20\n{}\n30\n0\n", p.re, p.im).unwrap();
+        }
+        self.entity("SEQEND", layer);
+        self
+    }
+
+    /// Subpaths of the `path` flattened within `tolerance` into polylines.
+    pub fn path(
+        &mut self,
+        layer: &str,
+        path: &Path<Complex<Float>, Float>,
+        tolerance: Float,
+    ) -> &mut Self {
+        for subpath in path.subpaths() {
+            let mut points: Vec<_> = subpath.flatten(tolerance).collect();
+            let closed = subpath.is_closed();
+            if closed && points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            self.polyline(layer, &points, closed);
+        }
+        self
+    }
+
+    /// Members of the `model` as lines, its nodes as points and the indices of the members as
+    /// labels of `height` at their midpoints, on the layers [`MEMBERS`], [`NODES`] and
+    /// [`LABELS`].
+    pub fn model(&mut self, model: &Model, height: Float) -> &mut Self {
+        let point = |n: usize| {
+            let [x, y] = model.nodes[n].position;
+            Complex::new(x, y)
+        };
+        for (i, member) in model.members.iter().enumerate() {
+            let [a, b] = member.nodes.map(point);
+            self.line(MEMBERS, a, b);
+            self.text(LABELS, (a + b) / 2.0, height, &i.to_string());
+        }
+        for n in 0..model.nodes.len() {
+            self.point(NODES, point(n));
+        }
+        self
+    }
+}
+
+impl Display for Dxf {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "0\nSECTION\n2\nTABLES\n0\nTABLE\n2\nLAYER\n70\n{}\n",
+            self.layers.len()
+        )?;
+        for (i, layer) in self.layers.iter().enumerate() {
+            // Colors from the seven standard ones of AutoCAD.
+            write!(
+                f,
+                "0\nLAYER\n2\n{layer}\n70\n0\n62\n{}\n6\nCONTINUOUS\n",
+                i % 7 + 1
+            )?;
+        }
+        f.write_str("0\nENDTAB\n0\nENDSEC\n0\nSECTION\n2\nENTITIES\n")?;
+        f.write_str(&self.entities)?;
+        f.write_str("0\nENDSEC\n0\nEOF\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::frame::Member;
+
+    #[test]
+    fn line() {
+        let mut dxf = Dxf::new();
+        dxf.line("A", Complex::new(0.0, 1.0), Complex::new(2.0, 3.0));
+        assert_eq!(
+            dxf.to_string(),
+            concat!(
+                "0\nSECTION\n2\nTABLES\n0\nTABLE\n2\nLAYER\n70\n1\n",
+                "0\nLAYER\n2\nA\n70\n0\n62\n1\n6\nCONTINUOUS\n",
+                "0\nENDTAB\n0\nENDSEC\n0\nSECTION\n2\nENTITIES\n",
+                "0\nLINE\n8\nA\n10\n0\n20\n1\n30\n0\n11\n2\n21\n3\n31\n0\n",
+                "0\nENDSEC\n0\nEOF\n",
+            )
+        );
+    }
+
+    #[test]
+    fn path() {
+        let mut square = Path::new();
+        square
+            .move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(1.0, 0.0))
+            .line_to(Complex::new(1.0, 1.0))
+            .close_path()
+            .move_to(Complex::new(5.0, 5.0))
+            .line_to(Complex::new(6.0, 5.0));
+        let mut dxf = Dxf::new();
+        dxf.path(SECTIONS, &square, 0.1);
+        let s = dxf.to_string();
+        assert_eq!(s.matches("0\nPOLYLINE\n").count(), 2);
+        assert_eq!(s.matches("0\nVERTEX\n").count(), 5);
+        assert_eq!(s.matches("0\nSEQEND\n").count(), 2);
+        assert!(s.contains("70\n1\n0\nVERTEX") && s.contains("70\n0\n0\nVERTEX"));
+    }
+
+    #[test]
+    fn model() {
+        let mut model = Model::new();
+        let nodes = [[0.0, 0.0], [0.0, 4.0], [6.0, 4.0]].map(|p| model.add_node(p));
+        for pair in nodes.windows(2) {
+            model.add_member(Member {
+                nodes: [pair[0], pair[1]],
+                elasticity: 1.0,
+                area: 1.0,
+                inertia: 1.0,
+            });
+        }
+        let mut dxf = Dxf::new();
+        dxf.model(&model, 0.25);
+        let s = dxf.to_string();
+        assert!(s.contains("70\n3\n0\nLAYER\n2\nMEMBERS\n"));
+        assert_eq!(s.matches("0\nLINE\n8\nMEMBERS\n").count(), 2);
+        assert_eq!(s.matches("0\nPOINT\n8\nNODES\n").count(), 3);
+        // The label of the beam at its midpoint.
+        assert!(s.contains("0\nTEXT\n8\nLABELS\n10\n3\n20\n4\n30\n0\n40\n0.25\n1\n1\n"));
+    }
+}
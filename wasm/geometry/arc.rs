@@ -0,0 +1,285 @@
+use super::bezier::CubicBezier;
+use crate::{
+    math::{affine2::Affine2, complex::Complex},
+    Float,
+};
+
+/// Elliptical arc in center parameterization, the points `center + R(rotation) (radius.re cos θ,
+/// radius.im sin θ)` for `θ` from `start` to `start + sweep`, with all angles in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EllipticalArc {
+    pub center: Complex<Float>,
+    pub radius: Complex<Float>,
+    pub rotation: Float,
+    pub start: Float,
+    /// Signed, positive counterclockwise.
+    pub sweep: Float,
+}
+
+impl EllipticalArc {
+    /// Converts an SVG arc from `from` to `to`, with the axis rotation in degrees, as described in
+    /// the implementation notes of SVG. Radii too small to reach `to` are scaled up uniformly.
+    ///
+    /// Returns `None` if the end points coincide or a radius is zero, when SVG draws nothing or a
+    /// line respectively.
+    pub fn from_endpoints(
+        from: Complex<Float>,
+        radius: Complex<Float>,
+        axis_rotation: Float,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        to: Complex<Float>,
+    ) -> Option<Self> {
+        let (mut rx, mut ry) = (radius.re.abs(), radius.im.abs());
+        if from == to || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+        let rotation = axis_rotation.to_radians();
+        let h = Affine2::from_rotation(&-rotation).apply_vector(&((from - to) / 2.0));
+        let lambda = (h.re / rx).powi(2) + (h.im / ry).powi(2);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let (rx2, ry2, x2, y2) = (rx * rx, ry * ry, h.re * h.re, h.im * h.im);
+        let mut coefficient = ((rx2 * ry2 - rx2 * y2 - ry2 * x2) / (rx2 * y2 + ry2 * x2))
+            .max(0.0)
+            .sqrt();
+        if large_arc_flag == sweep_flag {
+            coefficient = -coefficient;
+        }
+        let c = Complex::new(rx * h.im / ry, -ry * h.re / rx) * coefficient;
+        let start = Complex::new((h.re - c.re) / rx, (h.im - c.im) / ry).arg();
+        let end = Complex::new((-h.re - c.re) / rx, (-h.im - c.im) / ry).arg();
+        let mut sweep = end - start;
+        if sweep_flag && sweep < 0.0 {
+            sweep += (360.0 as Float).to_radians();
+        } else if !sweep_flag && sweep > 0.0 {
+            sweep -= (360.0 as Float).to_radians();
+        }
+        Some(Self {
+            center: Affine2::from_rotation(&rotation).apply_vector(&c) + (from + to) / 2.0,
+            radius: Complex::new(rx, ry),
+            rotation,
+            start,
+            sweep,
+        })
+    }
+
+    /// Transform mapping the unit circle onto the ellipse, the point at angle `θ` of the unit
+    /// circle to the point at `θ` of the arc.
+    pub fn ellipse(&self) -> Affine2<Float> {
+        Affine2::from_translation(self.center)
+            * Affine2::from_rotation(&self.rotation)
+            * Affine2::from_scale(self.radius.re, self.radius.im)
+    }
+
+    /// Angle of the parameter `t` from 0 at the start to 1 at the end.
+    pub fn angle_at(&self, t: Float) -> Float {
+        self.start + self.sweep * t
+    }
+
+    /// Point at the parameter `t` from 0 at the start to 1 at the end, proportional to the angle.
+    pub fn point_at(&self, t: Float) -> Complex<Float> {
+        let angle = self.angle_at(t);
+        self.ellipse()
+            .apply(&Complex::new(angle.cos(), angle.sin()))
+    }
+
+    /// Derivative of [`Self::point_at`] with respect to `t`.
+    pub fn tangent_at(&self, t: Float) -> Complex<Float> {
+        let angle = self.angle_at(t);
+        self.ellipse()
+            .apply_vector(&Complex::new(-angle.sin(), angle.cos()))
+            * self.sweep
+    }
+
+    /// Cubic Bézier curves within `tolerance` of the arc, from its start to its end, splitting it
+    /// evenly into arcs of at most a quarter turn.
+    ///
+    /// Each piece is the image of the cubic curve with the control points `4/3 tan(φ/4)` along the
+    /// end tangents of a unit circle arc of angle `φ`, whose distance from the circle is at most
+    /// `4/27 sin⁶(φ/4) / cos²(φ/4)`, scaled by the larger radius.
+    pub fn to_cubics(&self, tolerance: Float) -> Vec<CubicBezier> {
+        let error = |angle: Float| {
+            let (sin, cos) = (angle / 4.0).sin_cos();
+            4.0 / 27.0 * sin.powi(6) / (cos * cos) * self.radius.re.max(self.radius.im)
+        };
+        let quarter = (90.0 as Float).to_radians();
+        let mut count = (self.sweep.abs() / quarter).ceil().max(1.0) as usize;
+        // Halving the angle divides the error by about 64, so this ends before the precision.
+        while error(self.sweep / count as Float) > tolerance && count < 1 << 16 {
+            count *= 2;
+        }
+        let ellipse = self.ellipse();
+        let step = self.sweep / count as Float;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let unit = |angle: Float| Complex::new(angle.cos(), angle.sin());
+        (0..count)
+            .map(|i| {
+                let (p, q) = (
+                    unit(self.start + step * i as Float),
+                    unit(self.start + step * (i + 1) as Float),
+                );
+                let (cp1, cp2) = (p + left(p) * k, q - left(q) * k);
+                CubicBezier::new(
+                    ellipse.apply(&p),
+                    ellipse.apply(&cp1),
+                    ellipse.apply(&cp2),
+                    ellipse.apply(&q),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Tangent of the unit circle at `p` counterclockwise.
+fn left(p: Complex<Float>) -> Complex<Float> {
+    Complex::new(-p.im, p.re)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn from_endpoints() {
+        let (from, to) = (Complex::new(1.0, 0.0), Complex::new(0.0, 1.0));
+        let radius = Complex::new(1.0, 1.0);
+        // The four arcs of the two unit circles through both points.
+        let a = EllipticalArc::from_endpoints(from, radius, 0.0, false, true, to).unwrap();
+        assert_abs_diff_eq!(
+            a.center,
+            Complex::new(0.0, 0.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert!((a.sweep - (90.0 as Float).to_radians()).abs() < Float::EPSILON * 1e1);
+        let b = EllipticalArc::from_endpoints(from, radius, 0.0, true, true, to).unwrap();
+        assert_abs_diff_eq!(
+            b.center,
+            Complex::new(1.0, 1.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert!((b.sweep - (270.0 as Float).to_radians()).abs() < Float::EPSILON * 1e1);
+        let c = EllipticalArc::from_endpoints(from, radius, 0.0, false, false, to).unwrap();
+        assert_abs_diff_eq!(
+            c.center,
+            Complex::new(1.0, 1.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert!((c.sweep + (90.0 as Float).to_radians()).abs() < Float::EPSILON * 1e1);
+        let d = EllipticalArc::from_endpoints(from, radius, 0.0, true, false, to).unwrap();
+        assert_abs_diff_eq!(
+            d.center,
+            Complex::new(0.0, 0.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        for arc in [a, b, c, d] {
+            assert_abs_diff_eq!(arc.point_at(0.0), from, epsilon = Float::EPSILON * 1e1);
+            assert_abs_diff_eq!(arc.point_at(1.0), to, epsilon = Float::EPSILON * 1e1);
+        }
+        // Scaled up to a half turn, where the center is the square root of a rounding error.
+        let e = EllipticalArc::from_endpoints(from, radius * 0.1, 0.0, false, true, -from).unwrap();
+        assert_abs_diff_eq!(e.radius, radius, epsilon = Float::EPSILON * 1e1);
+        assert!((e.point_at(0.5) - Complex::new(0.0, 1.0)).abs() < Float::EPSILON.sqrt());
+        assert_eq!(
+            EllipticalArc::from_endpoints(from, Complex::new(0.0, 1.0), 0.0, false, true, to),
+            None
+        );
+        assert_eq!(
+            EllipticalArc::from_endpoints(from, radius, 0.0, false, true, from),
+            None
+        );
+    }
+
+    #[test]
+    fn rotated() {
+        // A quarter of an ellipse with the major axis along the diagonal.
+        let rotation = Affine2::from_rotation(&(45.0 as Float).to_radians());
+        let (rx, ry) = ((8.0 as Float).sqrt(), 1.0);
+        let from = rotation.apply(&Complex::new(rx, 0.0));
+        let to = rotation.apply(&Complex::new(0.0, ry));
+        let a = EllipticalArc::from_endpoints(from, Complex::new(rx, ry), 45.0, false, true, to)
+            .unwrap();
+        assert_abs_diff_eq!(
+            a.center,
+            Complex::new(0.0, 0.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        let half = (0.5 as Float).sqrt();
+        assert_abs_diff_eq!(
+            a.point_at(0.5),
+            rotation.apply(&Complex::new(rx * half, ry * half)),
+            epsilon = Float::EPSILON * 1e1
+        );
+        // The tangent is the derivative of the point, parallel to the major axis at the end.
+        let h = 1e-2;
+        let difference = (a.point_at(0.5 + h) - a.point_at(0.5 - h)) / (2.0 * h);
+        assert!((a.tangent_at(0.5) - difference).abs() < 1e-3);
+        let t = a.tangent_at(1.0);
+        assert!((t.re - t.im).abs() < Float::EPSILON * 1e2 && t.re < 0.0);
+    }
+
+    #[test]
+    fn to_cubics() {
+        let quarter = EllipticalArc::from_endpoints(
+            Complex::new(1.0, 0.0),
+            Complex::new(1.0, 1.0),
+            0.0,
+            false,
+            true,
+            Complex::new(0.0, 1.0),
+        )
+        .unwrap();
+        // The usual approximation, with the control points 0.5523 along the tangents.
+        let cubics = quarter.to_cubics(1e-3);
+        assert_eq!(cubics.len(), 1);
+        let k = 4.0 / 3.0 * ((2.0 as Float).sqrt() - 1.0);
+        assert_abs_diff_eq!(
+            cubics[0].cp1,
+            Complex::new(1.0, k),
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert_abs_diff_eq!(
+            cubics[0].cp2,
+            Complex::new(k, 1.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert!(quarter.to_cubics(1e-5).len() > 1);
+        // A rotated ellipse turning clockwise more than a half.
+        let a = EllipticalArc::from_endpoints(
+            Complex::new(2.0, 0.0),
+            Complex::new(3.0, 1.0),
+            30.0,
+            true,
+            false,
+            Complex::new(0.0, 1.0),
+        )
+        .unwrap();
+        let tolerance = 1e-4;
+        let cubics = a.to_cubics(tolerance);
+        assert!(cubics.len() >= 3);
+        assert_abs_diff_eq!(
+            cubics[0].from,
+            a.point_at(0.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert_abs_diff_eq!(
+            cubics[cubics.len() - 1].to,
+            a.point_at(1.0),
+            epsilon = Float::EPSILON * 1e1
+        );
+        let inverse = a.ellipse().inv();
+        for pair in cubics.windows(2) {
+            assert_eq!(pair[0].to, pair[1].from);
+        }
+        for cubic in &cubics {
+            for i in 0..=10 {
+                let p = inverse.apply(&cubic.point_at(i as Float / 10.0));
+                assert!((p.abs() - 1.0).abs() * 3.0 <= tolerance);
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{math::complex::Complex, Float};
+
+type Point = Complex<Float>;
+
+fn lerp(a: Point, b: Point, t: Float) -> Point {
+    a + (b - a) * t
+}
+
+/// Cubic Bézier curve parameterized by `t` from 0 at `from` to 1 at `to`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier {
+    pub from: Point,
+    pub cp1: Point,
+    pub cp2: Point,
+    pub to: Point,
+}
+
+impl CubicBezier {
+    pub const fn new(from: Point, cp1: Point, cp2: Point, to: Point) -> Self {
+        Self { from, cp1, cp2, to }
+    }
+
+    /// Point at the parameter `t`, by de Casteljau's algorithm.
+    pub fn point_at(&self, t: Float) -> Point {
+        self.split(t).0.to
+    }
+
+    /// Derivative of [`Self::point_at`] with respect to `t`, three times the quadratic curve of
+    /// the differences of the control points.
+    pub fn derivative_at(&self, t: Float) -> Point {
+        SquareBezier::new(
+            self.cp1 - self.from,
+            self.cp2 - self.cp1,
+            self.to - self.cp2,
+        )
+        .point_at(t)
+            * 3.0
+    }
+
+    /// Splits at the parameter `t` into the curves before and after it, which share the point at
+    /// `t` exactly.
+    pub fn split(&self, t: Float) -> (Self, Self) {
+        let (ab, bc, cd) = (
+            lerp(self.from, self.cp1, t),
+            lerp(self.cp1, self.cp2, t),
+            lerp(self.cp2, self.to, t),
+        );
+        let (abc, bcd) = (lerp(ab, bc, t), lerp(bc, cd, t));
+        let m = lerp(abc, bcd, t);
+        (
+            Self::new(self.from, ab, abc, m),
+            Self::new(m, bcd, cd, self.to),
+        )
+    }
+}
+
+/// Quadratic Bézier curve parameterized by `t` from 0 at `from` to 1 at `to`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SquareBezier {
+    pub from: Point,
+    pub cp: Point,
+    pub to: Point,
+}
+
+impl SquareBezier {
+    pub const fn new(from: Point, cp: Point, to: Point) -> Self {
+        Self { from, cp, to }
+    }
+
+    /// Point at the parameter `t`, by de Casteljau's algorithm.
+    pub fn point_at(&self, t: Float) -> Point {
+        self.split(t).0.to
+    }
+
+    /// Derivative of [`Self::point_at`] with respect to `t`.
+    pub fn derivative_at(&self, t: Float) -> Point {
+        lerp(self.cp - self.from, self.to - self.cp, t) * 2.0
+    }
+
+    /// Splits at the parameter `t` like [`CubicBezier::split`].
+    pub fn split(&self, t: Float) -> (Self, Self) {
+        let (ab, bc) = (lerp(self.from, self.cp, t), lerp(self.cp, self.to, t));
+        let m = lerp(ab, bc, t);
+        (Self::new(self.from, ab, m), Self::new(m, bc, self.to))
+    }
+
+    /// The same curve as a cubic one.
+    pub fn to_cubic(&self) -> CubicBezier {
+        CubicBezier::new(
+            self.from,
+            lerp(self.from, self.cp, 2.0 / 3.0),
+            lerp(self.to, self.cp, 2.0 / 3.0),
+            self.to,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn cubic() -> CubicBezier {
+        CubicBezier::new(
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(1.0, 1.0),
+            Complex::new(1.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn cubic_point_at() {
+        let c = cubic();
+        assert_eq!(c.point_at(0.0), c.from);
+        assert_eq!(c.point_at(1.0), c.to);
+        assert_eq!(c.point_at(0.5), Complex::new(0.5, 0.75));
+        // The Bernstein form, (1 - t)³ p0 + 3 (1 - t)² t p1 + 3 (1 - t) t² p2 + t³ p3.
+        let (t, s) = (0.25, 0.75);
+        let bernstein = c.from * (s * s * s)
+            + c.cp1 * (3.0 * s * s * t)
+            + c.cp2 * (3.0 * s * t * t)
+            + c.to * (t * t * t);
+        assert_abs_diff_eq!(c.point_at(t), bernstein, epsilon = Float::EPSILON * 1e1);
+    }
+
+    #[test]
+    fn cubic_derivative_at() {
+        let c = cubic();
+        assert_eq!(c.derivative_at(0.0), (c.cp1 - c.from) * 3.0);
+        assert_eq!(c.derivative_at(1.0), (c.to - c.cp2) * 3.0);
+        assert_eq!(c.derivative_at(0.5), Complex::new(1.5, 0.0));
+        let h = 1e-2;
+        for t in [0.1, 0.4, 0.8] {
+            let difference = (c.point_at(t + h) - c.point_at(t - h)) / (2.0 * h);
+            assert!((c.derivative_at(t) - difference).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn cubic_split() {
+        let c = cubic();
+        let t = 0.25;
+        let (a, b) = c.split(t);
+        assert_eq!(a.from, c.from);
+        assert_eq!(a.to, b.from);
+        assert_eq!(b.to, c.to);
+        assert_abs_diff_eq!(a.to, c.point_at(t), epsilon = Float::EPSILON * 1e1);
+        // Each half is the original curve over its range of the parameter.
+        for u in [0.0, 0.3, 0.6, 1.0] {
+            assert_abs_diff_eq!(
+                a.point_at(u),
+                c.point_at(t * u),
+                epsilon = Float::EPSILON * 1e1
+            );
+            assert_abs_diff_eq!(
+                b.point_at(u),
+                c.point_at(t + (1.0 - t) * u),
+                epsilon = Float::EPSILON * 1e1
+            );
+        }
+        // The derivatives scale with the length of the range.
+        assert_abs_diff_eq!(
+            a.derivative_at(1.0),
+            c.derivative_at(t) * t,
+            epsilon = Float::EPSILON * 1e1
+        );
+        assert_abs_diff_eq!(
+            b.derivative_at(0.0),
+            c.derivative_at(t) * (1.0 - t),
+            epsilon = Float::EPSILON * 1e1
+        );
+        let (a, b) = c.split(0.0);
+        assert_eq!((a.from, a.to), (c.from, c.from));
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn square() {
+        let q = SquareBezier::new(
+            Complex::new(0.0, 0.0),
+            Complex::new(1.0, 2.0),
+            Complex::new(2.0, 0.0),
+        );
+        assert_eq!(q.point_at(0.5), Complex::new(1.0, 1.0));
+        assert_eq!(q.derivative_at(0.0), Complex::new(2.0, 4.0));
+        assert_eq!(q.derivative_at(0.5), Complex::new(2.0, 0.0));
+        let (a, b) = q.split(0.75);
+        assert_eq!(a.to, b.from);
+        for u in [0.0, 0.5, 1.0] {
+            assert_abs_diff_eq!(
+                a.point_at(u),
+                q.point_at(0.75 * u),
+                epsilon = Float::EPSILON * 1e1
+            );
+            assert_abs_diff_eq!(
+                b.point_at(u),
+                q.point_at(0.75 + 0.25 * u),
+                epsilon = Float::EPSILON * 1e1
+            );
+        }
+        // Elevated to a cubic, the curve and its derivative are unchanged.
+        let c = q.to_cubic();
+        for t in [0.0, 0.2, 0.5, 0.9, 1.0] {
+            assert_abs_diff_eq!(c.point_at(t), q.point_at(t), epsilon = Float::EPSILON * 1e1);
+            assert_abs_diff_eq!(
+                c.derivative_at(t),
+                q.derivative_at(t),
+                epsilon = Float::EPSILON * 1e1
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use super::polyline::signed_area;
+use crate::{math::complex::Complex, Float};
+
+type Point = Complex<Float>;
+
+/// Part of the polygon `subject` where the affine function `f` is not positive, by one step of
+/// the Sutherland–Hodgman algorithm. Empty if less than a triangle is left.
+pub(crate) fn clip_by(subject: &[Point], f: impl Fn(Point) -> Float) -> Vec<Point> {
+    let n = subject.len();
+    let mut points = vec![];
+    for (i, &b) in subject.iter().enumerate() {
+        let a = subject[(i + n - 1) % n];
+        let (fa, fb) = (f(a), f(b));
+        if (fa < 0.0 && fb > 0.0) || (fa > 0.0 && fb < 0.0) {
+            points.push(a + (b - a) * (fa / (fa - fb)));
+        }
+        if fb <= 0.0 {
+            points.push(b);
+        }
+    }
+    if points.len() < 3 {
+        points.clear();
+    }
+    points
+}
+
+/// Part of the polygon `subject` on the left of the line through `a` toward `b`, including the
+/// line.
+pub fn clip_half_plane(subject: &[Point], a: Point, b: Point) -> Vec<Point> {
+    clip_by(subject, |p| -((b - a).conj() * (p - a)).im)
+}
+
+/// Intersection of the polygon `subject` with the convex polygon `clip`, by the
+/// Sutherland–Hodgman algorithm, with the orientation of `subject`. Concave subjects may give
+/// edges along the boundary of `clip` between their separate parts.
+pub fn clip_polygon(subject: &[Point], clip: &[Point]) -> Vec<Point> {
+    let n = clip.len();
+    let ccw = signed_area(clip) >= 0.0;
+    (0..n).fold(subject.to_vec(), |points, i| {
+        let (a, b) = (clip[i], clip[(i + 1) % n]);
+        if ccw {
+            clip_half_plane(&points, a, b)
+        } else {
+            clip_half_plane(&points, b, a)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coordinates: &[(Float, Float)]) -> Vec<Point> {
+        coordinates
+            .iter()
+            .map(|&(re, im)| Complex::new(re, im))
+            .collect()
+    }
+
+    #[test]
+    fn half_plane() {
+        let square = points(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        let (a, b) = (Complex::new(0.0, 1.0), Complex::new(1.0, 2.0));
+        assert_eq!(
+            clip_half_plane(&square, a, b),
+            points(&[(0.0, 1.0), (1.0, 2.0), (0.0, 2.0)])
+        );
+        assert_eq!(signed_area(&clip_half_plane(&square, b, a)), 3.5);
+        // Along an edge, all or nothing is left.
+        let (a, b) = (Complex::new(0.0, 0.0), Complex::new(2.0, 0.0));
+        assert_eq!(clip_half_plane(&square, a, b), square);
+        assert_eq!(clip_half_plane(&square, b, a), []);
+    }
+
+    #[test]
+    fn polygon() {
+        // Overlapping squares, the clip one clockwise.
+        let subject = points(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        let clip = points(&[(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)]);
+        let overlap = clip_polygon(&subject, &clip);
+        assert_eq!(signed_area(&overlap), 1.0);
+        assert_eq!(overlap.len(), 4);
+        // An L shape clipped by a triangle.
+        let l = points(&[
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 4.0),
+            (0.0, 4.0),
+        ]);
+        let triangle = points(&[(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        assert_eq!(signed_area(&clip_polygon(&l, &triangle)), 6.0);
+        let far = points(&[(5.0, 5.0), (6.0, 5.0), (6.0, 6.0)]);
+        assert_eq!(clip_polygon(&l, &far), []);
+    }
+}
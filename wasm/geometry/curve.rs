@@ -0,0 +1,366 @@
+use super::{
+    arc::EllipticalArc,
+    bezier::{CubicBezier, SquareBezier},
+};
+use crate::{
+    math::{affine2::Affine2, complex::Complex},
+    Float,
+};
+
+type Point = Complex<Float>;
+
+/// Limit of the halvings in [`Piece::flatten`], for tolerances too small for the precision.
+const MAX_DEPTH: u32 = 16;
+
+/// Piece of a path outline that can be subdivided, with a control polygon whose convex hull
+/// contains it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Piece {
+    Line([Point; 2]),
+    Cubic([Point; 4]),
+    /// Image under `ellipse` of the unit circle arc from `start` turning by `sweep` radians, with
+    /// `|sweep| <= π / 2`. The end points are kept exactly, so that consecutive pieces join
+    /// without rounding gaps.
+    Arc {
+        ellipse: Affine2<Float>,
+        start: Float,
+        sweep: Float,
+        ends: [Point; 2],
+    },
+}
+
+impl Piece {
+    pub(crate) fn quadratic(from: Point, cp: Point, to: Point) -> Self {
+        let c = SquareBezier::new(from, cp, to).to_cubic();
+        Self::Cubic([c.from, c.cp1, c.cp2, c.to])
+    }
+
+    /// Pieces of an SVG elliptical arc, with the axis rotation in degrees, a line if a radius is
+    /// zero.
+    pub(crate) fn arc(
+        from: Point,
+        radius: Point,
+        axis_rotation: Float,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        to: Point,
+    ) -> Vec<Self> {
+        if from == to {
+            return vec![];
+        }
+        let Some(arc) = EllipticalArc::from_endpoints(
+            from,
+            radius,
+            axis_rotation,
+            large_arc_flag,
+            sweep_flag,
+            to,
+        ) else {
+            return vec![Self::Line([from, to])];
+        };
+        let (ellipse, start, sweep) = (arc.ellipse(), arc.start, arc.sweep);
+        let count = (sweep.abs() / (90.0 as Float).to_radians()).ceil().max(1.0) as usize;
+        let step = sweep / count as Float;
+        let angle = |i: usize| start + step * i as Float;
+        let point = |i: usize| match i {
+            0 => from,
+            i if i == count => to,
+            i => ellipse.apply(&unit(angle(i))),
+        };
+        (0..count)
+            .map(|i| Self::Arc {
+                ellipse,
+                start: angle(i),
+                sweep: step,
+                ends: [point(i), point(i + 1)],
+            })
+            .collect()
+    }
+
+    pub(crate) fn from(&self) -> Point {
+        match self {
+            Self::Line([a, _]) | Self::Cubic([a, ..]) | Self::Arc { ends: [a, _], .. } => *a,
+        }
+    }
+
+    pub(crate) fn to(&self) -> Point {
+        match self {
+            Self::Line([_, b]) | Self::Cubic([.., b]) | Self::Arc { ends: [_, b], .. } => *b,
+        }
+    }
+
+    /// Control polygon, a triangle from the end tangents for arcs.
+    pub(crate) fn hull(&self) -> Vec<Point> {
+        match self {
+            Self::Line(p) => p.to_vec(),
+            Self::Cubic(p) => p.to_vec(),
+            Self::Arc {
+                ellipse,
+                start,
+                sweep,
+                ..
+            } => {
+                let apex = unit(start + sweep / 2.0) / (sweep / 2.0).cos();
+                vec![self.from(), ellipse.apply(&apex), self.to()]
+            }
+        }
+    }
+
+    /// Upper bound of the distance from the chord.
+    pub(crate) fn flatness(&self) -> Float {
+        let (a, b) = (self.from(), self.to());
+        let chord = b - a;
+        let length = chord.abs();
+        self.hull()
+            .into_iter()
+            .map(|p| {
+                if length == 0.0 {
+                    (p - a).abs()
+                } else {
+                    (chord.conj() * (p - a)).im.abs() / length
+                }
+            })
+            .fold(0.0, Float::max)
+    }
+
+    /// Appends the end points of line segments approximating the piece within `tolerance`, by
+    /// halving until the control polygon is within it of the chord.
+    pub(crate) fn flatten(&self, tolerance: Float, points: &mut Vec<Point>) {
+        self.flatten_to_depth(tolerance, MAX_DEPTH, points);
+    }
+
+    fn flatten_to_depth(&self, tolerance: Float, depth: u32, points: &mut Vec<Point>) {
+        let flatness = self.flatness();
+        if depth == 0 || matches!(self, Self::Line(_)) || flatness <= tolerance || flatness.is_nan()
+        {
+            points.push(self.to());
+        } else {
+            let (first, second) = self.split();
+            first.flatten_to_depth(tolerance, depth - 1, points);
+            second.flatten_to_depth(tolerance, depth - 1, points);
+        }
+    }
+
+    /// Halves at the middle of the parameter.
+    pub(crate) fn split(&self) -> (Self, Self) {
+        match *self {
+            Self::Line([a, b]) => {
+                let m = (a + b) / 2.0;
+                (Self::Line([a, m]), Self::Line([m, b]))
+            }
+            Self::Cubic([a, b, c, d]) => {
+                let (first, second) = CubicBezier::new(a, b, c, d).split(0.5);
+                (
+                    Self::Cubic([first.from, first.cp1, first.cp2, first.to]),
+                    Self::Cubic([second.from, second.cp1, second.cp2, second.to]),
+                )
+            }
+            Self::Arc {
+                ellipse,
+                start,
+                sweep,
+                ends: [a, b],
+            } => {
+                let m = ellipse.apply(&unit(start + sweep / 2.0));
+                (
+                    Self::Arc {
+                        ellipse,
+                        start,
+                        sweep: sweep / 2.0,
+                        ends: [a, m],
+                    },
+                    Self::Arc {
+                        ellipse,
+                        start: start + sweep / 2.0,
+                        sweep: sweep / 2.0,
+                        ends: [m, b],
+                    },
+                )
+            }
+        }
+    }
+
+    /// Signed area swept by the segment from the origin to a point moving along the piece, whose
+    /// sum over a closed outline is the area it encloses, positive counterclockwise.
+    pub(crate) fn signed_area(&self) -> Float {
+        let cross = |a: Point, b: Point| (a.conj() * b).im;
+        match *self {
+            Self::Line([a, b]) => cross(a, b) / 2.0,
+            Self::Cubic([a, b, c, d]) => {
+                (6.0 * cross(a, b)
+                    + 3.0 * cross(a, c)
+                    + cross(a, d)
+                    + 3.0 * cross(b, c)
+                    + 3.0 * cross(b, d)
+                    + 6.0 * cross(c, d))
+                    / 20.0
+            }
+            // The image of the unit circle sector, plus the triangle from the origin to the
+            // center over the chord.
+            Self::Arc {
+                ellipse,
+                sweep,
+                ends: [a, b],
+                ..
+            } => (ellipse.determinant() * sweep + cross(ellipse.t, b - a)) / 2.0,
+        }
+    }
+
+    /// Bounding box of the control polygon as its lower left and upper right corners.
+    pub(crate) fn bounds(&self) -> (Point, Point) {
+        self.hull().into_iter().fold(
+            (
+                Complex::new(Float::INFINITY, Float::INFINITY),
+                Complex::new(Float::NEG_INFINITY, Float::NEG_INFINITY),
+            ),
+            |(min, max), p| {
+                (
+                    Complex::new(min.re.min(p.re), min.im.min(p.im)),
+                    Complex::new(max.re.max(p.re), max.im.max(p.im)),
+                )
+            },
+        )
+    }
+}
+
+fn unit(angle: Float) -> Point {
+    Complex::new(angle.cos(), angle.sin())
+}
+
+/// Signed crossing of the rightward ray from `p` by the segment from `a` to `b`, counting
+/// upward crossings positive with half-open vertical ranges so shared vertices count once.
+pub(crate) fn crossing(a: Point, b: Point, p: Point) -> i32 {
+    let side = (b - a).conj() * (p - a);
+    if a.im <= p.im && p.im < b.im && side.im > 0.0 {
+        1
+    } else if b.im <= p.im && p.im < a.im && side.im < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Distance from `p` to the segment from `a` to `b`.
+pub(crate) fn distance_to_segment(a: Point, b: Point, p: Point) -> Float {
+    let d = b - a;
+    let length2 = d.re * d.re + d.im * d.im;
+    if length2 == 0.0 {
+        return (p - a).abs();
+    }
+    let t = ((p - a).re * d.re + (p - a).im * d.im) / length2;
+    (p - (a + d * t.clamp(0.0, 1.0))).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arc() {
+        // Half of the unit circle counterclockwise from (1, 0) to (-1, 0).
+        let pieces = Piece::arc(
+            Complex::new(1.0, 0.0),
+            Complex::new(1.0, 1.0),
+            0.0,
+            false,
+            true,
+            Complex::new(-1.0, 0.0),
+        );
+        assert_eq!(pieces.len(), 2);
+        assert!((pieces[0].to() - Complex::new(0.0, 1.0)).abs() < Float::EPSILON * 1e1);
+        assert!((pieces[1].to() - Complex::new(-1.0, 0.0)).abs() < Float::EPSILON * 1e1);
+        // With the diameter, half of the circle around a center off the origin.
+        let (from, to) = (Complex::new(3.0, 1.0), Complex::new(1.0, 1.0));
+        let pieces = Piece::arc(from, Complex::new(1.0, 1.0), 0.0, false, true, to);
+        let area = pieces.iter().map(Piece::signed_area).sum::<Float>()
+            + Piece::Line([to, from]).signed_area();
+        assert!((area - (180.0 as Float).to_radians() / 2.0).abs() < Float::EPSILON * 1e1);
+        // The large arc the other way around a scaled up circle.
+        let pieces = Piece::arc(
+            Complex::new(1.0, 0.0),
+            Complex::new(0.5, 0.5),
+            0.0,
+            true,
+            false,
+            Complex::new(-1.0, 0.0),
+        );
+        assert!((pieces[0].from() - Complex::new(1.0, 0.0)).abs() < Float::EPSILON * 1e1);
+        assert!(pieces.iter().all(|p| p.hull()[1].im <= Float::EPSILON));
+        let line = Piece::arc(
+            Complex::new(1.0, 0.0),
+            Complex::new(0.0, 1.0),
+            0.0,
+            false,
+            false,
+            Complex::new(2.0, 0.0),
+        );
+        assert_eq!(
+            line,
+            [Piece::Line([
+                Complex::new(1.0, 0.0),
+                Complex::new(2.0, 0.0)
+            ])]
+        );
+    }
+
+    #[test]
+    fn split() {
+        let cubic = Piece::Cubic([
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(1.0, 1.0),
+            Complex::new(1.0, 0.0),
+        ]);
+        let (a, b) = cubic.split();
+        assert_eq!(a.to(), Complex::new(0.5, 0.75));
+        assert_eq!(b.from(), a.to());
+        assert_eq!(cubic.flatness(), 1.0);
+        assert!((cubic.signed_area() + 0.6).abs() < Float::EPSILON * 1e1);
+        assert_eq!(
+            cubic.bounds(),
+            (Complex::new(0.0, 0.0), Complex::new(1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        let quarter = Piece::arc(
+            Complex::new(1.0, 0.0),
+            Complex::new(1.0, 1.0),
+            0.0,
+            false,
+            true,
+            Complex::new(0.0, 1.0),
+        )[0];
+        let mut points = vec![];
+        quarter.flatten(1e-3, &mut points);
+        assert_eq!(points.last(), Some(&Complex::new(0.0, 1.0)));
+        let mut from = Complex::new(1.0, 0.0);
+        for &to in &points {
+            // A chord deviates most from the circle at its middle.
+            assert!(1.0 - ((from + to) / 2.0).abs() <= 1e-3);
+            from = to;
+        }
+        // Chords of angle θ deviate 1 - cos(θ / 2), so 18 would do if they were not halved.
+        assert!(points.len() <= 64);
+        let mut points = vec![];
+        quarter.flatten(0.0, &mut points);
+        // Halving stops at the depth limit, or earlier where rounding makes pieces straight.
+        assert!(points.len() <= 1 << MAX_DEPTH);
+        assert_eq!(points.last(), Some(&Complex::new(0.0, 1.0)));
+    }
+
+    #[test]
+    fn segment() {
+        let (a, b) = (Complex::new(0.0, 0.0), Complex::new(2.0, 2.0));
+        assert_eq!(crossing(a, b, Complex::new(0.0, 1.0)), 1);
+        assert_eq!(crossing(b, a, Complex::new(0.0, 1.0)), -1);
+        assert_eq!(crossing(a, b, Complex::new(2.0, 1.0)), 0);
+        assert_eq!(crossing(a, b, Complex::new(0.0, 2.0)), 0);
+        assert_eq!(
+            distance_to_segment(a, b, Complex::new(2.0, 0.0)),
+            2.0_f64.sqrt() as Float
+        );
+        assert_eq!(distance_to_segment(a, b, Complex::new(3.0, 2.0)), 1.0);
+    }
+}
@@ -1 +1,6 @@
+pub mod arc;
+pub mod bezier;
+pub mod clip;
+mod curve;
 pub mod path;
+pub mod polyline;
@@ -1,3 +1,18 @@
+use super::{
+    arc::EllipticalArc,
+    bezier::{CubicBezier, SquareBezier},
+    curve::{crossing, distance_to_segment, Piece},
+    polyline,
+};
+use crate::{
+    math::{affine2::Affine2, complex::Complex},
+    Float,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, Bound, RangeBounds, Sub},
+};
+
 #[derive(Debug, Clone, PartialEq)]
 enum Command<T, S> {
     MoveTo {
@@ -6,14 +21,20 @@ enum Command<T, S> {
     LineTo {
         to: T,
     },
+    /// `smooth` curves are written as the SVG `S` command, with `cp1` the reflection of the
+    /// previous control point resolved when built.
     CubicBezier {
         cp1: T,
         cp2: T,
         to: T,
+        smooth: bool,
     },
+    /// `smooth` curves are written as the SVG `T` command, with `cp` resolved like `cp1` of
+    /// [`Command::CubicBezier`].
     SquareBezier {
         cp: T,
         to: T,
+        smooth: bool,
     },
     Arc {
         radius: T,
@@ -25,6 +46,20 @@ enum Command<T, S> {
     ClosePath,
 }
 
+impl<T, S> Command<T, S> {
+    /// End point, `None` for closing, which ends where the subpath starts.
+    fn to(&self) -> Option<&T> {
+        match self {
+            Command::MoveTo { to }
+            | Command::LineTo { to }
+            | Command::CubicBezier { to, .. }
+            | Command::SquareBezier { to, .. }
+            | Command::Arc { to, .. } => Some(to),
+            Command::ClosePath => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Segment<'a, T, S> {
     Line {
@@ -52,16 +87,175 @@ pub enum Segment<'a, T, S> {
     },
 }
 
+/// Nearest point of a path to another point, found by [`Path::project`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Projection {
+    pub point: Complex<Float>,
+    /// Index of the segment in [`Path::segments`].
+    pub segment: usize,
+    /// Parameter of the point in [`Segment::point_at`].
+    pub t: Float,
+    pub distance: Float,
+}
+
+/// Rule deciding from the winding number of a path around a point whether the point is inside,
+/// as the SVG `fill-rule` property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// Inside if the winding number is not zero.
+    #[default]
+    NonZero,
+    /// Inside if the winding number is odd.
+    EvenOdd,
+}
+
+/// Shape of the ends of open subpaths in [`Path::stroke`], as the SVG `stroke-linecap`
+/// property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineCap {
+    /// Ends cut square at the end points.
+    #[default]
+    Butt,
+    /// Half circles around the end points.
+    Round,
+    /// Ends cut square half of the width beyond the end points.
+    Square,
+}
+
+/// Shape of the outside of corners in [`Path::stroke`], as the SVG `stroke-linejoin` property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineJoin {
+    /// The edges extended until they meet, or beveled if that is farther than `limit` times
+    /// half of the width from the corner, as the SVG `stroke-miterlimit` property.
+    Miter { limit: Float },
+    /// Circular arcs around the corners.
+    Round,
+    /// The edges joined by lines.
+    Bevel,
+}
+
+/// Miter with the default limit of SVG.
+impl Default for LineJoin {
+    fn default() -> Self {
+        LineJoin::Miter { limit: 4.0 }
+    }
+}
+
+impl Segment<'_, Complex<Float>, Float> {
+    /// Center parameterization of an arc segment, `None` for other segments and for arcs drawn
+    /// as nothing or as a line.
+    pub fn elliptical_arc(&self) -> Option<EllipticalArc> {
+        match *self {
+            Segment::Arc {
+                from,
+                radius,
+                axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+                to,
+            } => EllipticalArc::from_endpoints(
+                *from,
+                *radius,
+                *axis_rotation,
+                large_arc_flag,
+                sweep_flag,
+                *to,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Cubic Bézier curve of a cubic segment, `None` for other segments.
+    pub fn cubic_bezier(&self) -> Option<CubicBezier> {
+        match *self {
+            Segment::CubicBezier { from, cp1, cp2, to } => {
+                Some(CubicBezier::new(*from, *cp1, *cp2, *to))
+            }
+            _ => None,
+        }
+    }
+
+    /// Quadratic Bézier curve of a quadratic segment, `None` for other segments.
+    pub fn square_bezier(&self) -> Option<SquareBezier> {
+        match *self {
+            Segment::SquareBezier { from, cp, to } => Some(SquareBezier::new(*from, *cp, *to)),
+            _ => None,
+        }
+    }
+
+    /// Point at the parameter `t` from 0 at the start to 1 at the end, with arcs drawn as lines
+    /// parameterized like lines.
+    pub fn point_at(&self, t: Float) -> Complex<Float> {
+        match *self {
+            Segment::Line { from, to } => *from + (*to - *from) * t,
+            Segment::CubicBezier { from, cp1, cp2, to } => {
+                CubicBezier::new(*from, *cp1, *cp2, *to).point_at(t)
+            }
+            Segment::SquareBezier { from, cp, to } => {
+                SquareBezier::new(*from, *cp, *to).point_at(t)
+            }
+            Segment::Arc { from, to, .. } => match self.elliptical_arc() {
+                Some(arc) => arc.point_at(t),
+                None => *from + (*to - *from) * t,
+            },
+        }
+    }
+
+    /// Derivative of [`Self::point_at`] with respect to `t`.
+    pub fn derivative_at(&self, t: Float) -> Complex<Float> {
+        match *self {
+            Segment::Line { from, to } => *to - *from,
+            Segment::CubicBezier { from, cp1, cp2, to } => {
+                CubicBezier::new(*from, *cp1, *cp2, *to).derivative_at(t)
+            }
+            Segment::SquareBezier { from, cp, to } => {
+                SquareBezier::new(*from, *cp, *to).derivative_at(t)
+            }
+            Segment::Arc { from, to, .. } => match self.elliptical_arc() {
+                Some(arc) => arc.tangent_at(t),
+                None => *to - *from,
+            },
+        }
+    }
+}
+
+/// Misuse of the path builder found by [`Path::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    /// Drawing started without [`Path::move_to`], from `T::default()`.
+    MissingMoveTo,
+    /// [`Path::close_path`] was called without any point to close.
+    EmptyClose,
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PathError::MissingMoveTo => "path drawn without moving to its start first",
+            PathError::EmptyClose => "path closed without any point",
+        })
+    }
+}
+
+impl std::error::Error for PathError {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Path<T, S> {
     commands: Vec<Command<T, S>>,
     init_pos: T,
+    /// Whether `init_pos` was set by [`Path::move_to`] rather than defaulted.
+    started: bool,
 }
 
 impl<T, S> Path<T, S> {
     pub fn move_to(&mut self, to: T) -> &mut Self {
         if self.commands.is_empty() {
             self.init_pos = to;
+            self.started = true;
         } else {
             self.commands.push(Command::MoveTo { to });
         }
@@ -74,12 +268,21 @@ impl<T, S> Path<T, S> {
     }
 
     pub fn cubic_bezier(&mut self, cp1: T, cp2: T, to: T) -> &mut Self {
-        self.commands.push(Command::CubicBezier { cp1, cp2, to });
+        self.commands.push(Command::CubicBezier {
+            cp1,
+            cp2,
+            to,
+            smooth: false,
+        });
         self
     }
 
     pub fn square_bezier(&mut self, cp: T, to: T) -> &mut Self {
-        self.commands.push(Command::SquareBezier { cp, to });
+        self.commands.push(Command::SquareBezier {
+            cp,
+            to,
+            smooth: false,
+        });
         self
     }
 
@@ -106,77 +309,1381 @@ impl<T, S> Path<T, S> {
         self
     }
 
-    pub fn segments(&self) -> impl Iterator<Item = Segment<T, S>> {
-        self.commands
-            .iter()
-            .scan(
-                [&self.init_pos, &self.init_pos],
-                |state, command| match command {
-                    Command::MoveTo { to } => {
-                        state[0] = to;
-                        state[1] = to;
-                        Some(None)
-                    }
-                    Command::LineTo { to } => {
-                        let from = state[1];
-                        state[1] = to;
-                        Some(Some(Segment::Line { from, to }))
-                    }
-                    Command::SquareBezier { cp, to } => {
-                        let from = state[1];
-                        state[1] = to;
-                        Some(Some(Segment::SquareBezier { from, cp, to }))
-                    }
-                    Command::CubicBezier { cp1, cp2, to } => {
-                        let from = state[1];
-                        state[1] = to;
-                        Some(Some(Segment::CubicBezier { from, cp1, cp2, to }))
+    /// End point of the last command, the start of the subpath after it is closed.
+    pub fn current_point(&self) -> &T {
+        let start = || {
+            self.commands
+                .iter()
+                .rev()
+                .find_map(|command| match command {
+                    Command::MoveTo { to } => Some(to),
+                    _ => None,
+                })
+                .unwrap_or(&self.init_pos)
+        };
+        match self.commands.last() {
+            None | Some(Command::ClosePath) => start(),
+            Some(
+                Command::MoveTo { to }
+                | Command::LineTo { to }
+                | Command::CubicBezier { to, .. }
+                | Command::SquareBezier { to, .. }
+                | Command::Arc { to, .. },
+            ) => to,
+        }
+    }
+
+    /// Checks that the path was started with [`Self::move_to`] before drawing or closing.
+    pub fn validate(&self) -> Result<(), PathError> {
+        match self.commands.first() {
+            _ if self.started => Ok(()),
+            None => Ok(()),
+            Some(Command::ClosePath) => Err(PathError::EmptyClose),
+            Some(_) => Err(PathError::MissingMoveTo),
+        }
+    }
+
+    /// Whether the path draws something and [`Self::close_path`] closes each subpath drawing
+    /// something.
+    pub fn is_closed(&self) -> bool {
+        let mut drawn = false;
+        let mut open = false;
+        for command in &self.commands {
+            match command {
+                Command::MoveTo { .. } => {}
+                Command::ClosePath => open = false,
+                _ => (drawn, open) = (true, true),
+            }
+        }
+        drawn && !open
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_, T, S>> {
+        self.command_segments().flatten()
+    }
+
+    /// Segment drawn by each command, `None` for moves.
+    fn command_segments(&self) -> impl Iterator<Item = Option<Segment<'_, T, S>>> {
+        self.commands.iter().scan(
+            [&self.init_pos, &self.init_pos],
+            |state, command| match command {
+                Command::MoveTo { to } => {
+                    state[0] = to;
+                    state[1] = to;
+                    Some(None)
+                }
+                Command::LineTo { to } => {
+                    let from = state[1];
+                    state[1] = to;
+                    Some(Some(Segment::Line { from, to }))
+                }
+                Command::SquareBezier { cp, to, .. } => {
+                    let from = state[1];
+                    state[1] = to;
+                    Some(Some(Segment::SquareBezier { from, cp, to }))
+                }
+                Command::CubicBezier { cp1, cp2, to, .. } => {
+                    let from = state[1];
+                    state[1] = to;
+                    Some(Some(Segment::CubicBezier { from, cp1, cp2, to }))
+                }
+                Command::Arc {
+                    radius,
+                    axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    to,
+                } => {
+                    let from = state[1];
+                    state[1] = to;
+                    Some(Some(Segment::Arc {
+                        from,
+                        radius,
+                        axis_rotation,
+                        large_arc_flag: *large_arc_flag,
+                        sweep_flag: *sweep_flag,
+                        to,
+                    }))
+                }
+                Command::ClosePath => {
+                    let from = state[1];
+                    state[1] = state[0];
+                    Some(Some(Segment::Line { from, to: state[0] }))
+                }
+            },
+        )
+    }
+}
+
+/// Commands with coordinates relative to the current point, as the lowercase SVG commands.
+impl<T, S> Path<T, S>
+where
+    for<'a> &'a T: Add<Output = T>,
+{
+    pub fn move_by(&mut self, d: T) -> &mut Self {
+        let to = self.current_point() + &d;
+        self.move_to(to)
+    }
+
+    pub fn line_by(&mut self, d: T) -> &mut Self {
+        let to = self.current_point() + &d;
+        self.line_to(to)
+    }
+
+    pub fn cubic_bezier_by(&mut self, cp1: T, cp2: T, to: T) -> &mut Self {
+        let from = self.current_point();
+        let (cp1, cp2, to) = (from + &cp1, from + &cp2, from + &to);
+        self.cubic_bezier(cp1, cp2, to)
+    }
+
+    pub fn square_bezier_by(&mut self, cp: T, to: T) -> &mut Self {
+        let from = self.current_point();
+        let (cp, to) = (from + &cp, from + &to);
+        self.square_bezier(cp, to)
+    }
+
+    pub fn arc_by(
+        &mut self,
+        radius: T,
+        axis_rotation: S,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        to: T,
+    ) -> &mut Self {
+        let to = self.current_point() + &to;
+        self.arc(radius, axis_rotation, large_arc_flag, sweep_flag, to)
+    }
+}
+
+/// Smooth continuations, as the SVG `S` and `T` commands.
+impl<T, S> Path<T, S>
+where
+    T: Clone,
+    for<'a> &'a T: Add<Output = T> + Sub<Output = T>,
+{
+    /// Cubic Bézier curve whose first control point is the reflection of the second control
+    /// point of the previous curve about the current point, or the current point if the previous
+    /// command is not a cubic Bézier curve.
+    pub fn smooth_cubic(&mut self, cp2: T, to: T) -> &mut Self {
+        let cp1 = match self.commands.last() {
+            Some(Command::CubicBezier { cp2, to, .. }) => to + &(to - cp2),
+            _ => self.current_point().clone(),
+        };
+        self.commands.push(Command::CubicBezier {
+            cp1,
+            cp2,
+            to,
+            smooth: true,
+        });
+        self
+    }
+
+    /// Quadratic Bézier curve whose control point is the reflection of the control point of the
+    /// previous curve about the current point, or the current point if the previous command is
+    /// not a quadratic Bézier curve.
+    pub fn smooth_quad(&mut self, to: T) -> &mut Self {
+        let cp = match self.commands.last() {
+            Some(Command::SquareBezier { cp, to, .. }) => to + &(to - cp),
+            _ => self.current_point().clone(),
+        };
+        self.commands.push(Command::SquareBezier {
+            cp,
+            to,
+            smooth: true,
+        });
+        self
+    }
+}
+
+/// Horizontal and vertical lines, as the SVG `H`, `V`, `h` and `v` commands.
+impl<T, S> Path<Complex<T>, S>
+where
+    T: Clone,
+    for<'a> &'a T: Add<Output = T>,
+{
+    pub fn horizontal_to(&mut self, x: T) -> &mut Self {
+        let to = Complex::new(x, self.current_point().im.clone());
+        self.line_to(to)
+    }
+
+    pub fn vertical_to(&mut self, y: T) -> &mut Self {
+        let to = Complex::new(self.current_point().re.clone(), y);
+        self.line_to(to)
+    }
+
+    pub fn horizontal_by(&mut self, dx: T) -> &mut Self {
+        let x = &self.current_point().re + &dx;
+        self.horizontal_to(x)
+    }
+
+    pub fn vertical_by(&mut self, dy: T) -> &mut Self {
+        let y = &self.current_point().im + &dy;
+        self.vertical_to(y)
+    }
+}
+
+impl<T, S> Path<T, S>
+where
+    T: Clone + PartialEq,
+    S: Clone,
+{
+    /// The same outline in the opposite direction, subpath by subpath in the same order. Closed
+    /// subpaths still start where they did, and smooth curves become explicit ones.
+    pub fn reverse(&self) -> Self {
+        if self.commands.is_empty() {
+            return self.clone();
+        }
+        let mut path = Self {
+            commands: vec![],
+            init_pos: self.init_pos.clone(),
+            started: self.started,
+        };
+        // Drawing commands of the current subpath, each with the point it starts from.
+        let mut subpath: Vec<(&T, &Command<T, S>)> = vec![];
+        let (mut start, mut pos) = (&self.init_pos, &self.init_pos);
+        let mut append = |subpath: &mut Vec<(&T, &Command<T, S>)>, start: &T, end: &T, closed| {
+            if closed {
+                path.move_to(start.clone());
+                if end != start {
+                    path.line_to(end.clone());
+                }
+            } else {
+                path.move_to(end.clone());
+            }
+            for (from, command) in subpath.drain(..).rev() {
+                let to = from.clone();
+                path.commands.push(match command.clone() {
+                    Command::CubicBezier { cp1, cp2, .. } => Command::CubicBezier {
+                        cp1: cp2,
+                        cp2: cp1,
+                        to,
+                        smooth: false,
+                    },
+                    Command::SquareBezier { cp, .. } => Command::SquareBezier {
+                        cp,
+                        to,
+                        smooth: false,
+                    },
+                    Command::Arc {
+                        radius,
+                        axis_rotation,
+                        large_arc_flag,
+                        sweep_flag,
+                        ..
+                    } => Command::Arc {
+                        radius,
+                        axis_rotation,
+                        large_arc_flag,
+                        sweep_flag: !sweep_flag,
+                        to,
+                    },
+                    _ => Command::LineTo { to },
+                });
+            }
+            if closed {
+                path.close_path();
+            }
+        };
+        let mut closed = false;
+        for command in &self.commands {
+            match command {
+                Command::MoveTo { to } => {
+                    if !closed {
+                        append(&mut subpath, start, pos, false);
                     }
+                    (start, pos, closed) = (to, to, false);
+                }
+                Command::ClosePath => {
+                    append(&mut subpath, start, pos, true);
+                    (pos, closed) = (start, true);
+                }
+                Command::LineTo { to }
+                | Command::CubicBezier { to, .. }
+                | Command::SquareBezier { to, .. }
+                | Command::Arc { to, .. } => {
+                    subpath.push((pos, command));
+                    (pos, closed) = (to, false);
+                }
+            }
+        }
+        if !closed {
+            append(&mut subpath, start, pos, false);
+        }
+        path
+    }
+
+    /// Appends the subpaths of `other` after those of this path.
+    pub fn append(&mut self, other: &Self) -> &mut Self {
+        if self.commands.is_empty() && !self.started {
+            *self = other.clone();
+        } else if !other.commands.is_empty() || other.started {
+            self.move_to(other.init_pos.clone());
+            self.commands.extend(other.commands.iter().cloned());
+        }
+        self
+    }
+
+    /// Subpaths each starting where the path moves, from which [`Self::append`] builds the path
+    /// back.
+    pub fn subpaths(&self) -> impl Iterator<Item = Self> + '_ {
+        let mut starts = vec![0];
+        starts.extend(
+            self.commands
+                .iter()
+                .enumerate()
+                .filter_map(|(i, command)| matches!(command, Command::MoveTo { .. }).then_some(i)),
+        );
+        let ends: Vec<_> = starts[1..]
+            .iter()
+            .copied()
+            .chain([self.commands.len()])
+            .collect();
+        if self.commands.is_empty() && !self.started {
+            starts.clear();
+        }
+        starts
+            .into_iter()
+            .zip(ends)
+            .map(|(start, end)| self.slice(start..end))
+    }
+
+    /// Path of the commands in `range`, counting those after the initial move as written by
+    /// [`Display`], starting where the first of them starts. Closing a subpath starting before
+    /// the range becomes a line back to its start, and a smooth curve first in the range becomes
+    /// an explicit one unless it starts in the direction of nothing anyway.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of the commands, like slicing.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.commands.len(),
+        };
+        let commands = &self.commands[start..end];
+        let (mut subpath_start, mut pos) = (&self.init_pos, &self.init_pos);
+        for command in &self.commands[..start] {
+            if let Command::MoveTo { to } = command {
+                subpath_start = to;
+            }
+            pos = command.to().unwrap_or(subpath_start);
+        }
+        let mut path = Self {
+            commands: vec![],
+            init_pos: pos.clone(),
+            started: self.started || start > 0,
+        };
+        let mut closes = subpath_start == pos;
+        for command in commands {
+            match command {
+                Command::MoveTo { to } => {
+                    path.move_to(to.clone());
+                    (subpath_start, closes) = (to, true);
+                }
+                Command::ClosePath if !closes => {
+                    path.line_to(subpath_start.clone());
+                }
+                // Without a curve before, smooth curves start in the direction of nothing.
+                Command::CubicBezier {
+                    cp1,
+                    cp2,
+                    to,
+                    smooth: true,
+                } if path.commands.is_empty() && cp1 != path.current_point() => {
+                    path.cubic_bezier(cp1.clone(), cp2.clone(), to.clone());
+                }
+                Command::SquareBezier {
+                    cp,
+                    to,
+                    smooth: true,
+                } if path.commands.is_empty() && cp != path.current_point() => {
+                    path.square_bezier(cp.clone(), to.clone());
+                }
+                _ => path.commands.push(command.clone()),
+            }
+        }
+        path
+    }
+}
+
+impl<T, S> Path<T, S>
+where
+    T: Default,
+{
+    pub fn new() -> Self {
+        Self {
+            commands: vec![],
+            init_pos: T::default(),
+            started: false,
+        }
+    }
+}
+
+impl<T, S> Default for Path<T, S>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Path<Complex<Float>, Float> {
+    /// Maps every point of the path by `transform`. The arc radii and axis rotations, in degrees
+    /// as in SVG, are those of the transformed ellipses, and the sweep direction is reversed by
+    /// reflections.
+    pub fn transform(&self, transform: &Affine2<Float>) -> Self {
+        let commands = self
+            .commands
+            .iter()
+            .map(|command| match command {
+                Command::MoveTo { to } => Command::MoveTo {
+                    to: transform.apply(to),
+                },
+                Command::LineTo { to } => Command::LineTo {
+                    to: transform.apply(to),
+                },
+                Command::CubicBezier {
+                    cp1,
+                    cp2,
+                    to,
+                    smooth,
+                } => Command::CubicBezier {
+                    cp1: transform.apply(cp1),
+                    cp2: transform.apply(cp2),
+                    to: transform.apply(to),
+                    smooth: *smooth,
+                },
+                Command::SquareBezier { cp, to, smooth } => Command::SquareBezier {
+                    cp: transform.apply(cp),
+                    to: transform.apply(to),
+                    smooth: *smooth,
+                },
+                Command::Arc {
+                    radius,
+                    axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    to,
+                } => {
+                    let (radius, axis_rotation) =
+                        transform_ellipse(transform, radius, *axis_rotation);
                     Command::Arc {
+                        radius,
+                        axis_rotation,
+                        large_arc_flag: *large_arc_flag,
+                        sweep_flag: *sweep_flag != (transform.determinant() < 0.0),
+                        to: transform.apply(to),
+                    }
+                }
+                Command::ClosePath => Command::ClosePath,
+            })
+            .collect();
+        Self {
+            commands,
+            init_pos: transform.apply(&self.init_pos),
+            started: self.started,
+        }
+    }
+
+    pub fn translate(&self, d: Complex<Float>) -> Self {
+        self.transform(&Affine2::from_translation(d))
+    }
+
+    /// Rotates counterclockwise about the origin by `angle` radians.
+    pub fn rotate(&self, angle: Float) -> Self {
+        self.transform(&Affine2::from_rotation(&angle))
+    }
+
+    /// Scales about the origin by `sx` along the real axis and `sy` along the imaginary axis.
+    pub fn scale(&self, sx: Float, sy: Float) -> Self {
+        self.transform(&Affine2::from_scale(sx, sy))
+    }
+}
+
+impl Path<Complex<Float>, Float> {
+    /// Outline pieces of each subpath, closed by a line back to its start if open, as filled.
+    fn closed_subpaths(&self) -> Vec<Vec<Piece>> {
+        let mut subpaths = vec![];
+        let mut pieces = vec![];
+        let (mut start, mut pos) = (self.init_pos, self.init_pos);
+        let close = |pieces: &mut Vec<Piece>, start: Complex<Float>, pos: Complex<Float>| {
+            if pos != start {
+                pieces.push(Piece::Line([pos, start]));
+            }
+        };
+        for command in &self.commands {
+            match *command {
+                Command::MoveTo { to } => {
+                    close(&mut pieces, start, pos);
+                    subpaths.push(std::mem::take(&mut pieces));
+                    (start, pos) = (to, to);
+                }
+                Command::LineTo { to } => {
+                    pieces.push(Piece::Line([pos, to]));
+                    pos = to;
+                }
+                Command::CubicBezier { cp1, cp2, to, .. } => {
+                    pieces.push(Piece::Cubic([pos, cp1, cp2, to]));
+                    pos = to;
+                }
+                Command::SquareBezier { cp, to, .. } => {
+                    pieces.push(Piece::quadratic(pos, cp, to));
+                    pos = to;
+                }
+                Command::Arc {
+                    radius,
+                    axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    to,
+                } => {
+                    pieces.extend(Piece::arc(
+                        pos,
                         radius,
                         axis_rotation,
                         large_arc_flag,
                         sweep_flag,
                         to,
-                    } => {
-                        let from = state[1];
-                        state[1] = to;
-                        Some(Some(Segment::Arc {
-                            from,
+                    ));
+                    pos = to;
+                }
+                Command::ClosePath => {
+                    close(&mut pieces, start, pos);
+                    pos = start;
+                }
+            }
+        }
+        close(&mut pieces, start, pos);
+        subpaths.push(pieces);
+        subpaths.retain(|pieces| !pieces.is_empty());
+        subpaths
+    }
+
+    /// Vertices of a polyline following the path within the chordal error `tolerance`, from the
+    /// start of the first subpath, with the curves subdivided adaptively to their curvature.
+    /// Subpaths follow one another, each starting with the point it moves to, and closing a
+    /// subpath repeats its start.
+    pub fn flatten(&self, tolerance: Float) -> impl Iterator<Item = Complex<Float>> + '_ {
+        let first = (!self.commands.is_empty()).then_some(self.init_pos);
+        let rest = self
+            .commands
+            .iter()
+            .scan(
+                (self.init_pos, self.init_pos),
+                move |(start, pos), command| {
+                    let mut points = vec![];
+                    let mut flatten = |piece: Piece| piece.flatten(tolerance, &mut points);
+                    match *command {
+                        Command::MoveTo { to } => {
+                            *start = to;
+                            points.push(to);
+                        }
+                        Command::LineTo { to } => points.push(to),
+                        Command::CubicBezier { cp1, cp2, to, .. } => {
+                            flatten(Piece::Cubic([*pos, cp1, cp2, to]))
+                        }
+                        Command::SquareBezier { cp, to, .. } => {
+                            flatten(Piece::quadratic(*pos, cp, to))
+                        }
+                        Command::Arc {
                             radius,
                             axis_rotation,
-                            large_arc_flag: *large_arc_flag,
-                            sweep_flag: *sweep_flag,
+                            large_arc_flag,
+                            sweep_flag,
                             to,
-                        }))
-                    }
-                    Command::ClosePath => {
-                        let from = state[1];
-                        state[1] = state[0];
-                        Some(Some(Segment::Line { from, to: state[0] }))
+                        } => {
+                            Piece::arc(*pos, radius, axis_rotation, large_arc_flag, sweep_flag, to)
+                                .into_iter()
+                                .for_each(flatten)
+                        }
+                        Command::ClosePath => points.push(*start),
                     }
+                    *pos = points.last().copied().unwrap_or(*pos);
+                    Some(points)
                 },
             )
-            .filter_map(|segment| segment)
+            .flatten();
+        first.into_iter().chain(rest)
+    }
+
+    /// Whether `point` is inside the area filled by the path under `rule`, with open subpaths
+    /// implicitly closed. Points on the outline, up to rounding errors, are inside.
+    ///
+    /// Curves are subdivided only near the point, until it is outside the bounding box of a
+    /// piece's control polygon, so the result is exact rather than within a flattening tolerance.
+    pub fn contains(&self, point: &Complex<Float>, rule: FillRule) -> bool {
+        let subpaths = self.closed_subpaths();
+        let extent = subpaths
+            .iter()
+            .flatten()
+            .flat_map(Piece::hull)
+            .map(|p| (p - *point).re.abs().max((p - *point).im.abs()))
+            .fold(0.0, Float::max);
+        let tolerance = extent * Float::EPSILON * 1e2;
+        let mut winding = 0;
+        for piece in subpaths.iter().flatten() {
+            match piece_winding(piece, *point, tolerance) {
+                Some(w) => winding += w,
+                None => return true,
+            }
+        }
+        match rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Subpaths flattened within `tolerance`, closed ones ending with their start.
+    fn flat_subpaths(&self, tolerance: Float) -> Vec<FlatSubpath> {
+        let mut subpaths = vec![];
+        let new = |start| FlatSubpath {
+            points: vec![start],
+            joints: vec![true],
+            closed: false,
+        };
+        // The subpath being drawn, empty after closing until drawing again.
+        let mut subpath = new(self.init_pos);
+        let mut start = self.init_pos;
+        for command in &self.commands {
+            if subpath.points.is_empty() && !matches!(command, Command::MoveTo { .. }) {
+                subpath = new(start);
+            }
+            let pos = subpath.points.last().copied().unwrap_or(start);
+            let mut flatten = |piece: Piece| piece.flatten(tolerance, &mut subpath.points);
+            match *command {
+                Command::MoveTo { to } => {
+                    if !subpath.points.is_empty() {
+                        subpaths.push(subpath);
+                    }
+                    (start, subpath) = (to, new(to));
+                }
+                Command::LineTo { to } => subpath.points.push(to),
+                Command::CubicBezier { cp1, cp2, to, .. } => {
+                    flatten(Piece::Cubic([pos, cp1, cp2, to]))
+                }
+                Command::SquareBezier { cp, to, .. } => flatten(Piece::quadratic(pos, cp, to)),
+                Command::Arc {
+                    radius,
+                    axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    to,
+                } => Piece::arc(pos, radius, axis_rotation, large_arc_flag, sweep_flag, to)
+                    .into_iter()
+                    .for_each(flatten),
+                Command::ClosePath => {
+                    subpath.points.push(start);
+                    subpath.closed = true;
+                }
+            }
+            subpath.joints.resize(subpath.points.len(), false);
+            if let Some(joint) = subpath.joints.last_mut() {
+                *joint = true;
+            }
+            if subpath.closed {
+                subpaths.push(std::mem::take(&mut subpath));
+            }
+        }
+        if !subpath.points.is_empty() {
+            subpaths.push(subpath);
+        }
+        subpaths
+    }
+
+    /// Polygonal path within `tolerance` of this one, with the curves flattened and the vertices
+    /// thinned by [`polyline::simplify`], each taking half of the tolerance. Subpaths and their
+    /// closing are kept.
+    pub fn simplify(&self, tolerance: Float) -> Self {
+        if self.commands.is_empty() {
+            return self.clone();
+        }
+        let tolerance = tolerance / 2.0;
+        let mut path = Self::new();
+        let append = |path: &mut Self, points: &[Complex<Float>], closed: bool| {
+            let points = polyline::simplify(points, tolerance);
+            let end = if closed && points.len() > 1 && points.first() == points.last() {
+                points.len() - 1
+            } else {
+                points.len()
+            };
+            path.move_to(points[0]);
+            points[1..end].iter().for_each(|&p| {
+                path.line_to(p);
+            });
+            if closed {
+                path.close_path();
+            }
+        };
+        for subpath in self.flat_subpaths(tolerance) {
+            append(&mut path, &subpath.points, subpath.closed);
+        }
+        path
+    }
+
+    /// Outline of the area covered by drawing the path with a pen of `width`, to be filled under
+    /// [`FillRule::NonZero`], with `cap` at the ends of open subpaths and `join` at the corners
+    /// between commands. Curves are flattened within [`STROKE_TOLERANCE`] of the width, while
+    /// round caps and joins are arcs.
+    ///
+    /// Each subpath gives a closed outline around it if open, or one on each side if closed. The
+    /// outlines cross themselves inside corners, covering the area there more than once, and
+    /// subpaths without length give nothing.
+    pub fn stroke(&self, width: Float, cap: LineCap, join: LineJoin) -> Self {
+        let mut path = Self::new();
+        let half = width / 2.0;
+        if half.is_nan() || half <= 0.0 {
+            return path;
+        }
+        for subpath in self.flat_subpaths(width * STROKE_TOLERANCE) {
+            let mut points: Vec<(Complex<Float>, bool)> = vec![];
+            for (&p, &joint) in subpath.points.iter().zip(&subpath.joints) {
+                match points.last_mut() {
+                    Some(last) if last.0 == p => last.1 |= joint,
+                    _ => points.push((p, joint)),
+                }
+            }
+            if subpath.closed {
+                if points.len() > 1 && points.first().map(|p| p.0) == points.last().map(|p| p.0) {
+                    points.pop();
+                }
+                if points.len() < 2 {
+                    continue;
+                }
+                for _ in 0..2 {
+                    stroke_loop(&mut path, &points, half, join);
+                    points.reverse();
+                }
+            } else if points.len() > 1 {
+                for i in 0..2 {
+                    stroke_side(&mut path, &points, half, join, i == 0);
+                    let (end, from) = (points[points.len() - 1].0, points[points.len() - 2].0);
+                    stroke_cap(&mut path, end, direction(from, end), half, cap, i == 1);
+                    points.reverse();
+                }
+                path.close_path();
+            }
+        }
+        path
+    }
+
+    /// Smooth path of cubic Bézier curves through `points`, closed back to the first point if
+    /// `closed`, as a cardinal spline. The tangent at each point is `1 - tension` times half of
+    /// the difference of its neighbors, the end points being their own neighbors if open, so
+    /// 0 gives a Catmull–Rom spline and 1 the polygon through the points.
+    pub fn catmull_rom(points: &[Complex<Float>], tension: Float, closed: bool) -> Self {
+        let mut path = Self::new();
+        let n = points.len();
+        let Some(&first) = points.first() else {
+            return path;
+        };
+        path.move_to(first);
+        let point = |i: usize| points[i.min(n - 1)];
+        let tangent = |i: usize| {
+            let (before, after) = if closed {
+                (points[(i + n - 1) % n], points[(i + 1) % n])
+            } else {
+                (points[i.saturating_sub(1)], point(i + 1))
+            };
+            (after - before) * ((1.0 - tension) / 2.0)
+        };
+        let count = if closed && n > 1 { n } else { n - 1 };
+        for i in 0..count {
+            let (j, to) = ((i + 1) % n, points[(i + 1) % n]);
+            path.cubic_bezier(point(i) + tangent(i) / 3.0, to - tangent(j) / 3.0, to);
+        }
+        if closed {
+            path.close_path();
+        }
+        path
+    }
+
+    /// The path with the arcs replaced by cubic Bézier curves within `tolerance`, as
+    /// [`EllipticalArc::to_cubics`], or by lines if a radius is zero.
+    pub fn arcs_to_cubics(&self, tolerance: Float) -> Self {
+        let mut path = Self {
+            commands: vec![],
+            init_pos: self.init_pos,
+            started: self.started,
+        };
+        let mut replaced = false;
+        for (command, segment) in self.commands.iter().zip(self.command_segments()) {
+            match (
+                command,
+                segment.and_then(|segment| segment.elliptical_arc()),
+            ) {
+                (Command::Arc { to, .. }, Some(arc)) => {
+                    let cubics = arc.to_cubics(tolerance);
+                    for (i, cubic) in cubics.iter().enumerate() {
+                        let to = if i + 1 == cubics.len() { *to } else { cubic.to };
+                        path.cubic_bezier(cubic.cp1, cubic.cp2, to);
+                    }
+                }
+                (Command::Arc { to, radius, .. }, None) => {
+                    if radius.re == 0.0 || radius.im == 0.0 {
+                        path.line_to(*to);
+                    }
+                }
+                // Reflecting the control point of the curve replacing the arc.
+                (
+                    &Command::CubicBezier {
+                        cp1,
+                        cp2,
+                        to,
+                        smooth: true,
+                    },
+                    _,
+                ) if replaced => {
+                    path.cubic_bezier(cp1, cp2, to);
+                }
+                _ => path.commands.push(command.clone()),
+            }
+            replaced = matches!(command, Command::Arc { .. });
+        }
+        path
+    }
+
+    /// Drawing commands of the canvas API as a flat array, each an opcode followed by its
+    /// arguments in the order of the corresponding method of `CanvasRenderingContext2D` and
+    /// `Path2D`:
+    ///
+    /// | opcode | method             | arguments                                                  |
+    /// |--------|--------------------|------------------------------------------------------------|
+    /// | 0      | `moveTo`           | x, y                                                       |
+    /// | 1      | `lineTo`           | x, y                                                       |
+    /// | 2      | `bezierCurveTo`    | cp1x, cp1y, cp2x, cp2y, x, y                               |
+    /// | 3      | `quadraticCurveTo` | cpx, cpy, x, y                                             |
+    /// | 4      | `ellipse`          | x, y, radiusX, radiusY, rotation, start, end, anticlockwise |
+    /// | 5      | `closePath`        |                                                            |
+    ///
+    /// Angles are in radians and `anticlockwise` is 1 or 0. Arcs with a zero radius are lines and
+    /// arcs to their start point are omitted, as SVG draws them.
+    pub fn canvas_commands(&self) -> Vec<Float> {
+        if self.commands.is_empty() {
+            return vec![];
+        }
+        let mut commands = vec![0.0, self.init_pos.re, self.init_pos.im];
+        for (command, segment) in self.commands.iter().zip(self.command_segments()) {
+            match (command, segment) {
+                (Command::MoveTo { to }, _) => commands.extend([0.0, to.re, to.im]),
+                (Command::ClosePath, _) => commands.push(5.0),
+                (_, Some(Segment::CubicBezier { cp1, cp2, to, .. })) => {
+                    commands.extend([2.0, cp1.re, cp1.im, cp2.re, cp2.im, to.re, to.im])
+                }
+                (_, Some(Segment::SquareBezier { cp, to, .. })) => {
+                    commands.extend([3.0, cp.re, cp.im, to.re, to.im])
+                }
+                (_, Some(segment @ Segment::Arc { from, to, .. })) => {
+                    match segment.elliptical_arc() {
+                        Some(arc) => commands.extend([
+                            4.0,
+                            arc.center.re,
+                            arc.center.im,
+                            arc.radius.re,
+                            arc.radius.im,
+                            arc.rotation,
+                            arc.start,
+                            arc.start + arc.sweep,
+                            if arc.sweep < 0.0 { 1.0 } else { 0.0 },
+                        ]),
+                        None if from != to => commands.extend([1.0, to.re, to.im]),
+                        None => {}
+                    }
+                }
+                (_, Some(Segment::Line { to, .. })) => commands.extend([1.0, to.re, to.im]),
+                (_, None) => {}
+            }
+        }
+        commands
+    }
+
+    /// Signed area enclosed by the outline, with open subpaths implicitly closed, positive where
+    /// it winds counterclockwise and negative where clockwise.
+    pub fn signed_area(&self) -> Float {
+        self.closed_subpaths()
+            .iter()
+            .flatten()
+            .map(Piece::signed_area)
+            .sum()
+    }
+
+    /// Whether the outline winds counterclockwise, by the sign of [`Self::signed_area`], so a
+    /// hole winding against its boundary counts against it. `None` if the area is zero.
+    pub fn winding(&self) -> Option<bool> {
+        let area = self.signed_area();
+        (area != 0.0).then_some(area > 0.0)
+    }
+
+    /// The path reversed if it does not wind counterclockwise when `ccw`, or clockwise if not,
+    /// keeping holes winding against their boundary.
+    pub fn with_winding(&self, ccw: bool) -> Self {
+        match self.winding() {
+            Some(winding) if winding != ccw => self.reverse(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Rounds the corners between consecutive lines, including where closed subpaths close, with
+    /// circular arcs of `radius` tangent to both. The radius is reduced where the arc would take
+    /// more than half of either line.
+    pub fn fillet(&self, radius: Float) -> Self {
+        self.cut_corners(Corner::Fillet(radius))
+    }
+
+    /// Cuts the corners between consecutive lines like [`Self::fillet`], with a line from
+    /// `setback` before to `setback` after each corner.
+    pub fn chamfer(&self, setback: Float) -> Self {
+        self.cut_corners(Corner::Chamfer(setback))
+    }
+
+    fn cut_corners(&self, corner: Corner) -> Self {
+        if self.commands.is_empty() {
+            return self.clone();
+        }
+        let mut path = Self::new();
+        let append = |path: &mut Self, start, edges: &[Edge], closed, closing| {
+            let n = edges.len();
+            let cuts: Vec<_> = (0..n)
+                .map(|i| match (edges[i], edges[(i + 1) % n]) {
+                    (Edge::Line(a), Edge::Line(b)) if closed || i + 1 < n => corner.cut(a, b),
+                    _ => None,
+                })
+                .collect();
+            let first = match cuts.last() {
+                Some(Some(cut)) if closed => cut.to,
+                _ => start,
+            };
+            path.move_to(first);
+            for (i, (edge, cut)) in edges.iter().zip(&cuts).enumerate() {
+                match *edge {
+                    Edge::Line([_, to]) => {
+                        let end = cut.map_or(to, |cut| cut.from);
+                        // The closing line is left to closing.
+                        let implicit = closing && i + 1 == n && cut.is_none();
+                        if !implicit && end != *path.current_point() {
+                            path.line_to(end);
+                        }
+                    }
+                    Edge::Curve(command) => path.commands.push(command.clone()),
+                }
+                if let Some(cut) = cut {
+                    match corner {
+                        Corner::Fillet(_) => path.arc(
+                            Complex::new(cut.radius, cut.radius),
+                            0.0,
+                            false,
+                            cut.sweep_flag,
+                            cut.to,
+                        ),
+                        Corner::Chamfer(_) => path.line_to(cut.to),
+                    };
+                }
+            }
+            if closed {
+                path.close_path();
+            }
+        };
+        let mut edges = vec![];
+        let (mut start, mut pos, mut closed) = (self.init_pos, self.init_pos, false);
+        for command in &self.commands {
+            match command {
+                Command::MoveTo { to } => {
+                    if !closed {
+                        append(&mut path, start, &edges, false, false);
+                    }
+                    edges.clear();
+                    (start, pos, closed) = (*to, *to, false);
+                }
+                Command::ClosePath => {
+                    let closing = pos != start;
+                    if closing {
+                        edges.push(Edge::Line([pos, start]));
+                    }
+                    append(&mut path, start, &edges, true, closing);
+                    edges.clear();
+                    (pos, closed) = (start, true);
+                }
+                Command::LineTo { to } => {
+                    edges.push(Edge::Line([pos, *to]));
+                    (pos, closed) = (*to, false);
+                }
+                Command::CubicBezier { to, .. }
+                | Command::SquareBezier { to, .. }
+                | Command::Arc { to, .. } => {
+                    edges.push(Edge::Curve(command));
+                    (pos, closed) = (*to, false);
+                }
+            }
+        }
+        if !closed {
+            append(&mut path, start, &edges, false, false);
+        }
+        path
+    }
+
+    /// Nearest point of the path to `point`, `None` if the path has no segments. Where several
+    /// points are the nearest, the first along the path is taken.
+    pub fn project(&self, point: &Complex<Float>) -> Option<Projection> {
+        self.segments()
+            .enumerate()
+            .map(|(i, segment)| {
+                let t = nearest_parameter(&segment, *point);
+                let nearest = segment.point_at(t);
+                Projection {
+                    point: nearest,
+                    segment: i,
+                    t,
+                    distance: (nearest - *point).abs(),
+                }
+            })
+            .reduce(|a, b| if b.distance < a.distance { b } else { a })
+    }
+}
+
+/// Flattening tolerance of [`Path::stroke`] relative to the width.
+pub const STROKE_TOLERANCE: Float = 1e-3;
+
+fn direction(from: Complex<Float>, to: Complex<Float>) -> Complex<Float> {
+    (to - from) / (to - from).abs()
+}
+
+/// Direction to the left of the direction `d`.
+fn left(d: Complex<Float>) -> Complex<Float> {
+    Complex::new(-d.im, d.re)
+}
+
+/// Appends the side on the left of the open polyline `points`, from beside the first point, if
+/// `start`, to beside the last one.
+fn stroke_side(
+    path: &mut Path<Complex<Float>, Float>,
+    points: &[(Complex<Float>, bool)],
+    half: Float,
+    join: LineJoin,
+    start: bool,
+) {
+    let d = |i: usize| direction(points[i].0, points[i + 1].0);
+    if start {
+        path.move_to(points[0].0 + left(d(0)) * half);
+    }
+    for w in points.windows(3) {
+        let (d_in, d_out) = (direction(w[0].0, w[1].0), direction(w[1].0, w[2].0));
+        stroke_joint(path, w[1], d_in, d_out, half, join);
+    }
+    let last = points.len() - 1;
+    path.line_to(points[last].0 + left(d(last - 1)) * half);
+}
+
+/// Appends the closed outline on the left of the polygon `points`.
+fn stroke_loop(
+    path: &mut Path<Complex<Float>, Float>,
+    points: &[(Complex<Float>, bool)],
+    half: Float,
+    join: LineJoin,
+) {
+    let n = points.len();
+    let d = |i: usize| direction(points[i % n].0, points[(i + 1) % n].0);
+    path.move_to(points[0].0 + left(d(0)) * half);
+    for i in 1..=n {
+        stroke_joint(path, points[i % n], d(i - 1), d(i), half, join);
+    }
+    path.close_path();
+}
+
+/// Appends the left side of the corner at `point` from the direction `d_in` to `d_out`,
+/// joining the edges as `join` outside a joint between commands.
+fn stroke_joint(
+    path: &mut Path<Complex<Float>, Float>,
+    (p, joint): (Complex<Float>, bool),
+    d_in: Complex<Float>,
+    d_out: Complex<Float>,
+    half: Float,
+    join: LineJoin,
+) {
+    let (a, b) = (p + left(d_in) * half, p + left(d_out) * half);
+    let turn = d_in.conj() * d_out;
+    path.line_to(a);
+    if a == b {
+        return;
+    }
+    if !joint {
+        path.line_to(b);
+    } else if turn.im > 0.0 {
+        // Inside, through the corner so the overlap winds the same way as the rest.
+        path.line_to(p).line_to(b);
+    } else {
+        match join {
+            LineJoin::Miter { limit } if 2.0 <= limit * limit * (1.0 + turn.re) => {
+                path.line_to(p + (left(d_in) + left(d_out)) * (half / (1.0 + turn.re)));
+            }
+            LineJoin::Round => {
+                path.arc(Complex::new(half, half), 0.0, false, false, b);
+                return;
+            }
+            _ => {}
+        }
+        path.line_to(b);
+    }
+}
+
+/// Appends the cap at `end` of an open subpath in the direction `d`, from the left side to the
+/// right side, but leaves its last line to closing if `closing`.
+fn stroke_cap(
+    path: &mut Path<Complex<Float>, Float>,
+    end: Complex<Float>,
+    d: Complex<Float>,
+    half: Float,
+    cap: LineCap,
+    closing: bool,
+) {
+    let n = left(d) * half;
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Round => {
+            let radius = Complex::new(half, half);
+            path.arc(radius, 0.0, false, false, end + d * half).arc(
+                radius,
+                0.0,
+                false,
+                false,
+                end - n,
+            );
+            return;
+        }
+        LineCap::Square => {
+            path.line_to(end + n + d * half).line_to(end - n + d * half);
+        }
+    }
+    if !closing {
+        path.line_to(end - n);
+    }
+}
+
+/// Subpath flattened to a polyline.
+#[derive(Default)]
+struct FlatSubpath {
+    points: Vec<Complex<Float>>,
+    /// Whether each point ends a command rather than a piece of a curve.
+    joints: Vec<bool>,
+    closed: bool,
+}
+
+/// Outline between two corners in [`Path::cut_corners`].
+#[derive(Clone, Copy)]
+enum Edge<'a> {
+    Line([Complex<Float>; 2]),
+    Curve(&'a Command<Complex<Float>, Float>),
+}
+
+#[derive(Clone, Copy)]
+enum Corner {
+    Fillet(Float),
+    Chamfer(Float),
+}
+
+/// Replacement of a corner, from `from` on the line before it to `to` on the line after it.
+#[derive(Clone, Copy)]
+struct Cut {
+    from: Complex<Float>,
+    to: Complex<Float>,
+    /// Radius of the fillet arc.
+    radius: Float,
+    sweep_flag: bool,
+}
+
+impl Corner {
+    /// Cut of the corner where the line `a` meets the line `b`, `None` where they do not turn.
+    fn cut(&self, a: [Complex<Float>; 2], b: [Complex<Float>; 2]) -> Option<Cut> {
+        let (u, v) = (a[1] - a[0], b[1] - b[0]);
+        let (la, lb) = (u.abs(), v.abs());
+        let (dot, cross) = ((u.conj() * v).re, (u.conj() * v).im);
+        // Tangent of half of the turn, from the sine and cosine of the turn.
+        let tan = cross.abs() / (la * lb + dot);
+        if cross == 0.0 || !tan.is_finite() {
+            return None;
+        }
+        let setback = match *self {
+            Corner::Fillet(radius) => radius * tan,
+            Corner::Chamfer(setback) => setback,
+        }
+        .min(la / 2.0)
+        .min(lb / 2.0);
+        (setback > 0.0).then(|| Cut {
+            from: a[1] - u * (setback / la),
+            to: b[0] + v * (setback / lb),
+            radius: setback / tan,
+            sweep_flag: cross > 0.0,
+        })
     }
 }
 
-impl<T, S> Path<T, S>
+/// Number of samples in [`Path::project`] bracketing the nearest point of each segment.
+const PROJECT_SAMPLES: usize = 16;
+
+/// Limit of the bisections in [`Path::project`], more than enough to reach the precision.
+const PROJECT_ITERATIONS: usize = 96;
+
+/// Parameter of the point of `segment` nearest to `p`. Bisects where the offset from `p` turns
+/// from against to along the direction of the segment, next to the nearest of evenly spaced
+/// samples, assuming it turns at most once between the neighbors of the sample.
+fn nearest_parameter(segment: &Segment<'_, Complex<Float>, Float>, p: Complex<Float>) -> Float {
+    let distance = |t: Float| (segment.point_at(t) - p).abs();
+    let along = |t: Float| (segment.derivative_at(t).conj() * (segment.point_at(t) - p)).re;
+    let nearest = |ts: &mut dyn Iterator<Item = Float>| {
+        ts.min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap_or(0.0)
+    };
+    let step = 1.0 / PROJECT_SAMPLES as Float;
+    let sample = nearest(&mut (0..=PROJECT_SAMPLES).map(|i| i as Float * step));
+    let (mut lower, mut upper) = ((sample - step).max(0.0), (sample + step).min(1.0));
+    if along(lower) < 0.0 && along(upper) > 0.0 {
+        for _ in 0..PROJECT_ITERATIONS {
+            let middle = (lower + upper) / 2.0;
+            if middle <= lower || middle >= upper {
+                break;
+            }
+            if along(middle) < 0.0 {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+    }
+    nearest(&mut [sample, lower, upper].into_iter())
+}
+
+/// Crossings of the rightward ray from `p` by `piece`, or `None` if `p` is on it.
+fn piece_winding(piece: &Piece, p: Complex<Float>, tolerance: Float) -> Option<i32> {
+    let (a, b) = (piece.from(), piece.to());
+    let (min, max) = piece.bounds();
+    let outside = p.re < min.re - tolerance
+        || p.re > max.re + tolerance
+        || p.im < min.im - tolerance
+        || p.im > max.im + tolerance;
+    if outside {
+        // The piece and its chord enclose only area within the box.
+        Some(crossing(a, b, p))
+    } else if matches!(piece, Piece::Line(_)) || piece.flatness() <= tolerance {
+        (distance_to_segment(a, b, p) > tolerance).then(|| crossing(a, b, p))
+    } else {
+        let (first, second) = piece.split();
+        Some(piece_winding(&first, p, tolerance)? + piece_winding(&second, p, tolerance)?)
+    }
+}
+
+/// Radii and axis rotation in degrees of the image of an ellipse under the linear part of
+/// `transform`, from the singular value decomposition of the 2×2 matrix mapping the unit circle
+/// onto it.
+fn transform_ellipse(
+    transform: &Affine2<Float>,
+    radius: &Complex<Float>,
+    axis_rotation: Float,
+) -> (Complex<Float>, Float) {
+    let rotation = Affine2::from_rotation(&axis_rotation.to_radians());
+    let m = transform * rotation;
+    let (a, b, c, d) = (
+        m.x.re * radius.re,
+        m.y.re * radius.im,
+        m.x.im * radius.re,
+        m.y.im * radius.im,
+    );
+    let (e, f, g, h) = ((a + d) / 2.0, (a - d) / 2.0, (c + b) / 2.0, (c - b) / 2.0);
+    let (q, r) = (e.hypot(h), f.hypot(g));
+    // Circles have no axis direction, so theirs is left unrotated.
+    let angle = if q.min(r) <= q.max(r) * Float::EPSILON * 4.0 {
+        0.0
+    } else {
+        (g.atan2(f) + h.atan2(e)) / 2.0
+    };
+    (Complex::new(q + r, (q - r).abs()), angle.to_degrees())
+}
+
+fn fmt_point<T>(point: &Complex<T>, f: &mut Formatter) -> fmt::Result
 where
-    T: Default,
+    T: Display,
 {
-    pub fn new() -> Self {
-        Self {
-            commands: vec![],
-            init_pos: T::default(),
+    point.re.fmt(f)?;
+    f.write_str(",")?;
+    point.im.fmt(f)
+}
+
+/// Formats as SVG path data, applying the formatter options to each coordinate.
+impl<T, S> Display for Path<Complex<T>, S>
+where
+    T: Display,
+    S: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.commands.is_empty() {
+            return Ok(());
+        }
+        f.write_str("M")?;
+        fmt_point(&self.init_pos, f)?;
+        for command in &self.commands {
+            match command {
+                Command::MoveTo { to } => {
+                    f.write_str(" M")?;
+                    fmt_point(to, f)?;
+                }
+                Command::LineTo { to } => {
+                    f.write_str(" L")?;
+                    fmt_point(to, f)?;
+                }
+                Command::CubicBezier {
+                    cp1,
+                    cp2,
+                    to,
+                    smooth,
+                } => {
+                    if *smooth {
+                        f.write_str(" S")?;
+                    } else {
+                        f.write_str(" C")?;
+                        fmt_point(cp1, f)?;
+                        f.write_str(" ")?;
+                    }
+                    fmt_point(cp2, f)?;
+                    f.write_str(" ")?;
+                    fmt_point(to, f)?;
+                }
+                Command::SquareBezier { cp, to, smooth } => {
+                    if *smooth {
+                        f.write_str(" T")?;
+                    } else {
+                        f.write_str(" Q")?;
+                        fmt_point(cp, f)?;
+                        f.write_str(" ")?;
+                    }
+                    fmt_point(to, f)?;
+                }
+                Command::Arc {
+                    radius,
+                    axis_rotation,
+                    large_arc_flag,
+                    sweep_flag,
+                    to,
+                } => {
+                    f.write_str(" A")?;
+                    fmt_point(radius, f)?;
+                    f.write_str(" ")?;
+                    axis_rotation.fmt(f)?;
+                    write!(f, " {} {} ", *large_arc_flag as u8, *sweep_flag as u8)?;
+                    fmt_point(to, f)?;
+                }
+                Command::ClosePath => f.write_str(" Z")?,
+            }
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::math::complex::Complex;
-
     use super::*;
 
     #[test]
@@ -314,4 +1821,905 @@ mod tests {
         );
         assert_eq!(i.next(), None);
     }
+
+    #[test]
+    fn display() {
+        let mut a = Path::new();
+        assert_eq!(a.to_string(), "");
+        a.move_to(Complex::new(3, 5))
+            .line_to(Complex::new(8, -2))
+            .line_to(Complex::new(-12, 6))
+            .close_path()
+            .arc(Complex::new(30, 25), 1, false, true, Complex::new(18, 21))
+            .move_to(Complex::new(32, 55))
+            .cubic_bezier(
+                Complex::new(61, 32),
+                Complex::new(83, 11),
+                Complex::new(108, 129),
+            )
+            .square_bezier(Complex::new(-21, 30), Complex::new(-71, 91));
+        assert_eq!(
+            a.to_string(),
+            "M3,5 L8,-2 L-12,6 Z A30,25 1 0 1 18,21 M32,55 C61,32 83,11 108,129 Q-21,30 -71,91",
+        );
+        let mut b = Path::new();
+        b.move_to(Complex::new(0.25, 1.0)).arc(
+            Complex::new(2.0, 1.5),
+            0.5,
+            true,
+            false,
+            Complex::new(-1.125, 3.0),
+        );
+        assert_eq!(
+            format!("{:.2}", b),
+            "M0.25,1.00 A2.00,1.50 0.50 1 0 -1.12,3.00"
+        );
+    }
+
+    #[test]
+    fn transform() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(1.0, 0.0))
+            .line_to(Complex::new(2.0, 1.0))
+            .cubic_bezier(
+                Complex::new(3.0, 1.0),
+                Complex::new(3.0, 2.0),
+                Complex::new(2.0, 3.0),
+            )
+            .square_bezier(Complex::new(1.0, 3.0), Complex::new(0.0, 2.0))
+            .arc(
+                Complex::new(2.0, 1.0),
+                0.0,
+                true,
+                false,
+                Complex::new(1.0, 0.0),
+            )
+            .close_path();
+        let t = Affine2::from_translation(Complex::new(1.0, -1.0));
+        assert_eq!(
+            a.transform(&t).to_string(),
+            "M2,-1 L3,0 C4,0 4,1 3,2 Q2,2 1,1 A2,1 0 1 0 2,-1 Z"
+        );
+
+        let b = a.transform(&(t * Affine2::from_rotation(&(30.0 as Float).to_radians())));
+        let Some(Segment::Arc {
+            radius,
+            axis_rotation,
+            sweep_flag,
+            ..
+        }) = b.segments().nth(3)
+        else {
+            panic!("expected an arc");
+        };
+        assert!((*radius - Complex::new(2.0, 1.0)).abs() < Float::EPSILON * 1e1);
+        assert!((axis_rotation - 30.0).abs() < Float::EPSILON * 1e3);
+        assert!(!sweep_flag);
+
+        let c = a.transform(&Affine2::from_scale(3.0, -1.0));
+        let Some(Segment::Arc {
+            radius,
+            axis_rotation,
+            sweep_flag,
+            to,
+            ..
+        }) = c.segments().nth(3)
+        else {
+            panic!("expected an arc");
+        };
+        assert!((*radius - Complex::new(6.0, 1.0)).abs() < Float::EPSILON * 1e1);
+        assert!(axis_rotation.to_radians().sin().abs() < Float::EPSILON * 1e1);
+        assert!(sweep_flag);
+        assert_eq!(*to, Complex::new(3.0, 0.0));
+    }
+
+    #[test]
+    fn contains() {
+        // A square with a circular hole drawn in the same direction, and an open triangle.
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(4.0, 0.0))
+            .line_to(Complex::new(4.0, 4.0))
+            .line_to(Complex::new(0.0, 4.0))
+            .close_path()
+            .move_to(Complex::new(3.0, 2.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(1.0, 2.0),
+            )
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(3.0, 2.0),
+            )
+            .move_to(Complex::new(5.0, 0.0))
+            .line_to(Complex::new(7.0, 0.0))
+            .line_to(Complex::new(6.0, 2.0));
+        let inside = |x, y, rule| a.contains(&Complex::new(x, y), rule);
+        assert!(inside(0.5, 0.5, FillRule::NonZero));
+        assert!(inside(0.5, 0.5, FillRule::EvenOdd));
+        assert!(inside(2.0, 2.0, FillRule::NonZero));
+        assert!(!inside(2.0, 2.0, FillRule::EvenOdd));
+        // Just inside and outside the hole, closer than any reasonable flattening.
+        assert!(!inside(2.0, 2.999, FillRule::EvenOdd));
+        assert!(inside(2.0, 3.001, FillRule::EvenOdd));
+        assert!(!inside(2.705, 2.705, FillRule::EvenOdd));
+        assert!(inside(2.709, 2.709, FillRule::EvenOdd));
+        assert!(inside(6.0, 0.5, FillRule::NonZero));
+        assert!(!inside(5.0, 1.0, FillRule::NonZero));
+        assert!(!inside(8.0, 2.0, FillRule::NonZero));
+        assert!(!inside(-1.0, 2.0, FillRule::NonZero));
+        // Points on the outline.
+        assert!(inside(4.0, 1.0, FillRule::EvenOdd));
+        assert!(inside(2.0, 1.0, FillRule::EvenOdd));
+        assert!(inside(6.5, 1.0, FillRule::EvenOdd));
+        assert!(!Path::new().contains(&Complex::new(0.0, 0.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn contains_bezier() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .cubic_bezier(
+                Complex::new(0.0, 4.0),
+                Complex::new(4.0, 4.0),
+                Complex::new(4.0, 0.0),
+            )
+            .square_bezier(Complex::new(2.0, -2.0), Complex::new(0.0, 0.0));
+        // The cubic peaks at (2, 3) and the quadratic bottoms out at (2, -1).
+        assert!(a.contains(&Complex::new(2.0, 2.999), FillRule::NonZero));
+        assert!(!a.contains(&Complex::new(2.0, 3.001), FillRule::NonZero));
+        assert!(a.contains(&Complex::new(2.0, 3.0), FillRule::NonZero));
+        assert!(a.contains(&Complex::new(2.0, -0.999), FillRule::NonZero));
+        assert!(!a.contains(&Complex::new(2.0, -1.001), FillRule::NonZero));
+        assert!(!a.contains(&Complex::new(0.1, 2.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn flatten() {
+        let mut a = Path::new();
+        assert_eq!(a.flatten(0.1).count(), 0);
+        a.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(2.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(0.0, 0.0),
+            )
+            .move_to(Complex::new(3.0, 0.0))
+            .square_bezier(Complex::new(4.0, 2.0), Complex::new(5.0, 0.0))
+            .close_path();
+        let points: Vec<_> = a.flatten(1e-2).collect();
+        let split = points
+            .iter()
+            .position(|&p| p == Complex::new(3.0, 0.0))
+            .unwrap();
+        let (arc, curve) = points.split_at(split);
+        assert_eq!(arc[..2], [Complex::new(0.0, 0.0), Complex::new(2.0, 0.0)]);
+        assert_eq!(arc.last(), Some(&Complex::new(0.0, 0.0)));
+        let center = Complex::new(1.0, 0.0);
+        assert!(arc.len() > 6);
+        for pair in arc[1..].windows(2) {
+            assert!(((pair[1] - center).abs() - 1.0).abs() < Float::EPSILON * 1e1);
+            assert!(1.0 - ((pair[0] + pair[1]) / 2.0 - center).abs() <= 1e-2);
+        }
+        assert_eq!(
+            curve[curve.len() - 2..],
+            [Complex::new(5.0, 0.0), Complex::new(3.0, 0.0)]
+        );
+        // The quadratic peaks at (4, 1).
+        let peak = curve.iter().map(|p| p.im).fold(0.0, Float::max);
+        assert!(peak <= 1.0 && peak > 1.0 - 1e-2);
+    }
+
+    #[test]
+    fn relative() {
+        let mut a: Path<Complex<i32>, i32> = Path::new();
+        a.move_by(Complex::new(1, 2))
+            .line_by(Complex::new(3, 0))
+            .vertical_by(2)
+            .horizontal_to(0)
+            .vertical_to(-1)
+            .horizontal_by(-1)
+            .close_path();
+        assert_eq!(*a.current_point(), Complex::new(1, 2));
+        a.cubic_bezier_by(Complex::new(1, 0), Complex::new(2, 1), Complex::new(2, 2))
+            .square_bezier_by(Complex::new(0, 1), Complex::new(-1, 1))
+            .arc_by(Complex::new(2, 2), 0, false, true, Complex::new(-2, 0))
+            .move_by(Complex::new(0, 5))
+            .line_by(Complex::new(1, 1));
+        assert_eq!(
+            a.to_string(),
+            "M1,2 L4,2 L4,4 L0,4 L0,-1 L-1,-1 Z C2,2 3,3 3,4 Q3,5 2,5 A2,2 0 0 1 0,5 M0,10 L1,11",
+        );
+        assert_eq!(*a.current_point(), Complex::new(1, 11));
+        assert_eq!(
+            *Path::<Complex<i32>, i32>::new().current_point(),
+            Complex::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn smooth() {
+        let mut a: Path<Complex<i32>, i32> = Path::new();
+        a.move_to(Complex::new(0, 0))
+            .smooth_cubic(Complex::new(1, 2), Complex::new(2, 0))
+            .smooth_cubic(Complex::new(4, -2), Complex::new(4, 0))
+            .square_bezier(Complex::new(5, 1), Complex::new(6, 0))
+            .smooth_quad(Complex::new(8, 0))
+            .smooth_quad(Complex::new(10, 0))
+            .line_to(Complex::new(10, 2))
+            .smooth_quad(Complex::new(12, 2));
+        assert_eq!(
+            a.to_string(),
+            "M0,0 S1,2 2,0 S4,-2 4,0 Q5,1 6,0 T8,0 T10,0 L10,2 T12,2",
+        );
+        let mut i = a.segments();
+        assert_eq!(
+            i.next(),
+            Some(Segment::CubicBezier {
+                from: &Complex::new(0, 0),
+                cp1: &Complex::new(0, 0),
+                cp2: &Complex::new(1, 2),
+                to: &Complex::new(2, 0),
+            }),
+        );
+        assert_eq!(
+            i.next(),
+            Some(Segment::CubicBezier {
+                from: &Complex::new(2, 0),
+                cp1: &Complex::new(3, -2),
+                cp2: &Complex::new(4, -2),
+                to: &Complex::new(4, 0),
+            }),
+        );
+        let quads: Vec<_> = i
+            .filter_map(|segment| match segment {
+                Segment::SquareBezier { cp, .. } => Some(*cp),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            quads,
+            [
+                Complex::new(5, 1),
+                Complex::new(7, -1),
+                Complex::new(9, 1),
+                Complex::new(10, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn transform_arcs() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(2.0, 1.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(0.0, 1.0),
+            )
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(2.0, 1.0),
+            );
+        let t = Affine2::from_rotation(&0.4) * Affine2::from_scale(3.0, 1.0);
+        let t = t * Affine2::from_rotation(&-1.1) * Affine2::from_scale(1.0, -0.5);
+        let b = a
+            .transform(&t)
+            .translate(Complex::new(1.0, 1.0))
+            .rotate(0.3)
+            .scale(2.0, 1.5);
+        let t = Affine2::from_scale(2.0, 1.5)
+            * Affine2::from_rotation(&0.3)
+            * Affine2::from_translation(Complex::new(1.0, 1.0))
+            * t;
+        // The flattened image lies on the image of the unit circle around (1, 1).
+        let inverse = t.inv();
+        let points: Vec<_> = b.flatten(1e-3).collect();
+        assert!(points.len() > 10);
+        for p in points {
+            let q = inverse.apply(&p);
+            // Half turns put the center where it is the square root of a rounding error.
+            assert!(((q - Complex::new(1.0, 1.0)).abs() - 1.0).abs() < Float::EPSILON.sqrt());
+        }
+        assert_eq!(
+            a.scale(-1.0, 1.0).to_string(),
+            "M-2,1 A1,1 0 0 0 0,1 A1,1 0 0 0 -2,1"
+        );
+    }
+
+    #[test]
+    fn elliptical_arc() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(1.0, 0.0))
+            .line_to(Complex::new(2.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(0.0, 2.0),
+            );
+        let segments: Vec<_> = a.segments().collect();
+        assert_eq!(segments[0].elliptical_arc(), None);
+        let arc = segments[1].elliptical_arc().unwrap();
+        assert!((arc.point_at(1.0) - Complex::new(0.0, 2.0)).abs() < Float::EPSILON * 1e1);
+    }
+
+    #[test]
+    fn bezier() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .square_bezier(Complex::new(1.0, 2.0), Complex::new(2.0, 0.0))
+            .cubic_bezier(
+                Complex::new(2.0, -1.0),
+                Complex::new(3.0, -2.0),
+                Complex::new(4.0, 0.0),
+            )
+            .smooth_cubic(Complex::new(6.0, 2.0), Complex::new(6.0, 0.0));
+        let segments: Vec<_> = a.segments().collect();
+        let q = segments[0].square_bezier().unwrap();
+        assert_eq!(q.point_at(0.5), Complex::new(1.0, 1.0));
+        assert_eq!(segments[0].cubic_bezier(), None);
+        // The smooth curve starts in the direction the previous one ends.
+        let c = segments[1].cubic_bezier().unwrap();
+        let s = segments[2].cubic_bezier().unwrap();
+        assert_eq!(s.cp1, Complex::new(5.0, 2.0));
+        assert_eq!(s.derivative_at(0.0), c.derivative_at(1.0));
+    }
+
+    #[test]
+    fn project() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(2.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(3.0, 1.0),
+            )
+            .cubic_bezier(
+                Complex::new(3.0, 2.0),
+                Complex::new(1.0, 3.0),
+                Complex::new(0.0, 3.0),
+            )
+            .close_path();
+        let p = a.project(&Complex::new(0.5, -1.0)).unwrap();
+        assert_eq!(p.segment, 0);
+        assert!((p.point - Complex::new(0.5, 0.0)).abs() < Float::EPSILON * 1e1);
+        assert!((p.t - 0.25).abs() < Float::EPSILON * 1e1);
+        assert!((p.distance - 1.0).abs() < Float::EPSILON * 1e1);
+        // The search includes the ends of the segments.
+        let p = a.project(&Complex::new(-1.0, -1.0)).unwrap();
+        assert_eq!((p.segment, p.point, p.t), (0, Complex::new(0.0, 0.0), 0.0));
+        let p = a.project(&Complex::new(4.0, 0.0)).unwrap();
+        assert_eq!(p.segment, 1);
+        // Toward the point from the center of the quarter circle.
+        let sqrt5 = (5.0 as Float).sqrt();
+        let expected = Complex::new(2.0, 1.0) + Complex::new(2.0, -1.0) / sqrt5;
+        assert!((p.point - expected).abs() < Float::EPSILON * 1e1);
+        assert!((p.distance - (sqrt5 - 1.0)).abs() < Float::EPSILON * 1e1);
+        // The nearest point of a curve is where the offset is normal to it.
+        let p = a.project(&Complex::new(2.5, 3.0)).unwrap();
+        assert_eq!(p.segment, 2);
+        let curve = a.segments().nth(2).unwrap().cubic_bezier().unwrap();
+        let (offset, tangent) = (Complex::new(2.5, 3.0) - p.point, curve.derivative_at(p.t));
+        assert!((offset.conj() * tangent).re.abs() < Float::EPSILON * 1e2);
+        // The closing line.
+        let p = a.project(&Complex::new(-1.0, 1.0)).unwrap();
+        assert_eq!(p.segment, 3);
+        assert!((p.distance - 1.0).abs() < Float::EPSILON * 1e1);
+        assert_eq!(
+            Path::<Complex<Float>, Float>::new().project(&Complex::new(0.0, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn simplify() {
+        // A circle of two half arcs, closed, then an open polyline with collinear vertices.
+        let mut a = Path::new();
+        a.move_to(Complex::new(1.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(-1.0, 0.0),
+            )
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(1.0, 0.0),
+            )
+            .close_path()
+            .move_to(Complex::new(3.0, 0.0))
+            .line_to(Complex::new(4.0, 0.0))
+            .line_to(Complex::new(5.0, 0.0))
+            .line_to(Complex::new(5.0, 1.0));
+        let tolerance = 1e-2;
+        let b = a.simplify(tolerance);
+        let segments: Vec<_> = b.segments().collect();
+        assert!(segments
+            .iter()
+            .all(|segment| matches!(segment, Segment::Line { .. })));
+        let (circle, polyline) = segments.split_at(segments.len() - 2);
+        for segment in circle {
+            // A chord deviates most from the circle at its middle.
+            let middle = segment.point_at(0.5);
+            assert!(1.0 - middle.abs() <= tolerance);
+        }
+        assert_eq!(circle.last().unwrap().point_at(1.0), Complex::new(1.0, 0.0));
+        assert_eq!(
+            polyline,
+            [
+                Segment::Line {
+                    from: &Complex::new(3.0, 0.0),
+                    to: &Complex::new(5.0, 0.0)
+                },
+                Segment::Line {
+                    from: &Complex::new(5.0, 0.0),
+                    to: &Complex::new(5.0, 1.0)
+                }
+            ]
+        );
+        assert_eq!(
+            b.to_string().matches('Z').count(),
+            a.to_string().matches('Z').count()
+        );
+        // Closing repeats the start only as the closing segment.
+        let mut square = Path::new();
+        square
+            .move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(1.0, 0.0))
+            .line_to(Complex::new(1.0, 1.0))
+            .line_to(Complex::new(0.0, 1.0))
+            .line_to(Complex::new(0.0, 0.0))
+            .close_path();
+        assert_eq!(square.simplify(0.1).to_string(), "M0,0 L1,0 L1,1 L0,1 Z");
+    }
+
+    #[test]
+    fn reverse() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0, 0))
+            .cubic_bezier(Complex::new(1, 1), Complex::new(2, 1), Complex::new(3, 0))
+            .smooth_quad(Complex::new(5, 0))
+            .arc(Complex::new(1, 1), 0, false, true, Complex::new(7, 0))
+            .move_to(Complex::new(0, 5))
+            .line_to(Complex::new(2, 5))
+            .line_to(Complex::new(2, 7))
+            .close_path()
+            .line_to(Complex::new(-1, 5));
+        assert_eq!(
+            a.reverse().to_string(),
+            "M7,0 A1,1 0 0 0 5,0 Q3,0 3,0 C2,1 1,1 0,0 \
+             M0,5 L2,7 L2,5 L0,5 Z M-1,5 L0,5"
+        );
+        assert_eq!(
+            Path::<Complex<i32>, i32>::new().reverse(),
+            Path::<Complex<i32>, i32>::new()
+        );
+    }
+
+    #[test]
+    fn winding() {
+        // A square counterclockwise with a square hole clockwise.
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .horizontal_to(3.0)
+            .vertical_to(3.0)
+            .horizontal_to(0.0)
+            .close_path()
+            .move_to(Complex::new(1.0, 1.0))
+            .vertical_to(2.0)
+            .horizontal_to(2.0)
+            .vertical_to(1.0)
+            .close_path();
+        assert_eq!(a.signed_area(), 8.0);
+        assert_eq!(a.winding(), Some(true));
+        assert_eq!(a.with_winding(true), a);
+        let b = a.with_winding(false);
+        assert_eq!(b.signed_area(), -8.0);
+        assert_eq!(b.winding(), Some(false));
+        // The hole is still a hole.
+        assert!(!b.contains(&Complex::new(1.5, 1.5), FillRule::NonZero));
+        assert!(b.contains(&Complex::new(0.5, 1.5), FillRule::NonZero));
+        // Curves count exactly, a quarter circle cut out of the unit square.
+        let mut c = Path::new();
+        c.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(1.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                false,
+                Complex::new(0.0, 1.0),
+            )
+            .close_path();
+        let quarter = (180.0 as Float).to_radians() / 4.0;
+        assert!((c.signed_area() - (1.0 - quarter)).abs() < Float::EPSILON * 1e1);
+        assert_eq!(Path::<Complex<Float>, Float>::new().winding(), None);
+    }
+
+    #[test]
+    fn fillet() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .horizontal_to(4.0)
+            .vertical_to(2.0)
+            .horizontal_to(0.0)
+            .close_path();
+        assert_eq!(
+            a.fillet(0.5).to_string(),
+            "M0.5,0 L3.5,0 A0.5,0.5 0 0 1 4,0.5 L4,1.5 A0.5,0.5 0 0 1 3.5,2 \
+             L0.5,2 A0.5,0.5 0 0 1 0,1.5 L0,0.5 A0.5,0.5 0 0 1 0.5,0 Z"
+        );
+        // Limited to half of the short sides, leaving nothing of them.
+        let b = a.fillet(5.0);
+        assert_eq!(
+            b.to_string(),
+            "M1,0 L3,0 A1,1 0 0 1 4,1 A1,1 0 0 1 3,2 L1,2 A1,1 0 0 1 0,1 A1,1 0 0 1 1,0 Z"
+        );
+        let area = 4.0 + (180.0 as Float).to_radians();
+        assert!((b.signed_area() - area).abs() < Float::EPSILON * 1e1);
+        // Clockwise, open, and next to a curve, which is kept as it is.
+        let mut c = Path::new();
+        c.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(2.0, 2.0))
+            .line_to(Complex::new(4.0, 0.0))
+            .square_bezier(Complex::new(5.0, 0.0), Complex::new(5.0, 1.0))
+            .line_to(Complex::new(5.0, 3.0));
+        let d = c.fillet(1.0);
+        let segments: Vec<_> = d.segments().collect();
+        assert_eq!(segments.len(), 5);
+        let arc = segments[1].elliptical_arc().unwrap();
+        assert!((arc.center - Complex::new(2.0, 2.0 - (2.0 as Float).sqrt())).abs() < 1e-6);
+        assert!(arc.sweep < 0.0);
+        assert_eq!(segments[3], c.segments().nth(2).unwrap());
+        assert_eq!(d.current_point(), &Complex::new(5.0, 3.0));
+    }
+
+    #[test]
+    fn chamfer() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(2.0, 0.0))
+            .line_to(Complex::new(2.0, 2.0))
+            .line_to(Complex::new(3.0, 2.0))
+            .move_to(Complex::new(0.0, 5.0))
+            .vertical_to(6.0)
+            .horizontal_to(1.0)
+            .vertical_to(5.0)
+            .close_path();
+        assert_eq!(
+            a.chamfer(0.25).to_string(),
+            "M0,0 L1.75,0 L2,0.25 L2,1.75 L2.25,2 L3,2 \
+             M0,5.25 L0,5.75 L0.25,6 L0.75,6 L1,5.75 L1,5.25 L0.75,5 L0.25,5 L0,5.25 Z"
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let mut a = Path::<Complex<i32>, i32>::new();
+        assert_eq!(a.validate(), Ok(()));
+        a.line_to(Complex::new(1, 1));
+        assert_eq!(a.validate(), Err(PathError::MissingMoveTo));
+        let mut b = Path::<Complex<i32>, i32>::new();
+        b.close_path();
+        assert_eq!(b.validate(), Err(PathError::EmptyClose));
+        assert_eq!(
+            b.validate().unwrap_err().to_string(),
+            "path closed without any point"
+        );
+        // Moving to the default point is still moving.
+        let mut c = Path::<Complex<i32>, i32>::new();
+        c.move_to(Complex::new(0, 0)).close_path();
+        assert_eq!(c.validate(), Ok(()));
+        assert_eq!(c.reverse().validate(), Ok(()));
+    }
+
+    #[test]
+    fn is_closed() {
+        let mut a = Path::<Complex<i32>, i32>::new();
+        assert!(!a.is_closed());
+        a.move_to(Complex::new(0, 0));
+        assert!(!a.is_closed());
+        a.line_to(Complex::new(1, 0)).line_to(Complex::new(1, 1));
+        assert!(!a.is_closed());
+        a.close_path().move_to(Complex::new(5, 5));
+        assert!(a.is_closed());
+        a.line_to(Complex::new(6, 5));
+        assert!(!a.is_closed());
+        a.close_path();
+        assert!(a.is_closed());
+        // Drawing again from the start of the closed subpath.
+        a.line_to(Complex::new(5, 6));
+        assert!(!a.is_closed());
+    }
+
+    #[test]
+    fn subpaths() {
+        let mut a = Path::<Complex<i32>, i32>::new();
+        a.move_to(Complex::new(0, 0))
+            .line_to(Complex::new(4, 0))
+            .line_to(Complex::new(0, 4))
+            .close_path()
+            .move_to(Complex::new(1, 1))
+            .smooth_cubic(Complex::new(2, 1), Complex::new(2, 2))
+            .close_path()
+            .line_to(Complex::new(1, 2))
+            .move_to(Complex::new(9, 9));
+        let subpaths: Vec<_> = a.subpaths().map(|path| path.to_string()).collect();
+        assert_eq!(subpaths, ["M0,0 L4,0 L0,4 Z", "M1,1 S2,1 2,2 Z L1,2", ""]);
+        assert_eq!(
+            a.subpaths().last().unwrap().current_point(),
+            &Complex::new(9, 9)
+        );
+        let b = a.subpaths().fold(Path::new(), |mut path, subpath| {
+            path.append(&subpath);
+            path
+        });
+        assert_eq!(b, a);
+        assert_eq!(Path::<Complex<i32>, i32>::new().subpaths().count(), 0);
+    }
+
+    #[test]
+    fn slice() {
+        let mut a = Path::<Complex<i32>, i32>::new();
+        a.move_to(Complex::new(0, 0))
+            .line_to(Complex::new(4, 0))
+            .smooth_cubic(Complex::new(5, 1), Complex::new(4, 2))
+            .smooth_cubic(Complex::new(3, 4), Complex::new(0, 4))
+            .close_path()
+            .line_to(Complex::new(-1, -1));
+        assert_eq!(a.slice(..2).to_string(), "M0,0 L4,0 S5,1 4,2");
+        // Starting within a subpath, with the curve before the range resolved.
+        assert_eq!(a.slice(2..).to_string(), "M4,2 C3,3 3,4 0,4 L0,0 L-1,-1");
+        assert_eq!(a.slice(1..=1).to_string(), "M4,0 S5,1 4,2");
+        assert_eq!(a.slice(5..5).current_point(), &Complex::new(-1, -1));
+        let mut b = Path::new();
+        b.append(&a.slice(..3)).append(&a.slice(3..));
+        assert_eq!(
+            b.to_string(),
+            "M0,0 L4,0 S5,1 4,2 S3,4 0,4 M0,4 L0,0 L-1,-1"
+        );
+    }
+
+    #[test]
+    fn stroke_caps() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(4.0, 0.0));
+        let butt = a.stroke(2.0, LineCap::Butt, LineJoin::default());
+        assert_eq!(butt.to_string(), "M0,1 L4,1 L4,-1 L0,-1 Z");
+        let square = a.stroke(2.0, LineCap::Square, LineJoin::default());
+        assert_eq!(
+            square.to_string(),
+            "M0,1 L4,1 L5,1 L5,-1 L4,-1 L0,-1 L-1,-1 L-1,1 Z"
+        );
+        let round = a.stroke(2.0, LineCap::Round, LineJoin::default());
+        let area = 8.0 + (180.0 as Float).to_radians();
+        assert!((round.signed_area() + area).abs() < Float::EPSILON * 1e2);
+        assert!(round.contains(&Complex::new(4.7, 0.7), FillRule::NonZero));
+        assert!(!round.contains(&Complex::new(4.8, 0.8), FillRule::NonZero));
+        // Nothing without width or length.
+        assert_eq!(a.stroke(0.0, LineCap::Round, LineJoin::Round), Path::new());
+        let mut b = Path::new();
+        b.move_to(Complex::new(1.0, 1.0))
+            .line_to(Complex::new(1.0, 1.0));
+        assert_eq!(b.stroke(1.0, LineCap::Round, LineJoin::Round), Path::new());
+    }
+
+    #[test]
+    fn stroke_joins() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(0.0, 0.0))
+            .horizontal_to(4.0)
+            .vertical_to(4.0)
+            .horizontal_to(0.0)
+            .close_path();
+        let stroke = |join| a.stroke(2.0, LineCap::Butt, join);
+        let inside =
+            |path: &Path<_, _>, x, y| path.contains(&Complex::new(x, y), FillRule::NonZero);
+        for join in [
+            LineJoin::Miter { limit: 4.0 },
+            LineJoin::Round,
+            LineJoin::Bevel,
+        ] {
+            let b = stroke(join);
+            // A ring between two squares, with the corners inside filled.
+            for (x, y) in [(-0.5, 2.0), (0.5, 2.0), (0.5, 0.5), (2.0, 4.9), (4.4, 4.4)] {
+                assert!(inside(&b, x, y), "{join:?} ({x}, {y})");
+            }
+            for (x, y) in [(2.0, 2.0), (1.1, 2.0), (-1.1, 2.0), (2.0, 5.1)] {
+                assert!(!inside(&b, x, y), "{join:?} ({x}, {y})");
+            }
+            assert!(b.is_closed());
+            assert_eq!(b.subpaths().count(), 2);
+        }
+        assert!(inside(&stroke(LineJoin::default()), 4.9, 4.9));
+        assert!(inside(&stroke(LineJoin::Round), 4.6, 4.6));
+        assert!(!inside(&stroke(LineJoin::Round), 4.8, 4.8));
+        assert!(!inside(&stroke(LineJoin::Bevel), 4.6, 4.6));
+        // Square corners extend √2 times half of the width.
+        assert!(!inside(&stroke(LineJoin::Miter { limit: 1.4 }), 4.6, 4.6));
+        assert!(inside(&stroke(LineJoin::Miter { limit: 1.5 }), 4.6, 4.6));
+    }
+
+    #[test]
+    fn stroke_curves() {
+        // A quarter circle of radius 3, and an open corner joining it.
+        let mut a = Path::new();
+        a.move_to(Complex::new(3.0, 0.0))
+            .arc(
+                Complex::new(3.0, 3.0),
+                0.0,
+                false,
+                true,
+                Complex::new(0.0, 3.0),
+            )
+            .line_to(Complex::new(0.0, 6.0));
+        let b = a.stroke(2.0, LineCap::Butt, LineJoin::Bevel);
+        let inside = |r: Float, angle: Float| {
+            let p = Complex::new(angle.cos(), angle.sin()) * r;
+            b.contains(&p, FillRule::NonZero)
+        };
+        let quarter = (45.0 as Float).to_radians();
+        assert!(inside(2.1, quarter) && inside(3.9, quarter));
+        assert!(!inside(1.9, quarter) && !inside(4.1, quarter));
+        // Only the tangent continuation of the arc at the joint.
+        assert!(b.contains(&Complex::new(-0.9, 4.0), FillRule::NonZero));
+        assert!(!b.contains(&Complex::new(-1.1, 4.0), FillRule::NonZero));
+        assert!(!b.contains(&Complex::new(0.5, -0.5), FillRule::NonZero));
+    }
+
+    #[test]
+    fn canvas_commands() {
+        let mut path = Path::new();
+        path.move_to(Complex::new(0.0, 0.0))
+            .line_to(Complex::new(2.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(2.0, 2.0),
+            )
+            .square_bezier(Complex::new(1.0, 3.0), Complex::new(0.0, 2.0))
+            .arc(
+                Complex::new(0.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(0.0, 1.0),
+            )
+            .close_path()
+            .move_to(Complex::new(5.0, 5.0))
+            .smooth_cubic(Complex::new(6.0, 6.0), Complex::new(7.0, 5.0));
+        let commands = path.canvas_commands();
+        let half = (90.0 as Float).to_radians();
+        // The half turn counterclockwise around (2, 1) from below.
+        assert_eq!(commands[..9], [0.0, 0.0, 0.0, 1.0, 2.0, 0.0, 4.0, 2.0, 1.0]);
+        assert_eq!(commands[9..11], [1.0, 1.0]);
+        assert!((commands[12] + half).abs() < Float::EPSILON * 1e1);
+        assert!((commands[13] - half).abs() < Float::EPSILON * 1e1);
+        assert_eq!(
+            commands[14..],
+            [
+                0.0, 3.0, 1.0, 3.0, 0.0, 2.0, 1.0, 0.0, 1.0, 5.0, 0.0, 5.0, 5.0, 2.0, 5.0, 5.0,
+                6.0, 6.0, 7.0, 5.0
+            ]
+        );
+        assert!(Path::<Complex<Float>, Float>::new()
+            .canvas_commands()
+            .is_empty());
+    }
+
+    #[test]
+    fn arcs_to_cubics() {
+        let mut a = Path::new();
+        a.move_to(Complex::new(1.0, 0.0))
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(-1.0, 0.0),
+            )
+            .smooth_cubic(Complex::new(-2.0, -1.0), Complex::new(0.0, -1.0))
+            .arc(
+                Complex::new(0.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(1.0, 0.0),
+            )
+            .arc(
+                Complex::new(1.0, 1.0),
+                0.0,
+                false,
+                true,
+                Complex::new(1.0, 0.0),
+            )
+            .close_path();
+        let b = a.arcs_to_cubics(1e-3);
+        let segments: Vec<_> = b.segments().collect();
+        // Two quarters, the curve no longer smooth, a line, and nothing for the empty arc.
+        assert_eq!(segments.len(), 5);
+        assert!(segments[..3]
+            .iter()
+            .all(|segment| segment.cubic_bezier().is_some()));
+        assert_eq!(segments[1].point_at(1.0), Complex::new(-1.0, 0.0));
+        assert!((segments[0].point_at(1.0) - Complex::new(0.0, 1.0)).abs() < Float::EPSILON * 1e1);
+        assert_eq!(segments[2], a.segments().nth(1).unwrap());
+        assert_eq!(
+            segments[3],
+            Segment::Line {
+                from: &Complex::new(0.0, -1.0),
+                to: &Complex::new(1.0, 0.0)
+            }
+        );
+        assert!(b.to_string().contains(" C-1,0 -2,-1 0,-1 L1,0 Z"));
+    }
+
+    #[test]
+    fn catmull_rom() {
+        let points = [
+            Complex::new(0.0, 0.0),
+            Complex::new(2.0, 1.0),
+            Complex::new(4.0, 0.0),
+            Complex::new(5.0, 3.0),
+        ];
+        let a = Path::catmull_rom(&points, 0.0, false);
+        let curves: Vec<_> = a.segments().map(|s| s.cubic_bezier().unwrap()).collect();
+        assert_eq!(curves.len(), 3);
+        for (curve, ends) in curves.iter().zip(points.windows(2)) {
+            assert_eq!([curve.from, curve.to], ends);
+        }
+        // The tangents of Catmull–Rom, with the ends their own neighbors.
+        assert_eq!(curves[0].cp1, points[0] + (points[1] - points[0]) / 6.0);
+        assert_eq!(curves[1].cp1, points[1] + (points[2] - points[0]) / 6.0);
+        for pair in curves.windows(2) {
+            let (a, b) = (pair[0].derivative_at(1.0), pair[1].derivative_at(0.0));
+            assert!((a - b).abs() < Float::EPSILON * 1e1);
+        }
+        // Fully tense, the polygon.
+        let b = Path::catmull_rom(&points, 1.0, true);
+        assert!(b.is_closed());
+        for segment in b.segments().take(4) {
+            let curve = segment.cubic_bezier().unwrap();
+            assert_eq!((curve.cp1, curve.cp2), (curve.from, curve.to));
+        }
+        // Closed, smooth at the first point too.
+        let c = Path::catmull_rom(&points, 0.5, true);
+        let curves: Vec<_> = c.segments().filter_map(|s| s.cubic_bezier()).collect();
+        assert_eq!(curves.len(), 4);
+        let (last, first) = (curves[3].derivative_at(1.0), curves[0].derivative_at(0.0));
+        assert!((last - first).abs() < Float::EPSILON * 1e1);
+        assert!((first - (points[1] - points[3]) * 0.25).abs() < Float::EPSILON * 1e1);
+        assert_eq!(
+            Path::catmull_rom(&points[..1], 0.0, true)
+                .segments()
+                .count(),
+            1
+        );
+        assert_eq!(Path::catmull_rom(&[], 0.0, false), Path::new());
+    }
 }
@@ -0,0 +1,113 @@
+use super::curve::distance_to_segment;
+use crate::{math::complex::Complex, Float};
+
+/// Vertices of the polyline through `points` kept by the Ramer–Douglas–Peucker algorithm, so
+/// that every point is within `tolerance` of the simplified polyline. The ends are always kept,
+/// so closed polylines, which end where they start, stay closed.
+pub fn simplify(points: &[Complex<Float>], tolerance: Float) -> Vec<Complex<Float>> {
+    let Some(end) = points.len().checked_sub(1).filter(|&end| end > 1) else {
+        return points.to_vec();
+    };
+    let mut keep = vec![false; points.len()];
+    (keep[0], keep[end]) = (true, true);
+    // Ranges left to simplify, iterated rather than recursed for long polylines.
+    let mut ranges = vec![(0, end)];
+    while let Some((first, last)) = ranges.pop() {
+        let (farthest, distance) = (first + 1..last)
+            .map(|i| {
+                (
+                    i,
+                    distance_to_segment(points[first], points[last], points[i]),
+                )
+            })
+            .fold((first, 0.0), |a, b| if b.1 > a.1 { b } else { a });
+        if distance > tolerance {
+            keep[farthest] = true;
+            ranges.push((first, farthest));
+            ranges.push((farthest, last));
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(*p))
+        .collect()
+}
+
+/// Signed area of the polygon with the vertices `points`, positive if counterclockwise, by the
+/// shoelace formula.
+pub fn signed_area(points: &[Complex<Float>]) -> Float {
+    let n = points.len();
+    (0..n)
+        .map(|i| (points[i].conj() * points[(i + 1) % n]).im)
+        .sum::<Float>()
+        / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coordinates: &[(Float, Float)]) -> Vec<Complex<Float>> {
+        coordinates
+            .iter()
+            .map(|&(re, im)| Complex::new(re, im))
+            .collect()
+    }
+
+    #[test]
+    fn simplify() {
+        // Collinear points are dropped even without a tolerance.
+        let line = points(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (4.0, 4.0)]);
+        assert_eq!(
+            super::simplify(&line, 0.0),
+            points(&[(0.0, 0.0), (4.0, 4.0)])
+        );
+        // Noise within the tolerance is dropped, a spike beyond it is kept.
+        let noisy = points(&[
+            (0.0, 0.0),
+            (1.0, 0.1),
+            (2.0, -0.1),
+            (3.0, 2.0),
+            (4.0, 0.05),
+            (5.0, 0.0),
+        ]);
+        assert_eq!(
+            super::simplify(&noisy, 0.2),
+            points(&[(0.0, 0.0), (2.0, -0.1), (3.0, 2.0), (4.0, 0.05), (5.0, 0.0)])
+        );
+        assert_eq!(
+            super::simplify(&noisy, 3.0),
+            points(&[(0.0, 0.0), (5.0, 0.0)])
+        );
+        assert_eq!(super::simplify(&noisy[..2], 3.0), noisy[..2]);
+        assert_eq!(super::simplify(&[], 1.0), []);
+    }
+
+    #[test]
+    fn closed() {
+        let square = points(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 2.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+            (0.0, 0.0),
+        ]);
+        assert_eq!(
+            super::simplify(&square, 0.1),
+            points(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)])
+        );
+    }
+
+    #[test]
+    fn signed_area() {
+        let triangle = points(&[(0.0, 0.0), (4.0, 0.0), (0.0, 3.0)]);
+        assert_eq!(super::signed_area(&triangle), 6.0);
+        let reversed: Vec<_> = triangle.iter().rev().copied().collect();
+        assert_eq!(super::signed_area(&reversed), -6.0);
+        assert_eq!(super::signed_area(&triangle[..2]), 0.0);
+    }
+}
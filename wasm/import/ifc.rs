@@ -0,0 +1,642 @@
+//! Reader of a subset of the structural analysis domain of IFC in the STEP physical file format
+//! into a plane [`Model`].
+//!
+//! Structural curve members become members between the vertices of their edges, structural
+//! point connections become supports where their boundary conditions are fixed, and single
+//! forces on connections and linear forces on members become loads, those on a member on each of
+//! its edges. Object placements are taken as the identity, and stiffnesses of boundary
+//! conditions, the profiles and materials of members and loads other than these are ignored.
+//! Loads in local coordinates are rejected.
+
+use crate::{
+    model::frame::{Load, Member, Model, Support},
+    Float,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    iter::Peekable,
+    str::CharIndices,
+};
+
+/// Plane of the model within the coordinates of IFC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Plane {
+    /// Plan, with the x and y axes of the model those of IFC.
+    XY,
+    /// Elevation, with the x axis of the model that of IFC and the y axis the vertical z axis.
+    #[default]
+    XZ,
+}
+
+/// Controls of [`read`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IfcOptions {
+    pub plane: Plane,
+    /// Properties given to all members.
+    pub elasticity: Float,
+    pub area: Float,
+    pub inertia: Float,
+}
+
+/// Reason an IFC file cannot be read.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IfcError {
+    /// The file is not valid STEP around the byte `offset`.
+    Syntax { offset: usize },
+    /// An entity refers to the instance `id`, which does not exist.
+    MissingEntity { id: usize },
+    /// An attribute of the instance `id` is not of the type expected of its entity.
+    UnexpectedValue { id: usize },
+    /// The activity `id` applies its load in local coordinates, which are not read.
+    LocalCoordinates { id: usize },
+}
+
+impl Display for IfcError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IfcError::Syntax { offset } => write!(f, "syntax error at byte {offset}"),
+            IfcError::MissingEntity { id } => write!(f, "instance #{id} does not exist"),
+            IfcError::UnexpectedValue { id } => {
+                write!(f, "instance #{id} has an unexpected attribute")
+            }
+            IfcError::LocalCoordinates { id } => {
+                write!(f, "instance #{id} is in local coordinates")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IfcError {}
+
+/// Attribute value of a STEP instance.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Ref(usize),
+    Number(Float),
+    String(String),
+    Enum(String),
+    /// Unset `$` or derived `*`.
+    Null,
+    List(Vec<Value>),
+    /// Value of a defined type in a select, as `IFCBOOLEAN(.T.)`.
+    Typed(String, Box<Value>),
+}
+
+/// Instances by their ids, as the entity names in upper case and the attributes.
+type Instances = HashMap<usize, (String, Vec<Value>)>;
+
+struct Parser<'a> {
+    source: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.char_indices().peekable(),
+        }
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars.peek().map_or(self.source.len(), |&(i, _)| i)
+    }
+
+    fn error(&mut self) -> IfcError {
+        IfcError::Syntax {
+            offset: self.offset(),
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip(&mut self) {
+        loop {
+            while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+            if !self.source[self.offset()..].starts_with("/*") {
+                return;
+            }
+            let end = self.source[self.offset()..]
+                .find("*/")
+                .map_or(self.source.len(), |end| self.offset() + end + 2);
+            while self.chars.next_if(|&(i, _)| i < end).is_some() {}
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), IfcError> {
+        self.skip();
+        match self.chars.next_if(|&(_, d)| d == c) {
+            Some(_) => Ok(()),
+            None => Err(self.error()),
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some((_, c)) = self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+        {
+            word.push(c.to_ascii_uppercase());
+        }
+        word
+    }
+
+    fn number(&mut self) -> Result<Float, IfcError> {
+        let start = self.offset();
+        while self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'E' | 'e'))
+            .is_some()
+        {}
+        let end = self.offset();
+        self.source[start..end]
+            .parse()
+            .map_err(|_| IfcError::Syntax { offset: start })
+    }
+
+    fn value(&mut self) -> Result<Value, IfcError> {
+        self.skip();
+        let Some(&(_, c)) = self.chars.peek() else {
+            return Err(self.error());
+        };
+        Ok(match c {
+            '#' => {
+                self.chars.next();
+                Value::Ref(self.number()? as usize)
+            }
+            '$' | '*' => {
+                self.chars.next();
+                Value::Null
+            }
+            '\'' => {
+                self.chars.next();
+                let mut string = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some((_, '\'')) if self.chars.next_if(|(_, c)| *c == '\'').is_some() => {
+                            string.push('\'')
+                        }
+                        Some((_, '\'')) => break,
+                        Some((_, c)) => string.push(c),
+                        None => return Err(self.error()),
+                    }
+                }
+                Value::String(string)
+            }
+            '.' => {
+                self.chars.next();
+                let word = self.word();
+                self.expect('.')?;
+                Value::Enum(word)
+            }
+            '(' => Value::List(self.list()?),
+            c if c.is_ascii_alphabetic() => {
+                let name = self.word();
+                self.expect('(')?;
+                let value = self.value()?;
+                self.expect(')')?;
+                Value::Typed(name, Box::new(value))
+            }
+            _ => Value::Number(self.number()?),
+        })
+    }
+
+    fn list(&mut self) -> Result<Vec<Value>, IfcError> {
+        self.expect('(')?;
+        let mut values = vec![];
+        self.skip();
+        if self.chars.next_if(|(_, c)| *c == ')').is_some() {
+            return Ok(values);
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip();
+            match self.chars.next() {
+                Some((_, ',')) => {}
+                Some((_, ')')) => return Ok(values),
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Instances of the data sections. Complex instances of several entities are skipped.
+    fn instances(mut self) -> Result<Instances, IfcError> {
+        let mut instances = HashMap::new();
+        let Some(start) = self.source.find("DATA;") else {
+            return Ok(instances);
+        };
+        while self.chars.next_if(|&(i, _)| i < start + 5).is_some() {}
+        loop {
+            self.skip();
+            match self.chars.peek() {
+                Some((_, '#')) => {
+                    self.chars.next();
+                    let id = self.number()? as usize;
+                    self.expect('=')?;
+                    self.skip();
+                    if self.chars.peek().is_some_and(|(_, c)| *c == '(') {
+                        self.list_of_entities()?;
+                    } else {
+                        let name = self.word();
+                        let attributes = self.list()?;
+                        instances.insert(id, (name, attributes));
+                    }
+                    self.expect(';')?;
+                }
+                Some(_) => {
+                    // ENDSEC, and possibly further data sections.
+                    let word = self.word();
+                    self.expect(';')?;
+                    if word != "ENDSEC" {
+                        return Err(self.error());
+                    }
+                    match self.source[self.offset()..].find("DATA;") {
+                        Some(next) => {
+                            let next = self.offset() + next + 5;
+                            while self.chars.next_if(|&(i, _)| i < next).is_some() {}
+                        }
+                        None => return Ok(instances),
+                    }
+                }
+                None => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Skips the parts of a complex instance.
+    fn list_of_entities(&mut self) -> Result<(), IfcError> {
+        self.expect('(')?;
+        loop {
+            self.skip();
+            if self.chars.next_if(|(_, c)| *c == ')').is_some() {
+                return Ok(());
+            }
+            self.word();
+            self.list()?;
+        }
+    }
+}
+
+/// Typed access to the instances.
+struct Reader<'a> {
+    instances: &'a Instances,
+}
+
+impl Reader<'_> {
+    fn get(&self, id: usize) -> Result<&(String, Vec<Value>), IfcError> {
+        self.instances
+            .get(&id)
+            .ok_or(IfcError::MissingEntity { id })
+    }
+
+    fn attribute(&self, id: usize, index: usize) -> Result<&Value, IfcError> {
+        self.get(id)?
+            .1
+            .get(index)
+            .ok_or(IfcError::UnexpectedValue { id })
+    }
+
+    fn reference(&self, id: usize, index: usize) -> Result<usize, IfcError> {
+        match self.attribute(id, index)? {
+            Value::Ref(r) => Ok(*r),
+            _ => Err(IfcError::UnexpectedValue { id }),
+        }
+    }
+
+    /// Number, zero if unset.
+    fn number(&self, id: usize, index: usize) -> Result<Float, IfcError> {
+        match self.attribute(id, index)? {
+            Value::Number(n) => Ok(*n),
+            Value::Typed(_, value) => match **value {
+                Value::Number(n) => Ok(n),
+                _ => Err(IfcError::UnexpectedValue { id }),
+            },
+            Value::Null => Ok(0.0),
+            _ => Err(IfcError::UnexpectedValue { id }),
+        }
+    }
+
+    /// Whether a stiffness of a boundary condition is fixed, `IFCBOOLEAN(.T.)`.
+    fn fixed(&self, id: usize, index: usize) -> Result<bool, IfcError> {
+        Ok(match self.attribute(id, index)? {
+            Value::Typed(_, value) => **value == Value::Enum("T".to_owned()),
+            Value::Enum(value) => value == "T",
+            _ => false,
+        })
+    }
+
+    /// Items of the topology representations of a product, from its `Representation`.
+    fn topology(&self, product: usize) -> Result<Vec<usize>, IfcError> {
+        let shape = self.reference(product, 6)?;
+        let Value::List(representations) = self.attribute(shape, 2)? else {
+            return Err(IfcError::UnexpectedValue { id: shape });
+        };
+        let mut items = vec![];
+        for representation in representations {
+            let Value::Ref(r) = representation else {
+                return Err(IfcError::UnexpectedValue { id: shape });
+            };
+            if let Value::List(list) = self.attribute(*r, 3)? {
+                items.extend(list.iter().filter_map(|item| match item {
+                    Value::Ref(item) => Some(*item),
+                    _ => None,
+                }));
+            }
+        }
+        Ok(items)
+    }
+
+    /// Coordinates of an `IFCVERTEXPOINT`.
+    fn vertex(&self, vertex: usize) -> Result<[Float; 3], IfcError> {
+        let point = self.reference(vertex, 0)?;
+        let Value::List(coordinates) = self.attribute(point, 0)? else {
+            return Err(IfcError::UnexpectedValue { id: point });
+        };
+        let mut xyz = [0.0; 3];
+        for (x, c) in xyz.iter_mut().zip(coordinates) {
+            let Value::Number(c) = c else {
+                return Err(IfcError::UnexpectedValue { id: point });
+            };
+            *x = *c;
+        }
+        Ok(xyz)
+    }
+}
+
+/// Reads the structural analysis model in the IFC `source`.
+pub fn read(source: &str, options: &IfcOptions) -> Result<Model, IfcError> {
+    let instances = Parser::new(source).instances()?;
+    let reader = Reader {
+        instances: &instances,
+    };
+    let mut ids: Vec<_> = instances.keys().copied().collect();
+    ids.sort_unstable();
+    let of = |entity: &str| {
+        ids.iter()
+            .copied()
+            .filter(|id| instances[id].0 == entity)
+            .collect::<Vec<_>>()
+    };
+    let project = |[x, y, z]: [Float; 3]| match options.plane {
+        Plane::XY => [x, y],
+        Plane::XZ => [x, z],
+    };
+    let mut model = Model::new();
+    // Nodes of the vertex points, merged where they coincide.
+    let mut nodes = HashMap::new();
+    let mut node = |model: &mut Model, vertex: usize| -> Result<usize, IfcError> {
+        let position = project(reader.vertex(vertex)?);
+        let key = position.map(|c| (c + 0.0).to_bits());
+        Ok(*nodes.entry(key).or_insert_with(|| model.add_node(position)))
+    };
+    // Members of the edges of each curve member.
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for id in of("IFCSTRUCTURALCURVEMEMBER") {
+        for edge in reader.topology(id)? {
+            if reader.get(edge)?.0 != "IFCEDGE" {
+                continue;
+            }
+            let a = node(&mut model, reader.reference(edge, 0)?)?;
+            let b = node(&mut model, reader.reference(edge, 1)?)?;
+            let member = model.add_member(Member {
+                nodes: [a, b],
+                elasticity: options.elasticity,
+                area: options.area,
+                inertia: options.inertia,
+            });
+            members.entry(id).or_default().push(member);
+        }
+    }
+    let mut connections = HashMap::new();
+    for id in of("IFCSTRUCTURALPOINTCONNECTION") {
+        let Some(&vertex) = reader.topology(id)?.first() else {
+            continue;
+        };
+        let n = node(&mut model, vertex)?;
+        connections.insert(id, n);
+        if let Value::Ref(condition) = reader.attribute(id, 7)? {
+            // Translations along the axes of the plane and the rotation about the normal.
+            let axes = match options.plane {
+                Plane::XY => [1, 2, 6],
+                Plane::XZ => [1, 3, 5],
+            };
+            let [x, y, rotation] = axes.map(|i| reader.fixed(*condition, i));
+            let fixed = [x?, y?, rotation?];
+            if fixed.contains(&true) {
+                model.add_support(Support { node: n, fixed });
+            }
+        }
+    }
+    // Components of the forces and the moment about the normal counterclockwise in the plane.
+    let (axes, sign) = match options.plane {
+        Plane::XY => ([1, 2, 6], 1.0),
+        Plane::XZ => ([1, 3, 5], -1.0),
+    };
+    for id in of("IFCRELCONNECTSSTRUCTURALACTIVITY") {
+        let element = reader.reference(id, 4)?;
+        let activity = reader.reference(id, 5)?;
+        let load = reader.reference(activity, 7)?;
+        let (entity, _) = reader.get(load)?;
+        let local =
+            matches!(reader.attribute(activity, 8), Ok(Value::Enum(c)) if c == "LOCAL_COORDS");
+        match (
+            entity.as_str(),
+            connections.get(&element),
+            members.get(&element),
+        ) {
+            ("IFCSTRUCTURALLOADSINGLEFORCE", Some(_), _)
+            | ("IFCSTRUCTURALLOADLINEARFORCE", _, Some(_))
+                if local =>
+            {
+                return Err(IfcError::LocalCoordinates { id: activity });
+            }
+            ("IFCSTRUCTURALLOADSINGLEFORCE", Some(&node), _) => {
+                let [fx, fy, m] = axes.map(|i| reader.number(load, i));
+                model.add_load(Load::Node {
+                    node,
+                    force: [fx?, fy?, m? * sign],
+                });
+            }
+            ("IFCSTRUCTURALLOADLINEARFORCE", _, Some(edges)) => {
+                let [wx, wy] = [axes[0], axes[1]].map(|i| reader.number(load, i));
+                let load = [wx?, wy?];
+                for &member in edges {
+                    model.add_load(Load::Member { member, load });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::frame::fixtures::member;
+
+    /// A cantilever column fixed at its base with a horizontal force at the top and wind along
+    /// it, in elevation.
+    const COLUMN: &str = r"ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('ViewDefinition [StructuralAnalysisView]'),'2;1');
+FILE_NAME('column.ifc','2024-01-01T00:00:00',(''),(''),'','','');
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+/* Topology */
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCCARTESIANPOINT((0.,0.,3.5));
+#3=IFCVERTEXPOINT(#1);
+#4=IFCVERTEXPOINT(#2);
+#5=IFCEDGE(#3,#4);
+#6=IFCTOPOLOGYREPRESENTATION($,'Reference','Edge',(#5));
+#7=IFCPRODUCTDEFINITIONSHAPE($,$,(#6));
+#8=IFCSTRUCTURALCURVEMEMBER('0a',$,'Column',$,$,$,#7,.RIGID_JOINED_MEMBER.,$);
+#9=IFCTOPOLOGYREPRESENTATION($,'Reference','Vertex',(#3));
+#10=IFCPRODUCTDEFINITIONSHAPE($,$,(#9));
+#11=IFCBOUNDARYNODECONDITION('Fixed',IFCBOOLEAN(.T.),IFCBOOLEAN(.T.),IFCBOOLEAN(.T.),IFCBOOLEAN(.T.),IFCBOOLEAN(.T.),IFCBOOLEAN(.T.));
+#12=IFCSTRUCTURALPOINTCONNECTION('0b',$,'Base',$,$,$,#10,#11,$);
+#13=IFCTOPOLOGYREPRESENTATION($,'Reference','Vertex',(#4));
+#14=IFCPRODUCTDEFINITIONSHAPE($,$,(#13));
+#15=IFCSTRUCTURALPOINTCONNECTION('0c',$,'Top',$,$,$,#14,$,$);
+#16=IFCSTRUCTURALLOADSINGLEFORCE('H',5.,0.,-2.,0.,1.5,0.);
+#17=IFCSTRUCTURALPOINTACTION('0d',$,'H',$,$,$,$,#16,.GLOBAL_COORDS.,.F.);
+#18=IFCRELCONNECTSSTRUCTURALACTIVITY('0e',$,$,$,#15,#17);
+#19=IFCSTRUCTURALLOADLINEARFORCE('Wind',0.5,0.,0.,$,$,$);
+#20=IFCSTRUCTURALCURVEACTION('0f',$,'Wind',$,$,$,$,#19,.GLOBAL_COORDS.,.F.,.TRUE_LENGTH.,.CONST.);
+#21=IFCRELCONNECTSSTRUCTURALACTIVITY('0g',$,$,$,#8,#20);
+#22=IFCSTRUCTURALANALYSISMODEL('0h',$,'It''s a model',$,$,.LOADING_3D.,$,$,$,$);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    fn options() -> IfcOptions {
+        let Member {
+            elasticity,
+            area,
+            inertia,
+            ..
+        } = member([0, 1]);
+        IfcOptions {
+            plane: Plane::XZ,
+            elasticity,
+            area,
+            inertia,
+        }
+    }
+
+    #[test]
+    fn read() {
+        let model = super::read(COLUMN, &options()).unwrap();
+        assert_eq!(model.nodes.len(), 2);
+        assert_eq!(model.nodes[1].position, [0.0, 3.5]);
+        assert_eq!(model.members[0].nodes, [0, 1]);
+        assert_eq!(model.members[0].elasticity, 2e8);
+        assert_eq!(
+            model.supports,
+            [Support {
+                node: 0,
+                fixed: [true; 3]
+            }]
+        );
+        // The moment about y is clockwise in the elevation seen from the front.
+        assert_eq!(
+            model.loads,
+            [
+                Load::Node {
+                    node: 1,
+                    force: [5.0, -2.0, -1.5]
+                },
+                Load::Member {
+                    member: 0,
+                    load: [0.5, 0.0]
+                }
+            ]
+        );
+        let solution = model.solve().unwrap();
+        let [h, v, m] = solution.reactions[0];
+        let tolerance = Float::EPSILON.sqrt() * 1e2;
+        assert!((h + 5.0 + 0.5 * 3.5).abs() < tolerance && (v - 2.0).abs() < tolerance);
+        assert!((m - (5.0 * 3.5 + 0.5 * 3.5 * 3.5 / 2.0 + 1.5)).abs() < tolerance);
+        // In plan, the column is a point.
+        let plan = IfcOptions {
+            plane: Plane::XY,
+            ..options()
+        };
+        assert_eq!(
+            super::read(COLUMN, &plan),
+            Ok(Model {
+                nodes: vec![crate::model::frame::Node {
+                    position: [0.0, 0.0]
+                }],
+                ..super::read(COLUMN, &plan).unwrap()
+            })
+        );
+    }
+
+    /// A beam of two edges under a uniform load along it.
+    const BEAM: &str = r"DATA;
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCCARTESIANPOINT((2.,0.,0.));
+#3=IFCCARTESIANPOINT((4.,0.,0.));
+#4=IFCVERTEXPOINT(#1);
+#5=IFCVERTEXPOINT(#2);
+#6=IFCVERTEXPOINT(#3);
+#7=IFCEDGE(#4,#5);
+#8=IFCEDGE(#5,#6);
+#9=IFCTOPOLOGYREPRESENTATION($,'Reference','Edge',(#7,#8));
+#10=IFCPRODUCTDEFINITIONSHAPE($,$,(#9));
+#11=IFCSTRUCTURALCURVEMEMBER('0a',$,'Beam',$,$,$,#10,.RIGID_JOINED_MEMBER.,$);
+#12=IFCSTRUCTURALLOADLINEARFORCE('Dead',0.,0.,-1.,$,$,$);
+#13=IFCSTRUCTURALCURVEACTION('0b',$,'Dead',$,$,$,$,#12,.GLOBAL_COORDS.,.F.,.TRUE_LENGTH.,.CONST.);
+#14=IFCRELCONNECTSSTRUCTURALACTIVITY('0c',$,$,$,#11,#13);
+ENDSEC;
+";
+
+    #[test]
+    fn edges() {
+        let model = super::read(BEAM, &options()).unwrap();
+        assert_eq!(model.members.len(), 2);
+        assert_eq!(model.members[1].nodes, [1, 2]);
+        assert_eq!(
+            model.loads,
+            [0, 1].map(|member| Load::Member {
+                member,
+                load: [0.0, -1.0]
+            })
+        );
+        let local = BEAM.replace(".GLOBAL_COORDS.", ".LOCAL_COORDS.");
+        assert_eq!(
+            super::read(&local, &options()),
+            Err(IfcError::LocalCoordinates { id: 13 })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            super::read("DATA;\n#1=IFCEDGE(#2,#3)\nENDSEC;", &options()),
+            Err(IfcError::Syntax { offset: 24 })
+        );
+        assert_eq!(
+            super::read(
+                "DATA;#1=IFCSTRUCTURALCURVEMEMBER($,$,$,$,$,$,#2,$,$);ENDSEC;",
+                &options()
+            ),
+            Err(IfcError::MissingEntity { id: 2 })
+        );
+        assert_eq!(
+            super::read(
+                "DATA;#1=IFCSTRUCTURALCURVEMEMBER($,$,$,$,$,$,'x',$,$);ENDSEC;",
+                &options()
+            ),
+            Err(IfcError::UnexpectedValue { id: 1 })
+        );
+        assert_eq!(super::read("", &options()), Ok(Model::new()));
+    }
+}
@@ -1,31 +1,18 @@
-mod model;
-
-use wasm_bindgen::prelude::*;
-
-type Float = f64;
-
-#[wasm_bindgen]
-pub fn multiply(left: Float, right: Float) -> Float {
-    left * right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = multiply(2.5, 2.3);
-        assert_eq!(result, 5.75);
-    }
-}
-
-use wasm_bindgen_test::*;
-
-wasm_bindgen_test_configure!(run_in_browser);
-
-#[wasm_bindgen_test]
-fn pass() {
-    let result = multiply(3.0, 5.0);
-    assert_eq!(result, 15.0);
-}
+pub mod api;
+pub mod cancel;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod clock;
+pub mod export;
+pub mod geometry;
+pub mod import;
+pub mod math;
+pub mod model;
+#[cfg(feature = "tracing")]
+pub mod trace;
+
+/// Scalar type of the model and section subsystems, `f32` with the `f32` feature.
+#[cfg(not(feature = "f32"))]
+pub type Float = f64;
+#[cfg(feature = "f32")]
+pub type Float = f32;
@@ -0,0 +1,262 @@
+use super::{
+    complex::Complex,
+    traits::{Constants, Cos, IsNormal, Sin},
+};
+use std::ops::{Add, Div, Mul, MulAssign, Neg, Sub};
+
+/// Planar affine transform, the 2×3 matrix `[x y t]` mapping the point `p` to
+/// `x p.re + y p.im + t`. Points and directions are [`Complex`] numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Affine2<T> {
+    /// Image of the unit vector along the real axis, the first column.
+    pub x: Complex<T>,
+    /// Image of the unit vector along the imaginary axis, the second column.
+    pub y: Complex<T>,
+    /// Image of the origin, the third column.
+    pub t: Complex<T>,
+}
+
+impl<T> Affine2<T> {
+    pub const fn new(x: Complex<T>, y: Complex<T>, t: Complex<T>) -> Self {
+        Self { x, y, t }
+    }
+}
+
+impl<T> Affine2<T>
+where
+    T: Constants,
+{
+    pub fn identity() -> Self {
+        Self::from_translation(Complex::new(T::zero(), T::zero()))
+    }
+
+    pub fn from_translation(t: Complex<T>) -> Self {
+        Self {
+            x: Complex::new(T::one(), T::zero()),
+            y: Complex::new(T::zero(), T::one()),
+            t,
+        }
+    }
+
+    /// Scaling about the origin by `sx` along the real axis and `sy` along the imaginary axis.
+    pub fn from_scale(sx: T, sy: T) -> Self {
+        Self {
+            x: Complex::new(sx, T::zero()),
+            y: Complex::new(T::zero(), sy),
+            t: Complex::new(T::zero(), T::zero()),
+        }
+    }
+}
+
+/// Scalar types [`Affine2::from_rotation`] is available for.
+///
+/// Implemented for every scalar with the required operations, for the same reason as
+/// [`RotationScalar`](super::quaternion::RotationScalar).
+pub trait PlanarRotationScalar: Sized {
+    fn planar_rotation(angle: &Self) -> Affine2<Self>;
+}
+
+impl<T> PlanarRotationScalar for T
+where
+    T: Constants + Sin + Cos + Clone,
+    for<'a> &'a T: Neg<Output = T>,
+{
+    fn planar_rotation(angle: &T) -> Affine2<T> {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        Affine2 {
+            x: Complex::new(cos.clone(), sin.clone()),
+            y: Complex::new(-&sin, cos),
+            t: Complex::new(T::zero(), T::zero()),
+        }
+    }
+}
+
+impl<T> Affine2<T>
+where
+    T: PlanarRotationScalar,
+{
+    /// Counterclockwise rotation about the origin by `angle` radians.
+    pub fn from_rotation(angle: &T) -> Self {
+        T::planar_rotation(angle)
+    }
+}
+
+impl<T> Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    /// Transforms a point.
+    pub fn apply(&self, p: &Complex<T>) -> Complex<T> {
+        &self.apply_vector(p) + &self.t
+    }
+
+    /// Transforms a direction or displacement, which is unaffected by the translation.
+    pub fn apply_vector(&self, v: &Complex<T>) -> Complex<T> {
+        &(&self.x * &v.re) + &(&self.y * &v.im)
+    }
+}
+
+impl<T> Affine2<T>
+where
+    for<'a> &'a T: Mul<Output = T> + Sub<Output = T>,
+{
+    /// Determinant of the linear part, the area scale factor, negative for reflections.
+    pub fn determinant(&self) -> T {
+        &(&self.x.re * &self.y.im) - &(&self.y.re * &self.x.im)
+    }
+}
+
+impl<T> Affine2<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+{
+    pub fn inv(&self) -> Self {
+        let d = self.determinant();
+        let x = Complex::new(&self.y.im / &d, -&(&self.x.im / &d));
+        let y = Complex::new(-&(&self.y.re / &d), &self.x.re / &d);
+        let t = -&(&(&x * &self.t.re) + &(&y * &self.t.im));
+        Self { x, y, t }
+    }
+}
+
+impl<T> Affine2<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: IsNormal,
+{
+    /// Returns `None` if the determinant is zero, subnormal or not finite.
+    pub fn checked_inv(&self) -> Option<Self> {
+        self.determinant().is_normal().then(|| self.inv())
+    }
+}
+
+/// Composition, `(a * b).apply(p) == a.apply(&b.apply(p))`.
+impl<T> Mul for &Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Affine2<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            x: self.apply_vector(&rhs.x),
+            y: self.apply_vector(&rhs.y),
+            t: self.apply(&rhs.t),
+        }
+    }
+}
+
+impl<T> Mul<&Complex<T>> for &Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Complex<T>;
+    fn mul(self, rhs: &Complex<T>) -> Self::Output {
+        self.apply(rhs)
+    }
+}
+
+impl<T> Mul<Complex<T>> for &Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Complex<T>;
+    fn mul(self, rhs: Complex<T>) -> Self::Output {
+        self.apply(&rhs)
+    }
+}
+
+impl<T> Mul<Complex<T>> for Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Complex<T>;
+    fn mul(self, rhs: Complex<T>) -> Self::Output {
+        self.apply(&rhs)
+    }
+}
+
+impl<T> Mul<&Complex<T>> for Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Complex<T>;
+    fn mul(self, rhs: &Complex<T>) -> Self::Output {
+        self.apply(rhs)
+    }
+}
+
+/// Applies `rhs` first, `a *= b` equals `a = a * b`.
+impl<T> MulAssign<&Affine2<T>> for Affine2<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    fn mul_assign(&mut self, rhs: &Affine2<T>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl_owned_binop!(Affine2, Affine2<T>, Mul::mul, MulAssign::mul_assign);
+
+#[cfg(test)]
+#[allow(clippy::op_ref)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn constructors() {
+        let p = Complex::new(2.0, 1.0);
+        assert_eq!(Affine2::<f64>::identity().apply(&p), p);
+        assert_eq!(
+            Affine2::from_translation(Complex::new(1.0, -3.0)) * p,
+            Complex::new(3.0, -2.0)
+        );
+        assert_eq!(Affine2::from_scale(2.0, -1.0) * p, Complex::new(4.0, -1.0));
+        assert_abs_diff_eq!(
+            Affine2::from_rotation(&FRAC_PI_2) * p,
+            Complex::new(-1.0, 2.0),
+            epsilon = f64::EPSILON * 1e2
+        );
+        let t = Affine2::from_translation(Complex::new(5.0, 5.0));
+        assert_eq!(t.apply_vector(&p), p);
+    }
+
+    #[test]
+    fn compose() {
+        let r = Affine2::<f64>::from_rotation(&0.3);
+        let s = Affine2::from_scale(2.0, 0.5);
+        let t = Affine2::from_translation(Complex::new(1.0, -2.0));
+        let p = Complex::new(0.7, -1.1);
+        let a = &(&t * &r) * &s;
+        assert_abs_diff_eq!(
+            &a * &p,
+            t.apply(&r.apply(&s.apply(&p))),
+            epsilon = f64::EPSILON * 1e2
+        );
+        let mut b = t;
+        b *= r;
+        b *= &s;
+        assert_eq!(b, a);
+        assert!((a.determinant() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn inverse() {
+        let a = Affine2::new(
+            Complex::new(2.0, 1.0),
+            Complex::new(-1.0, 3.0),
+            Complex::new(4.0, -2.0),
+        );
+        let p = Complex::new(0.5, 1.5);
+        assert_abs_diff_eq!(a.inv().apply(&a.apply(&p)), p, epsilon = f64::EPSILON * 1e2);
+        let i = a * a.checked_inv().unwrap();
+        assert_abs_diff_eq!(i.x, Complex::new(1.0, 0.0), epsilon = f64::EPSILON * 1e2);
+        assert_abs_diff_eq!(i.y, Complex::new(0.0, 1.0), epsilon = f64::EPSILON * 1e2);
+        assert_abs_diff_eq!(i.t, Complex::new(0.0, 0.0), epsilon = f64::EPSILON * 1e2);
+        assert_eq!(Affine2::from_scale(1.0, 0.0).checked_inv(), None);
+    }
+}
@@ -0,0 +1,104 @@
+use super::{
+    complex::Complex, dual_quaternion::DualQuaternion, quaternion::Quaternion, vector::Vector,
+};
+use ::approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+/// Implements the `approx` comparison traits component-wise over the given fields.
+macro_rules! impl_approx {
+    ($type:ident { $($field:ident),+ }) => {
+        impl<T> AbsDiffEq for $type<T>
+        where
+            T: AbsDiffEq,
+            T::Epsilon: Clone,
+        {
+            type Epsilon = T::Epsilon;
+
+            fn default_epsilon() -> Self::Epsilon {
+                T::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                $(self.$field.abs_diff_eq(&other.$field, epsilon.clone()))&&+
+            }
+        }
+
+        impl<T> RelativeEq for $type<T>
+        where
+            T: RelativeEq,
+            T::Epsilon: Clone,
+        {
+            fn default_max_relative() -> Self::Epsilon {
+                T::default_max_relative()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                $(self.$field.relative_eq(&other.$field, epsilon.clone(), max_relative.clone()))&&+
+            }
+        }
+
+        impl<T> UlpsEq for $type<T>
+        where
+            T: UlpsEq,
+            T::Epsilon: Clone,
+        {
+            fn default_max_ulps() -> u32 {
+                T::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                $(self.$field.ulps_eq(&other.$field, epsilon.clone(), max_ulps))&&+
+            }
+        }
+    };
+}
+
+impl_approx!(Vector { x, y, z });
+impl_approx!(Complex { re, im });
+impl_approx!(Quaternion { v, w });
+impl_approx!(DualQuaternion { p, q });
+
+#[cfg(test)]
+#[allow(clippy::op_ref)]
+mod tests {
+    use super::*;
+    use ::approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    #[test]
+    fn vector() {
+        let a = Vector::new(0.1 + 0.2, 1.0e8 + 0.3, -4.4);
+        assert_ne!(a, Vector::new(0.3, 1.0e8 + 0.1 + 0.2, -4.4));
+        assert_relative_eq!(a, Vector::new(0.3, 1.0e8 + 0.1 + 0.2, -4.4));
+        assert_ulps_eq!(a, Vector::new(0.3, 1.0e8 + 0.1 + 0.2, -4.4));
+        assert_relative_ne!(a, Vector::new(0.3, 1.0e8 + 0.1 + 0.2, -4.41));
+    }
+
+    #[test]
+    fn complex() {
+        let a = Complex::new(0.1 + 0.2, -3.0);
+        assert_abs_diff_eq!(a, Complex::new(0.3, -3.0));
+        assert_abs_diff_eq!(a, Complex::new(0.31, -2.99), epsilon = 0.02);
+        assert_relative_ne!(a, Complex::new(0.3, 3.0));
+    }
+
+    #[test]
+    fn quaternion() {
+        let a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        assert_relative_eq!(&(&a * &a.inv()), &Quaternion::from(1.0), epsilon = 1e-15);
+        assert_relative_ne!(a, Quaternion::new(Vector::new(1.3, 0.1, -2.1), 0.8));
+    }
+
+    #[test]
+    fn dual_quaternion() {
+        let a = DualQuaternion::from_rotation_and_translation(
+            &Vector::new(0.0, 0.001, 0.008),
+            &Vector::new(32.8, -6.35, -9.97),
+        );
+        assert_relative_eq!(a, &a * &1.0);
+        assert_relative_ne!(a, a.conj_from_quaternion());
+    }
+}
@@ -0,0 +1,364 @@
+use super::{
+    dual::Dual,
+    traits::{Cos, Exp, Ln, Sin},
+};
+use crate::{
+    cancel::{uncancelled, CancelToken, Cancelled},
+    Float,
+};
+use std::{
+    collections::VecDeque,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Sufficient decrease parameter of the Armijo condition.
+const ARMIJO: Float = 1e-4;
+
+/// Scalars an [`Objective`] is evaluated with: [`Float`] for values and [`Dual`] for derivatives.
+pub trait Real:
+    Copy
+    + From<Float>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Sin
+    + Cos
+    + Exp
+    + Ln
+{
+    /// Value without the derivative part, for branching and comparisons.
+    fn re(self) -> Float;
+}
+
+impl Real for Float {
+    fn re(self) -> Float {
+        self
+    }
+}
+
+impl Real for Dual<Float> {
+    fn re(self) -> Float {
+        self.re
+    }
+}
+
+/// Function minimized by [`minimize`]. It is written once, generically over the scalar, and the
+/// gradient is obtained by evaluating it with [`Dual`] numbers unless given explicitly.
+pub trait Objective {
+    fn value<T: Real>(&self, x: &[T]) -> T;
+
+    /// Gradient at `x`, by forward-mode automatic differentiation with one evaluation per
+    /// variable.
+    fn gradient(&self, x: &[Float]) -> Vec<Float> {
+        let mut dual: Vec<Dual<Float>> = x.iter().map(|&x| Dual::from(x)).collect();
+        (0..x.len())
+            .map(|i| {
+                dual[i].eps = 1.0;
+                let g = self.value(&dual).eps;
+                dual[i].eps = 0.0;
+                g
+            })
+            .collect()
+    }
+}
+
+/// Controls of [`minimize`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BfgsOptions {
+    /// Number of correction pairs kept to approximate the inverse Hessian.
+    pub memory: usize,
+    /// Convergence is reached when the largest component of the projected gradient falls below
+    /// this fraction of `max(1, |f|)`.
+    pub tolerance: Float,
+    pub max_iterations: usize,
+}
+
+impl Default for BfgsOptions {
+    fn default() -> Self {
+        Self {
+            memory: 10,
+            tolerance: Float::EPSILON.sqrt(),
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// Result of [`minimize`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minimum {
+    pub x: Vec<Float>,
+    pub value: Float,
+    pub iterations: usize,
+    /// Whether the gradient condition was met, rather than the iteration limit or a line search
+    /// that could make no progress.
+    pub converged: bool,
+}
+
+/// Minimizes an unconstrained objective by the limited-memory BFGS method from `x0`.
+pub fn minimize(objective: &impl Objective, x0: &[Float], options: &BfgsOptions) -> Minimum {
+    let lower = vec![Float::NEG_INFINITY; x0.len()];
+    let upper = vec![Float::INFINITY; x0.len()];
+    minimize_bounded(objective, x0, &lower, &upper, options)
+}
+
+/// Minimizes an objective subject to `lower <= x <= upper` by the projected limited-memory BFGS
+/// method from `x0`. Variables at a bound with the gradient pointing outward are held fixed, and
+/// the line search follows the projection of the search direction onto the box.
+///
+/// # Panics
+///
+/// Panics if the bounds and `x0` have different lengths.
+pub fn minimize_bounded(
+    objective: &impl Objective,
+    x0: &[Float],
+    lower: &[Float],
+    upper: &[Float],
+    options: &BfgsOptions,
+) -> Minimum {
+    uncancelled(|cancel| minimize_with_cancel(objective, x0, lower, upper, options, cancel))
+}
+
+/// [`minimize_bounded`] checking the `cancel` token before each iteration, stopping with the
+/// number of iterations done out of the limit.
+///
+/// # Panics
+///
+/// Panics if the bounds and `x0` have different lengths.
+pub fn minimize_with_cancel(
+    objective: &impl Objective,
+    x0: &[Float],
+    lower: &[Float],
+    upper: &[Float],
+    options: &BfgsOptions,
+    cancel: &CancelToken,
+) -> Result<Minimum, Cancelled> {
+    assert!(
+        lower.len() == x0.len() && upper.len() == x0.len(),
+        "bounds size mismatch"
+    );
+    let n = x0.len();
+    let project = |x: &mut [Float]| {
+        for ((x, l), u) in x.iter_mut().zip(lower).zip(upper) {
+            *x = x.max(*l).min(*u);
+        }
+    };
+    let mut x = x0.to_vec();
+    project(&mut x);
+    let mut f = objective.value(&x);
+    let mut g = objective.gradient(&x);
+    let mut pairs: VecDeque<(Vec<Float>, Vec<Float>, Float)> = VecDeque::new();
+    let mut iterations = 0;
+    loop {
+        let active: Vec<bool> = (0..n)
+            .map(|i| (x[i] <= lower[i] && g[i] > 0.0) || (x[i] >= upper[i] && g[i] < 0.0))
+            .collect();
+        let projected = (0..n)
+            .filter(|&i| !active[i])
+            .map(|i| g[i].abs())
+            .fold(0.0, Float::max);
+        if projected <= options.tolerance * f.abs().max(1.0) {
+            return Ok(Minimum {
+                x,
+                value: f,
+                iterations,
+                converged: true,
+            });
+        }
+        if iterations == options.max_iterations {
+            break;
+        }
+        cancel.check(iterations, options.max_iterations)?;
+        // Two-loop recursion for the quasi-Newton direction over the free variables.
+        let mut d: Vec<Float> = (0..n)
+            .map(|i| if active[i] { 0.0 } else { -g[i] })
+            .collect();
+        let mut a = Vec::with_capacity(pairs.len());
+        for (s, y, rho) in pairs.iter().rev() {
+            let ai = rho * dot(s, &d);
+            for (d, y) in d.iter_mut().zip(y) {
+                *d -= ai * y;
+            }
+            a.push(ai);
+        }
+        if let Some((s, y, _)) = pairs.back() {
+            let gamma = dot(s, y) / dot(y, y);
+            d.iter_mut().for_each(|d| *d *= gamma);
+        } else {
+            // Unit first step in the largest component, as the curvature is still unknown.
+            let scale = 1.0 / projected;
+            d.iter_mut().for_each(|d| *d *= scale.min(1.0));
+        }
+        for ((s, y, rho), ai) in pairs.iter().zip(a.iter().rev()) {
+            let b = rho * dot(y, &d);
+            for (d, s) in d.iter_mut().zip(s) {
+                *d += (ai - b) * s;
+            }
+        }
+        for (d, &active) in d.iter_mut().zip(&active) {
+            if active {
+                *d = 0.0;
+            }
+        }
+        if dot(&d, &g) >= 0.0 {
+            pairs.clear();
+            d = (0..n)
+                .map(|i| if active[i] { 0.0 } else { -g[i] })
+                .collect();
+        }
+        // Backtracking along the projected path.
+        let mut t = 1.0;
+        let next = loop {
+            let mut trial: Vec<Float> = x.iter().zip(&d).map(|(x, d)| x + t * d).collect();
+            project(&mut trial);
+            let step: Vec<Float> = trial.iter().zip(&x).map(|(a, b)| a - b).collect();
+            let decrease = dot(&g, &step);
+            if decrease >= 0.0 {
+                break None;
+            }
+            let value = objective.value(&trial);
+            if value <= f + ARMIJO * decrease {
+                break Some((trial, step, value));
+            }
+            t /= 2.0;
+            if t < Float::EPSILON {
+                break None;
+            }
+        };
+        let Some((trial, s, value)) = next else {
+            break;
+        };
+        let gradient = objective.gradient(&trial);
+        let y: Vec<Float> = gradient.iter().zip(&g).map(|(a, b)| a - b).collect();
+        let sy = dot(&s, &y);
+        // Skipping pairs without positive curvature keeps the approximation positive definite.
+        if sy > Float::EPSILON * dot(&y, &y) {
+            if pairs.len() == options.memory {
+                pairs.pop_front();
+            }
+            if options.memory > 0 {
+                pairs.push_back((s, y, 1.0 / sy));
+            }
+        }
+        x = trial;
+        f = value;
+        g = gradient;
+        iterations += 1;
+    }
+    Ok(Minimum {
+        x,
+        value: f,
+        iterations,
+        converged: false,
+    })
+}
+
+fn dot(a: &[Float], b: &[Float]) -> Float {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Rosenbrock;
+
+    impl Objective for Rosenbrock {
+        fn value<T: Real>(&self, x: &[T]) -> T {
+            let one = T::from(1.0);
+            let a = one - x[0];
+            let b = x[1] - x[0] * x[0];
+            a * a + T::from(100.0) * b * b
+        }
+    }
+
+    /// Distance from a point outside the box, with a transcendental term to exercise [`Real`].
+    struct Shifted;
+
+    impl Objective for Shifted {
+        fn value<T: Real>(&self, x: &[T]) -> T {
+            let a = x[0] - T::from(3.0);
+            let b = x[1] + T::from(1.0);
+            a * a + b * b + (x[0] * T::from(0.1)).exp()
+        }
+    }
+
+    #[test]
+    fn gradient() {
+        let g = Rosenbrock.gradient(&[-1.2, 1.0]);
+        assert!((g[0] - (-215.6)).abs() < 1e-3);
+        assert!((g[1] - (-88.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rosenbrock() {
+        let m = minimize(&Rosenbrock, &[-1.2, 1.0], &BfgsOptions::default());
+        assert!(m.converged);
+        assert!((m.x[0] - 1.0).abs() < 1e-3 && (m.x[1] - 1.0).abs() < 1e-3);
+        assert!(m.value < 1e-5);
+    }
+
+    #[test]
+    fn bounded() {
+        let m = minimize_bounded(
+            &Shifted,
+            &[0.5, 0.5],
+            &[0.0, 0.0],
+            &[2.0, 2.0],
+            &BfgsOptions::default(),
+        );
+        assert!(m.converged);
+        assert_eq!(m.x, [2.0, 0.0]);
+    }
+
+    #[test]
+    fn iteration_limit() {
+        let options = BfgsOptions {
+            max_iterations: 2,
+            ..Default::default()
+        };
+        let m = minimize(&Rosenbrock, &[-1.2, 1.0], &options);
+        assert!(!m.converged);
+        assert_eq!(m.iterations, 2);
+        assert!(m.value < Rosenbrock.value(&[-1.2, 1.0]));
+    }
+
+    /// [`Rosenbrock`] cancelling the token of its minimization at the tenth evaluation.
+    struct Interrupted {
+        cancel: CancelToken,
+        evaluations: std::cell::Cell<usize>,
+    }
+
+    impl Objective for Interrupted {
+        fn value<T: Real>(&self, x: &[T]) -> T {
+            self.evaluations.set(self.evaluations.get() + 1);
+            if self.evaluations.get() == 10 {
+                self.cancel.cancel();
+            }
+            Rosenbrock.value(x)
+        }
+    }
+
+    #[test]
+    fn cancel() {
+        let objective = Interrupted {
+            cancel: CancelToken::new(),
+            evaluations: Default::default(),
+        };
+        let (lower, upper) = ([Float::NEG_INFINITY; 2], [Float::INFINITY; 2]);
+        let options = BfgsOptions::default();
+        let cancelled = minimize_with_cancel(
+            &objective,
+            &[-1.2, 1.0],
+            &lower,
+            &upper,
+            &options,
+            &objective.cancel,
+        )
+        .unwrap_err();
+        assert!(cancelled.done > 0 && cancelled.done < 10);
+        assert_eq!(cancelled.total, options.max_iterations);
+    }
+}
@@ -1,8 +1,12 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
-use super::traits::{Atan2, Cos, Exp, Hypot, Ln, Sin};
+use super::traits::{Atan2, Constants, Cos, Exp, Hypot, IsNormal, Ln, Sin};
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Complex<T> {
     pub re: T,
     pub im: T,
@@ -92,6 +96,30 @@ where
     }
 }
 
+impl<T> Complex<T>
+where
+    for<'a> &'a T: Div<Output = T> + Mul<Output = T> + Add<Output = T> + Sub<Output = T>,
+    T: IsNormal,
+{
+    /// Returns `None` if the squared norm of `rhs` is zero, subnormal or not finite.
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        let denominator = &(&rhs.re * &rhs.re) + &(&rhs.im * &rhs.im);
+        denominator.is_normal().then(|| self / rhs)
+    }
+}
+
+impl<T> Complex<T>
+where
+    for<'a> &'a T: Div<Output = T>,
+    T: Hypot + IsNormal,
+{
+    /// Returns `None` instead of NaNs if the norm is zero, subnormal or not finite.
+    pub fn checked_normalized(&self) -> Option<Self> {
+        let abs = self.abs();
+        abs.is_normal().then(|| self / &abs)
+    }
+}
+
 impl<T> Add for &Complex<T>
 where
     for<'a> &'a T: Add<Output = T>,
@@ -303,7 +331,51 @@ where
     }
 }
 
+impl_scalar_mul!(Complex);
+impl_owned_binop!(Complex, Complex<T>, Add::add, AddAssign::add_assign);
+impl_owned_binop!(Complex, T, Add::add, AddAssign::add_assign);
+impl_owned_binop!(Complex, Complex<T>, Sub::sub, SubAssign::sub_assign);
+impl_owned_binop!(Complex, T, Sub::sub, SubAssign::sub_assign);
+impl_owned_binop!(Complex, Complex<T>, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Complex, T, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Complex, Complex<T>, Div::div, DivAssign::div_assign);
+impl_owned_binop!(Complex, T, Div::div, DivAssign::div_assign);
+impl_owned_neg!(Complex);
+impl_sum!(Complex);
+impl_product!(Complex);
+
+/// Implements addition, subtraction and division with a primitive scalar on the left-hand side.
+macro_rules! impl_scalar_lhs {
+    ($($scalar:ty),+) => {
+        $(
+            impl Add<&Complex<$scalar>> for &$scalar {
+                type Output = Complex<$scalar>;
+                fn add(self, rhs: &Complex<$scalar>) -> Self::Output {
+                    rhs + self
+                }
+            }
+
+            impl Sub<&Complex<$scalar>> for &$scalar {
+                type Output = Complex<$scalar>;
+                fn sub(self, rhs: &Complex<$scalar>) -> Self::Output {
+                    &-rhs + self
+                }
+            }
+
+            impl Div<&Complex<$scalar>> for &$scalar {
+                type Output = Complex<$scalar>;
+                fn div(self, rhs: &Complex<$scalar>) -> Self::Output {
+                    &Complex::from(*self) / rhs
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar_lhs!(i8, i16, i32, i64, i128, isize, f32, f64);
+
 #[cfg(test)]
+#[allow(clippy::op_ref)]
 mod tests {
     use core::{f32, f64};
 
@@ -319,8 +391,8 @@ mod tests {
     fn abs() {
         let a = Complex::new(8.1, -6.2);
         let b = Complex::new(4.1, 1.2);
-        assert_eq!(a.abs(), (8.1 as f64).hypot(6.2));
-        assert_eq!(b.abs(), (4.1 as f64).hypot(1.2));
+        assert_eq!(a.abs(), 8.1_f64.hypot(6.2));
+        assert_eq!(b.abs(), 4.1_f64.hypot(1.2));
     }
 
     #[test]
@@ -551,13 +623,13 @@ mod tests {
     #[test]
     fn arg() {
         let a = Complex::new(56.3, -33.8);
-        assert_eq!(a.arg(), (-33.8 as f32).atan2(56.3));
+        assert_eq!(a.arg(), (-33.8_f32).atan2(56.3));
         let b = Complex::new(56.3, 0.0);
         assert_eq!(b.arg(), 0.0);
         let c = Complex::new(0.0, -33.8);
         assert_eq!(c.arg(), -f64::consts::PI / 2.0);
         let d = Complex::new(-56.3, 33.8);
-        assert_eq!(d.arg(), (-33.8 as f32).atan2(56.3) + f32::consts::PI);
+        assert_eq!(d.arg(), (-33.8_f32).atan2(56.3) + f32::consts::PI);
         let e = Complex::new(-56.3, 0.0);
         assert_eq!(e.arg(), f32::consts::PI);
         let f = Complex::new(0.0, 0.0);
@@ -569,7 +641,7 @@ mod tests {
         let a = Complex::new(56.3, -33.8);
         assert_eq!(
             a.ln(),
-            Complex::new((56.3 as f32).hypot(33.8).ln(), -(33.8 as f32).atan2(56.3)),
+            Complex::new(56.3_f32.hypot(33.8).ln(), -33.8_f32.atan2(56.3)),
         );
         let b = Complex::new(1.0, 0.0);
         assert_eq!(b.ln(), Complex::new(0.0, 0.0));
@@ -582,12 +654,10 @@ mod tests {
         assert_eq!(
             a.pow(&b),
             Complex::new(
-                (4.41 * (33.8 as f64).atan2(56.3) - 3.1 * (56.3 as f64).hypot(33.8).ln()).exp()
-                    * (4.41 * (56.3 as f64).hypot(33.8).ln() + 3.1 * (33.8 as f64).atan2(56.3))
-                        .cos(),
-                (4.41 * (33.8 as f64).atan2(56.3) - 3.1 * (56.3 as f64).hypot(33.8).ln()).exp()
-                    * (4.41 * (56.3 as f64).hypot(33.8).ln() + 3.1 * (33.8 as f64).atan2(56.3))
-                        .sin(),
+                (4.41 * 33.8_f64.atan2(56.3) - 3.1 * 56.3_f64.hypot(33.8).ln()).exp()
+                    * (4.41 * 56.3_f64.hypot(33.8).ln() + 3.1 * 33.8_f64.atan2(56.3)).cos(),
+                (4.41 * 33.8_f64.atan2(56.3) - 3.1 * 56.3_f64.hypot(33.8).ln()).exp()
+                    * (4.41 * 56.3_f64.hypot(33.8).ln() + 3.1 * 33.8_f64.atan2(56.3)).sin(),
             ),
         );
     }
@@ -603,4 +673,82 @@ mod tests {
         assert_eq!(-&Complex::new(72, 369), Complex::new(-72, -369));
         assert_eq!(-&Complex::new(56.3, -33.8), Complex::new(-56.3, 33.8));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = Complex::new(56.3, -33.8);
+        let s = serde_json::to_string(&a).unwrap();
+        assert_eq!(s, r#"{"re":56.3,"im":-33.8}"#);
+        assert_eq!(serde_json::from_str::<Complex<f64>>(&s).unwrap(), a);
+    }
+
+    #[test]
+    fn scalar_lhs() {
+        let a = Complex::new(8, 41);
+        assert_eq!(&9 + &a, Complex::new(17, 41));
+        assert_eq!(&9 - &a, Complex::new(1, -41));
+        assert_eq!(&9 * &a, Complex::new(72, 369));
+        let b = Complex::new(-3.98, 12.6);
+        assert_eq!(&22.8 + &b, &b + &22.8);
+        assert_eq!(&22.8 - &b, Complex::new(22.8 + 3.98, -12.6));
+        assert_eq!(&22.8 * &b, &b * &22.8);
+        assert_eq!(&22.8 / &b, &Complex::from(22.8) / &b);
+        assert_eq!(&-1168 / &Complex::new(-32, 12), Complex::new(32, 12));
+    }
+
+    #[test]
+    fn checked_div() {
+        let c = Complex::new(201.8, 843.9);
+        let d = Complex::new(-3.98, 12.6);
+        assert_eq!(c.checked_div(&d), Some(&c / &d));
+        assert_eq!(c.checked_div(&Complex::new(0.0, 0.0)), None);
+        assert_eq!(c.checked_div(&Complex::new(1.0e-170, -1.0e-170)), None);
+    }
+
+    #[test]
+    fn checked_normalized() {
+        let c = Complex::new(3.0, -4.0);
+        assert_eq!(c.checked_normalized(), Some(Complex::new(0.6, -0.8)));
+        assert_eq!(Complex::new(0.0, 0.0).checked_normalized(), None);
+        assert_eq!(Complex::new(1.0e-320, 0.0).checked_normalized(), None);
+        assert_eq!(Complex::new(f64::INFINITY, 1.0).checked_normalized(), None);
+        assert_eq!(Complex::new(f32::NAN, 1.0).checked_normalized(), None);
+    }
+
+    #[test]
+    fn owned_operators() {
+        let a = Complex::new(1.5, -2.0);
+        let b = Complex::new(-0.5, 4.0);
+        assert_eq!(a + b, &a + &b);
+        assert_eq!(a - &b, &a - &b);
+        assert_eq!(&a * b, &a * &b);
+        assert_eq!(a / b, &a / &b);
+        assert_eq!(a + 2.0, &a + &2.0);
+        assert_eq!(a - 2.0, &a - &2.0);
+        assert_eq!(2.0 * a, &a * &2.0);
+        assert_eq!(a / 4.0, &a / &4.0);
+        assert_eq!(-a, -&a);
+        let mut c = a;
+        c -= b;
+        c /= 2.0;
+        assert_eq!(c, &(&a - &b) / &2.0);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let c = [Complex::new(1, -2), Complex::new(3, 4), Complex::new(-2, 1)];
+        assert_eq!(c.iter().sum::<Complex<i32>>(), Complex::new(2, 3));
+        assert_eq!(c.iter().product::<Complex<i32>>(), Complex::new(-20, 15));
+        assert_eq!(
+            c.into_iter().product::<Complex<i32>>(),
+            Complex::new(-20, 15)
+        );
+        assert_eq!(
+            Vec::<Complex<f64>>::new()
+                .into_iter()
+                .product::<Complex<_>>(),
+            Complex::from(1.0)
+        );
+    }
 }
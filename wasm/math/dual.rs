@@ -0,0 +1,357 @@
+use super::traits::{Constants, Cos, Exp, Ln, Sin};
+use std::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Dual number `re + eps ε` with `ε² = 0`. Evaluating a function on `x + ε` gives its value and
+/// its derivative at `x` in `re` and `eps`, which is forward-mode automatic differentiation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dual<T> {
+    pub re: T,
+    pub eps: T,
+}
+
+impl<T> Dual<T> {
+    pub const fn new(re: T, eps: T) -> Self {
+        Self { re, eps }
+    }
+}
+
+impl<T> Dual<T>
+where
+    T: Constants,
+{
+    /// Independent variable `x + ε`, with unit derivative.
+    pub fn variable(x: T) -> Self {
+        Self {
+            re: x,
+            eps: T::one(),
+        }
+    }
+}
+
+impl<T> From<T> for Dual<T>
+where
+    T: Default,
+{
+    /// Constant, with zero derivative.
+    fn from(value: T) -> Self {
+        Self {
+            re: value,
+            eps: T::default(),
+        }
+    }
+}
+
+impl<T> Sin for Dual<T>
+where
+    T: Sin + Cos,
+    for<'a> &'a T: Mul<Output = T>,
+{
+    fn sin(&self) -> Self {
+        Self {
+            re: self.re.sin(),
+            eps: &self.re.cos() * &self.eps,
+        }
+    }
+}
+
+impl<T> Cos for Dual<T>
+where
+    T: Sin + Cos,
+    for<'a> &'a T: Mul<Output = T> + Neg<Output = T>,
+{
+    fn cos(&self) -> Self {
+        Self {
+            re: self.re.cos(),
+            eps: -&(&self.re.sin() * &self.eps),
+        }
+    }
+}
+
+impl<T> Exp for Dual<T>
+where
+    T: Exp,
+    for<'a> &'a T: Mul<Output = T>,
+{
+    fn exp(&self) -> Self {
+        let re = self.re.exp();
+        Self {
+            eps: &re * &self.eps,
+            re,
+        }
+    }
+}
+
+impl<T> Ln for Dual<T>
+where
+    T: Ln,
+    for<'a> &'a T: Div<Output = T>,
+{
+    fn ln(&self) -> Self {
+        Self {
+            re: self.re.ln(),
+            eps: &self.eps / &self.re,
+        }
+    }
+}
+
+impl<T> Add for &Dual<T>
+where
+    for<'a> &'a T: Add<Output = T>,
+{
+    type Output = Dual<T>;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            re: &self.re + &rhs.re,
+            eps: &self.eps + &rhs.eps,
+        }
+    }
+}
+
+impl<T> Add<&T> for &Dual<T>
+where
+    for<'a> &'a T: Add<Output = T>,
+    T: Clone,
+{
+    type Output = Dual<T>;
+    fn add(self, rhs: &T) -> Self::Output {
+        Self::Output {
+            re: &self.re + rhs,
+            eps: self.eps.clone(),
+        }
+    }
+}
+
+impl<T> AddAssign<&Dual<T>> for Dual<T>
+where
+    for<'a> T: AddAssign<&'a T>,
+{
+    fn add_assign(&mut self, rhs: &Dual<T>) {
+        self.re += &rhs.re;
+        self.eps += &rhs.eps;
+    }
+}
+
+impl<T> AddAssign<&T> for Dual<T>
+where
+    for<'a> T: AddAssign<&'a T>,
+{
+    fn add_assign(&mut self, rhs: &T) {
+        self.re += rhs;
+    }
+}
+
+impl<T> Sub for &Dual<T>
+where
+    for<'a> &'a T: Sub<Output = T>,
+{
+    type Output = Dual<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            re: &self.re - &rhs.re,
+            eps: &self.eps - &rhs.eps,
+        }
+    }
+}
+
+impl<T> Sub<&T> for &Dual<T>
+where
+    for<'a> &'a T: Sub<Output = T>,
+    T: Clone,
+{
+    type Output = Dual<T>;
+    fn sub(self, rhs: &T) -> Self::Output {
+        Self::Output {
+            re: &self.re - rhs,
+            eps: self.eps.clone(),
+        }
+    }
+}
+
+impl<T> SubAssign<&Dual<T>> for Dual<T>
+where
+    for<'a> T: SubAssign<&'a T>,
+{
+    fn sub_assign(&mut self, rhs: &Dual<T>) {
+        self.re -= &rhs.re;
+        self.eps -= &rhs.eps;
+    }
+}
+
+impl<T> SubAssign<&T> for Dual<T>
+where
+    for<'a> T: SubAssign<&'a T>,
+{
+    fn sub_assign(&mut self, rhs: &T) {
+        self.re -= rhs;
+    }
+}
+
+impl<T> Mul for &Dual<T>
+where
+    for<'a> &'a T: Mul<Output = T> + Add<Output = T>,
+{
+    type Output = Dual<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            re: &self.re * &rhs.re,
+            eps: &(&self.re * &rhs.eps) + &(&self.eps * &rhs.re),
+        }
+    }
+}
+
+impl<T> Mul<&T> for &Dual<T>
+where
+    for<'a> &'a T: Mul<Output = T>,
+{
+    type Output = Dual<T>;
+    fn mul(self, rhs: &T) -> Self::Output {
+        Self::Output {
+            re: &self.re * rhs,
+            eps: &self.eps * rhs,
+        }
+    }
+}
+
+impl<T> MulAssign<&Dual<T>> for Dual<T>
+where
+    for<'a> &'a T: Mul<Output = T> + Add<Output = T>,
+{
+    fn mul_assign(&mut self, rhs: &Dual<T>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<T> MulAssign<&T> for Dual<T>
+where
+    for<'a> T: MulAssign<&'a T>,
+{
+    fn mul_assign(&mut self, rhs: &T) {
+        self.re *= rhs;
+        self.eps *= rhs;
+    }
+}
+
+impl<T> Div for &Dual<T>
+where
+    for<'a> &'a T: Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    type Output = Dual<T>;
+    fn div(self, rhs: Self) -> Self::Output {
+        // (a + a'ε)/(b + b'ε) = a/b + (a'b - ab')/b² ε
+        Self::Output {
+            re: &self.re / &rhs.re,
+            eps: &(&(&self.eps * &rhs.re) - &(&self.re * &rhs.eps)) / &(&rhs.re * &rhs.re),
+        }
+    }
+}
+
+impl<T> Div<&T> for &Dual<T>
+where
+    for<'a> &'a T: Div<Output = T>,
+{
+    type Output = Dual<T>;
+    fn div(self, rhs: &T) -> Self::Output {
+        Self::Output {
+            re: &self.re / rhs,
+            eps: &self.eps / rhs,
+        }
+    }
+}
+
+impl<T> DivAssign<&Dual<T>> for Dual<T>
+where
+    for<'a> &'a T: Div<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    fn div_assign(&mut self, rhs: &Dual<T>) {
+        *self = &*self / rhs;
+    }
+}
+
+impl<T> DivAssign<&T> for Dual<T>
+where
+    for<'a> T: DivAssign<&'a T>,
+{
+    fn div_assign(&mut self, rhs: &T) {
+        self.re /= rhs;
+        self.eps /= rhs;
+    }
+}
+
+impl<T> Neg for &Dual<T>
+where
+    for<'a> &'a T: Neg<Output = T>,
+{
+    type Output = Dual<T>;
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            re: -&self.re,
+            eps: -&self.eps,
+        }
+    }
+}
+
+impl_scalar_mul!(Dual);
+impl_owned_binop!(Dual, Dual<T>, Add::add, AddAssign::add_assign);
+impl_owned_binop!(Dual, T, Add::add, AddAssign::add_assign);
+impl_owned_binop!(Dual, Dual<T>, Sub::sub, SubAssign::sub_assign);
+impl_owned_binop!(Dual, T, Sub::sub, SubAssign::sub_assign);
+impl_owned_binop!(Dual, Dual<T>, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Dual, T, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Dual, Dual<T>, Div::div, DivAssign::div_assign);
+impl_owned_binop!(Dual, T, Div::div, DivAssign::div_assign);
+impl_owned_neg!(Dual);
+impl_sum!(Dual);
+impl_product!(Dual);
+
+#[cfg(test)]
+#[allow(clippy::op_ref)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let x = Dual::variable(3.0_f64);
+        // f(x) = (2x² - 1) / x, f'(x) = 2 + 1/x²
+        let f: Dual<f64> = (2.0 * x * x - 1.0) / x;
+        assert_eq!(f.re, 17.0 / 3.0);
+        assert!((f.eps - (2.0 + 1.0 / 9.0)).abs() < 1e-12);
+        assert_eq!(&x - &Dual::from(1.0), Dual::new(2.0, 1.0));
+        assert_eq!(-x, Dual::new(-3.0, -1.0));
+    }
+
+    #[test]
+    fn assign() {
+        let mut a = Dual::new(2.0, 1.0);
+        a *= &Dual::new(3.0, 0.5);
+        assert_eq!(a, Dual::new(6.0, 4.0));
+        a /= Dual::new(2.0, 0.0);
+        assert_eq!(a, Dual::new(3.0, 2.0));
+        a += 1.0;
+        a -= Dual::new(1.0, 1.0);
+        assert_eq!(a, Dual::new(3.0, 1.0));
+    }
+
+    #[test]
+    fn functions() {
+        let x = Dual::variable(0.5_f64);
+        assert_eq!(x.sin(), Dual::new(0.5_f64.sin(), 0.5_f64.cos()));
+        assert_eq!(x.cos(), Dual::new(0.5_f64.cos(), -0.5_f64.sin()));
+        assert_eq!(x.exp(), Dual::new(0.5_f64.exp(), 0.5_f64.exp()));
+        assert_eq!(x.ln(), Dual::new(0.5_f64.ln(), 2.0));
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let v = [
+            Dual::new(1.0, 1.0),
+            Dual::new(2.0, 0.0),
+            Dual::new(3.0, 2.0),
+        ];
+        assert_eq!(v.iter().sum::<Dual<f64>>(), Dual::new(6.0, 3.0));
+        assert_eq!(v.into_iter().product::<Dual<f64>>(), Dual::new(6.0, 10.0));
+    }
+}
@@ -1,11 +1,16 @@
 use super::{
-    quaternion::Quaternion,
-    traits::{Cos, Hypot, Sin},
+    quaternion::{Quaternion, RotationScalar},
+    traits::{Constants, IsNormal},
     vector::Vector,
 };
-use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{
+    fmt::{self, Display, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DualQuaternion<T> {
     pub p: Quaternion<T>,
     pub q: Quaternion<T>,
@@ -29,38 +34,70 @@ where
     }
 }
 
-impl<T> DualQuaternion<T>
+/// Scalar types the rigid transform constructors of [`DualQuaternion`] are available for.
+///
+/// Like [`RotationScalar`], this lets the scalar type be inferred from the arguments.
+pub trait RigidTransformScalar: RotationScalar {
+    fn translation_and_rotation(
+        translation: &Vector<Self>,
+        rotation_axis: &Vector<Self>,
+    ) -> DualQuaternion<Self>;
+
+    fn rotation_and_translation(
+        rotation_axis: &Vector<Self>,
+        translation: &Vector<Self>,
+    ) -> DualQuaternion<Self>;
+}
+
+impl<T> RigidTransformScalar for T
 where
-    T: From<u8> + Clone + Sin + Cos + Hypot + PartialOrd,
+    T: RotationScalar + Constants + Clone,
     for<'a> &'a T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
 {
-    /// This function needs explicit type specification to be called because of a compiler bug.
-    pub fn from_translation_and_rotation(
+    fn translation_and_rotation(
         translation: &Vector<T>,
         rotation_axis: &Vector<T>,
-    ) -> Self {
-        let r = Quaternion::<T>::from_rotation(rotation_axis);
+    ) -> DualQuaternion<T> {
+        let r = Quaternion::from_rotation(rotation_axis);
         let rt = &r * &Quaternion::from_translation(translation);
-        Self {
-            p: r.into(),
-            q: &rt / &2.into(),
+        DualQuaternion {
+            p: r,
+            q: &rt / &T::two(),
         }
     }
 
-    /// This function needs explicit type specification to be called because of a compiler bug.
-    pub fn from_rotation_and_translation(
+    fn rotation_and_translation(
         rotation_axis: &Vector<T>,
         translation: &Vector<T>,
-    ) -> Self {
-        let r = Quaternion::<T>::from_rotation(rotation_axis);
+    ) -> DualQuaternion<T> {
+        let r = Quaternion::from_rotation(rotation_axis);
         let tr = &Quaternion::from_translation(translation) * &r;
-        Self {
+        DualQuaternion {
             p: r,
-            q: &tr / &2.into(),
+            q: &tr / &T::two(),
         }
     }
 }
 
+impl<T> DualQuaternion<T>
+where
+    T: RigidTransformScalar,
+{
+    pub fn from_translation_and_rotation(
+        translation: &Vector<T>,
+        rotation_axis: &Vector<T>,
+    ) -> Self {
+        T::translation_and_rotation(translation, rotation_axis)
+    }
+
+    pub fn from_rotation_and_translation(
+        rotation_axis: &Vector<T>,
+        translation: &Vector<T>,
+    ) -> Self {
+        T::rotation_and_translation(rotation_axis, translation)
+    }
+}
+
 impl<T> DualQuaternion<T>
 where
     for<'a> &'a T: Neg<Output = T>,
@@ -99,11 +136,59 @@ where
 
 impl<T> DualQuaternion<T>
 where
-    T: From<u8> + Clone,
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone,
+{
+    /// (p + εq)⁻¹ = p⁻¹ - εp⁻¹qp⁻¹
+    pub fn inv(&self) -> Self {
+        let p = self.p.inv();
+        Self {
+            q: -&(&(&p * &self.q) * &p),
+            p,
+        }
+    }
+}
+
+impl<T> DualQuaternion<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone + IsNormal,
+{
+    /// Returns `None` if the real part is not invertible.
+    pub fn checked_inv(&self) -> Option<Self> {
+        self.p.checked_inv().map(|p| Self {
+            q: -&(&(&p * &self.q) * &p),
+            p,
+        })
+    }
+
+    /// Returns `None` if `other` is not invertible, see [`Self::checked_inv`].
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.checked_inv().map(|inv| self * &inv)
+    }
+}
+
+impl<T> DualQuaternion<T>
+where
+    T: Constants + Clone,
     for<'a> &'a T: Neg<Output = T> + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
 {
     pub fn translation(&self) -> Vector<T> {
-        &(&self.q * &self.p.conj()).v * &T::from(2)
+        &(&self.q * &self.p.conj()).v * &T::two()
+    }
+}
+
+/// Formats as `p + εq` with both quaternions in their own display format.
+impl<T> Display for DualQuaternion<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.p.fmt(f)?;
+        f.write_str(" + ε")?;
+        self.q.fmt(f)
     }
 }
 
@@ -164,7 +249,7 @@ where
     fn mul(self, other: Self) -> Self::Output {
         Self::Output {
             p: &self.p * &other.p,
-            q: &(&self.p * &other.q) + &(&other.p * &self.q),
+            q: &(&self.p * &other.q) + &(&self.q * &other.p),
         }
     }
 }
@@ -188,7 +273,7 @@ where
     for<'a> &'a T: Mul<Output = T> + Add<Output = T> + Sub<Output = T>,
 {
     fn mul_assign(&mut self, other: &DualQuaternion<T>) {
-        self.q = &(&self.p * &other.q) + &(&other.p * &self.q);
+        self.q = &(&self.p * &other.q) + &(&self.q * &other.p);
         self.p *= &other.p;
     }
 }
@@ -217,9 +302,67 @@ where
     }
 }
 
+impl<T> Div for &DualQuaternion<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone,
+{
+    type Output = DualQuaternion<T>;
+
+    /// Multiplies by the inverse of `other` from the right, so that `&(a / b) * b == a`.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * &other.inv()
+    }
+}
+
+impl<T> DivAssign<&DualQuaternion<T>> for DualQuaternion<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone,
+{
+    fn div_assign(&mut self, other: &DualQuaternion<T>) {
+        *self = &*self / other;
+    }
+}
+
+impl_scalar_mul!(DualQuaternion);
+impl_owned_binop!(
+    DualQuaternion,
+    DualQuaternion<T>,
+    Add::add,
+    AddAssign::add_assign
+);
+impl_owned_binop!(
+    DualQuaternion,
+    DualQuaternion<T>,
+    Sub::sub,
+    SubAssign::sub_assign
+);
+impl_owned_binop!(
+    DualQuaternion,
+    DualQuaternion<T>,
+    Mul::mul,
+    MulAssign::mul_assign
+);
+impl_owned_binop!(DualQuaternion, T, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(
+    DualQuaternion,
+    DualQuaternion<T>,
+    Div::div,
+    DivAssign::div_assign
+);
+impl_owned_binop!(DualQuaternion, T, Div::div);
+impl_sum!(DualQuaternion);
+impl_product!(DualQuaternion);
+
 #[cfg(test)]
+#[allow(clippy::op_ref)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
 
     #[test]
     fn from() {
@@ -426,8 +569,8 @@ mod tests {
                     * &Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2),
                 &(&Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27)
                     * &Quaternion::new(Vector::new(3.3, 4.9, -6.13), -9.34))
-                    + &(&Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)
-                        * &Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3)),
+                    + &(&Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3)
+                        * &Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)),
             ),
         );
         assert_eq!(
@@ -435,10 +578,10 @@ mod tests {
             DualQuaternion::new(
                 &Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)
                     * &Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27),
-                &(&Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27)
-                    * &Quaternion::new(Vector::new(3.3, 4.9, -6.13), -9.34))
-                    + &(&Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)
-                        * &Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3)),
+                &(&Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)
+                    * &Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3))
+                    + &(&Quaternion::new(Vector::new(3.3, 4.9, -6.13), -9.34)
+                        * &Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27)),
             ),
         );
     }
@@ -487,8 +630,8 @@ mod tests {
                     * &Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2),
                 &(&Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27)
                     * &Quaternion::new(Vector::new(3.3, 4.9, -6.13), -9.34))
-                    + &(&Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)
-                        * &Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3)),
+                    + &(&Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3)
+                        * &Quaternion::new(Vector::new(5.3, 3.2, -10.98), 41.2)),
             ),
         );
     }
@@ -617,14 +760,14 @@ mod tests {
     #[test]
     fn from_rotation_and_translation() {
         assert_eq!(
-            DualQuaternion::<f64>::from_rotation_and_translation(
+            DualQuaternion::from_rotation_and_translation(
                 &Vector::new(0.13, -0.24, 0.66),
                 &Vector::new(4.2, 3.1, -10.6),
             ),
             DualQuaternion::new(
-                Quaternion::<f64>::from_rotation(&Vector::new(0.13, -0.24, 0.66)),
+                Quaternion::from_rotation(&Vector::new(0.13, -0.24, 0.66)),
                 &(&Quaternion::from_translation(&Vector::new(4.2, 3.1, -10.6))
-                    * &Quaternion::<f64>::from_rotation(&Vector::new(0.13, -0.24, 0.66)))
+                    * &Quaternion::from_rotation(&Vector::new(0.13, -0.24, 0.66)))
                     / &2.0,
             ),
         );
@@ -632,18 +775,16 @@ mod tests {
 
     #[test]
     fn translation() {
-        let a = DualQuaternion::<f64>::from_rotation_and_translation(
+        let a = DualQuaternion::from_rotation_and_translation(
             &Vector::new(0.0, 0.0, 0.0),
             &Vector::new(32.8, -6.35, -9.97),
         );
         assert_eq!(a.translation(), Vector::new(32.8, -6.35, -9.97));
-        let b = DualQuaternion::<f64>::from_rotation_and_translation(
+        let b = DualQuaternion::from_rotation_and_translation(
             &Vector::new(0.0, 0.001, 0.008),
             &Vector::new(32.8, -6.35, -9.97),
         );
-        assert!((b.translation().x - 32.8).abs() < f64::EPSILON * 32.8);
-        assert!((b.translation().y + 6.35).abs() < f64::EPSILON * 6.35);
-        assert!((b.translation().z + 9.97).abs() < f64::EPSILON * 9.97);
+        assert_relative_eq!(b.translation(), Vector::new(32.8, -6.35, -9.97));
     }
 
     #[test]
@@ -663,4 +804,123 @@ mod tests {
             )
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = DualQuaternion::new(
+            Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27),
+            Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3),
+        );
+        let s = serde_json::to_string(&a).unwrap();
+        assert_eq!(
+            s,
+            r#"{"p":{"v":{"x":3.8,"y":-9.9,"z":-0.84},"w":3.27},"q":{"v":{"x":-1.2,"y":-2.2,"z":64.3},"w":3.3}}"#
+        );
+        assert_eq!(serde_json::from_str::<DualQuaternion<f64>>(&s).unwrap(), a);
+    }
+
+    #[test]
+    fn display() {
+        let a = DualQuaternion::new(
+            Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27),
+            Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3),
+        );
+        assert_eq!(
+            a.to_string(),
+            "((3.8, -9.9, -0.84), 3.27) + ε((-1.2, -2.2, 64.3), 3.3)"
+        );
+        assert_eq!(
+            format!("{:.1}", a),
+            "((3.8, -9.9, -0.8), 3.3) + ε((-1.2, -2.2, 64.3), 3.3)"
+        );
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let a = DualQuaternion::new(
+            Quaternion::new(Vector::new(5, 3, -10), 41),
+            Quaternion::new(Vector::new(3, 4, -6), -9),
+        );
+        assert_eq!(&8 * &a, &a * &8);
+    }
+
+    #[test]
+    fn inv() {
+        let a = DualQuaternion::new(
+            Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27),
+            Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3),
+        );
+        assert_relative_eq!(&a * &a.inv(), DualQuaternion::from(1.0), epsilon = 1e-14);
+        assert_relative_eq!(&a.inv() * &a, DualQuaternion::from(1.0), epsilon = 1e-14);
+    }
+
+    #[test]
+    fn div_dual_quaternion() {
+        let a = &DualQuaternion::from_rotation_and_translation(
+            &Vector::new(0.13, -0.24, 0.66),
+            &Vector::new(4.2, 3.1, -10.6),
+        );
+        let b = &DualQuaternion::from_rotation_and_translation(
+            &Vector::new(-0.5, 0.1, 0.2),
+            &Vector::new(-1.0, 0.4, 2.5),
+        );
+        assert_relative_eq!(&(a / b) * b, a.clone(), epsilon = 1e-14);
+        let mut c = a.clone();
+        c /= b;
+        assert_eq!(c, a / b);
+    }
+
+    #[test]
+    fn checked() {
+        let a = DualQuaternion::new(
+            Quaternion::new(Vector::new(3.8, -9.9, -0.84), 3.27),
+            Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3),
+        );
+        let b = DualQuaternion::new(
+            Quaternion::new(Vector::new(0.0, 0.0, 0.0), 0.0),
+            Quaternion::new(Vector::new(-1.2, -2.2, 64.3), 3.3),
+        );
+        assert_eq!(a.checked_inv(), Some(a.inv()));
+        assert_eq!(b.checked_inv(), None);
+        assert_eq!(b.checked_div(&a), Some(&b / &a));
+        assert_eq!(a.checked_div(&b), None);
+    }
+
+    #[test]
+    fn owned_operators() {
+        let a = DualQuaternion::new(
+            Quaternion::new(Vector::new(1.5, -2.0, 3.25), 0.5),
+            Quaternion::new(Vector::new(0.25, 1.0, -1.5), 2.0),
+        );
+        let b = DualQuaternion::new(
+            Quaternion::new(Vector::new(-0.5, 4.0, 1.5), -2.0),
+            Quaternion::new(Vector::new(3.0, -0.75, 0.5), 1.0),
+        );
+        assert_eq!(a.clone() + b.clone(), &a + &b);
+        assert_eq!(a.clone() - &b, &a - &b);
+        assert_eq!(&a * b.clone(), &a * &b);
+        assert_eq!(a.clone() / b.clone(), &a / &b);
+        assert_eq!(2.0 * a.clone(), &a * &2.0);
+        assert_eq!(a.clone() / 4.0, &a / &4.0);
+        let mut c = a.clone();
+        c *= b.clone();
+        c -= a.clone();
+        assert_eq!(c, &(&a * &b) - &a);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let a = DualQuaternion::from_rotation_and_translation(
+            &Vector::new(0.1, -0.3, 0.2),
+            &Vector::new(1.5, 2.0, -0.5),
+        );
+        let b = DualQuaternion::from_rotation_and_translation(
+            &Vector::new(-0.4, 0.0, 0.7),
+            &Vector::new(-3.0, 0.25, 1.0),
+        );
+        let d = [a.clone(), b.clone()];
+        assert_eq!(d.iter().sum::<DualQuaternion<f64>>(), &a + &b);
+        assert_eq!(d.into_iter().product::<DualQuaternion<f64>>(), &a * &b);
+    }
 }
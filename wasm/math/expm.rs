@@ -0,0 +1,166 @@
+use super::matrix::Matrix;
+use crate::Float;
+
+/// Coefficients of the [13/13] Padé approximant of the exponential.
+const PADE13: [Float; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+
+/// Largest 1-norm for which the [13/13] approximant is accurate to double precision.
+#[allow(clippy::excessive_precision)]
+const THETA13: Float = 5.371920351148152;
+
+/// Computes the matrix exponential `exp(A)` of a small dense matrix by scaling and squaring with
+/// the [13/13] Padé approximant (Higham, 2005).
+///
+/// Returns `None` if the denominator of the approximant is singular or not finite, as it is for a
+/// matrix of non-finite elements.
+///
+/// # Panics
+///
+/// Panics if `a` is not square.
+pub fn expm(a: &Matrix<Float>) -> Option<Matrix<Float>> {
+    assert!(a.is_square(), "matrix is not square");
+    let n = a.rows();
+    let norm = a.norm1();
+    let s = if norm > THETA13 {
+        (norm / THETA13).log2().ceil() as i32
+    } else {
+        0
+    };
+    let a = a * (2.0 as Float).powi(-s);
+    let b = &PADE13;
+    let i: Matrix<Float> = Matrix::identity(n);
+    let a2 = &a * &a;
+    let a4 = &a2 * &a2;
+    let a6 = &a4 * &a2;
+    let u = &a
+        * &(&a6 * &(&(&a6 * b[13]) + &(&(&a4 * b[11]) + &(&a2 * b[9])))
+            + &(&a6 * b[7])
+            + &(&a4 * b[5])
+            + &(&a2 * b[3])
+            + &(&i * b[1]));
+    let v = &a6 * &(&(&a6 * b[12]) + &(&(&a4 * b[10]) + &(&a2 * b[8])))
+        + &(&a6 * b[6])
+        + &(&a4 * b[4])
+        + &(&a2 * b[2])
+        + &(&i * b[0]);
+    let mut r = (&v - &u).solve(&(&v + &u))?;
+    for _ in 0..s {
+        r = &r * &r;
+    }
+    Some(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const TOLERANCE: Float = Float::EPSILON * 1.0e3;
+
+    #[test]
+    fn zero() {
+        assert_eq!(expm(&Matrix::zeros(3, 3)), Some(Matrix::identity(3)));
+    }
+
+    #[test]
+    fn non_finite() {
+        let a = Matrix::new(2, 2, vec![1.0, Float::NAN, 0.0, 1.0]);
+        assert_eq!(expm(&a), None);
+        let a = Matrix::new(2, 2, vec![Float::INFINITY, 0.0, 0.0, 1.0]);
+        assert_eq!(expm(&a), None);
+    }
+
+    #[test]
+    fn diagonal() {
+        let a = Matrix::new(3, 3, vec![1.0, 0.0, 0.0, 0.0, -2.5, 0.0, 0.0, 0.0, 12.0]);
+        let e = Matrix::new(
+            3,
+            3,
+            vec![
+                (1.0 as Float).exp(),
+                0.0,
+                0.0,
+                0.0,
+                (-2.5 as Float).exp(),
+                0.0,
+                0.0,
+                0.0,
+                (12.0 as Float).exp(),
+            ],
+        );
+        assert_relative_eq!(
+            expm(&a).unwrap().as_slice(),
+            e.as_slice(),
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn nilpotent() {
+        let a = Matrix::new(3, 3, vec![0.0, 1.0, 2.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0]);
+        let e = Matrix::new(3, 3, vec![1.0, 1.0, 3.5, 0.0, 1.0, 3.0, 0.0, 0.0, 1.0]);
+        assert_relative_eq!(
+            expm(&a).unwrap().as_slice(),
+            e.as_slice(),
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn rotation() {
+        // The exponential of a skew-symmetric generator is a rotation.
+        let t = 7.3 as Float;
+        let a = Matrix::new(2, 2, vec![0.0, -t, t, 0.0]);
+        let e = Matrix::new(2, 2, vec![t.cos(), -t.sin(), t.sin(), t.cos()]);
+        assert_relative_eq!(
+            expm(&a).unwrap().as_slice(),
+            e.as_slice(),
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn twist() {
+        // A twist about the z axis through (1, 0, 0) maps to the homogeneous transform rotating
+        // about that axis.
+        let t = (90.0 as Float).to_radians();
+        #[rustfmt::skip]
+        let a = Matrix::new(4, 4, vec![
+            0.0, -t, 0.0, 0.0,
+            t, 0.0, 0.0, -t,
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+        ]);
+        #[rustfmt::skip]
+        let e = Matrix::new(4, 4, vec![
+            0.0, -1.0, 0.0, 1.0,
+            1.0, 0.0, 0.0, -1.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]);
+        assert_relative_eq!(
+            expm(&a).unwrap().as_slice(),
+            e.as_slice(),
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use super::complex::Complex;
+use crate::Float;
+
+/// Computes the discrete Fourier transform of `buffer` in place.
+///
+/// Power-of-two lengths use the iterative radix-2 algorithm, other lengths are handled by
+/// Bluestein's algorithm on top of it.
+pub fn fft(buffer: &mut [Complex<Float>]) {
+    transform(buffer, false);
+}
+
+/// Computes the inverse discrete Fourier transform of `buffer` in place, including the `1/n`
+/// normalization.
+pub fn ifft(buffer: &mut [Complex<Float>]) {
+    transform(buffer, true);
+    let n = buffer.len() as Float;
+    for c in buffer.iter_mut() {
+        *c /= &n;
+    }
+}
+
+/// Frequency of the `k`-th bin of an `n` point transform of samples spaced by `dt`.
+pub fn frequency(k: usize, n: usize, dt: Float) -> Float {
+    k as Float / (n as Float * dt)
+}
+
+fn transform(buffer: &mut [Complex<Float>], inverse: bool) {
+    match buffer.len() {
+        0 | 1 => {}
+        n if n.is_power_of_two() => radix2(buffer, inverse),
+        _ => bluestein(buffer, inverse),
+    }
+}
+
+fn twiddle(numerator: Float, denominator: Float, inverse: bool) -> Complex<Float> {
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let theta = sign * (180.0 as Float).to_radians() * numerator / denominator;
+    Complex::new(theta.cos(), theta.sin())
+}
+
+fn radix2(buffer: &mut [Complex<Float>], inverse: bool) {
+    let n = buffer.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let w: Vec<Complex<Float>> = (0..half)
+            .map(|k| twiddle((k * 2) as Float, len as Float, inverse))
+            .collect();
+        for chunk in buffer.chunks_exact_mut(len) {
+            let (a, b) = chunk.split_at_mut(half);
+            for k in 0..half {
+                let t = b[k] * w[k];
+                b[k] = a[k] - t;
+                a[k] += t;
+            }
+        }
+        len *= 2;
+    }
+}
+
+fn bluestein(buffer: &mut [Complex<Float>], inverse: bool) {
+    let n = buffer.len();
+    let m = (n * 2 - 1).next_power_of_two();
+    // Chirp exp(-πik²/n); k² is reduced modulo 2n to keep the angle accurate.
+    let chirp: Vec<Complex<Float>> = (0..n)
+        .map(|k| twiddle((k * k % (n * 2)) as Float, n as Float, inverse))
+        .collect();
+    let mut a = vec![Complex::default(); m];
+    for (a, (x, w)) in a.iter_mut().zip(buffer.iter().zip(&chirp)) {
+        *a = x * w;
+    }
+    let mut b = vec![Complex::default(); m];
+    b[0] = chirp[0].conj();
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[m - k] = chirp[k].conj();
+    }
+    radix2(&mut a, false);
+    radix2(&mut b, false);
+    for (a, b) in a.iter_mut().zip(&b) {
+        *a *= b;
+    }
+    radix2(&mut a, true);
+    let scale = m as Float;
+    for (x, (a, w)) in buffer.iter_mut().zip(a.iter().zip(&chirp)) {
+        *x = (a * w) / scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn dft(input: &[Complex<Float>]) -> Vec<Complex<Float>> {
+        let n = input.len();
+        (0..n)
+            .map(|k| {
+                input
+                    .iter()
+                    .enumerate()
+                    .fold(Complex::<Float>::default(), |s, (j, x)| {
+                        s + x * twiddle((j * k * 2) as Float, n as Float, false)
+                    })
+            })
+            .collect()
+    }
+
+    fn samples(n: usize) -> Vec<Complex<Float>> {
+        (0..n)
+            .map(|i| {
+                let t = i as Float;
+                Complex::new((t * 0.7).sin() * 3.1 + t * 0.2, (t * 1.9).cos() - 0.4)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn power_of_two() {
+        let x = samples(16);
+        let mut y = x.clone();
+        fft(&mut y);
+        assert_abs_diff_eq!(y[..], dft(&x)[..], epsilon = Float::EPSILON * 1e5);
+    }
+
+    #[test]
+    fn arbitrary_length() {
+        for n in [3, 6, 7, 12, 15] {
+            let x = samples(n);
+            let mut y = x.clone();
+            fft(&mut y);
+            assert_abs_diff_eq!(y[..], dft(&x)[..], epsilon = Float::EPSILON * 1e5);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        for n in [1, 8, 10] {
+            let x = samples(n);
+            let mut y = x.clone();
+            fft(&mut y);
+            ifft(&mut y);
+            assert_abs_diff_eq!(y[..], x[..], epsilon = Float::EPSILON * 1e5);
+        }
+    }
+
+    #[test]
+    fn dominant_frequency() {
+        // 2.5 Hz sine sampled at 40 Hz.
+        let dt = 0.025;
+        let mut x: Vec<Complex<Float>> = (0..64)
+            .map(|i| Complex::from((i as Float * dt * 5.0 * (180.0 as Float).to_radians()).sin()))
+            .collect();
+        fft(&mut x);
+        let k = (0..32)
+            .max_by(|&a, &b| x[a].abs().total_cmp(&x[b].abs()))
+            .unwrap();
+        assert_eq!(k, 4);
+        assert!((frequency(k, 64, dt) - 2.5).abs() < Float::EPSILON * 1e4);
+    }
+}
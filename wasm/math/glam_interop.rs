@@ -0,0 +1,122 @@
+//! Conversions to and from the single precision types of glam and their double precision
+//! counterparts, between the same scalar types. Narrow with glam, as in
+//! `DVec3::from(v).as_vec3()`.
+
+use super::{
+    dual_quaternion::DualQuaternion, quaternion::Quaternion, transform3::Transform3, vector::Vector,
+};
+use ::glam::{DMat4, DQuat, DVec3, Mat4, Quat, Vec3};
+
+macro_rules! impl_glam {
+    ($t:ty, $vec3:ty, $quat:ty, $mat4:ty) => {
+        impl From<Vector<$t>> for $vec3 {
+            fn from(v: Vector<$t>) -> Self {
+                <$vec3>::new(v.x, v.y, v.z)
+            }
+        }
+
+        impl From<$vec3> for Vector<$t> {
+            fn from(v: $vec3) -> Self {
+                Vector::new(v.x, v.y, v.z)
+            }
+        }
+
+        impl From<Quaternion<$t>> for $quat {
+            fn from(q: Quaternion<$t>) -> Self {
+                <$quat>::from_xyzw(q.v.x, q.v.y, q.v.z, q.w)
+            }
+        }
+
+        impl From<$quat> for Quaternion<$t> {
+            fn from(q: $quat) -> Self {
+                Quaternion::new(Vector::new(q.x, q.y, q.z), q.w)
+            }
+        }
+
+        /// Transposes the rows into the columns of glam.
+        impl From<Transform3<$t>> for $mat4 {
+            fn from(t: Transform3<$t>) -> Self {
+                <$mat4>::from_cols_array_2d(&t.m).transpose()
+            }
+        }
+
+        impl From<$mat4> for Transform3<$t> {
+            fn from(m: $mat4) -> Self {
+                Transform3::new(m.transpose().to_cols_array_2d())
+            }
+        }
+
+        /// The rotation and the translation following it, of a unit dual quaternion.
+        impl From<DualQuaternion<$t>> for ($quat, $vec3) {
+            fn from(d: DualQuaternion<$t>) -> Self {
+                let t = d.translation();
+                (d.p.into(), t.into())
+            }
+        }
+
+        impl From<($quat, $vec3)> for DualQuaternion<$t> {
+            fn from((r, t): ($quat, $vec3)) -> Self {
+                let r = Quaternion::from(r);
+                let q = &(&Quaternion::from_translation(&t.into()) * &r) / 2.0;
+                DualQuaternion::new(r, q)
+            }
+        }
+    };
+}
+
+impl_glam!(f32, Vec3, Quat, Mat4);
+impl_glam!(f64, DVec3, DQuat, DMat4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector() {
+        let v = Vector::new(1.0, 2.0, 3.0);
+        let g = DVec3::from(v.clone());
+        assert_eq!(g, DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector::from(g), v);
+        assert_eq!(g.as_vec3(), Vec3::from(Vector::new(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn quaternion() {
+        let q = Quaternion::new(Vector::new(1.0, 2.0, 3.0), 4.0);
+        let g = DQuat::from(q.clone());
+        assert_eq!(g.to_array(), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(Quaternion::from(g), q);
+        // Products agree, the Hamilton product in both.
+        let p = Quaternion::new(Vector::new(-1.0, 0.5, 2.0), 1.0);
+        assert_eq!(Quaternion::from(g * DQuat::from(p.clone())), &q * &p);
+        let (sin, cos) = 0.25f32.sin_cos();
+        let q = Quaternion::<f32>::from(Quat::from_rotation_z(0.5));
+        assert!((q.w - cos).abs() < 1e-6 && (q.v.z - sin).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transform() {
+        let t = Transform3::<f32>::from_translation(Vector::new(1.0, 2.0, 3.0));
+        let m = Mat4::from(t);
+        assert_eq!(m, Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)));
+        assert_eq!(Transform3::<f32>::from(m), t);
+        let r = Quaternion::new(Vector::new(0.0, 0.6, 0.0), 0.8);
+        let rotation = Transform3::<f64>::from(&r);
+        let p = Vector::new(1.0, -2.0, 0.5);
+        let g = DMat4::from(rotation).transform_point3(p.clone().into());
+        assert!(g.distance(rotation.apply(&p).into()) < 1e-12);
+    }
+
+    #[test]
+    fn dual_quaternion() {
+        let r = DQuat::from_rotation_y(0.5);
+        let t = DVec3::new(1.0, -2.0, 3.0);
+        let d = DualQuaternion::from((r, t));
+        let p = DVec3::new(0.5, 0.25, -1.0);
+        let expected = r * p + t;
+        let actual = Transform3::<f64>::from(&d).apply(&p.into());
+        assert!(DVec3::from(actual).distance(expected) < 1e-12);
+        let (r2, t2): (DQuat, DVec3) = d.into();
+        assert!(r2.abs_diff_eq(r, 1e-12) && t2.abs_diff_eq(t, 1e-12));
+    }
+}
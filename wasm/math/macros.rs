@@ -0,0 +1,155 @@
+/// Implements `scalar * value` for the primitive scalar types by delegating to `value * scalar`.
+macro_rules! impl_scalar_mul {
+    ($type:ident) => {
+        impl_scalar_mul!($type; i8, i16, i32, i64, i128, isize, f32, f64);
+    };
+    ($type:ident; $($scalar:ty),+) => {
+        $(
+            impl Mul<&$type<$scalar>> for &$scalar {
+                type Output = $type<$scalar>;
+
+                fn mul(self, rhs: &$type<$scalar>) -> Self::Output {
+                    rhs * self
+                }
+            }
+
+            impl Mul<$type<$scalar>> for $scalar {
+                type Output = $type<$scalar>;
+
+                fn mul(self, rhs: $type<$scalar>) -> Self::Output {
+                    &rhs * &self
+                }
+            }
+        )+
+    };
+}
+
+/// Implements the owned and mixed owned/reference variants of a binary operator, and optionally of
+/// its assignment operator, by delegating to the reference implementations.
+macro_rules! impl_owned_binop {
+    ($type:ident, $rhs:ty, $trait:ident::$method:ident) => {
+        impl<T> $trait<$rhs> for $type<T>
+        where
+            for<'a> &'a $type<T>: $trait<&'a $rhs, Output = $type<T>>,
+        {
+            type Output = $type<T>;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $trait::$method(&self, &rhs)
+            }
+        }
+
+        impl<T> $trait<&$rhs> for $type<T>
+        where
+            for<'a> &'a $type<T>: $trait<&'a $rhs, Output = $type<T>>,
+        {
+            type Output = $type<T>;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $trait::$method(&self, rhs)
+            }
+        }
+
+        impl<T> $trait<$rhs> for &$type<T>
+        where
+            for<'a> &'a $type<T>: $trait<&'a $rhs, Output = $type<T>>,
+        {
+            type Output = $type<T>;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $trait::$method(self, &rhs)
+            }
+        }
+    };
+    ($type:ident, $rhs:ty, $trait:ident::$method:ident, $assign:ident::$assign_method:ident) => {
+        impl_owned_binop!($type, $rhs, $trait::$method);
+
+        impl<T> $assign<$rhs> for $type<T>
+        where
+            for<'a> $type<T>: $assign<&'a $rhs>,
+        {
+            fn $assign_method(&mut self, rhs: $rhs) {
+                self.$assign_method(&rhs);
+            }
+        }
+    };
+}
+
+/// Implements `-value` for owned values by delegating to `-&value`.
+macro_rules! impl_owned_neg {
+    ($type:ident) => {
+        impl<T> Neg for $type<T>
+        where
+            for<'a> &'a $type<T>: Neg<Output = $type<T>>,
+        {
+            type Output = $type<T>;
+
+            fn neg(self) -> Self::Output {
+                -&self
+            }
+        }
+    };
+}
+
+/// Implements `Sum` over owned values and references, starting from the default (zero) value.
+macro_rules! impl_sum {
+    ($type:ident) => {
+        impl<T> Sum for $type<T>
+        where
+            T: Default,
+            for<'a> $type<T>: AddAssign<&'a $type<T>>,
+        {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::default(), |mut s, v| {
+                    s += &v;
+                    s
+                })
+            }
+        }
+
+        impl<'b, T> Sum<&'b $type<T>> for $type<T>
+        where
+            T: Default,
+            for<'a> $type<T>: AddAssign<&'a $type<T>>,
+        {
+            fn sum<I: Iterator<Item = &'b Self>>(iter: I) -> Self {
+                iter.fold(Self::default(), |mut s, v| {
+                    s += v;
+                    s
+                })
+            }
+        }
+    };
+}
+
+/// Implements `Product` over owned values and references, multiplying from the left starting
+/// from the identity.
+macro_rules! impl_product {
+    ($type:ident) => {
+        impl<T> Product for $type<T>
+        where
+            T: Default + Constants,
+            for<'a> $type<T>: MulAssign<&'a $type<T>>,
+        {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::from(T::one()), |mut p, v| {
+                    p *= &v;
+                    p
+                })
+            }
+        }
+
+        impl<'b, T> Product<&'b $type<T>> for $type<T>
+        where
+            T: Default + Constants,
+            for<'a> $type<T>: MulAssign<&'a $type<T>>,
+        {
+            fn product<I: Iterator<Item = &'b Self>>(iter: I) -> Self {
+                iter.fold(Self::from(T::one()), |mut p, v| {
+                    p *= v;
+                    p
+                })
+            }
+        }
+    };
+}
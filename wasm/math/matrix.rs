@@ -0,0 +1,399 @@
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub};
+
+use super::traits::Constants;
+use crate::Float;
+
+/// Dense matrix stored in row-major order.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Fields<T>")
+)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+/// Deserialized fields of a [`Matrix`], before the number of elements is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Fields<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<Fields<T>> for Matrix<T> {
+    type Error = &'static str;
+
+    fn try_from(Fields { rows, cols, data }: Fields<T>) -> Result<Self, Self::Error> {
+        if rows.checked_mul(cols) != Some(data.len()) {
+            return Err("matrix size mismatch");
+        }
+        Ok(Self { rows, cols, data })
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Constructs a `rows` by `cols` matrix from elements in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len()` is not `rows * cols`.
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "matrix size mismatch");
+        Self { rows, cols, data }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..rows * cols).map(|k| f(k / cols, k % cols)).collect();
+        Self { rows, cols, data }
+    }
+
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub const fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Elements in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Default,
+{
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::from_fn(rows, cols, |_, _| T::default())
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Default + Constants,
+{
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { T::one() } else { T::default() })
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Default,
+    for<'a> &'a T: Mul<Output = T>,
+    for<'a> T: AddAssign<&'a T>,
+{
+    /// Computes the matrix-vector product `A x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len()` is not the number of columns.
+    pub fn mul_vec(&self, x: &[T]) -> Vec<T> {
+        assert_eq!(x.len(), self.cols, "matrix size mismatch");
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(x)
+                    .fold(T::default(), |mut s, (a, x)| {
+                        s += &(a * x);
+                        s
+                    })
+            })
+            .collect()
+    }
+}
+
+impl Matrix<Float> {
+    /// Computes `A x` as [`Self::mul_vec`], by the kernel of [`simd`](super::simd).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len()` is not the number of columns.
+    pub fn mul_vec_simd(&self, x: &[Float]) -> Vec<Float> {
+        assert_eq!(x.len(), self.cols, "matrix size mismatch");
+        let mut y = vec![0.0; self.rows];
+        super::simd::mat_vec(&self.data, x, &mut y);
+        y
+    }
+
+    /// Maximum absolute column sum.
+    pub fn norm1(&self) -> Float {
+        (0..self.cols)
+            .map(|j| (0..self.rows).map(|i| self[(i, j)].abs()).sum::<Float>())
+            .fold(0.0, Float::max)
+    }
+
+    /// Solves `A X = B` by LU decomposition with partial pivoting.
+    ///
+    /// Returns `None` if `A` is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `A` is not square or the number of rows of `B` differs.
+    pub fn solve(&self, b: &Self) -> Option<Self> {
+        assert!(self.is_square(), "matrix is not square");
+        assert_eq!(self.rows, b.rows, "matrix size mismatch");
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut x = b.clone();
+        for k in 0..n {
+            let p = (k..n).max_by(|&i, &j| a[(i, k)].abs().total_cmp(&a[(j, k)].abs()))?;
+            if a[(p, k)] == 0.0 || !a[(p, k)].is_finite() {
+                return None;
+            }
+            a.swap_rows(k, p);
+            x.swap_rows(k, p);
+            for i in k + 1..n {
+                let l = a[(i, k)] / a[(k, k)];
+                for j in k..n {
+                    a[(i, j)] -= l * a[(k, j)];
+                }
+                for j in 0..x.cols {
+                    x[(i, j)] -= l * x[(k, j)];
+                }
+            }
+        }
+        for k in (0..n).rev() {
+            for j in 0..x.cols {
+                let s = (k + 1..n).map(|i| a[(k, i)] * x[(i, j)]).sum::<Float>();
+                x[(k, j)] = (x[(k, j)] - s) / a[(k, k)];
+            }
+        }
+        Some(x)
+    }
+
+    /// Returns `None` if the matrix is singular.
+    pub fn inv(&self) -> Option<Self> {
+        self.solve(&Self::identity(self.rows))
+    }
+
+    fn swap_rows(&mut self, i: usize, j: usize) {
+        if i != j {
+            for k in 0..self.cols {
+                self.data.swap(i * self.cols + k, j * self.cols + k);
+            }
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(j < self.cols, "column index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(j < self.cols, "column index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T> Add for &Matrix<T>
+where
+    for<'a> &'a T: Add<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn add(self, other: Self) -> Self::Output {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols));
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+impl<T> Sub for &Matrix<T>
+where
+    for<'a> &'a T: Sub<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols));
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| a - b)
+                .collect(),
+        }
+    }
+}
+
+impl<T> Neg for &Matrix<T>
+where
+    for<'a> &'a T: Neg<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Self::Output {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|a| -a).collect(),
+        }
+    }
+}
+
+impl<T> Mul for &Matrix<T>
+where
+    T: Default,
+    for<'a> &'a T: Mul<Output = T>,
+    for<'a> T: AddAssign<&'a T>,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        assert_eq!(self.cols, other.rows, "matrix size mismatch");
+        let mut m = Matrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for (k, a) in self.row(i).iter().enumerate() {
+                for (j, b) in other.row(k).iter().enumerate() {
+                    m[(i, j)] += &(a * b);
+                }
+            }
+        }
+        m
+    }
+}
+
+impl<T> Mul<&T> for &Matrix<T>
+where
+    for<'a> &'a T: Mul<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, s: &T) -> Self::Output {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|a| a * s).collect(),
+        }
+    }
+}
+
+impl_owned_binop!(Matrix, Matrix<T>, Add::add);
+impl_owned_binop!(Matrix, Matrix<T>, Sub::sub);
+impl_owned_binop!(Matrix, Matrix<T>, Mul::mul);
+impl_owned_binop!(Matrix, T, Mul::mul);
+impl_owned_neg!(Matrix);
+
+#[cfg(test)]
+#[allow(clippy::op_ref)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn construct() {
+        let a = Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(a.rows(), 2);
+        assert_eq!(a.cols(), 3);
+        assert_eq!(a[(1, 0)], 4);
+        assert_eq!(a.row(0), [1, 2, 3]);
+        assert_eq!(Matrix::from_fn(2, 3, |i, j| (i * 3 + j + 1) as i32), a);
+        assert_eq!(a.transpose(), Matrix::new(3, 2, vec![1, 4, 2, 5, 3, 6]));
+        assert_eq!(
+            Matrix::<i32>::identity(2),
+            Matrix::new(2, 2, vec![1, 0, 0, 1])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn size_mismatch() {
+        Matrix::new(2, 2, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        let s = serde_json::to_string(&a).unwrap();
+        assert_eq!(s, r#"{"rows":2,"cols":3,"data":[1,2,3,4,5,6]}"#);
+        assert_eq!(serde_json::from_str::<Matrix<i32>>(&s).unwrap(), a);
+        let short = serde_json::from_str::<Matrix<i32>>(r#"{"rows":2,"cols":3,"data":[1,2]}"#);
+        assert!(short.unwrap_err().to_string().contains("size mismatch"));
+        let overflow = format!(r#"{{"rows":{},"cols":2,"data":[]}}"#, usize::MAX);
+        assert!(serde_json::from_str::<Matrix<i32>>(&overflow).is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Matrix::new(2, 2, vec![1, 2, 3, 4]);
+        let b = Matrix::new(2, 2, vec![0, -1, 5, 2]);
+        assert_eq!(&a + &b, Matrix::new(2, 2, vec![1, 1, 8, 6]));
+        assert_eq!(&a - &b, Matrix::new(2, 2, vec![1, 3, -2, 2]));
+        assert_eq!(&a * &b, Matrix::new(2, 2, vec![10, 3, 20, 5]));
+        assert_eq!(&a * &3, Matrix::new(2, 2, vec![3, 6, 9, 12]));
+        assert_eq!(-&a, Matrix::new(2, 2, vec![-1, -2, -3, -4]));
+        assert_eq!(a.mul_vec(&[1, -1]), [-1, -1]);
+        let c = Matrix::new(2, 3, vec![1, 0, 2, 0, 1, 1]);
+        assert_eq!(&a * &c, Matrix::new(2, 3, vec![1, 2, 4, 3, 4, 10]));
+        assert_eq!(a.clone() * b.clone() - a.clone(), &(&a * &b) - &a);
+    }
+
+    #[test]
+    fn mul_vec_simd() {
+        let a = Matrix::new(2, 3, vec![1.0, 0.5, -2.0, 3.0, 0.0, 4.0]);
+        let x = [2.0, -1.0, 0.25];
+        assert_eq!(a.mul_vec_simd(&x), a.mul_vec(&x));
+        assert_eq!(Matrix::<Float>::zeros(2, 0).mul_vec_simd(&[]), [0.0; 2]);
+    }
+
+    #[test]
+    fn norm1() {
+        let a = Matrix::new(2, 2, vec![1.0, -7.0, -2.0, 3.0]);
+        assert_eq!(a.norm1(), 10.0);
+    }
+
+    #[test]
+    fn solve() {
+        let a = Matrix::new(3, 3, vec![0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let x = Matrix::new(3, 2, vec![1.0, -1.0, 2.0, 0.5, -3.0, 1.0]);
+        assert!((&a.solve(&(&a * &x)).unwrap() - &x).norm1() < 1e-6);
+        let i = a.inv().unwrap();
+        assert!((&(&a * &i) - &Matrix::identity(3)).norm1() < 1e-6);
+        let singular = Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(singular.solve(&Matrix::identity(2)), None);
+    }
+}
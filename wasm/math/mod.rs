@@ -1,5 +1,28 @@
+#[macro_use]
+mod macros;
+
+pub mod affine2;
+#[cfg(any(test, feature = "approx"))]
+mod approx_eq;
+pub mod bfgs;
 pub mod complex;
+pub mod dual;
 pub mod dual_quaternion;
+pub mod expm;
+pub mod fft;
+#[cfg(feature = "glam")]
+mod glam_interop;
+pub mod matrix;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop;
 pub mod quaternion;
+pub mod random;
+pub mod simd;
+pub mod sparse;
+pub mod spline;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
+pub mod sum;
 pub mod traits;
+pub mod transform3;
 pub mod vector;
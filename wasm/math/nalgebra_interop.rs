@@ -0,0 +1,121 @@
+use super::{complex::Complex, matrix::Matrix, quaternion::Quaternion, vector::Vector};
+use ::nalgebra::{DMatrix, Scalar, UnitQuaternion, Vector3};
+
+impl<T: Scalar> From<Vector<T>> for Vector3<T> {
+    fn from(v: Vector<T>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Scalar> From<Vector3<T>> for Vector<T> {
+    fn from(v: Vector3<T>) -> Self {
+        let [[x, y, z]] = v.data.0;
+        Vector::new(x, y, z)
+    }
+}
+
+/// The vector part of a quaternion is `i`, `j` and `k` of nalgebra, which stores them before `w`.
+impl<T: Scalar> From<Quaternion<T>> for ::nalgebra::Quaternion<T> {
+    fn from(q: Quaternion<T>) -> Self {
+        ::nalgebra::Quaternion::new(q.w, q.v.x, q.v.y, q.v.z)
+    }
+}
+
+impl<T: Scalar> From<::nalgebra::Quaternion<T>> for Quaternion<T> {
+    fn from(q: ::nalgebra::Quaternion<T>) -> Self {
+        let [[x, y, z, w]] = q.coords.data.0;
+        Quaternion::new(Vector::new(x, y, z), w)
+    }
+}
+
+impl<T: Scalar> From<UnitQuaternion<T>> for Quaternion<T> {
+    fn from(q: UnitQuaternion<T>) -> Self {
+        q.into_inner().into()
+    }
+}
+
+impl<T> From<Complex<T>> for ::nalgebra::Complex<T> {
+    fn from(z: Complex<T>) -> Self {
+        ::nalgebra::Complex::new(z.re, z.im)
+    }
+}
+
+impl<T> From<::nalgebra::Complex<T>> for Complex<T> {
+    fn from(z: ::nalgebra::Complex<T>) -> Self {
+        Complex::new(z.re, z.im)
+    }
+}
+
+/// Copies the elements from row-major order to the column-major order of nalgebra.
+impl<T: Scalar> From<&Matrix<T>> for DMatrix<T> {
+    fn from(a: &Matrix<T>) -> Self {
+        DMatrix::from_row_slice(a.rows(), a.cols(), a.as_slice())
+    }
+}
+
+impl<T: Scalar> From<Matrix<T>> for DMatrix<T> {
+    fn from(a: Matrix<T>) -> Self {
+        (&a).into()
+    }
+}
+
+impl<T: Scalar> From<&DMatrix<T>> for Matrix<T> {
+    fn from(a: &DMatrix<T>) -> Self {
+        Matrix::from_fn(a.nrows(), a.ncols(), |i, j| a[(i, j)].clone())
+    }
+}
+
+impl<T: Scalar> From<DMatrix<T>> for Matrix<T> {
+    fn from(a: DMatrix<T>) -> Self {
+        (&a).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Float;
+
+    #[test]
+    fn vector() {
+        let v = Vector::new(1.0, 2.0, 3.0);
+        let n: Vector3<Float> = v.clone().into();
+        assert_eq!(n, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector::from(n), v);
+    }
+
+    #[test]
+    fn quaternion() {
+        let q = Quaternion::new(Vector::new(1.0, 2.0, 3.0), 4.0);
+        let n: ::nalgebra::Quaternion<Float> = q.clone().into();
+        assert_eq!((n.w, n.i, n.j, n.k), (4.0, 1.0, 2.0, 3.0));
+        assert_eq!(Quaternion::from(n), q);
+        // Products agree, the Hamilton product in both.
+        let p = Quaternion::new(Vector::new(-1.0, 0.5, 2.0), 1.0);
+        let np: ::nalgebra::Quaternion<Float> = p.clone().into();
+        assert_eq!(Quaternion::from(n * np), &q * &p);
+        let unit = UnitQuaternion::<Float>::from_euler_angles(0.0, 0.0, 0.5);
+        let (sin, cos) = (0.25 as Float).sin_cos();
+        let q: Quaternion<Float> = unit.into();
+        assert!((q.w - cos).abs() < 1e-6 && (q.v.z - sin).abs() < 1e-6);
+    }
+
+    #[test]
+    fn complex() {
+        let z = Complex::new(1.0, -2.0);
+        let n: ::nalgebra::Complex<Float> = z.into();
+        assert_eq!((n.re, n.im), (1.0, -2.0));
+        assert_eq!(Complex::from(n), z);
+    }
+
+    #[test]
+    fn matrix() {
+        let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let n = DMatrix::from(&a);
+        assert_eq!(n[(0, 2)], 3.0);
+        assert_eq!(n[(1, 0)], 4.0);
+        let product = Matrix::from(&n * n.transpose());
+        assert_eq!(product, &a * &a.transpose());
+        assert_eq!(Matrix::from(n), a);
+    }
+}
@@ -1,10 +1,15 @@
 use super::{
-    traits::{Cos, Hypot, Sin},
+    traits::{Constants, Cos, Hypot, IsNormal, Sin},
     vector::Vector,
 };
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{
+    fmt::{self, Display, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quaternion<T> {
     pub v: Vector<T>,
     pub w: T,
@@ -28,23 +33,28 @@ where
     }
 }
 
-impl<T> Quaternion<T>
+/// Scalar types [`Quaternion::from_rotation`] is available for.
+///
+/// Implemented for every scalar with the required operations. Bounding the constructor by this
+/// trait rather than by the operators lets the compiler infer the scalar type from the argument.
+pub trait RotationScalar: Sized {
+    fn rotation(axis: &Vector<Self>) -> Quaternion<Self>;
+}
+
+impl<T> RotationScalar for T
 where
-    T: From<u8> + Sin + Cos + Hypot + PartialOrd,
+    T: Constants + Sin + Cos + Hypot + PartialEq,
     for<'a> &'a T: Add<Output = T>,
     for<'a> &'a T: Div<Output = T>,
     for<'a> &'a T: Mul<Output = T>,
 {
-    /// This function needs explicit type specification to be called because of a compiler bug.
-    ///
-    /// The `axis` vector's direction is parallel to the rotation axis, and its norm represents the rotation angle.
-    pub fn from_rotation(axis: &Vector<T>) -> Self {
+    fn rotation(axis: &Vector<T>) -> Quaternion<T> {
         match axis.abs() {
-            theta if theta > 0.into() => Self {
-                v: &(axis / &theta) * &(&theta / &2.into()).sin(),
-                w: (&theta / &2.into()).cos(),
+            theta if theta != T::zero() => Quaternion {
+                v: &(axis / &theta) * &(&theta / &T::two()).sin(),
+                w: (&theta / &T::two()).cos(),
             },
-            theta => Self {
+            theta => Quaternion {
                 v: axis * &theta,
                 w: theta.cos(),
             },
@@ -54,12 +64,22 @@ where
 
 impl<T> Quaternion<T>
 where
-    T: From<u8> + Clone,
+    T: RotationScalar,
+{
+    /// The `axis` vector's direction is parallel to the rotation axis, and its norm represents the rotation angle.
+    pub fn from_rotation(axis: &Vector<T>) -> Self {
+        T::rotation(axis)
+    }
+}
+
+impl<T> Quaternion<T>
+where
+    T: Constants + Clone,
 {
     pub fn from_translation(t: &Vector<T>) -> Self {
         Self {
             v: t.clone(),
-            w: 0.into(),
+            w: T::zero(),
         }
     }
 }
@@ -114,6 +134,50 @@ where
     }
 }
 
+impl<T> Quaternion<T>
+where
+    for<'a> &'a T: Div<Output = T>,
+    T: Hypot + IsNormal,
+{
+    /// Returns `None` instead of NaNs if the norm is zero, subnormal or not finite.
+    pub fn checked_normalized(&self) -> Option<Self> {
+        let abs = self.abs();
+        abs.is_normal().then(|| self / &abs)
+    }
+}
+
+impl<T> Quaternion<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone + IsNormal,
+{
+    /// Returns `None` if the squared norm is zero, subnormal or not finite.
+    pub fn checked_inv(&self) -> Option<Self> {
+        let d = self.dot(self);
+        d.is_normal().then(|| &self.conj() / &d)
+    }
+
+    /// Returns `None` if `other` is not invertible, see [`Self::checked_inv`].
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.checked_inv().map(|inv| self * &inv)
+    }
+}
+
+/// Formats as `((x, y, z), w)`, applying the formatter options to each component.
+impl<T> Display for Quaternion<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("(")?;
+        self.v.fmt(f)?;
+        f.write_str(", ")?;
+        self.w.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
 impl<T> Add for &Quaternion<T>
 where
     for<'a> &'a T: Add<Output = T>,
@@ -239,6 +303,32 @@ where
     }
 }
 
+impl<T> Div for &Quaternion<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone,
+{
+    type Output = Quaternion<T>;
+
+    /// Multiplies by the inverse of `other` from the right, so that `&(a / b) * b == a`.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * &other.inv()
+    }
+}
+
+impl<T> DivAssign<&Quaternion<T>> for Quaternion<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: Clone,
+{
+    fn div_assign(&mut self, other: &Quaternion<T>) {
+        *self = &*self / other;
+    }
+}
+
 impl<T> DivAssign<&T> for Quaternion<T>
 where
     for<'a> T: DivAssign<&'a T>,
@@ -249,9 +339,22 @@ where
     }
 }
 
+impl_scalar_mul!(Quaternion);
+impl_owned_binop!(Quaternion, Quaternion<T>, Add::add, AddAssign::add_assign);
+impl_owned_binop!(Quaternion, Quaternion<T>, Sub::sub, SubAssign::sub_assign);
+impl_owned_binop!(Quaternion, Quaternion<T>, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Quaternion, T, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Quaternion, Quaternion<T>, Div::div, DivAssign::div_assign);
+impl_owned_binop!(Quaternion, T, Div::div, DivAssign::div_assign);
+impl_owned_neg!(Quaternion);
+impl_sum!(Quaternion);
+impl_product!(Quaternion);
+
 #[cfg(test)]
+#[allow(clippy::op_ref)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
 
     #[test]
     fn from() {
@@ -414,8 +517,8 @@ mod tests {
     fn abs() {
         let a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
         let b = Quaternion::new(Vector::new(0.2, -0.4, 31.1), 0.11);
-        assert_eq!(a.abs(), (1.3 as f64).hypot(0.1).hypot(2.1).hypot(0.8));
-        assert_eq!(b.abs(), (0.2 as f32).hypot(0.4).hypot(31.1).hypot(0.11));
+        assert_eq!(a.abs(), 1.3_f64.hypot(0.1).hypot(2.1).hypot(0.8));
+        assert_eq!(b.abs(), 0.2_f32.hypot(0.4).hypot(31.1).hypot(0.11));
     }
 
     #[test]
@@ -426,22 +529,22 @@ mod tests {
             a.normalized(),
             Quaternion::new(
                 Vector::new(
-                    1.3 / (1.3 as f64).hypot(0.1).hypot(2.1).hypot(0.8),
-                    0.1 / (1.3 as f64).hypot(0.1).hypot(2.1).hypot(0.8),
-                    -2.1 / (1.3 as f64).hypot(0.1).hypot(2.1).hypot(0.8)
+                    1.3 / 1.3_f64.hypot(0.1).hypot(2.1).hypot(0.8),
+                    0.1 / 1.3_f64.hypot(0.1).hypot(2.1).hypot(0.8),
+                    -2.1 / 1.3_f64.hypot(0.1).hypot(2.1).hypot(0.8)
                 ),
-                -0.8 / (1.3 as f64).hypot(0.1).hypot(2.1).hypot(0.8)
+                -0.8 / 1.3_f64.hypot(0.1).hypot(2.1).hypot(0.8)
             ),
         );
         assert_eq!(
             b.normalized(),
             Quaternion::new(
                 Vector::new(
-                    0.2 / (0.2 as f32).hypot(0.4).hypot(31.1).hypot(0.11),
-                    -0.4 / (0.2 as f32).hypot(0.4).hypot(31.1).hypot(0.11),
-                    31.1 / (0.2 as f32).hypot(0.4).hypot(31.1).hypot(0.11)
+                    0.2 / 0.2_f32.hypot(0.4).hypot(31.1).hypot(0.11),
+                    -0.4 / 0.2_f32.hypot(0.4).hypot(31.1).hypot(0.11),
+                    31.1 / 0.2_f32.hypot(0.4).hypot(31.1).hypot(0.11)
                 ),
-                0.11 / (0.2 as f32).hypot(0.4).hypot(31.1).hypot(0.11)
+                0.11 / 0.2_f32.hypot(0.4).hypot(31.1).hypot(0.11)
             ),
         );
     }
@@ -486,17 +589,17 @@ mod tests {
     #[test]
     fn from_rotation() {
         assert_eq!(
-            Quaternion::<f64>::from_rotation(&Vector::new(0.8, 3.2, -1.4)),
+            Quaternion::from_rotation(&Vector::new(0.8, 3.2, -1.4)),
             Quaternion::new(
                 Vector::new(
-                    ((0.8 as f64).hypot(3.2).hypot(1.4) / 2.0).sin()
-                        * (0.8 / (0.8 as f64).hypot(3.2).hypot(1.4)),
-                    ((0.8 as f64).hypot(3.2).hypot(1.4) / 2.0).sin()
-                        * (3.2 / (0.8 as f64).hypot(3.2).hypot(1.4)),
-                    -((0.8 as f64).hypot(3.2).hypot(1.4) / 2.0).sin()
-                        * (1.4 / (0.8 as f64).hypot(3.2).hypot(1.4)),
+                    (0.8_f64.hypot(3.2).hypot(1.4) / 2.0).sin()
+                        * (0.8 / 0.8_f64.hypot(3.2).hypot(1.4)),
+                    (0.8_f64.hypot(3.2).hypot(1.4) / 2.0).sin()
+                        * (3.2 / 0.8_f64.hypot(3.2).hypot(1.4)),
+                    -(0.8_f64.hypot(3.2).hypot(1.4) / 2.0).sin()
+                        * (1.4 / 0.8_f64.hypot(3.2).hypot(1.4)),
                 ),
-                ((0.8 as f64).hypot(3.2).hypot(1.4) / 2.0).cos()
+                (0.8_f64.hypot(3.2).hypot(1.4) / 2.0).cos()
             )
         );
         assert_eq!(
@@ -528,4 +631,84 @@ mod tests {
             Quaternion::new(Vector::new(0.0, 0.0, 0.0), 0.0)
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        let s = serde_json::to_string(&a).unwrap();
+        assert_eq!(s, r#"{"v":{"x":1.3,"y":0.1,"z":-2.1},"w":-0.8}"#);
+        assert_eq!(serde_json::from_str::<Quaternion<f64>>(&s).unwrap(), a);
+    }
+
+    #[test]
+    fn display() {
+        let a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        assert_eq!(a.to_string(), "((1.3, 0.1, -2.1), -0.8)");
+        assert_eq!(format!("{:>5.2}", a), "(( 1.30,  0.10, -2.10), -0.80)");
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        assert_eq!(&2.3 * &a, &a * &2.3);
+    }
+
+    #[test]
+    fn div_quaternion() {
+        let a = &Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        let b = &Quaternion::new(Vector::new(0.2, -0.4, 31.1), 0.11);
+        assert_eq!(a / b, a * &b.inv());
+        assert_relative_eq!(&(a / b) * b, a.clone());
+        assert_relative_eq!(a / a, Quaternion::from(1.0));
+    }
+
+    #[test]
+    fn div_assign_quaternion() {
+        let mut a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        let b = &Quaternion::new(Vector::new(0.2, -0.4, 31.1), 0.11);
+        a /= b;
+        assert_eq!(a, &Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8) / b);
+    }
+
+    #[test]
+    fn checked() {
+        let a = Quaternion::new(Vector::new(1.3, 0.1, -2.1), -0.8);
+        let b = Quaternion::new(Vector::new(0.2, -0.4, 31.1), 0.11);
+        let zero = Quaternion::new(Vector::new(0.0, 0.0, 0.0), 0.0);
+        assert_eq!(a.checked_normalized(), Some(a.normalized()));
+        assert_eq!(zero.checked_normalized(), None);
+        assert_eq!(a.checked_inv(), Some(a.inv()));
+        assert_eq!(zero.checked_inv(), None);
+        assert_eq!(a.checked_div(&b), Some(&a / &b));
+        assert_eq!(a.checked_div(&zero), None);
+    }
+
+    #[test]
+    fn owned_operators() {
+        let a = Quaternion::new(Vector::new(1.5, -2.0, 3.25), 0.5);
+        let b = Quaternion::new(Vector::new(-0.5, 4.0, 1.5), -2.0);
+        assert_eq!(a.clone() + b.clone(), &a + &b);
+        assert_eq!(a.clone() - &b, &a - &b);
+        assert_eq!(&a * b.clone(), &a * &b);
+        assert_eq!(a.clone() / b.clone(), &a / &b);
+        assert_eq!(2.0 * a.clone(), &a * &2.0);
+        assert_eq!(a.clone() / 4.0, &a / &4.0);
+        assert_eq!(-a.clone(), -&a);
+        let mut c = a.clone();
+        c *= b.clone();
+        c += a.clone();
+        assert_eq!(c, &(&a * &b) + &a);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let a = Quaternion::new(Vector::new(1.5, -2.0, 3.25), 0.5);
+        let b = Quaternion::new(Vector::new(-0.5, 4.0, 1.5), -2.0);
+        let c = Quaternion::new(Vector::new(2.0, 0.5, -1.0), 1.0);
+        let q = [a.clone(), b.clone(), c.clone()];
+        assert_eq!(q.iter().sum::<Quaternion<f64>>(), &(&a + &b) + &c);
+        assert_eq!(q.iter().product::<Quaternion<f64>>(), &(&a * &b) * &c);
+        assert_eq!(q.into_iter().product::<Quaternion<f64>>(), &(&a * &b) * &c);
+    }
 }
@@ -0,0 +1,230 @@
+//! Kernels of the products of [`Vector`], [`Quaternion`] and the dense and sparse matrices of
+//! [`Float`], using WebAssembly SIMD128 for `f64` when built with the `simd` feature for a `wasm32`
+//! target with `simd128` enabled (`RUSTFLAGS="-C target-feature=+simd128"`). Other builds use the
+//! scalar fallbacks, which give the same results as the generic operators.
+//!
+//! The generic operators stay scalar; the solver calls the kernels where its scalar is [`Float`],
+//! through [`Matrix::mul_vec_simd`](super::matrix::Matrix::mul_vec_simd) and the
+//! [`Scalar`](super::sparse::scalar::Scalar) of the sparse products.
+
+use super::{quaternion::Quaternion, vector::Vector};
+use crate::Float;
+
+#[cfg(all(
+    feature = "simd",
+    not(feature = "f32"),
+    target_arch = "wasm32",
+    target_feature = "simd128"
+))]
+mod wasm {
+    use super::{Quaternion, Vector};
+    use core::arch::wasm32::*;
+
+    fn sum(v: v128) -> f64 {
+        f64x2_extract_lane::<0>(v) + f64x2_extract_lane::<1>(v)
+    }
+
+    pub fn dot(a: &Vector<f64>, b: &Vector<f64>) -> f64 {
+        sum(f64x2_mul(f64x2(a.x, a.y), f64x2(b.x, b.y))) + a.z * b.z
+    }
+
+    pub fn quaternion_mul(a: &Quaternion<f64>, b: &Quaternion<f64>) -> Quaternion<f64> {
+        let (p, q) = (&a.v, &b.v);
+        // Lanes (x, y) and (z, w) of the Hamilton product.
+        let xy = f64x2_sub(
+            f64x2_add(
+                f64x2_add(
+                    f64x2_mul(f64x2_splat(a.w), f64x2(q.x, q.y)),
+                    f64x2_mul(f64x2(p.x, p.y), f64x2_splat(b.w)),
+                ),
+                f64x2_mul(f64x2(p.y, p.z), f64x2(q.z, q.x)),
+            ),
+            f64x2_mul(f64x2(p.z, p.x), f64x2(q.y, q.z)),
+        );
+        let zw = f64x2_sub(
+            f64x2_add(
+                f64x2_add(
+                    f64x2_mul(f64x2_splat(a.w), f64x2(q.z, b.w)),
+                    f64x2_mul(f64x2(p.z, -p.x), f64x2(b.w, q.x)),
+                ),
+                f64x2_mul(f64x2(p.x, -p.y), f64x2_splat(q.y)),
+            ),
+            f64x2_mul(f64x2(p.y, p.z), f64x2(q.x, q.z)),
+        );
+        Quaternion::new(
+            Vector::new(
+                f64x2_extract_lane::<0>(xy),
+                f64x2_extract_lane::<1>(xy),
+                f64x2_extract_lane::<0>(zw),
+            ),
+            f64x2_extract_lane::<1>(zw),
+        )
+    }
+
+    pub fn row_dot(row: &[f64], x: &[f64]) -> f64 {
+        let mut acc = f64x2_splat(0.0);
+        let (r, r_tail) = row.as_chunks::<2>();
+        let (v, v_tail) = x.as_chunks::<2>();
+        for (r, v) in r.iter().zip(v) {
+            acc = f64x2_add(acc, f64x2_mul(f64x2(r[0], r[1]), f64x2(v[0], v[1])));
+        }
+        sum(acc) + r_tail.iter().zip(v_tail).map(|(r, v)| r * v).sum::<f64>()
+    }
+
+    pub fn gather_dot(values: &[f64], columns: &[usize], x: &[f64]) -> f64 {
+        let mut acc = f64x2_splat(0.0);
+        let (v, v_tail) = values.as_chunks::<2>();
+        let (c, c_tail) = columns.as_chunks::<2>();
+        for (v, c) in v.iter().zip(c) {
+            acc = f64x2_add(acc, f64x2_mul(f64x2(v[0], v[1]), f64x2(x[c[0]], x[c[1]])));
+        }
+        sum(acc)
+            + v_tail
+                .iter()
+                .zip(c_tail)
+                .map(|(v, &c)| v * x[c])
+                .sum::<f64>()
+    }
+}
+
+#[cfg(not(all(
+    feature = "simd",
+    not(feature = "f32"),
+    target_arch = "wasm32",
+    target_feature = "simd128"
+)))]
+mod scalar {
+    use super::{Float, Quaternion, Vector};
+
+    pub fn dot(a: &Vector<Float>, b: &Vector<Float>) -> Float {
+        a.x * b.x + a.y * b.y + a.z * b.z
+    }
+
+    pub fn quaternion_mul(a: &Quaternion<Float>, b: &Quaternion<Float>) -> Quaternion<Float> {
+        let (p, q) = (&a.v, &b.v);
+        Quaternion::new(
+            Vector::new(
+                p.x * b.w + q.x * a.w + (p.y * q.z - p.z * q.y),
+                p.y * b.w + q.y * a.w + (p.z * q.x - p.x * q.z),
+                p.z * b.w + q.z * a.w + (p.x * q.y - p.y * q.x),
+            ),
+            a.w * b.w - dot(p, q),
+        )
+    }
+
+    pub fn row_dot(row: &[Float], x: &[Float]) -> Float {
+        row.iter().zip(x).fold(0.0, |s, (r, v)| s + r * v)
+    }
+
+    pub fn gather_dot(values: &[Float], columns: &[usize], x: &[Float]) -> Float {
+        values
+            .iter()
+            .zip(columns)
+            .fold(0.0, |s, (v, &c)| s + v * x[c])
+    }
+}
+
+#[cfg(not(all(
+    feature = "simd",
+    not(feature = "f32"),
+    target_arch = "wasm32",
+    target_feature = "simd128"
+)))]
+use scalar as imp;
+#[cfg(all(
+    feature = "simd",
+    not(feature = "f32"),
+    target_arch = "wasm32",
+    target_feature = "simd128"
+))]
+use wasm as imp;
+
+/// Dot product, as [`Vector::dot`].
+pub fn dot(a: &Vector<Float>, b: &Vector<Float>) -> Float {
+    imp::dot(a, b)
+}
+
+/// Hamilton product, as `a * b`.
+pub fn quaternion_mul(a: &Quaternion<Float>, b: &Quaternion<Float>) -> Quaternion<Float> {
+    imp::quaternion_mul(a, b)
+}
+
+/// Computes `y = A x` for a dense row-major matrix `a` with `x.len()` columns and `y.len()` rows.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not `x.len() * y.len()`.
+pub fn mat_vec(a: &[Float], x: &[Float], y: &mut [Float]) {
+    assert_eq!(a.len(), x.len() * y.len(), "matrix size mismatch");
+    if x.is_empty() {
+        y.fill(0.0);
+        return;
+    }
+    for (y, row) in y.iter_mut().zip(a.chunks_exact(x.len())) {
+        *y = imp::row_dot(row, x);
+    }
+}
+
+/// Sum of the `values` times the elements of `x` at the `columns`, a row of a sparse product.
+///
+/// # Panics
+///
+/// Panics if a column is out of the bounds of `x`.
+pub fn gather_dot(values: &[Float], columns: &[usize], x: &[Float]) -> Float {
+    imp::gather_dot(values, columns, x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn vector_dot() {
+        let a = Vector::new(1.3, -0.2, 4.1);
+        let b = Vector::new(-2.5, 0.7, 3.3);
+        assert_relative_eq!(dot(&a, &b), a.dot(&b));
+        assert_relative_eq!(dot(&a, &b), 1.3 * -2.5 + -0.2 * 0.7 + 4.1 * 3.3);
+    }
+
+    #[test]
+    fn quaternion_product() {
+        // Against the generic product of integers, exact in floating point.
+        let a = Quaternion::new(Vector::new(13, -2, 41), 6);
+        let b = Quaternion::new(Vector::new(-25, 7, 33), -19);
+        let real = |q: &Quaternion<i32>| {
+            let v = Vector::new(q.v.x as Float, q.v.y as Float, q.v.z as Float);
+            Quaternion::new(v, q.w as Float)
+        };
+        let expected = real(&(&a * &b));
+        assert_eq!(quaternion_mul(&real(&a), &real(&b)), expected);
+        assert_eq!(&real(&a) * &real(&b), expected);
+    }
+
+    #[test]
+    fn dense_mat_vec() {
+        let a = [1.0, 2.0, 3.0, -1.0, 0.5, 4.0, 2.0, 0.0, -3.0];
+        let mut y = [0.0; 3];
+        mat_vec(&a, &[2.0, -1.0, 0.5], &mut y);
+        assert_eq!(y, [1.5, -0.5, 2.5]);
+        let mut y = [0.0; 2];
+        mat_vec(&[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0], &mut y);
+        assert_eq!(y, [3.0, 7.0]);
+        let mut y = [1.0; 2];
+        mat_vec(&[], &[], &mut y);
+        assert_eq!(y, [0.0; 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mat_vec_size_mismatch() {
+        mat_vec(&[1.0, 2.0, 3.0], &[1.0, 1.0], &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn sparse_row() {
+        let x = [1.0, 2.0, -1.0, 0.5];
+        assert_eq!(gather_dot(&[2.0, 4.0, -2.0], &[0, 1, 3], &x), 9.0);
+        assert_eq!(gather_dot(&[], &[], &x), 0.0);
+    }
+}
@@ -0,0 +1,144 @@
+use super::{csr::CsrMatrix, scalar::Scalar};
+use std::collections::BTreeSet;
+
+/// Approximate minimum degree ordering of a structurally symmetric matrix, reducing the fill-in of
+/// its `L D Lᵀ` factor.
+///
+/// Elimination is simulated on the quotient graph, where each eliminated node becomes an element
+/// standing for the clique it creates and absorbs the elements it was adjacent to. The degree of a
+/// node is approximated by the number of its neighbouring nodes plus the sizes of its neighbouring
+/// elements, which bounds the true degree from above (Amestoy, Davis and Duff, 1996).
+///
+/// Returns the old indices in their new order, as taken by [`CsrMatrix::permute`].
+pub fn approximate_minimum_degree<T: Scalar>(a: &CsrMatrix<T>) -> Vec<usize> {
+    let n = a.rows();
+    let mut nodes: Vec<Vec<usize>> = (0..n)
+        .map(|i| {
+            a.row_indices(i)
+                .iter()
+                .copied()
+                .filter(|&j| j != i)
+                .collect()
+        })
+        .collect();
+    let mut elements: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut clique: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut eliminated = vec![false; n];
+    let mut absorbed = vec![false; n];
+    let mut mark = vec![usize::MAX; n];
+    let mut degree: Vec<usize> = nodes.iter().map(Vec::len).collect();
+    let mut queue: BTreeSet<(usize, usize)> = (0..n).map(|i| (degree[i], i)).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some((_, p)) = queue.pop_first() {
+        eliminated[p] = true;
+        order.push(p);
+        // The new element joins the neighbouring nodes and the nodes of absorbed elements.
+        mark[p] = p;
+        let mut members = Vec::new();
+        for e in std::mem::take(&mut elements[p]) {
+            absorbed[e] = true;
+            for i in std::mem::take(&mut clique[e]) {
+                if mark[i] != p {
+                    mark[i] = p;
+                    members.push(i);
+                }
+            }
+        }
+        for &i in &nodes[p] {
+            if !eliminated[i] && mark[i] != p {
+                mark[i] = p;
+                members.push(i);
+            }
+        }
+        for &i in &members {
+            queue.remove(&(degree[i], i));
+            // Edges inside the element are implied by it.
+            nodes[i].retain(|&j| mark[j] != p);
+            elements[i].retain(|&e| !absorbed[e]);
+            elements[i].push(p);
+        }
+        let remaining = n - order.len();
+        for &i in &members {
+            let d = nodes[i].len()
+                + elements[i]
+                    .iter()
+                    .map(|&e| if e == p { members.len() } else { clique[e].len() } - 1)
+                    .sum::<usize>();
+            degree[i] = d.min(remaining - 1);
+            queue.insert((degree[i], i));
+        }
+        nodes[p] = Vec::new();
+        clique[p] = members;
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{math::sparse::ldl::Symbolic, Float};
+
+    fn grid(n: usize) -> CsrMatrix {
+        let index = |i: usize, j: usize| i * n + j;
+        CsrMatrix::from_triplets(
+            n * n,
+            n * n,
+            (0..n).flat_map(|i| {
+                (0..n).flat_map(move |j| {
+                    let k = index(i, j);
+                    let mut t = vec![(k, k, 4.0)];
+                    if i + 1 < n {
+                        t.push((k, index(i + 1, j), -1.0));
+                        t.push((index(i + 1, j), k, -1.0));
+                    }
+                    if j + 1 < n {
+                        t.push((k, index(i, j + 1), -1.0));
+                        t.push((index(i, j + 1), k, -1.0));
+                    }
+                    t
+                })
+            }),
+        )
+    }
+
+    fn is_permutation(p: &[usize]) -> bool {
+        let mut sorted = p.to_vec();
+        sorted.sort();
+        sorted.iter().enumerate().all(|(k, &i)| k == i)
+    }
+
+    #[test]
+    fn arrowhead() {
+        let n = 8;
+        let a = CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(i, i, 4.0 + i as Float)];
+                if i > 0 {
+                    t.push((0, i, 1.0));
+                    t.push((i, 0, 1.0));
+                }
+                t
+            }),
+        );
+        let p = approximate_minimum_degree(&a);
+        assert!(is_permutation(&p));
+        assert_eq!(Symbolic::new(&a.permute(&p)).nnz(), n - 1);
+    }
+
+    #[test]
+    fn reduces_fill() {
+        let a = grid(12);
+        let p = approximate_minimum_degree(&a);
+        assert!(is_permutation(&p));
+        assert!(Symbolic::new(&a.permute(&p)).nnz() < Symbolic::new(&a).nnz());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(
+            approximate_minimum_degree(&CsrMatrix::<Float>::from_triplets(0, 0, [])).is_empty()
+        );
+    }
+}
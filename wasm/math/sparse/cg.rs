@@ -0,0 +1,303 @@
+use super::{
+    csr::CsrMatrix,
+    ic::{IcOptions, IncompleteCholesky},
+};
+use crate::{
+    cancel::{uncancelled, CancelToken, Cancelled},
+    math::sum::stable_sum,
+    Float,
+};
+
+/// Preconditioner of the conjugate gradient method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Preconditioner {
+    None,
+    /// Inverse of the diagonal.
+    Jacobi,
+    /// Incomplete Cholesky factorization.
+    IncompleteCholesky(IcOptions),
+}
+
+/// Controls of [`conjugate_gradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverOptions {
+    /// Convergence is reached when the residual norm falls below this fraction of `‖b‖`.
+    pub tolerance: Float,
+    pub max_iterations: usize,
+    pub preconditioner: Preconditioner,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: Float::EPSILON.sqrt(),
+            max_iterations: 10000,
+            preconditioner: Preconditioner::IncompleteCholesky(IcOptions::default()),
+        }
+    }
+}
+
+/// Converged solution of [`conjugate_gradient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgSolution {
+    pub x: Vec<Float>,
+    pub iterations: usize,
+    /// Relative residual norm `‖b - A x‖ / ‖b‖`.
+    pub residual: Float,
+}
+
+enum Apply {
+    None,
+    Jacobi(Vec<Float>),
+    IncompleteCholesky(IncompleteCholesky),
+}
+
+impl Apply {
+    fn new(a: &CsrMatrix, preconditioner: &Preconditioner) -> Option<Self> {
+        Some(match preconditioner {
+            Preconditioner::None => Self::None,
+            Preconditioner::Jacobi => Self::Jacobi(
+                a.diagonal()
+                    .iter()
+                    .map(|&d| if d == 0.0 { 1.0 } else { 1.0 / d })
+                    .collect(),
+            ),
+            Preconditioner::IncompleteCholesky(options) => {
+                Self::IncompleteCholesky(IncompleteCholesky::new(a, options)?)
+            }
+        })
+    }
+
+    fn apply(&self, r: &[Float]) -> Vec<Float> {
+        match self {
+            Self::None => r.to_vec(),
+            Self::Jacobi(d) => r.iter().zip(d).map(|(r, d)| r * d).collect(),
+            Self::IncompleteCholesky(f) => f.solve(r),
+        }
+    }
+}
+
+fn dot(a: &[Float], b: &[Float]) -> Float {
+    stable_sum(a.iter().zip(b).map(|(a, b)| a * b))
+}
+
+/// Solves a symmetric positive definite system `A x = b` by the preconditioned conjugate gradient
+/// method, starting from zero.
+///
+/// Returns `None` if the preconditioner cannot be built or the method does not converge within
+/// the iteration limit.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square or `b.len()` is not its dimension.
+pub fn conjugate_gradient(
+    a: &CsrMatrix,
+    b: &[Float],
+    options: &SolverOptions,
+) -> Option<CgSolution> {
+    uncancelled(|cancel| conjugate_gradient_with_cancel(a, b, options, cancel))
+}
+
+/// [`conjugate_gradient`] checking the `cancel` token before each iteration, stopping with the
+/// number of iterations done out of the limit.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square or `b.len()` is not its dimension.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "conjugate_gradient", level = "debug", skip_all, fields(n = a.rows()))
+)]
+pub fn conjugate_gradient_with_cancel(
+    a: &CsrMatrix,
+    b: &[Float],
+    options: &SolverOptions,
+    cancel: &CancelToken,
+) -> Result<Option<CgSolution>, Cancelled> {
+    assert!(a.rows() == a.cols(), "matrix is not square");
+    assert_eq!(b.len(), a.rows(), "vector size mismatch");
+    let Some(m) = Apply::new(a, &options.preconditioner) else {
+        return Ok(None);
+    };
+    let b_norm = dot(b, b).sqrt();
+    let mut x = vec![0.0; b.len()];
+    if b_norm == 0.0 {
+        return Ok(Some(CgSolution {
+            x,
+            iterations: 0,
+            residual: 0.0,
+        }));
+    }
+    let mut r = b.to_vec();
+    let mut z = m.apply(&r);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+    for iterations in 1..=options.max_iterations {
+        cancel.check(iterations - 1, options.max_iterations)?;
+        let q = a.mul_vec(&p);
+        let alpha = rz / dot(&p, &q);
+        for ((x, r), (p, q)) in x.iter_mut().zip(&mut r).zip(p.iter().zip(&q)) {
+            *x += alpha * p;
+            *r -= alpha * q;
+        }
+        let residual = dot(&r, &r).sqrt() / b_norm;
+        if !residual.is_finite() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(iterations, "breakdown");
+            return Ok(None);
+        }
+        if residual <= options.tolerance {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(iterations, residual, "converged");
+            return Ok(Some(CgSolution {
+                x,
+                iterations,
+                residual,
+            }));
+        }
+        z = m.apply(&r);
+        let rz_next = dot(&r, &z);
+        let beta = rz_next / rz;
+        rz = rz_next;
+        for (p, z) in p.iter_mut().zip(&z) {
+            *p = z + beta * *p;
+        }
+    }
+    #[cfg(feature = "tracing")]
+    tracing::warn!(iterations = options.max_iterations, "not converged");
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chain of springs alternating between stiff and soft segments, fixed at the first node.
+    fn stiff_soft(n: usize) -> CsrMatrix {
+        let k = |i: usize| {
+            if (i / 10).is_multiple_of(2) {
+                100.0
+            } else {
+                1.0
+            }
+        };
+        CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(i, i, k(i) + if i + 1 < n { k(i + 1) } else { 0.0 })];
+                if i + 1 < n {
+                    t.push((i, i + 1, -k(i + 1)));
+                    t.push((i + 1, i, -k(i + 1)));
+                }
+                t
+            }),
+        )
+    }
+
+    /// Checks the true residual, which drifts from the recursively updated one by rounding.
+    fn check(a: &CsrMatrix, b: &[Float], s: &CgSolution, options: &SolverOptions) {
+        let r: Vec<Float> = a.mul_vec(&s.x).iter().zip(b).map(|(r, b)| r - b).collect();
+        assert!(dot(&r, &r).sqrt() <= options.tolerance * 1e2 * dot(b, b).sqrt());
+    }
+
+    #[test]
+    fn preconditioners() {
+        let a = stiff_soft(50);
+        let b: Vec<Float> = (0..50).map(|i| (i as Float * 0.1).cos()).collect();
+        let iterations = [
+            Preconditioner::None,
+            Preconditioner::Jacobi,
+            Preconditioner::IncompleteCholesky(IcOptions::default()),
+        ]
+        .map(|preconditioner| {
+            let options = SolverOptions {
+                preconditioner,
+                ..Default::default()
+            };
+            let s = conjugate_gradient(&a, &b, &options).unwrap();
+            check(&a, &b, &s, &options);
+            s.iterations
+        });
+        assert!(iterations[1] < iterations[0]);
+        // The chain is tridiagonal, so IC(0) is exact.
+        assert!(iterations[2] <= 2);
+    }
+
+    #[test]
+    fn fill_reduces_iterations() {
+        let n = 12;
+        let index = |i: usize, j: usize| i * n + j;
+        let a = CsrMatrix::from_triplets(
+            n * n,
+            n * n,
+            (0..n).flat_map(|i| {
+                (0..n).flat_map(move |j| {
+                    let k = index(i, j);
+                    let mut t = vec![(k, k, 4.0 + if i == 0 { 1.0 } else { 0.0 })];
+                    if i + 1 < n {
+                        t.push((k, index(i + 1, j), -1.0));
+                        t.push((index(i + 1, j), k, -1.0));
+                    }
+                    if j + 1 < n {
+                        t.push((k, index(i, j + 1), -1.0));
+                        t.push((index(i, j + 1), k, -1.0));
+                    }
+                    t
+                })
+            }),
+        );
+        let b = vec![1.0; n * n];
+        let solve = |ic| {
+            let options = SolverOptions {
+                preconditioner: Preconditioner::IncompleteCholesky(ic),
+                ..Default::default()
+            };
+            let s = conjugate_gradient(&a, &b, &options).unwrap();
+            check(&a, &b, &s, &options);
+            s.iterations
+        };
+        let ic0 = solve(IcOptions::default());
+        let filled = solve(IcOptions {
+            fill: 4,
+            drop_tolerance: 1e-3,
+        });
+        assert!(filled < ic0);
+    }
+
+    #[test]
+    fn iteration_limit() {
+        let a = stiff_soft(50);
+        let options = SolverOptions {
+            max_iterations: 3,
+            preconditioner: Preconditioner::None,
+            ..Default::default()
+        };
+        assert_eq!(conjugate_gradient(&a, &[1.0; 50], &options), None);
+    }
+
+    #[test]
+    fn cancel() {
+        let a = stiff_soft(50);
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let options = SolverOptions::default();
+        assert_eq!(
+            conjugate_gradient_with_cancel(&a, &[1.0; 50], &options, &cancel),
+            Err(Cancelled {
+                done: 0,
+                total: options.max_iterations
+            })
+        );
+    }
+
+    #[test]
+    fn zero_rhs() {
+        let a = stiff_soft(5);
+        let s = conjugate_gradient(&a, &[0.0; 5], &SolverOptions::default()).unwrap();
+        assert_eq!(s.x, [0.0; 5]);
+        assert_eq!(s.iterations, 0);
+    }
+}
@@ -0,0 +1,376 @@
+use super::scalar::Scalar;
+use crate::{math::matrix::Matrix, Float};
+
+/// Sparse matrix in compressed sparse row format with sorted, unique column indices per row.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Fields<T>")
+)]
+pub struct CsrMatrix<T = Float> {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// Deserialized fields of a [`CsrMatrix`], before its structure is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Fields<T> {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<Fields<T>> for CsrMatrix<T> {
+    type Error = &'static str;
+
+    fn try_from(fields: Fields<T>) -> Result<Self, Self::Error> {
+        let Fields {
+            rows,
+            cols,
+            row_ptr,
+            col_idx,
+            values,
+        } = fields;
+        let ends = (row_ptr.first(), row_ptr.last());
+        if row_ptr.len().checked_sub(1) != Some(rows) || ends != (Some(&0), Some(&col_idx.len())) {
+            return Err("row pointers do not match the rows and elements");
+        }
+        if values.len() != col_idx.len() {
+            return Err("column indices and values differ in length");
+        }
+        if row_ptr.windows(2).any(|r| r[0] > r[1]) {
+            return Err("row pointers are decreasing");
+        }
+        let sorted = row_ptr.windows(2).all(|r| {
+            let row = &col_idx[r[0]..r[1]];
+            row.windows(2).all(|j| j[0] < j[1]) && row.last().is_none_or(|&j| j < cols)
+        });
+        if !sorted {
+            return Err("column indices are unsorted, repeated or out of bounds");
+        }
+        Ok(Self {
+            rows,
+            cols,
+            row_ptr,
+            col_idx,
+            values,
+        })
+    }
+}
+
+impl<T: Scalar> CsrMatrix<T> {
+    /// Assembles a matrix from `(row, column, value)` triplets, summing duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    pub fn from_triplets(
+        rows: usize,
+        cols: usize,
+        triplets: impl IntoIterator<Item = (usize, usize, T)>,
+    ) -> Self {
+        let mut t: Vec<(usize, usize, T)> = triplets.into_iter().collect();
+        assert!(
+            t.iter().all(|&(i, j, _)| i < rows && j < cols),
+            "index out of bounds"
+        );
+        t.sort_by_key(|&(i, j, _)| (i, j));
+        let mut row_ptr = vec![0; rows + 1];
+        let mut col_idx: Vec<usize> = Vec::with_capacity(t.len());
+        let mut values: Vec<T> = Vec::with_capacity(t.len());
+        let mut last = None;
+        for (i, j, v) in t {
+            if last == Some((i, j)) {
+                *values.last_mut().unwrap() += v;
+            } else {
+                row_ptr[i + 1] += 1;
+                col_idx.push(j);
+                values.push(v);
+                last = Some((i, j));
+            }
+        }
+        for i in 0..rows {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+        Self {
+            rows,
+            cols,
+            row_ptr,
+            col_idx,
+            values,
+        }
+    }
+
+    /// Converts the nonzero elements of a dense matrix.
+    pub fn from_dense(a: &Matrix<T>) -> Self {
+        Self::from_triplets(
+            a.rows(),
+            a.cols(),
+            (0..a.rows())
+                .flat_map(|i| (0..a.cols()).map(move |j| (i, j)))
+                .filter(|&(i, j)| a[(i, j)] != T::default())
+                .map(|(i, j)| (i, j, a[(i, j)])),
+        )
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> {
+        let mut m = Matrix::zeros(self.rows, self.cols);
+        for i in 0..self.rows {
+            for (j, v) in self.row(i) {
+                m[(i, j)] = v;
+            }
+        }
+        m
+    }
+
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of stored elements.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Stored `(column, value)` pairs of row `i` in increasing column order.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let r = self.row_ptr[i]..self.row_ptr[i + 1];
+        self.col_idx[r.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[r].iter().copied())
+    }
+
+    /// Stored column indices of row `i`.
+    pub fn row_indices(&self, i: usize) -> &[usize] {
+        &self.col_idx[self.row_ptr[i]..self.row_ptr[i + 1]]
+    }
+
+    /// Stored `(row, column, value)` triplets in row-major order.
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.rows).flat_map(move |i| self.row(i).map(move |(j, v)| (i, j, v)))
+    }
+
+    /// Element `(i, j)`, zero if not stored.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        let r = self.row_ptr[i]..self.row_ptr[i + 1];
+        match self.col_idx[r.clone()].binary_search(&j) {
+            Ok(k) => self.values[r.start + k],
+            Err(_) => T::default(),
+        }
+    }
+
+    /// Diagonal elements.
+    pub fn diagonal(&self) -> Vec<T> {
+        (0..self.rows.min(self.cols))
+            .map(|i| self.get(i, i))
+            .collect()
+    }
+
+    /// Maximum distance of a stored element from the diagonal.
+    pub fn bandwidth(&self) -> usize {
+        (0..self.rows)
+            .flat_map(|i| self.row_indices(i).iter().map(move |&j| i.abs_diff(j)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Maximum absolute column sum.
+    pub fn norm1(&self) -> Float {
+        let mut sums = vec![0.0; self.cols];
+        for (_, j, v) in self.triplets() {
+            sums[j] += v.modulus();
+        }
+        sums.into_iter().fold(0.0, Float::max)
+    }
+
+    /// Computes the matrix-vector product `A x`, over the rows in parallel with the `rayon`
+    /// feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len()` is not the number of columns.
+    pub fn mul_vec(&self, x: &[T]) -> Vec<T> {
+        assert_eq!(x.len(), self.cols, "matrix size mismatch");
+        let row = |i| {
+            let r = self.row_ptr[i]..self.row_ptr[i + 1];
+            T::gather_dot(&self.values[r.clone()], &self.col_idx[r], x)
+        };
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            (0..self.rows).into_par_iter().map(row).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        (0..self.rows).map(row).collect()
+    }
+
+    /// Computes `A + factor B`, with the union of both patterns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices have different sizes.
+    pub fn add_scaled(&self, b: &Self, factor: T) -> Self {
+        assert!(
+            self.rows == b.rows && self.cols == b.cols,
+            "matrix size mismatch"
+        );
+        Self::from_triplets(
+            self.rows,
+            self.cols,
+            self.triplets()
+                .chain(b.triplets().map(|(i, j, v)| (i, j, factor * v))),
+        )
+    }
+
+    /// Symmetrically permuted matrix `P A Pᵀ`, where row and column `k` of the result are row and
+    /// column `permutation[k]` of `A`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square or `permutation` has a different length.
+    pub fn permute(&self, permutation: &[usize]) -> Self {
+        assert!(self.rows == self.cols, "matrix is not square");
+        assert_eq!(permutation.len(), self.rows, "permutation size mismatch");
+        let inverse = inverse_permutation(permutation);
+        Self::from_triplets(
+            self.rows,
+            self.cols,
+            permutation.iter().enumerate().flat_map(|(k, &i)| {
+                let inverse = &inverse;
+                self.row(i).map(move |(j, v)| (k, inverse[j], v))
+            }),
+        )
+    }
+
+    /// Whether the stored pattern and values are symmetric.
+    pub fn is_symmetric(&self) -> bool {
+        self.rows == self.cols
+            && (0..self.rows).all(|i| self.row(i).all(|(j, v)| self.get(j, i) == v))
+    }
+}
+
+/// Inverts a permutation given as a list of old indices in their new order.
+pub fn inverse_permutation(permutation: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; permutation.len()];
+    for (k, &i) in permutation.iter().enumerate() {
+        inverse[i] = k;
+    }
+    inverse
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CsrMatrix {
+        CsrMatrix::from_triplets(
+            3,
+            3,
+            [
+                (0, 0, 4.0),
+                (1, 1, 5.0),
+                (0, 2, 1.0),
+                (2, 0, 1.0),
+                (2, 2, 3.0),
+                (1, 1, 1.0),
+                (2, 1, -2.0),
+                (1, 2, -2.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn from_triplets() {
+        let a = sample();
+        assert_eq!(a.nnz(), 7);
+        assert_eq!(a.get(1, 1), 6.0);
+        assert_eq!(a.get(0, 1), 0.0);
+        assert_eq!(
+            a.row(2).collect::<Vec<_>>(),
+            [(0, 1.0), (1, -2.0), (2, 3.0)]
+        );
+        assert_eq!(a.diagonal(), [4.0, 6.0, 3.0]);
+        assert_eq!(a.bandwidth(), 2);
+        assert_eq!(a.norm1(), 8.0);
+        assert!(a.is_symmetric());
+        assert_eq!(CsrMatrix::from_dense(&a.to_dense()), a);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = sample();
+        let s = serde_json::to_string(&a).unwrap();
+        assert_eq!(serde_json::from_str::<CsrMatrix>(&s).unwrap(), a);
+        let parse = |row_ptr: &str, col_idx: &str, values: &str| {
+            let s = format!(
+                r#"{{"rows":2,"cols":2,"row_ptr":{row_ptr},"col_idx":{col_idx},"values":{values}}}"#
+            );
+            serde_json::from_str::<CsrMatrix>(&s).map_err(|e| e.to_string())
+        };
+        assert!(parse("[0,1,2]", "[1,0]", "[1.0,2.0]").is_ok());
+        for (row_ptr, col_idx, values) in [
+            ("[0,2]", "[0,1]", "[1.0,2.0]"),
+            ("[0,1,3]", "[0,1]", "[1.0,2.0]"),
+            ("[1,1,2]", "[0,1]", "[1.0,2.0]"),
+            ("[0,1,2]", "[0,1]", "[1.0]"),
+            ("[0,3,2]", "[0,1]", "[1.0,2.0]"),
+            ("[0,2,2]", "[1,0]", "[1.0,2.0]"),
+            ("[0,2,2]", "[1,1]", "[1.0,2.0]"),
+            ("[0,1,2]", "[0,2]", "[1.0,2.0]"),
+        ] {
+            assert!(
+                parse(row_ptr, col_idx, values).is_err(),
+                "{row_ptr} {col_idx}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        CsrMatrix::from_triplets(2, 2, [(0, 2, 1.0)]);
+    }
+
+    #[test]
+    fn mul_vec() {
+        assert_eq!(sample().mul_vec(&[1.0, 2.0, -1.0]), [3.0, 14.0, -6.0]);
+    }
+
+    #[test]
+    fn add_scaled() {
+        let a = sample();
+        let b = CsrMatrix::from_triplets(3, 3, [(0, 1, 1.0), (2, 2, 1.0)]);
+        let c = a.add_scaled(&b, -2.0);
+        assert_eq!(c.nnz(), 8);
+        assert_eq!(c.get(0, 1), -2.0);
+        assert_eq!(c.get(2, 2), 1.0);
+        assert_eq!(c.triplets().count(), 8);
+    }
+
+    #[test]
+    fn permute() {
+        let a = sample();
+        let p = [2, 0, 1];
+        let b = a.permute(&p);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(b.get(i, j), a.get(p[i], p[j]));
+            }
+        }
+        assert_eq!(inverse_permutation(&p), [1, 2, 0]);
+    }
+}
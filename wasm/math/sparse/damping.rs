@@ -0,0 +1,127 @@
+use super::{csr::CsrMatrix, lanczos::Eigenpair};
+use crate::Float;
+
+/// Coefficients `(α, β)` of the Rayleigh damping `α M + β K` giving the damping ratios `zeta1`
+/// and `zeta2` at the angular frequencies `omega1` and `omega2`, from `ζ = α / 2ω + β ω / 2`.
+///
+/// Returns `None` if the frequencies coincide or are not positive.
+pub fn rayleigh_coefficients(
+    omega1: Float,
+    zeta1: Float,
+    omega2: Float,
+    zeta2: Float,
+) -> Option<(Float, Float)> {
+    if omega1 <= 0.0 || omega2 <= 0.0 || omega1 == omega2 {
+        return None;
+    }
+    let d = omega2 * omega2 - omega1 * omega1;
+    let alpha = 2.0 * omega1 * omega2 * (omega2 * zeta1 - omega1 * zeta2) / d;
+    let beta = 2.0 * (omega2 * zeta2 - omega1 * zeta1) / d;
+    Some((alpha, beta))
+}
+
+/// Rayleigh damping matrix `α M + β K`.
+///
+/// # Panics
+///
+/// Panics if the matrices have different sizes.
+pub fn rayleigh_damping(m: &CsrMatrix, k: &CsrMatrix, alpha: Float, beta: Float) -> CsrMatrix {
+    CsrMatrix::from_triplets(m.rows(), m.cols(), [])
+        .add_scaled(m, alpha)
+        .add_scaled(k, beta)
+}
+
+/// Damping matrix `C = Σ 2 ζᵢ ωᵢ (M φᵢ)(M φᵢ)ᵀ` giving the damping ratio `ratios[i]` to the
+/// mode `modes[i]`, with the squared angular frequency as its eigenvalue and its shape
+/// normalized by the mass, as computed by [`lanczos`](super::lanczos::lanczos). The modes not
+/// given are undamped.
+///
+/// The matrix is generally full, so this suits models with moderate numbers of degrees of freedom.
+///
+/// # Panics
+///
+/// Panics if `ratios` and `modes` have different lengths or a mode does not match the mass.
+pub fn modal_damping(m: &CsrMatrix, modes: &[Eigenpair], ratios: &[Float]) -> CsrMatrix {
+    assert_eq!(modes.len(), ratios.len(), "ratio count mismatch");
+    let n = m.rows();
+    let mut c = vec![0.0; n * n];
+    for (mode, &zeta) in modes.iter().zip(ratios) {
+        let factor = 2.0 * zeta * mode.value.max(0.0).sqrt();
+        let mphi = m.mul_vec(&mode.vector);
+        for (i, a) in mphi.iter().enumerate() {
+            for (j, b) in mphi.iter().enumerate() {
+                c[i * n + j] += factor * a * b;
+            }
+        }
+    }
+    CsrMatrix::from_triplets(
+        n,
+        n,
+        c.into_iter()
+            .enumerate()
+            .filter(|&(_, v)| v != 0.0)
+            .map(|(k, v)| (k / n, k % n, v)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::sparse::lanczos::{lanczos, LanczosOptions};
+
+    fn damping_ratio(omega: Float, (alpha, beta): (Float, Float)) -> Float {
+        alpha / (2.0 * omega) + beta * omega / 2.0
+    }
+
+    #[test]
+    fn rayleigh() {
+        let coefficients = rayleigh_coefficients(2.0, 0.02, 10.0, 0.05).unwrap();
+        assert!((damping_ratio(2.0, coefficients) - 0.02).abs() < Float::EPSILON * 10.0);
+        assert!((damping_ratio(10.0, coefficients) - 0.05).abs() < Float::EPSILON * 10.0);
+        assert_eq!(rayleigh_coefficients(2.0, 0.02, 2.0, 0.05), None);
+
+        let m = CsrMatrix::from_triplets(2, 2, [(0, 0, 2.0), (1, 1, 1.0)]);
+        let k = CsrMatrix::from_triplets(2, 2, [(0, 0, 3.0), (0, 1, -1.0), (1, 0, -1.0)]);
+        let c = rayleigh_damping(&m, &k, 0.5, 0.1);
+        assert_eq!(c.get(0, 0), 1.3);
+        assert_eq!(c.get(0, 1), -0.1);
+        assert_eq!(c.get(1, 1), 0.5);
+    }
+
+    #[test]
+    fn modal() {
+        let k = CsrMatrix::from_triplets(
+            3,
+            3,
+            [
+                (0, 0, 2.0),
+                (0, 1, -1.0),
+                (1, 0, -1.0),
+                (1, 1, 2.0),
+                (1, 2, -1.0),
+                (2, 1, -1.0),
+                (2, 2, 1.0),
+            ],
+        );
+        let m = CsrMatrix::from_triplets(3, 3, [(0, 0, 1.0), (1, 1, 2.0), (2, 2, 1.0)]);
+        let modes = lanczos(&k, &m, 3, &LanczosOptions::default()).unwrap();
+        let ratios = [0.02, 0.05, 0.0];
+        let c = modal_damping(&m, &modes[..2], &ratios[..2]);
+        for (i, mode) in modes.iter().enumerate() {
+            for (j, other) in modes.iter().enumerate() {
+                let cij: Float = other
+                    .vector
+                    .iter()
+                    .zip(c.mul_vec(&mode.vector))
+                    .map(|(a, b)| a * b)
+                    .sum();
+                let expected = if i == j {
+                    2.0 * ratios[i] * mode.value.sqrt()
+                } else {
+                    0.0
+                };
+                assert!((cij - expected).abs() < 1e-5);
+            }
+        }
+    }
+}
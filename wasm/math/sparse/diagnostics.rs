@@ -0,0 +1,184 @@
+use super::csr::CsrMatrix;
+use crate::Float;
+
+/// Models are reported ill-conditioned once the condition number times the machine epsilon
+/// exceeds this, that is when fewer than about four significant digits of the solution remain.
+const CONDITION_LIMIT: Float = 1e-4;
+
+/// Statistics of the pivots `D` of an `L D Lᵀ` factorization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct PivotStats {
+    /// Smallest pivot magnitude.
+    pub min: Float,
+    /// Largest pivot magnitude.
+    pub max: Float,
+    /// Number of negative pivots, which equals the number of negative eigenvalues.
+    pub negative: usize,
+    /// Degree of freedom, in the original numbering, whose pivot is smallest relative to its
+    /// diagonal element. A nearly unrestrained degree of freedom loses most of its diagonal
+    /// stiffness during the elimination.
+    pub weakest: usize,
+    /// Ratio of the pivot of [`Self::weakest`] to its diagonal element.
+    pub weakest_ratio: Float,
+}
+
+impl PivotStats {
+    /// Collects the statistics of `pivots`, where pivot `k` belongs to the diagonal element
+    /// `permutation[k]` of `a`.
+    pub fn new(a: &CsrMatrix, permutation: &[usize], pivots: &[Float]) -> Self {
+        let mut stats = Self {
+            min: Float::INFINITY,
+            max: 0.0,
+            negative: 0,
+            weakest: 0,
+            weakest_ratio: Float::INFINITY,
+        };
+        for (&i, &d) in permutation.iter().zip(pivots) {
+            stats.min = stats.min.min(d.abs());
+            stats.max = stats.max.max(d.abs());
+            if d < 0.0 {
+                stats.negative += 1;
+            }
+            let ratio = d.abs() / a.get(i, i).abs();
+            if ratio < stats.weakest_ratio {
+                stats.weakest = i;
+                stats.weakest_ratio = ratio;
+            }
+        }
+        stats
+    }
+}
+
+/// Diagnostics of a factorization, computed once after it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Diagnostics {
+    /// Estimate of the 1-norm condition number, a lower bound that is usually within a factor of
+    /// three.
+    pub condition: Float,
+    pub pivots: PivotStats,
+}
+
+/// Problem of a stiffness matrix detected by its [`Diagnostics`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Warning {
+    /// Negative pivots: the structure is unstable, for example buckled under the geometric
+    /// stiffness, or the matrix is not a stiffness.
+    Indefinite { negative: usize },
+    /// The displacements are dominated by rounding, typically because the structure is nearly a
+    /// mechanism around the degree of freedom `dof`.
+    IllConditioned { condition: Float, dof: usize },
+}
+
+impl Diagnostics {
+    /// Problem of the factorization if the matrix is a stiffness, which should be positive
+    /// definite and reasonably conditioned.
+    pub fn warning(&self) -> Option<Warning> {
+        if self.pivots.negative > 0 {
+            Some(Warning::Indefinite {
+                negative: self.pivots.negative,
+            })
+        } else if self.condition * Float::EPSILON > CONDITION_LIMIT {
+            Some(Warning::IllConditioned {
+                condition: self.condition,
+                dof: self.pivots.weakest,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Estimates `‖A⁻¹‖₁` of a symmetric matrix of dimension `n` from a few solves, by Hager's method
+/// with Higham's safeguards.
+pub fn inverse_norm1(n: usize, solve: impl Fn(&[Float]) -> Vec<Float>) -> Float {
+    if n == 0 {
+        return 0.0;
+    }
+    let norm1 = |x: &[Float]| x.iter().map(|x| x.abs()).sum::<Float>();
+    let mut x = vec![1.0 / n as Float; n];
+    let mut estimate = 0.0;
+    for iteration in 0..5 {
+        let y = solve(&x);
+        let next = norm1(&y);
+        if iteration > 0 && next <= estimate {
+            break;
+        }
+        estimate = next;
+        let sign: Vec<Float> = y
+            .iter()
+            .map(|&y| if y < 0.0 { -1.0 } else { 1.0 })
+            .collect();
+        // The transpose of the inverse is the inverse itself.
+        let z = solve(&sign);
+        let (j, zj) = z
+            .iter()
+            .map(|z| z.abs())
+            .enumerate()
+            .fold((0, 0.0), |m, (j, z)| if z > m.1 { (j, z) } else { m });
+        let zx: Float = z.iter().zip(&x).map(|(z, x)| z * x).sum();
+        if iteration > 0 && zj <= zx {
+            break;
+        }
+        x = vec![0.0; n];
+        x[j] = 1.0;
+    }
+    // An alternating vector catches the cases where the iteration stalls.
+    let b: Vec<Float> = (0..n)
+        .map(|i| {
+            let v = 1.0 + i as Float / (n.max(2) - 1) as Float;
+            if i % 2 == 0 {
+                v
+            } else {
+                -v
+            }
+        })
+        .collect();
+    estimate.max(2.0 * norm1(&solve(&b)) / (3 * n) as Float)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::Matrix;
+
+    #[test]
+    fn estimate_is_exact_for_diagonal() {
+        let d = [4.0, 0.5, 2.0, 8.0];
+        let estimate = inverse_norm1(4, |x| x.iter().zip(&d).map(|(x, d)| x / d).collect());
+        assert!((estimate - 2.0).abs() < Float::EPSILON * 10.0);
+    }
+
+    #[test]
+    fn estimate_dense() {
+        #[rustfmt::skip]
+        let a = Matrix::new(3, 3, vec![
+            4.0, -2.0, 1.0,
+            -2.0, 4.0, -2.0,
+            1.0, -2.0, 3.0,
+        ]);
+        let inv = a.inv().unwrap();
+        let estimate = inverse_norm1(3, |x| inv.mul_vec(x));
+        assert!(estimate <= inv.norm1() * (1.0 + Float::EPSILON * 1e2));
+        assert!(estimate >= inv.norm1() / 3.0);
+    }
+
+    #[test]
+    fn pivot_stats() {
+        let a = CsrMatrix::from_triplets(3, 3, [(0, 0, 2.0), (1, 1, 4.0), (2, 2, -1.0)]);
+        let stats = PivotStats::new(&a, &[2, 0, 1], &[-1.0, 2.0, 0.01]);
+        assert_eq!(stats.min, 0.01);
+        assert_eq!(stats.max, 2.0);
+        assert_eq!(stats.negative, 1);
+        assert_eq!(stats.weakest, 1);
+        assert_eq!(stats.weakest_ratio, 0.0025);
+    }
+}
@@ -0,0 +1,431 @@
+use super::{
+    amd::approximate_minimum_degree,
+    csr::{inverse_permutation, CsrMatrix},
+    diagnostics::{inverse_norm1, Diagnostics, PivotStats},
+    ldl::{SparseLdl, Symbolic},
+    rcm::reverse_cuthill_mckee,
+    skyline::{column_counts, skyline, SkylineLdl, SkylineMatrix},
+};
+use crate::{
+    cancel::{uncancelled, CancelToken, Cancelled},
+    Float,
+};
+
+/// Skyline storage is chosen while its profile is at most this many times the number of elements
+/// of the general sparse factor, as its contiguous rows are much cheaper to traverse.
+const SKYLINE_PREFERENCE: usize = 2;
+
+/// Storage backend of a [`DirectSolver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Storage {
+    /// Skyline `L D Lᵀ` after reverse Cuthill-McKee ordering.
+    Skyline,
+    /// General sparse `L D Lᵀ` after approximate minimum degree ordering.
+    Sparse,
+}
+
+/// Size and cost of a factorization, known from the pattern of the matrix before computing it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub storage: Storage,
+    /// As [`DirectSolver::nnz`].
+    pub nnz: usize,
+    /// As [`DirectSolver::flops`].
+    pub flops: Float,
+    /// As [`DirectSolver::memory`].
+    pub memory: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Factor {
+    Skyline(SkylineLdl),
+    Sparse(SparseLdl),
+}
+
+/// Direct solver for symmetric sparse systems.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectSolver {
+    permutation: Vec<usize>,
+    factor: Factor,
+    diagnostics: Diagnostics,
+}
+
+impl DirectSolver {
+    /// Factorizes a symmetric matrix, choosing the storage by comparing the profile after reverse
+    /// Cuthill-McKee ordering with the size of the general sparse factor after approximate minimum
+    /// degree ordering.
+    ///
+    /// Returns `None` if the matrix is singular.
+    pub fn new(a: &CsrMatrix) -> Option<Self> {
+        Self::with_storage(a, Self::select_storage(a))
+    }
+
+    /// Factorizes a symmetric matrix with the given storage.
+    ///
+    /// Returns `None` if the matrix is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn with_storage(a: &CsrMatrix, storage: Storage) -> Option<Self> {
+        uncancelled(|cancel| Self::with_cancel(a, storage, cancel))
+    }
+
+    /// [`Self::with_storage`] checking the `cancel` token before each row of the factorization,
+    /// stopping with the number of rows factorized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "factorize", level = "debug", skip_all, fields(n = a.rows(), ?storage))
+    )]
+    pub fn with_cancel(
+        a: &CsrMatrix,
+        storage: Storage,
+        cancel: &CancelToken,
+    ) -> Result<Option<Self>, Cancelled> {
+        assert!(a.rows() == a.cols(), "matrix is not square");
+        let (permutation, factor) = match storage {
+            Storage::Skyline => {
+                let permutation = reverse_cuthill_mckee(a);
+                let matrix = SkylineMatrix::from_csr(&a.permute(&permutation));
+                let Some(factor) = matrix.factorize_with_cancel(cancel)? else {
+                    return Ok(None);
+                };
+                (permutation, Factor::Skyline(factor))
+            }
+            Storage::Sparse => {
+                let permutation = approximate_minimum_degree(a);
+                let permuted = a.permute(&permutation);
+                let symbolic = Symbolic::new(&permuted);
+                let Some(factor) = SparseLdl::with_cancel(&permuted, &symbolic, cancel)? else {
+                    return Ok(None);
+                };
+                (permutation, Factor::Sparse(factor))
+            }
+        };
+        let pivots = match &factor {
+            Factor::Skyline(f) => f.pivots(),
+            Factor::Sparse(f) => f.pivots(),
+        };
+        let pivots = PivotStats::new(a, &permutation, pivots);
+        let mut solver = Self {
+            permutation,
+            factor,
+            diagnostics: Diagnostics {
+                condition: 0.0,
+                pivots,
+            },
+        };
+        solver.diagnostics.condition = a.norm1() * inverse_norm1(a.rows(), |b| solver.solve(b));
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            condition = solver.diagnostics.condition,
+            negative = solver.diagnostics.pivots.negative,
+            "factorized"
+        );
+        Ok(Some(solver))
+    }
+
+    /// Storage [`Self::new`] would choose for `a`.
+    pub fn select_storage(a: &CsrMatrix) -> Storage {
+        Self::estimate(a).storage
+    }
+
+    /// Storage, size and cost of the factorization [`Self::new`] would compute for `a`, from
+    /// the orderings and the symbolic analysis of its pattern alone.
+    pub fn estimate(a: &CsrMatrix) -> Estimate {
+        let profile = column_counts(&skyline(&a.permute(&reverse_cuthill_mckee(a))));
+        let symbolic = Symbolic::new(&a.permute(&approximate_minimum_degree(a)));
+        let (storage, counts) =
+            if profile.iter().sum::<usize>() <= SKYLINE_PREFERENCE * symbolic.nnz() {
+                (Storage::Skyline, &profile[..])
+            } else {
+                (Storage::Sparse, symbolic.column_counts())
+            };
+        let nnz = counts.iter().sum();
+        Estimate {
+            storage,
+            nnz,
+            flops: flops(counts),
+            memory: memory(storage, a.rows(), nnz),
+        }
+    }
+
+    pub fn storage(&self) -> Storage {
+        match self.factor {
+            Factor::Skyline(_) => Storage::Skyline,
+            Factor::Sparse(_) => Storage::Sparse,
+        }
+    }
+
+    /// Ordering applied before the factorization, as the old indices in their new order. Exposed
+    /// for inspecting the fill-in of a model.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Number of elements of `L` below the diagonal, including the zeros inside the skyline for
+    /// [`Storage::Skyline`].
+    pub fn nnz(&self) -> usize {
+        self.column_counts().iter().sum()
+    }
+
+    /// Estimate of the floating-point operations of the factorization, `Σ c (c + 1)` over the
+    /// numbers `c` of elements of the columns of `L` below the diagonal, for the division by the
+    /// pivot and the update of the trailing matrix by each column.
+    pub fn flops(&self) -> Float {
+        flops(&self.column_counts())
+    }
+
+    /// Approximate bytes of the factors and the ordering.
+    pub fn memory(&self) -> usize {
+        memory(self.storage(), self.permutation.len(), self.nnz())
+    }
+
+    fn column_counts(&self) -> Vec<usize> {
+        match &self.factor {
+            Factor::Skyline(f) => f.column_counts(),
+            Factor::Sparse(f) => f.column_counts(),
+        }
+    }
+
+    /// Condition estimate and pivot statistics of the factorization.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Solves `A x = b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not the dimension of the matrix.
+    pub fn solve(&self, b: &[Float]) -> Vec<Float> {
+        self.solve_block(b, 1)
+    }
+
+    /// Solves `A X = B` for the `k` columns of `B` stored by rows at once, which for many right
+    /// hand sides is cheaper than as many calls to [`Self::solve`], each traversing the factors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not `k` times the dimension of the matrix.
+    pub fn solve_block(&self, b: &[Float], k: usize) -> Vec<Float> {
+        assert_eq!(b.len(), self.permutation.len() * k, "vector size mismatch");
+        let row = |x: &[Float], i: usize| x[i * k..(i + 1) * k].to_vec();
+        let pb: Vec<Float> = self.permutation.iter().flat_map(|&i| row(b, i)).collect();
+        let px = match &self.factor {
+            Factor::Skyline(f) => f.solve_block(&pb, k),
+            Factor::Sparse(f) => f.solve_block(&pb, k),
+        };
+        let inverse = inverse_permutation(&self.permutation);
+        inverse.iter().flat_map(|&i| row(&px, i)).collect()
+    }
+}
+
+/// Floating-point operations of a factorization with the `counts` of elements of the columns of
+/// `L` below the diagonal, as [`DirectSolver::flops`].
+fn flops(counts: &[usize]) -> Float {
+    counts.iter().map(|&c| (c * (c + 1)) as Float).sum()
+}
+
+/// Bytes of the factors of dimension `n` with `nnz` elements below the diagonal in the `storage`,
+/// and the ordering.
+fn memory(storage: Storage, n: usize, nnz: usize) -> usize {
+    let (float, index) = (size_of::<Float>(), size_of::<usize>());
+    let factor = match storage {
+        // Rows with their unit diagonals, the pivots, and the first columns and offsets.
+        Storage::Skyline => (nnz + 2 * n) * float + (2 * n + 1) * index,
+        // Values and row indices of the columns, the pivots and the column offsets.
+        Storage::Sparse => nnz * (float + index) + n * float + (n + 1) * index,
+    };
+    factor + n * index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::sparse::diagnostics::Warning;
+
+    /// Stiffness-like matrix of a chain of springs with shuffled node numbers.
+    fn chain(n: usize) -> CsrMatrix {
+        let label = |i: usize| (i * 7) % n;
+        CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(label(i), label(i), 2.0 + i as Float * 0.1)];
+                if i + 1 < n {
+                    t.push((label(i), label(i + 1), -1.0));
+                    t.push((label(i + 1), label(i), -1.0));
+                }
+                t
+            }),
+        )
+    }
+
+    /// Matrix coupling every node to its neighbours and to nodes far apart, whose profile cannot
+    /// be made narrow.
+    fn scattered(n: usize) -> CsrMatrix {
+        CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(i, i, 10.0)];
+                for j in [(i * 13 + 5) % n, (i * 29 + 11) % n] {
+                    if j != i {
+                        t.push((i, j, -1.0));
+                        t.push((j, i, -1.0));
+                    }
+                }
+                t
+            }),
+        )
+    }
+
+    fn check(a: &CsrMatrix, s: &DirectSolver) {
+        let x: Vec<Float> = (0..a.rows()).map(|i| (i as Float * 0.37).sin()).collect();
+        let y = s.solve(&a.mul_vec(&x));
+        for (y, x) in y.iter().zip(&x) {
+            assert!((y - x).abs() < 1e-4, "{} != {}", y, x);
+        }
+    }
+
+    #[test]
+    fn banded() {
+        let a = chain(30);
+        let s = DirectSolver::new(&a).unwrap();
+        assert_eq!(s.storage(), Storage::Skyline);
+        assert_eq!(s.permutation().len(), 30);
+        check(&a, &s);
+        let sparse = DirectSolver::with_storage(&a, Storage::Sparse).unwrap();
+        check(&a, &sparse);
+        // Orderings of a chain without fill, with one element below each pivot but the last.
+        for s in [&s, &sparse] {
+            assert_eq!(s.nnz(), 29);
+            assert_eq!(s.flops(), 29.0 * 2.0);
+            assert!(s.memory() >= 29 * size_of::<Float>());
+        }
+    }
+
+    #[test]
+    fn scattered_pattern() {
+        let a = scattered(40);
+        let s = DirectSolver::new(&a).unwrap();
+        check(&a, &s);
+        check(
+            &a,
+            &DirectSolver::with_storage(&a, Storage::Skyline).unwrap(),
+        );
+        check(
+            &a,
+            &DirectSolver::with_storage(&a, Storage::Sparse).unwrap(),
+        );
+    }
+
+    #[test]
+    fn solve_block() {
+        let a = scattered(40);
+        let k = 3;
+        let b: Vec<Float> = (0..40 * k).map(|i| (i as Float * 0.61).cos()).collect();
+        for storage in [Storage::Skyline, Storage::Sparse] {
+            let s = DirectSolver::with_storage(&a, storage).unwrap();
+            let x = s.solve_block(&b, k);
+            // Each column as solved alone.
+            for c in 0..k {
+                let column: Vec<_> = b.iter().skip(c).step_by(k).copied().collect();
+                let expected = s.solve(&column);
+                assert!(x.iter().skip(c).step_by(k).eq(&expected));
+            }
+            assert!(s.solve_block(&[], 0).is_empty());
+        }
+    }
+
+    #[test]
+    fn estimate() {
+        for (a, storage) in [
+            (chain(30), Storage::Skyline),
+            (scattered(400), Storage::Sparse),
+        ] {
+            let estimate = DirectSolver::estimate(&a);
+            assert_eq!(estimate.storage, storage);
+            let s = DirectSolver::new(&a).unwrap();
+            assert_eq!(estimate.storage, s.storage());
+            assert_eq!(estimate.nnz, s.nnz());
+            assert_eq!(estimate.flops, s.flops());
+            assert_eq!(estimate.memory, s.memory());
+        }
+    }
+
+    #[test]
+    fn cancel() {
+        let a = chain(30);
+        let cancel = CancelToken::new();
+        assert!(DirectSolver::with_cancel(&a, Storage::Sparse, &cancel).is_ok());
+        cancel.cancel();
+        for storage in [Storage::Skyline, Storage::Sparse] {
+            assert_eq!(
+                DirectSolver::with_cancel(&a, storage, &cancel),
+                Err(Cancelled { done: 0, total: 30 })
+            );
+        }
+    }
+
+    #[test]
+    fn diagnostics() {
+        let a = chain(30);
+        let d = *DirectSolver::new(&a).unwrap().diagnostics();
+        let exact = a.to_dense().norm1() * a.to_dense().inv().unwrap().norm1();
+        assert!(d.condition <= exact * 1.001 && d.condition >= exact / 3.0);
+        assert_eq!(d.pivots.negative, 0);
+        assert_eq!(d.warning(), None);
+    }
+
+    #[test]
+    fn nearly_mechanism() {
+        // Chain of springs whose last two nodes hang from a spring too weak for the rounding.
+        let k = [1.0, 1.0, Float::EPSILON, 1.0];
+        let a = CsrMatrix::from_triplets(
+            4,
+            4,
+            (0..4).flat_map(|i| {
+                let mut t = vec![(i, i, k[i] + if i < 3 { k[i + 1] } else { 0.0 })];
+                if i < 3 {
+                    t.push((i, i + 1, -k[i + 1]));
+                    t.push((i + 1, i, -k[i + 1]));
+                }
+                t
+            }),
+        );
+        let s = DirectSolver::new(&a).unwrap();
+        assert!(matches!(
+            s.diagnostics().warning(),
+            Some(Warning::IllConditioned { dof: 2 | 3, .. })
+        ));
+    }
+
+    #[test]
+    fn indefinite() {
+        let a =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 1.0), (0, 1, 2.0), (1, 0, 2.0), (1, 1, 1.0)]);
+        let s = DirectSolver::new(&a).unwrap();
+        assert_eq!(s.diagnostics().pivots.negative, 1);
+        assert_eq!(
+            s.diagnostics().warning(),
+            Some(Warning::Indefinite { negative: 1 })
+        );
+    }
+
+    #[test]
+    fn singular() {
+        let a =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 1.0), (0, 1, 1.0), (1, 0, 1.0), (1, 1, 1.0)]);
+        assert_eq!(DirectSolver::new(&a), None);
+    }
+}
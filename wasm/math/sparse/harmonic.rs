@@ -0,0 +1,128 @@
+use super::{
+    amd::approximate_minimum_degree,
+    csr::{inverse_permutation, CsrMatrix},
+    ldl::{SparseLdl, Symbolic},
+};
+use crate::{math::complex::Complex, Float};
+
+/// Dynamic stiffness `K + iωC - ω²M` at the angular frequency `omega`, with the union of the
+/// patterns of the three matrices.
+///
+/// # Panics
+///
+/// Panics if the matrices have different sizes.
+pub fn dynamic_stiffness(
+    k: &CsrMatrix,
+    c: &CsrMatrix,
+    m: &CsrMatrix,
+    omega: Float,
+) -> CsrMatrix<Complex<Float>> {
+    assert!(
+        [c, m]
+            .iter()
+            .all(|a| a.rows() == k.rows() && a.cols() == k.cols()),
+        "matrix size mismatch"
+    );
+    CsrMatrix::from_triplets(
+        k.rows(),
+        k.cols(),
+        k.triplets()
+            .map(|(i, j, v)| (i, j, Complex::new(v, 0.0)))
+            .chain(
+                c.triplets()
+                    .map(|(i, j, v)| (i, j, Complex::new(0.0, omega * v))),
+            )
+            .chain(
+                m.triplets()
+                    .map(|(i, j, v)| (i, j, Complex::new(-omega * omega * v, 0.0))),
+            ),
+    )
+}
+
+/// Steady-state response `x` of `M ẍ + C ẋ + K x = f e^{iωt}` at each angular frequency of
+/// `omegas`, by factorizing the dynamic stiffness directly. Unlike modal superposition, this
+/// handles nonproportional damping exactly.
+///
+/// The ordering and symbolic analysis are shared by all frequencies. A frequency is `None` when
+/// the dynamic stiffness is singular there, as at an undamped resonance.
+///
+/// # Panics
+///
+/// Panics if the matrices have different sizes or `f.len()` is not their dimension.
+pub fn frequency_response(
+    k: &CsrMatrix,
+    c: &CsrMatrix,
+    m: &CsrMatrix,
+    f: &[Complex<Float>],
+    omegas: &[Float],
+) -> Vec<Option<Vec<Complex<Float>>>> {
+    assert!(k.rows() == k.cols(), "matrix is not square");
+    assert_eq!(f.len(), k.rows(), "vector size mismatch");
+    // The pattern does not depend on the frequency, since explicit zeros are kept.
+    let pattern = dynamic_stiffness(k, c, m, 1.0);
+    let permutation = approximate_minimum_degree(&pattern);
+    let inverse = inverse_permutation(&permutation);
+    let symbolic = Symbolic::new(&pattern.permute(&permutation));
+    let pf: Vec<Complex<Float>> = permutation.iter().map(|&i| f[i]).collect();
+    omegas
+        .iter()
+        .map(|&omega| {
+            let a = dynamic_stiffness(k, c, m, omega).permute(&permutation);
+            let px = SparseLdl::with_symbolic(&a, &symbolic)?.solve(&pf);
+            Some(inverse.iter().map(|&p| px[p]).collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::sparse::scalar::Scalar;
+
+    fn diagonal(values: &[Float]) -> CsrMatrix {
+        CsrMatrix::from_triplets(
+            values.len(),
+            values.len(),
+            values.iter().enumerate().map(|(i, &v)| (i, i, v)),
+        )
+    }
+
+    #[test]
+    fn single_degree_of_freedom() {
+        let (k, c, m) = (diagonal(&[4.0]), diagonal(&[0.2]), diagonal(&[1.0]));
+        let f = [Complex::new(1.0, 0.0)];
+        let x = frequency_response(&k, &c, &m, &f, &[0.0, 1.0]);
+        assert_eq!(x[0], Some(vec![Complex::new(0.25, 0.0)]));
+        let expected = Complex::new(1.0, 0.0) / Complex::new(3.0, 0.2);
+        let x = x[1].as_ref().unwrap();
+        assert!((x[0] - expected).modulus() < Float::EPSILON * 10.0);
+    }
+
+    #[test]
+    fn nonproportional_damping() {
+        // Two masses on springs with a single damper at the first, which is not proportional.
+        let k =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 3.0), (0, 1, -1.0), (1, 0, -1.0), (1, 1, 1.0)]);
+        let c = diagonal(&[0.5, 0.0]);
+        let m = diagonal(&[1.0, 2.0]);
+        let f = [Complex::new(0.0, 0.0), Complex::new(1.0, -1.0)];
+        for (omega, x) in
+            [0.3, 0.7, 1.5]
+                .iter()
+                .zip(frequency_response(&k, &c, &m, &f, &[0.3, 0.7, 1.5]))
+        {
+            let x = x.unwrap();
+            let r = dynamic_stiffness(&k, &c, &m, *omega).mul_vec(&x);
+            for (r, f) in r.iter().zip(&f) {
+                assert!((*r - *f).modulus() < Float::EPSILON * 1e2);
+            }
+        }
+    }
+
+    #[test]
+    fn undamped_resonance() {
+        let (k, c, m) = (diagonal(&[4.0]), diagonal(&[0.0]), diagonal(&[1.0]));
+        let x = frequency_response(&k, &c, &m, &[Complex::new(1.0, 0.0)], &[2.0]);
+        assert_eq!(x, [None]);
+    }
+}
@@ -0,0 +1,256 @@
+use super::csr::CsrMatrix;
+use crate::Float;
+
+/// Controls of the incomplete Cholesky factorization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IcOptions {
+    /// Maximum number of elements per column of the factor kept outside the pattern of the
+    /// matrix. Zero gives IC(0).
+    pub fill: usize,
+    /// Off-diagonal elements smaller than this fraction of the 2-norm of the corresponding column
+    /// of the matrix are dropped.
+    pub drop_tolerance: Float,
+}
+
+impl Default for IcOptions {
+    fn default() -> Self {
+        Self {
+            fill: 0,
+            drop_tolerance: 0.0,
+        }
+    }
+}
+
+/// Incomplete Cholesky factor `L` with `A ≈ L Lᵀ`, stored by columns with the diagonal first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncompleteCholesky {
+    columns: Vec<Vec<(usize, Float)>>,
+    shift: Float,
+}
+
+impl IncompleteCholesky {
+    /// Factorizes a symmetric positive definite matrix, of which only the lower triangle is read.
+    ///
+    /// If a nonpositive pivot is encountered, the factorization is restarted on `A + α diag(A)`
+    /// with an increasing shift `α`. Returns `None` if no shift succeeds, which happens when the
+    /// diagonal is not positive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn new(a: &CsrMatrix, options: &IcOptions) -> Option<Self> {
+        assert!(a.rows() == a.cols(), "matrix is not square");
+        let mut shift = 0.0;
+        for _ in 0..32 {
+            if let Some(columns) = factorize(a, options, shift) {
+                return Some(Self { columns, shift });
+            }
+            shift = if shift == 0.0 { 1e-3 } else { shift * 2.0 };
+        }
+        None
+    }
+
+    /// Number of off-diagonal elements of `L`.
+    pub fn nnz(&self) -> usize {
+        self.columns.iter().map(|c| c.len() - 1).sum()
+    }
+
+    /// Diagonal shift `α` needed to complete the factorization.
+    pub fn shift(&self) -> Float {
+        self.shift
+    }
+
+    /// Solves `L Lᵀ x = b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not the dimension of the matrix.
+    pub fn solve(&self, b: &[Float]) -> Vec<Float> {
+        assert_eq!(b.len(), self.columns.len(), "vector size mismatch");
+        let mut x = b.to_vec();
+        for (j, column) in self.columns.iter().enumerate() {
+            x[j] /= column[0].1;
+            for &(i, l) in &column[1..] {
+                x[i] -= l * x[j];
+            }
+        }
+        for (j, column) in self.columns.iter().enumerate().rev() {
+            let s: Float = column[1..].iter().map(|&(i, l)| l * x[i]).sum();
+            x[j] = (x[j] - s) / column[0].1;
+        }
+        x
+    }
+}
+
+/// Left-looking column factorization of `A + shift diag(A)`, or `None` on a nonpositive pivot.
+fn factorize(a: &CsrMatrix, options: &IcOptions, shift: Float) -> Option<Vec<Vec<(usize, Float)>>> {
+    let n = a.rows();
+    let mut columns: Vec<Vec<(usize, Float)>> = Vec::with_capacity(n);
+    // Columns k < j with a stored element in row j, and the position of that element.
+    let mut rows: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    let mut w = vec![0.0; n];
+    let mut in_pattern = vec![false; n];
+    let mut original = vec![false; n];
+    let mut pattern = Vec::new();
+    for j in 0..n {
+        // Column j of the lower triangle is row j of the upper triangle.
+        let mut norm = 0.0;
+        for (i, v) in a.row(j).filter(|&(i, _)| i >= j) {
+            w[i] = if i == j { v * (1.0 + shift) } else { v };
+            norm += v * v;
+            in_pattern[i] = true;
+            original[i] = true;
+            pattern.push(i);
+        }
+        if !in_pattern[j] {
+            in_pattern[j] = true;
+            pattern.push(j);
+        }
+        for &(k, p) in &rows[j] {
+            let column: &[(usize, Float)] = &columns[k];
+            let ljk = column[p].1;
+            for &(i, lik) in &column[p..] {
+                if !in_pattern[i] {
+                    in_pattern[i] = true;
+                    pattern.push(i);
+                }
+                w[i] -= lik * ljk;
+            }
+        }
+        let d = w[j];
+        if d <= 0.0 || !d.is_finite() {
+            return None;
+        }
+        let d = d.sqrt();
+        let threshold = options.drop_tolerance * norm.sqrt();
+        let mut kept = Vec::new();
+        let mut fill = Vec::new();
+        for &i in pattern.iter().filter(|&&i| i != j) {
+            let l = w[i] / d;
+            if l != 0.0 && l.abs() >= threshold {
+                if original[i] {
+                    kept.push((i, l));
+                } else {
+                    fill.push((i, l));
+                }
+            }
+        }
+        if fill.len() > options.fill {
+            fill.select_nth_unstable_by(options.fill, |a, b| b.1.abs().total_cmp(&a.1.abs()));
+            fill.truncate(options.fill);
+        }
+        kept.append(&mut fill);
+        kept.sort_by_key(|&(i, _)| i);
+        let mut column = Vec::with_capacity(kept.len() + 1);
+        column.push((j, d));
+        column.extend(kept);
+        for (p, &(i, _)) in column.iter().enumerate().skip(1) {
+            rows[i].push((j, p));
+        }
+        columns.push(column);
+        for i in pattern.drain(..) {
+            w[i] = 0.0;
+            in_pattern[i] = false;
+            original[i] = false;
+        }
+    }
+    Some(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::sparse::ldl::SparseLdl;
+
+    /// Laplacian of an `n` by `n` grid with a small diagonal shift.
+    fn grid(n: usize) -> CsrMatrix {
+        let index = |i: usize, j: usize| i * n + j;
+        CsrMatrix::from_triplets(
+            n * n,
+            n * n,
+            (0..n).flat_map(|i| {
+                (0..n).flat_map(move |j| {
+                    let k = index(i, j);
+                    let mut t = vec![(k, k, 4.01)];
+                    if i + 1 < n {
+                        t.push((k, index(i + 1, j), -1.0));
+                        t.push((index(i + 1, j), k, -1.0));
+                    }
+                    if j + 1 < n {
+                        t.push((k, index(i, j + 1), -1.0));
+                        t.push((index(i, j + 1), k, -1.0));
+                    }
+                    t
+                })
+            }),
+        )
+    }
+
+    #[test]
+    fn zero_fill_keeps_pattern() {
+        let a = grid(5);
+        let f = IncompleteCholesky::new(&a, &IcOptions::default()).unwrap();
+        assert_eq!(f.shift(), 0.0);
+        assert_eq!(f.nnz(), (a.nnz() - a.rows()) / 2);
+    }
+
+    #[test]
+    fn exact_on_tridiagonal() {
+        // No fill-in occurs, so IC(0) is the complete factorization.
+        let n = 10;
+        let a = CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(i, i, 2.0)];
+                if i + 1 < n {
+                    t.push((i, i + 1, -1.0));
+                    t.push((i + 1, i, -1.0));
+                }
+                t
+            }),
+        );
+        let b: Vec<Float> = (0..n).map(|i| i as Float).collect();
+        let x = IncompleteCholesky::new(&a, &IcOptions::default())
+            .unwrap()
+            .solve(&b);
+        let y = SparseLdl::new(&a).unwrap().solve(&b);
+        for (x, y) in x.iter().zip(&y) {
+            assert!((x - y).abs() <= Float::EPSILON * 1e3 * y.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn fill_and_drop() {
+        let a = grid(6);
+        let ic0 = IncompleteCholesky::new(&a, &IcOptions::default()).unwrap();
+        let filled = IncompleteCholesky::new(
+            &a,
+            &IcOptions {
+                fill: 3,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(filled.nnz() > ic0.nnz());
+        let dropped = IncompleteCholesky::new(
+            &a,
+            &IcOptions {
+                fill: 3,
+                drop_tolerance: 0.1,
+            },
+        )
+        .unwrap();
+        assert!(dropped.nnz() < filled.nnz());
+    }
+
+    #[test]
+    fn shifted() {
+        // Indefinite, so the factorization only completes with a shift.
+        let a =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 1.0), (0, 1, 2.0), (1, 0, 2.0), (1, 1, 1.0)]);
+        let f = IncompleteCholesky::new(&a, &IcOptions::default()).unwrap();
+        assert!(f.shift() > 1.0);
+    }
+}
@@ -0,0 +1,385 @@
+use super::{csr::CsrMatrix, direct::DirectSolver};
+use crate::{
+    cancel::{uncancelled, CancelToken, Cancelled},
+    math::{matrix::Matrix, sum::stable_sum},
+    Float,
+};
+
+/// Spectral transformation of the generalized eigenproblem `A φ = λ B φ`, turning the eigenvalues
+/// nearest the shift `σ` into the extreme ones of the Lanczos operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Transformation {
+    /// Operator `(A - σ B)⁻¹ B` with the `B` inner product, for vibration problems where `B` is a
+    /// positive semidefinite mass matrix.
+    ShiftInvert,
+    /// Operator `(A - σ B)⁻¹ A` with the `A` inner product, for buckling problems where `A` is a
+    /// positive definite stiffness and `B` an indefinite geometric stiffness. The shift must not
+    /// be zero.
+    Buckling,
+}
+
+/// Controls of [`lanczos`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LanczosOptions {
+    pub shift: Float,
+    pub transformation: Transformation,
+    /// An eigenpair is converged when its estimated residual falls below this fraction of the
+    /// corresponding eigenvalue of the operator.
+    pub tolerance: Float,
+    /// Maximum dimension of the Krylov subspace.
+    pub max_iterations: usize,
+}
+
+impl Default for LanczosOptions {
+    fn default() -> Self {
+        Self {
+            shift: 0.0,
+            transformation: Transformation::ShiftInvert,
+            tolerance: Float::EPSILON.sqrt(),
+            max_iterations: 200,
+        }
+    }
+}
+
+/// Eigenpair computed by [`lanczos`], with its convergence information.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eigenpair {
+    pub value: Float,
+    /// Eigenvector normalized in the inner product of the transformation.
+    pub vector: Vec<Float>,
+    /// Estimated relative residual of the operator eigenpair.
+    pub residual: Float,
+    pub converged: bool,
+}
+
+fn dot(a: &[Float], b: &[Float]) -> Float {
+    stable_sum(a.iter().zip(b).map(|(a, b)| a * b))
+}
+
+/// Computes the `count` eigenpairs of `A φ = λ B φ` nearest the shift by the Lanczos method with
+/// full reorthogonalization on the spectral transformation, factorizing `A - σ B` once.
+///
+/// The pairs are ordered by the distance of their eigenvalues from the shift. Fewer pairs are
+/// returned if the Krylov subspace becomes invariant first. Pairs that have not converged within
+/// the iteration limit are returned with `converged` unset. Returns `None` if `A - σ B` is
+/// singular.
+///
+/// # Panics
+///
+/// Panics if the matrices are not square or have different sizes.
+pub fn lanczos(
+    a: &CsrMatrix,
+    b: &CsrMatrix,
+    count: usize,
+    options: &LanczosOptions,
+) -> Option<Vec<Eigenpair>> {
+    uncancelled(|cancel| lanczos_with_cancel(a, b, count, options, cancel))
+}
+
+/// [`lanczos`] checking the `cancel` token during the factorization and before each iteration,
+/// stopping with the number of iterations done out of the most it would have taken.
+///
+/// # Panics
+///
+/// Panics if the matrices are not square or have different sizes.
+pub fn lanczos_with_cancel(
+    a: &CsrMatrix,
+    b: &CsrMatrix,
+    count: usize,
+    options: &LanczosOptions,
+    cancel: &CancelToken,
+) -> Result<Option<Vec<Eigenpair>>, Cancelled> {
+    assert!(a.rows() == a.cols(), "matrix is not square");
+    let n = a.rows();
+    let steps = options.max_iterations.min(n);
+    let shifted = a.add_scaled(b, -options.shift);
+    let storage = DirectSolver::select_storage(&shifted);
+    let Some(solver) =
+        DirectSolver::with_cancel(&shifted, storage, cancel).map_err(|_| Cancelled {
+            done: 0,
+            total: steps,
+        })?
+    else {
+        return Ok(None);
+    };
+    // Eigenvalues at infinity map to the center of the operator spectrum.
+    let (w, center) = match options.transformation {
+        Transformation::ShiftInvert => (b, 0.0),
+        Transformation::Buckling => (a, 1.0),
+    };
+    if count == 0 || steps == 0 {
+        return Ok(Some(Vec::new()));
+    }
+    // Starting in the range of the operator purges the infinite modes of a singular `B`.
+    let start: Vec<Float> = (0..n)
+        .map(|i| ((i + 1) as Float * 0.618_034).fract() - 0.5)
+        .collect();
+    let mut r = solver.solve(&w.mul_vec(&start));
+    let mut wr = w.mul_vec(&r);
+    let mut norm = dot(&r, &wr).sqrt();
+    if norm == 0.0 || !norm.is_finite() {
+        return Ok(Some(Vec::new()));
+    }
+    let mut q: Vec<Vec<Float>> = Vec::new();
+    let mut wq: Vec<Vec<Float>> = Vec::new();
+    let mut alpha = Vec::new();
+    let mut beta = Vec::new();
+    loop {
+        cancel.check(q.len(), steps)?;
+        q.push(r.iter().map(|r| r / norm).collect());
+        wq.push(wr.iter().map(|r| r / norm).collect());
+        let j = q.len() - 1;
+        r = solver.solve(&wq[j]);
+        if j > 0 {
+            for (r, q) in r.iter_mut().zip(&q[j - 1]) {
+                *r -= norm * q;
+            }
+        }
+        alpha.push(dot(&wq[j], &r));
+        // Twice is enough to keep the basis orthogonal to working precision.
+        for _ in 0..2 {
+            for (q, wq) in q.iter().zip(&wq) {
+                let c = dot(wq, &r);
+                for (r, q) in r.iter_mut().zip(q) {
+                    *r -= c * q;
+                }
+            }
+        }
+        wr = w.mul_vec(&r);
+        norm = dot(&wr, &r).max(0.0).sqrt();
+        let scale = alpha.iter().map(|a: &Float| a.abs()).fold(norm, Float::max);
+        let invariant = norm <= Float::EPSILON * scale;
+        let (theta, last) = tridiagonal_eigen(
+            &alpha,
+            &beta,
+            &Matrix::from_fn(1, j + 1, |_, k| if k == j { 1.0 } else { 0.0 }),
+        );
+        let order = nearest(&theta, center, count);
+        let converged = order
+            .iter()
+            .all(|&k| norm * last[(0, k)].abs() <= options.tolerance * theta[k].abs());
+        if invariant || (converged && order.len() == count) || q.len() == steps {
+            let (theta, s) = tridiagonal_eigen(&alpha, &beta, &Matrix::identity(j + 1));
+            return Ok(Some(
+                nearest(&theta, center, count)
+                    .into_iter()
+                    .map(|k| {
+                        let residual = if invariant {
+                            0.0
+                        } else {
+                            norm * s[(j, k)].abs() / theta[k].abs()
+                        };
+                        let mut vector = vec![0.0; n];
+                        for (l, q) in q.iter().enumerate() {
+                            for (v, q) in vector.iter_mut().zip(q) {
+                                *v += s[(l, k)] * q;
+                            }
+                        }
+                        Eigenpair {
+                            value: match options.transformation {
+                                Transformation::ShiftInvert => options.shift + 1.0 / theta[k],
+                                Transformation::Buckling => {
+                                    options.shift * theta[k] / (theta[k] - 1.0)
+                                }
+                            },
+                            vector,
+                            residual,
+                            converged: residual <= options.tolerance,
+                        }
+                    })
+                    .collect(),
+            ));
+        }
+        beta.push(norm);
+    }
+}
+
+/// Indices of at most `count` values farthest from `center`, in decreasing distance.
+fn nearest(theta: &[Float], center: Float, count: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..theta.len()).collect();
+    order.sort_by(|&i, &j| {
+        (theta[j] - center)
+            .abs()
+            .total_cmp(&(theta[i] - center).abs())
+    });
+    order.truncate(count);
+    order
+}
+
+/// Eigenvalues of the symmetric tridiagonal matrix with diagonal `d` and off-diagonal `e` by the
+/// implicit QL method, together with `z` multiplied by the eigenvectors. Passing selected rows of
+/// the identity as `z` yields only those rows of the eigenvectors.
+fn tridiagonal_eigen(d: &[Float], e: &[Float], z: &Matrix<Float>) -> (Vec<Float>, Matrix<Float>) {
+    let n = d.len();
+    let mut d = d.to_vec();
+    let mut e: Vec<Float> = e.iter().copied().chain([0.0]).collect();
+    let mut z = z.clone();
+    for l in 0..n {
+        for _ in 0..64 {
+            let m = (l..n - 1)
+                .find(|&m| e[m].abs() <= Float::EPSILON * (d[m].abs() + d[m + 1].abs()))
+                .unwrap_or(n - 1);
+            if m == l {
+                break;
+            }
+            let mut g = (d[l + 1] - d[l]) / (2.0 * e[l]);
+            let mut r = g.hypot(1.0);
+            g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+            let (mut s, mut c, mut p) = (1.0, 1.0, 0.0);
+            let mut deflated = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == 0.0 {
+                    d[i + 1] -= p;
+                    e[m] = 0.0;
+                    deflated = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + 2.0 * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+                for k in 0..z.rows() {
+                    let f = z[(k, i + 1)];
+                    z[(k, i + 1)] = s * z[(k, i)] + c * f;
+                    z[(k, i)] = c * z[(k, i)] - s * f;
+                }
+            }
+            if !deflated {
+                d[l] -= p;
+                e[l] = g;
+                e[m] = 0.0;
+            }
+        }
+    }
+    (d, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stiffness of a chain of `n` unit springs fixed at both ends.
+    fn chain(n: usize) -> CsrMatrix {
+        CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(i, i, 2.0)];
+                if i + 1 < n {
+                    t.push((i, i + 1, -1.0));
+                    t.push((i + 1, i, -1.0));
+                }
+                t
+            }),
+        )
+    }
+
+    fn identity(n: usize) -> CsrMatrix {
+        CsrMatrix::from_triplets(n, n, (0..n).map(|i| (i, i, 1.0)))
+    }
+
+    /// Eigenvalue `j` of [`chain`], counting from one.
+    fn exact(n: usize, j: usize) -> Float {
+        let pi = (180.0 as Float).to_radians();
+        2.0 * (1.0 - (j as Float * pi / (n + 1) as Float).cos())
+    }
+
+    #[test]
+    fn tridiagonal() {
+        let d = [2.0, 2.0, 2.0, 2.0];
+        let e = [-1.0, -1.0, -1.0];
+        let (mut values, _) = tridiagonal_eigen(&d, &e, &Matrix::identity(4));
+        values.sort_by(Float::total_cmp);
+        for (j, v) in values.iter().enumerate() {
+            assert!((v - exact(4, j + 1)).abs() < Float::EPSILON * 1e2);
+        }
+    }
+
+    #[test]
+    fn vibration() {
+        let n = 60;
+        let k = chain(n);
+        // Lumped masses of 2 give the eigenvalues of the chain halved.
+        let m = CsrMatrix::from_triplets(n, n, (0..n).map(|i| (i, i, 2.0)));
+        let pairs = lanczos(&k, &m, 4, &LanczosOptions::default()).unwrap();
+        assert_eq!(pairs.len(), 4);
+        for (j, p) in pairs.iter().enumerate() {
+            assert!(p.converged);
+            assert!((p.value - exact(n, j + 1) / 2.0).abs() < exact(n, 1) * 1e-3);
+            let mass: Float = p.vector.iter().map(|v| 2.0 * v * v).sum();
+            assert!((mass - 1.0).abs() < 1e-3);
+            let kv = k.mul_vec(&p.vector);
+            let mv = m.mul_vec(&p.vector);
+            for (kv, mv) in kv.iter().zip(&mv) {
+                assert!((kv - p.value * mv).abs() < 1e-2 * p.value);
+            }
+        }
+    }
+
+    #[test]
+    fn interior_shift() {
+        let n = 40;
+        let options = LanczosOptions {
+            shift: exact(n, 10) + 1e-3,
+            ..Default::default()
+        };
+        let pairs = lanczos(&chain(n), &identity(n), 1, &options).unwrap();
+        assert!(pairs[0].converged);
+        assert!((pairs[0].value - exact(n, 10)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn buckling() {
+        let n = 30;
+        let options = LanczosOptions {
+            shift: -1.0,
+            transformation: Transformation::Buckling,
+            ..Default::default()
+        };
+        let pairs = lanczos(&chain(n), &identity(n), 3, &options).unwrap();
+        for (j, p) in pairs.iter().enumerate() {
+            assert!(p.converged);
+            assert!((p.value - exact(n, j + 1)).abs() < exact(n, 1) * 1e-3);
+        }
+    }
+
+    #[test]
+    fn cancel() {
+        let n = 30;
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let options = LanczosOptions::default();
+        let result = lanczos_with_cancel(&chain(n), &identity(n), 3, &options, &cancel);
+        assert_eq!(result, Err(Cancelled { done: 0, total: n }));
+    }
+
+    #[test]
+    fn not_converged() {
+        let n = 100;
+        let options = LanczosOptions {
+            max_iterations: 3,
+            tolerance: 1e-12,
+            ..Default::default()
+        };
+        let pairs = lanczos(&chain(n), &identity(n), 3, &options).unwrap();
+        assert_eq!(pairs.len(), 3);
+        assert!(!pairs[2].converged);
+    }
+
+    #[test]
+    fn invariant_subspace() {
+        let pairs = lanczos(&identity(5), &identity(5), 3, &LanczosOptions::default()).unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert!((pairs[0].value - 1.0).abs() < 1e-5);
+        assert!(pairs[0].converged);
+    }
+}
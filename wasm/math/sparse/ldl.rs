@@ -0,0 +1,271 @@
+use super::{csr::CsrMatrix, scalar::Scalar};
+use crate::{
+    cancel::{uncancelled, CancelToken, Cancelled},
+    Float,
+};
+
+/// Sparse `L D Lᵀ` factors of a symmetric matrix, computed by the up-looking algorithm over the
+/// elimination tree (Davis, 2005). `L` is stored by columns without its unit diagonal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseLdl<T = Float> {
+    col_ptr: Vec<usize>,
+    row_idx: Vec<usize>,
+    values: Vec<T>,
+    d: Vec<T>,
+}
+
+/// Elimination tree and column counts of the factor of a symmetric matrix.
+pub struct Symbolic {
+    parent: Vec<Option<usize>>,
+    counts: Vec<usize>,
+}
+
+impl Symbolic {
+    /// Analyzes the pattern of the upper triangle of `a`.
+    pub fn new<T: Scalar>(a: &CsrMatrix<T>) -> Self {
+        let n = a.rows();
+        let mut parent = vec![None; n];
+        let mut counts = vec![0; n];
+        let mut flag = vec![usize::MAX; n];
+        for k in 0..n {
+            flag[k] = k;
+            for &j in a.row_indices(k).iter().take_while(|&&j| j < k) {
+                let mut i = j;
+                while flag[i] != k {
+                    if parent[i].is_none() {
+                        parent[i] = Some(k);
+                    }
+                    counts[i] += 1;
+                    flag[i] = k;
+                    i = parent[i].unwrap();
+                }
+            }
+        }
+        Self { parent, counts }
+    }
+
+    /// Number of off-diagonal elements of `L`.
+    pub fn nnz(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Number of elements of each column of `L` below the diagonal.
+    pub fn column_counts(&self) -> &[usize] {
+        &self.counts
+    }
+}
+
+impl<T: Scalar> SparseLdl<T> {
+    /// Factorizes a symmetric matrix, of which only the lower triangle is read. Complex matrices
+    /// are factorized as complex symmetric, not Hermitian.
+    ///
+    /// Returns `None` if a zero or non-finite pivot is encountered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn new(a: &CsrMatrix<T>) -> Option<Self> {
+        assert!(a.rows() == a.cols(), "matrix is not square");
+        Self::with_symbolic(a, &Symbolic::new(a))
+    }
+
+    /// Factorizes reusing the symbolic analysis of a matrix with the same pattern.
+    pub fn with_symbolic(a: &CsrMatrix<T>, symbolic: &Symbolic) -> Option<Self> {
+        uncancelled(|cancel| Self::with_cancel(a, symbolic, cancel))
+    }
+
+    /// [`Self::with_symbolic`] checking the `cancel` token before each row.
+    pub fn with_cancel(
+        a: &CsrMatrix<T>,
+        symbolic: &Symbolic,
+        cancel: &CancelToken,
+    ) -> Result<Option<Self>, Cancelled> {
+        let n = a.rows();
+        let mut col_ptr = vec![0; n + 1];
+        for i in 0..n {
+            col_ptr[i + 1] = col_ptr[i] + symbolic.counts[i];
+        }
+        let mut row_idx = vec![0; col_ptr[n]];
+        let mut values = vec![T::default(); col_ptr[n]];
+        let mut d = vec![T::default(); n];
+        let mut y = vec![T::default(); n];
+        let mut len = vec![0; n];
+        let mut flag = vec![usize::MAX; n];
+        let mut pattern = vec![0; n];
+        for k in 0..n {
+            cancel.check(k, n)?;
+            // Nonzero pattern of row k of L, in topological order in pattern[top..].
+            let mut top = n;
+            flag[k] = k;
+            for (j, v) in a.row(k).take_while(|&(j, _)| j <= k) {
+                y[j] += v;
+                let mut l = 0;
+                let mut i = j;
+                while flag[i] != k {
+                    pattern[l] = i;
+                    l += 1;
+                    flag[i] = k;
+                    i = symbolic.parent[i].unwrap();
+                }
+                while l > 0 {
+                    top -= 1;
+                    l -= 1;
+                    pattern[top] = pattern[l];
+                }
+            }
+            d[k] = y[k];
+            y[k] = T::default();
+            for &i in &pattern[top..n] {
+                let yi = y[i];
+                y[i] = T::default();
+                let end = col_ptr[i] + len[i];
+                for p in col_ptr[i]..end {
+                    y[row_idx[p]] -= values[p] * yi;
+                }
+                let l = yi / d[i];
+                d[k] -= l * yi;
+                row_idx[end] = k;
+                values[end] = l;
+                len[i] += 1;
+            }
+            if d[k] == T::default() || !d[k].is_finite() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(Self {
+            col_ptr,
+            row_idx,
+            values,
+            d,
+        }))
+    }
+
+    /// Number of off-diagonal elements of `L`.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Number of elements of each column of `L` below the diagonal.
+    pub fn column_counts(&self) -> Vec<usize> {
+        self.col_ptr.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Pivots `D`.
+    pub fn pivots(&self) -> &[T] {
+        &self.d
+    }
+
+    /// Solves `A x = b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not the dimension of the matrix.
+    pub fn solve(&self, b: &[T]) -> Vec<T> {
+        self.solve_block(b, 1)
+    }
+
+    /// Solves `A X = B` for the `k` columns of `B` stored by rows, traversing the factors once for
+    /// all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not `k` times the dimension of the matrix.
+    pub fn solve_block(&self, b: &[T], k: usize) -> Vec<T> {
+        let n = self.d.len();
+        assert_eq!(b.len(), n * k, "vector size mismatch");
+        let mut x = b.to_vec();
+        if k == 0 {
+            return x;
+        }
+        for j in 0..n {
+            for p in self.col_ptr[j]..self.col_ptr[j + 1] {
+                let (l, i) = (self.values[p], self.row_idx[p]);
+                for c in 0..k {
+                    let v = l * x[j * k + c];
+                    x[i * k + c] -= v;
+                }
+            }
+        }
+        for (x, &d) in x.chunks_mut(k).zip(&self.d) {
+            for x in x {
+                *x = *x / d;
+            }
+        }
+        for j in (0..n).rev() {
+            for p in self.col_ptr[j]..self.col_ptr[j + 1] {
+                let (l, i) = (self.values[p], self.row_idx[p]);
+                for c in 0..k {
+                    let v = l * x[i * k + c];
+                    x[j * k + c] -= v;
+                }
+            }
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::Matrix;
+
+    /// Arrowhead matrix with the hub first, which fills in completely.
+    fn arrowhead(n: usize) -> CsrMatrix {
+        CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(i, i, 4.0 + i as Float)];
+                if i > 0 {
+                    t.push((0, i, 1.0));
+                    t.push((i, 0, 1.0));
+                }
+                t
+            }),
+        )
+    }
+
+    #[test]
+    fn symbolic() {
+        let a = arrowhead(5);
+        assert_eq!(Symbolic::new(&a).nnz(), 4 + 3 + 2 + 1);
+        assert_eq!(Symbolic::new(&a.permute(&[4, 3, 2, 1, 0])).nnz(), 4);
+    }
+
+    #[test]
+    fn solve() {
+        let a = arrowhead(6);
+        let x = [1.0, -2.0, 0.5, 3.0, -1.5, 2.0];
+        let f = SparseLdl::new(&a).unwrap();
+        assert_eq!(f.nnz(), 15);
+        for (y, x) in f.solve(&a.mul_vec(&x)).iter().zip(x) {
+            assert!((y - x).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn matches_dense() {
+        #[rustfmt::skip]
+        let a = Matrix::new(4, 4, vec![
+            2.0, 0.0, -1.0, 0.0,
+            0.0, 3.0, 0.0, 1.0,
+            -1.0, 0.0, -4.0, 2.0,
+            0.0, 1.0, 2.0, 5.0,
+        ]);
+        let b = [1.0, 2.0, 3.0, 4.0];
+        let x = SparseLdl::new(&CsrMatrix::from_dense(&a))
+            .unwrap()
+            .solve(&b);
+        let y = a.solve(&Matrix::new(4, 1, b.to_vec())).unwrap();
+        for (x, y) in x.iter().zip(y.as_slice()) {
+            assert!((x - y).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn singular() {
+        let a =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 1.0), (0, 1, 1.0), (1, 0, 1.0), (1, 1, 1.0)]);
+        assert_eq!(SparseLdl::new(&a), None);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod amd;
+pub mod cg;
+pub mod csr;
+pub mod damping;
+pub mod diagnostics;
+pub mod direct;
+pub mod harmonic;
+pub mod ic;
+pub mod lanczos;
+pub mod ldl;
+pub mod rcm;
+pub mod scalar;
+pub mod skyline;
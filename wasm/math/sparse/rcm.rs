@@ -0,0 +1,137 @@
+use super::csr::CsrMatrix;
+use std::collections::VecDeque;
+
+/// Reverse Cuthill-McKee ordering of a structurally symmetric matrix, reducing its bandwidth and
+/// profile.
+///
+/// Returns the old indices in their new order, as taken by [`CsrMatrix::permute`]. Each connected
+/// component starts from a pseudo-peripheral node.
+pub fn reverse_cuthill_mckee(a: &CsrMatrix) -> Vec<usize> {
+    let n = a.rows();
+    let degree: Vec<usize> = (0..n)
+        .map(|i| a.row_indices(i).iter().filter(|&&j| j != i).count())
+        .collect();
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut nodes: Vec<usize> = (0..n).collect();
+    nodes.sort_by_key(|&i| degree[i]);
+    for &start in &nodes {
+        if visited[start] {
+            continue;
+        }
+        let start = pseudo_peripheral(a, start, &degree);
+        let first = order.len();
+        visited[start] = true;
+        order.push(start);
+        let mut k = first;
+        while k < order.len() {
+            let i = order[k];
+            let mut next: Vec<usize> = a
+                .row_indices(i)
+                .iter()
+                .copied()
+                .filter(|&j| !visited[j])
+                .collect();
+            next.sort_by_key(|&j| degree[j]);
+            for j in next {
+                visited[j] = true;
+                order.push(j);
+            }
+            k += 1;
+        }
+    }
+    order.reverse();
+    order
+}
+
+/// Finds a node of maximal eccentricity in the component of `start` by repeated breadth-first
+/// searches (George and Liu).
+fn pseudo_peripheral(a: &CsrMatrix, start: usize, degree: &[usize]) -> usize {
+    let mut node = start;
+    let (mut depth, mut last) = levels(a, node);
+    loop {
+        let candidate = *last.iter().min_by_key(|&&i| degree[i]).unwrap();
+        let (d, l) = levels(a, candidate);
+        if d <= depth {
+            return node;
+        }
+        node = candidate;
+        depth = d;
+        last = l;
+    }
+}
+
+/// Depth of the breadth-first level structure rooted at `root`, and the nodes of its last level.
+fn levels(a: &CsrMatrix, root: usize) -> (usize, Vec<usize>) {
+    let mut level = vec![usize::MAX; a.rows()];
+    level[root] = 0;
+    let mut queue = VecDeque::from([root]);
+    let mut depth = 0;
+    let mut last = vec![root];
+    while let Some(i) = queue.pop_front() {
+        for &j in a.row_indices(i) {
+            if level[j] == usize::MAX {
+                level[j] = level[i] + 1;
+                if level[j] > depth {
+                    depth = level[j];
+                    last.clear();
+                }
+                last.push(j);
+                queue.push_back(j);
+            }
+        }
+    }
+    (depth, last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tridiagonal matrix of a path graph with its nodes shuffled.
+    fn shuffled_path(n: usize) -> CsrMatrix {
+        let label = |i: usize| (i * 7) % n;
+        CsrMatrix::from_triplets(
+            n,
+            n,
+            (0..n).flat_map(|i| {
+                let mut t = vec![(label(i), label(i), 2.0)];
+                if i + 1 < n {
+                    t.push((label(i), label(i + 1), -1.0));
+                    t.push((label(i + 1), label(i), -1.0));
+                }
+                t
+            }),
+        )
+    }
+
+    #[test]
+    fn restores_band() {
+        let a = shuffled_path(20);
+        assert!(a.bandwidth() > 1);
+        let p = reverse_cuthill_mckee(&a);
+        let mut sorted = p.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_eq!(a.permute(&p).bandwidth(), 1);
+    }
+
+    #[test]
+    fn disconnected() {
+        let a = CsrMatrix::from_triplets(
+            4,
+            4,
+            [
+                (0, 0, 1.0),
+                (1, 1, 1.0),
+                (2, 2, 1.0),
+                (3, 3, 1.0),
+                (0, 3, 1.0),
+                (3, 0, 1.0),
+            ],
+        );
+        let p = reverse_cuthill_mckee(&a);
+        assert_eq!(p.len(), 4);
+        assert_eq!(a.permute(&p).bandwidth(), 1);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{math::complex::Complex, Float};
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+/// Element of the sparse matrices and their factorizations, either real or complex.
+pub trait Scalar:
+    Copy
+    + Send
+    + Sync
+    + Debug
+    + Default
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    fn from_real(value: Float) -> Self;
+
+    /// Absolute value.
+    fn modulus(self) -> Float;
+
+    fn is_finite(self) -> bool {
+        self.modulus().is_finite()
+    }
+
+    /// Sum of the `values` times the elements of `x` at the `columns`, a row of a sparse product.
+    fn gather_dot(values: &[Self], columns: &[usize], x: &[Self]) -> Self {
+        (values.iter().zip(columns)).fold(Self::default(), |s, (&v, &j)| s + v * x[j])
+    }
+}
+
+impl Scalar for Float {
+    fn from_real(value: Float) -> Self {
+        value
+    }
+
+    fn modulus(self) -> Float {
+        self.abs()
+    }
+
+    /// By the kernel of [`simd`](crate::math::simd).
+    fn gather_dot(values: &[Self], columns: &[usize], x: &[Self]) -> Self {
+        crate::math::simd::gather_dot(values, columns, x)
+    }
+}
+
+impl Scalar for Complex<Float> {
+    fn from_real(value: Float) -> Self {
+        Complex::new(value, 0.0)
+    }
+
+    fn modulus(self) -> Float {
+        self.re.hypot(self.im)
+    }
+}
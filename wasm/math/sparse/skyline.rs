@@ -0,0 +1,253 @@
+use super::csr::CsrMatrix;
+use crate::{
+    cancel::{uncancelled, CancelToken, Cancelled},
+    Float,
+};
+
+/// Symmetric matrix storing the lower triangle row by row, from the first nonzero column of each
+/// row (the skyline) to the diagonal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkylineMatrix {
+    first: Vec<usize>,
+    row_ptr: Vec<usize>,
+    values: Vec<Float>,
+}
+
+impl SkylineMatrix {
+    /// Copies the lower triangle of a symmetric matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn from_csr(a: &CsrMatrix) -> Self {
+        assert!(a.rows() == a.cols(), "matrix is not square");
+        let n = a.rows();
+        let first = skyline(a);
+        let mut row_ptr = vec![0; n + 1];
+        for i in 0..n {
+            row_ptr[i + 1] = row_ptr[i] + i + 1 - first[i];
+        }
+        let mut values = vec![0.0; row_ptr[n]];
+        for i in 0..n {
+            for (j, v) in a.row(i).take_while(|&(j, _)| j <= i) {
+                values[row_ptr[i] + j - first[i]] = v;
+            }
+        }
+        Self {
+            first,
+            row_ptr,
+            values,
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.first.len()
+    }
+
+    /// Number of stored elements, including the zeros inside the skyline.
+    pub fn profile(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Element `(i, j)` of the lower triangle, `j <= i`.
+    fn get(&self, i: usize, j: usize) -> Float {
+        if j < self.first[i] {
+            0.0
+        } else {
+            self.values[self.row_ptr[i] + j - self.first[i]]
+        }
+    }
+
+    /// Factorizes into `L D Lᵀ` in place. The factors keep the skyline, so no fill-in occurs
+    /// outside the stored profile.
+    ///
+    /// Returns `None` if a zero or non-finite pivot is encountered.
+    pub fn factorize(self) -> Option<SkylineLdl> {
+        uncancelled(|cancel| self.factorize_with_cancel(cancel))
+    }
+
+    /// [`Self::factorize`] checking the `cancel` token before each row.
+    pub fn factorize_with_cancel(
+        mut self,
+        cancel: &CancelToken,
+    ) -> Result<Option<SkylineLdl>, Cancelled> {
+        let n = self.dim();
+        let mut d = vec![0.0; n];
+        for i in 0..n {
+            cancel.check(i, n)?;
+            let fi = self.first[i];
+            let ri = self.row_ptr[i];
+            // Row i holds g = L D first, then L after division by the pivots.
+            for j in fi..i {
+                let fj = self.first[j];
+                let rj = self.row_ptr[j];
+                let k0 = fi.max(fj);
+                let s: Float = (k0..j)
+                    .map(|k| self.values[ri + k - fi] * self.values[rj + k - fj])
+                    .sum();
+                self.values[ri + j - fi] -= s;
+            }
+            let mut di = self.values[ri + i - fi];
+            for (v, dj) in self.values[ri..ri + i - fi].iter_mut().zip(&d[fi..i]) {
+                let l = *v / dj;
+                di -= l * *v;
+                *v = l;
+            }
+            if di == 0.0 || !di.is_finite() {
+                return Ok(None);
+            }
+            d[i] = di;
+            self.values[ri + i - fi] = 1.0;
+        }
+        Ok(Some(SkylineLdl { l: self, d }))
+    }
+}
+
+/// First stored column of each row of the lower triangle.
+pub fn skyline(a: &CsrMatrix) -> Vec<usize> {
+    (0..a.rows())
+        .map(|i| a.row_indices(i).first().map_or(i, |&j| j.min(i)))
+        .collect()
+}
+
+/// Number of elements of each column below the diagonal of the skyline of the `first` stored
+/// columns of the rows, that of `L` as the factorization fills the skyline.
+pub fn column_counts(first: &[usize]) -> Vec<usize> {
+    let n = first.len();
+    // Row i covers the columns from its first to i, exclusive.
+    let mut steps = vec![0_isize; n + 1];
+    for (i, &first) in first.iter().enumerate() {
+        steps[first] += 1;
+        steps[i] -= 1;
+    }
+    steps[..n]
+        .iter()
+        .scan(0, |count, step| {
+            *count += step;
+            Some(*count as usize)
+        })
+        .collect()
+}
+
+/// `L D Lᵀ` factors in skyline storage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkylineLdl {
+    l: SkylineMatrix,
+    d: Vec<Float>,
+}
+
+impl SkylineLdl {
+    /// Number of elements of each column of `L` below the diagonal, including the zeros inside the
+    /// skyline.
+    pub fn column_counts(&self) -> Vec<usize> {
+        column_counts(&self.l.first)
+    }
+
+    /// Pivots `D`.
+    pub fn pivots(&self) -> &[Float] {
+        &self.d
+    }
+
+    /// Solves `A x = b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not the dimension of the matrix.
+    pub fn solve(&self, b: &[Float]) -> Vec<Float> {
+        self.solve_block(b, 1)
+    }
+
+    /// Solves `A X = B` for the `k` columns of `B` stored by rows, traversing the factors once for
+    /// all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len()` is not `k` times the dimension of the matrix.
+    pub fn solve_block(&self, b: &[Float], k: usize) -> Vec<Float> {
+        let n = self.l.dim();
+        assert_eq!(b.len(), n * k, "vector size mismatch");
+        let mut x = b.to_vec();
+        if k == 0 {
+            return x;
+        }
+        let mut s = vec![0.0; k];
+        for i in 0..n {
+            s.fill(0.0);
+            for j in self.l.first[i]..i {
+                let l = self.l.get(i, j);
+                for (s, x) in s.iter_mut().zip(&x[j * k..(j + 1) * k]) {
+                    *s += l * x;
+                }
+            }
+            for (x, s) in x[i * k..(i + 1) * k].iter_mut().zip(&s) {
+                *x -= s;
+            }
+        }
+        for (x, d) in x.chunks_mut(k).zip(&self.d) {
+            for x in x {
+                *x /= d;
+            }
+        }
+        for i in (0..n).rev() {
+            for j in self.l.first[i]..i {
+                let l = self.l.get(i, j);
+                for c in 0..k {
+                    x[j * k + c] -= l * x[i * k + c];
+                }
+            }
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::Matrix;
+
+    fn sample() -> CsrMatrix {
+        #[rustfmt::skip]
+        let a = Matrix::new(5, 5, vec![
+            4.0, 1.0, 0.0, 0.0, 0.0,
+            1.0, 5.0, 2.0, 0.0, 1.0,
+            0.0, 2.0, 6.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 3.0, -1.0,
+            0.0, 1.0, 0.0, -1.0, 7.0,
+        ]);
+        CsrMatrix::from_dense(&a)
+    }
+
+    #[test]
+    fn profile() {
+        let s = SkylineMatrix::from_csr(&sample());
+        assert_eq!(skyline(&sample()), [0, 0, 1, 3, 1]);
+        assert_eq!(s.profile(), 1 + 2 + 2 + 1 + 4);
+    }
+
+    #[test]
+    fn solve() {
+        let a = sample();
+        let x = [1.0, -2.0, 0.5, 3.0, -1.5];
+        let f = SkylineMatrix::from_csr(&a).factorize().unwrap();
+        let y = f.solve(&a.mul_vec(&x));
+        for (y, x) in y.iter().zip(x) {
+            assert!((y - x).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn indefinite() {
+        let a =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 1.0), (0, 1, 2.0), (1, 0, 2.0), (1, 1, 1.0)]);
+        let f = SkylineMatrix::from_csr(&a).factorize().unwrap();
+        assert_eq!(f.pivots(), [1.0, -3.0]);
+        assert_eq!(f.solve(&[3.0, 3.0]), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn singular() {
+        let a =
+            CsrMatrix::from_triplets(2, 2, [(0, 0, 1.0), (0, 1, 1.0), (1, 0, 1.0), (1, 1, 1.0)]);
+        assert_eq!(SkylineMatrix::from_csr(&a).factorize(), None);
+    }
+}
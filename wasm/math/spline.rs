@@ -0,0 +1,189 @@
+use crate::Float;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Boundary {
+    /// Zero second derivatives at both ends.
+    Natural,
+    /// Prescribed first derivatives at the first and the last points.
+    Clamped([Float; 2]),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicSpline {
+    x: Vec<Float>,
+    y: Vec<Float>,
+    m: Vec<Float>,
+}
+
+impl CubicSpline {
+    /// Constructs a cubic spline interpolating `points` given as `[x, y]` pairs.
+    ///
+    /// Returns `None` if fewer than two points are given or `x` is not strictly increasing.
+    pub fn new(points: &[[Float; 2]], boundary: Boundary) -> Option<Self> {
+        if points.len() < 2 || points.windows(2).any(|p| p[0][0] >= p[1][0]) {
+            return None;
+        }
+        let (x, y): (Vec<Float>, Vec<Float>) = points.iter().map(|&[x, y]| (x, y)).unzip();
+        let n = x.len();
+        let h: Vec<Float> = x.windows(2).map(|w| w[1] - w[0]).collect();
+        let s: Vec<Float> = y
+            .windows(2)
+            .zip(&h)
+            .map(|(w, h)| (w[1] - w[0]) / h)
+            .collect();
+        // Tridiagonal system for the second derivatives `m`.
+        let mut lower = vec![0.0; n];
+        let mut diag = vec![1.0; n];
+        let mut upper = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+        for i in 1..n - 1 {
+            lower[i] = h[i - 1];
+            diag[i] = (h[i - 1] + h[i]) * 2.0;
+            upper[i] = h[i];
+            rhs[i] = (s[i] - s[i - 1]) * 6.0;
+        }
+        if let Boundary::Clamped([d0, dn]) = boundary {
+            diag[0] = h[0] * 2.0;
+            upper[0] = h[0];
+            rhs[0] = (s[0] - d0) * 6.0;
+            lower[n - 1] = h[n - 2];
+            diag[n - 1] = h[n - 2] * 2.0;
+            rhs[n - 1] = (dn - s[n - 2]) * 6.0;
+        }
+        // Thomas algorithm.
+        for i in 1..n {
+            let w = lower[i] / diag[i - 1];
+            diag[i] -= w * upper[i - 1];
+            rhs[i] -= w * rhs[i - 1];
+        }
+        let mut m = rhs;
+        m[n - 1] /= diag[n - 1];
+        for i in (0..n - 1).rev() {
+            m[i] = (m[i] - upper[i] * m[i + 1]) / diag[i];
+        }
+        Some(Self { x, y, m })
+    }
+
+    /// Index of the interval containing `x`. Points outside the range use the end intervals.
+    fn interval(&self, x: Float) -> usize {
+        self.x[1..self.x.len() - 1].partition_point(|&v| v <= x)
+    }
+
+    pub fn value(&self, x: Float) -> Float {
+        let i = self.interval(x);
+        let h = self.x[i + 1] - self.x[i];
+        let t = x - self.x[i];
+        let u = self.x[i + 1] - x;
+        (self.m[i] * u * u * u + self.m[i + 1] * t * t * t) / (h * 6.0)
+            + (self.y[i] / h - self.m[i] * h / 6.0) * u
+            + (self.y[i + 1] / h - self.m[i + 1] * h / 6.0) * t
+    }
+
+    pub fn derivative(&self, x: Float) -> Float {
+        let i = self.interval(x);
+        let h = self.x[i + 1] - self.x[i];
+        let t = x - self.x[i];
+        let u = self.x[i + 1] - x;
+        (self.m[i + 1] * t * t - self.m[i] * u * u) / (h * 2.0) + (self.y[i + 1] - self.y[i]) / h
+            - (self.m[i + 1] - self.m[i]) * h / 6.0
+    }
+
+    pub fn second_derivative(&self, x: Float) -> Float {
+        let i = self.interval(x);
+        let h = self.x[i + 1] - self.x[i];
+        (self.m[i] * (self.x[i + 1] - x) + self.m[i + 1] * (x - self.x[i])) / h
+    }
+
+    /// Samples the spline at `count` evenly spaced points over the interpolated range.
+    pub fn resample(&self, count: usize) -> Vec<[Float; 2]> {
+        let first = self.x[0];
+        let last = self.x[self.x.len() - 1];
+        let step = (last - first) / (count.max(2) - 1) as Float;
+        (0..count)
+            .map(|i| {
+                let x = if i + 1 == count {
+                    last
+                } else {
+                    first + step * i as Float
+                };
+                [x, self.value(x)]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_points() {
+        assert_eq!(CubicSpline::new(&[[0.0, 1.0]], Boundary::Natural), None);
+        assert_eq!(
+            CubicSpline::new(&[[0.0, 1.0], [0.0, 2.0]], Boundary::Natural),
+            None
+        );
+        assert_eq!(
+            CubicSpline::new(&[[1.0, 1.0], [0.5, 2.0], [2.0, 0.0]], Boundary::Natural),
+            None
+        );
+    }
+
+    #[test]
+    fn interpolates_points() {
+        let p = [
+            [-1.2, 3.1],
+            [0.3, -0.8],
+            [1.1, 2.4],
+            [2.9, 2.2],
+            [4.0, -1.3],
+        ];
+        for boundary in [Boundary::Natural, Boundary::Clamped([0.4, -2.1])] {
+            let s = CubicSpline::new(&p, boundary).unwrap();
+            for [x, y] in p {
+                assert!((s.value(x) - y).abs() < Float::EPSILON * 1e4);
+            }
+        }
+    }
+
+    #[test]
+    fn natural_boundary() {
+        let p = [[0.0, 0.0], [1.0, 2.3], [2.5, 1.9], [3.0, -0.4]];
+        let s = CubicSpline::new(&p, Boundary::Natural).unwrap();
+        assert!(s.second_derivative(0.0).abs() < Float::EPSILON * 1e4);
+        assert!(s.second_derivative(3.0).abs() < Float::EPSILON * 1e4);
+    }
+
+    #[test]
+    fn natural_linear() {
+        let s = CubicSpline::new(&[[1.0, 2.0], [3.0, 6.0]], Boundary::Natural).unwrap();
+        assert_eq!(s.value(2.0), 4.0);
+        assert_eq!(s.derivative(2.5), 2.0);
+        assert_eq!(s.value(-1.0), -2.0);
+    }
+
+    #[test]
+    fn clamped_cubic() {
+        // A clamped spline reproduces cubic polynomials exactly.
+        let f = |x: Float| x * x * x * 0.5 - x * x * 1.3 + x * 0.2 - 4.0;
+        let df = |x: Float| x * x * 1.5 - x * 2.6 + 0.2;
+        let p: Vec<[Float; 2]> = [-2.0, -0.5, 0.4, 1.8, 3.0]
+            .iter()
+            .map(|&x| [x, f(x)])
+            .collect();
+        let s = CubicSpline::new(&p, Boundary::Clamped([df(-2.0), df(3.0)])).unwrap();
+        for x in [-1.7, -0.1, 0.9, 2.2, 2.95] {
+            assert!((s.value(x) - f(x)).abs() < Float::EPSILON * 1e4);
+            assert!((s.derivative(x) - df(x)).abs() < Float::EPSILON * 1e4);
+        }
+    }
+
+    #[test]
+    fn resample() {
+        let s = CubicSpline::new(&[[0.0, 0.0], [2.0, 4.0]], Boundary::Natural).unwrap();
+        assert_eq!(
+            s.resample(5),
+            vec![[0.0, 0.0], [0.5, 1.0], [1.0, 2.0], [1.5, 3.0], [2.0, 4.0]]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! `proptest` strategies for the math types and helpers measuring the invariants they should
+//! satisfy. Available in tests and with the `proptest` feature.
+
+use super::{
+    complex::Complex, dual_quaternion::DualQuaternion, quaternion::Quaternion, vector::Vector,
+};
+use crate::Float;
+use proptest::prelude::*;
+
+/// Finite scalars in `[-100, 100]`.
+pub fn scalar() -> impl Strategy<Value = Float> {
+    -100.0 as Float..100.0
+}
+
+/// Scalars whose magnitude lies in `[0.01, 100]`, safe to divide by.
+pub fn nonzero_scalar() -> impl Strategy<Value = Float> {
+    (0.01 as Float..100.0, any::<bool>()).prop_map(|(v, neg)| if neg { -v } else { v })
+}
+
+pub fn vector() -> impl Strategy<Value = Vector<Float>> {
+    (scalar(), scalar(), scalar()).prop_map(|(x, y, z)| Vector::new(x, y, z))
+}
+
+pub fn complex() -> impl Strategy<Value = Complex<Float>> {
+    (scalar(), scalar()).prop_map(|(re, im)| Complex::new(re, im))
+}
+
+/// Complex numbers whose norm is at least `0.01`.
+pub fn nonzero_complex() -> impl Strategy<Value = Complex<Float>> {
+    complex().prop_filter("norm too small", |c| c.abs() >= 0.01)
+}
+
+pub fn quaternion() -> impl Strategy<Value = Quaternion<Float>> {
+    (vector(), scalar()).prop_map(|(v, w)| Quaternion::new(v, w))
+}
+
+/// Quaternions whose norm is at least `0.01`.
+pub fn nonzero_quaternion() -> impl Strategy<Value = Quaternion<Float>> {
+    quaternion().prop_filter("norm too small", |q| q.abs() >= 0.01)
+}
+
+/// Unit quaternions representing rotations of up to 2π about arbitrary axes.
+pub fn rotation() -> impl Strategy<Value = Quaternion<Float>> {
+    let max = (360.0 as Float).to_radians();
+    (-max..max, -max..max, -max..max)
+        .prop_map(|(x, y, z)| Quaternion::from_rotation(&Vector::new(x, y, z)))
+}
+
+pub fn dual_quaternion() -> impl Strategy<Value = DualQuaternion<Float>> {
+    (quaternion(), quaternion()).prop_map(|(p, q)| DualQuaternion::new(p, q))
+}
+
+/// Unit dual quaternions representing rigid transforms.
+pub fn rigid_transform() -> impl Strategy<Value = DualQuaternion<Float>> {
+    let max = (360.0 as Float).to_radians();
+    (-max..max, -max..max, -max..max, vector()).prop_map(|(x, y, z, t)| {
+        DualQuaternion::from_rotation_and_translation(&Vector::new(x, y, z), &t)
+    })
+}
+
+/// |q·q⁻¹ − 1|, which should vanish for invertible `q`.
+pub fn inverse_residual(q: &Quaternion<Float>) -> Float {
+    (&(q * &q.inv()) - &Quaternion::from(1.0)).abs()
+}
+
+/// ||qvq*| − |v||, which should vanish for unit `q`.
+pub fn rotation_length_error(q: &Quaternion<Float>, v: &Vector<Float>) -> Float {
+    let r = &(q * &Quaternion::from_translation(v)) * &q.conj();
+    (r.v.abs() - v.abs()).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Relative tolerance scaled to the precision of `Float`.
+    const TOLERANCE: Float = Float::EPSILON * 1.0e3;
+
+    proptest! {
+        #[test]
+        fn quaternion_inverse(q in nonzero_quaternion()) {
+            prop_assert!(inverse_residual(&q) < TOLERANCE);
+        }
+
+        #[test]
+        fn rotation_preserves_length(q in rotation(), v in vector()) {
+            prop_assert!((q.abs() - 1.0).abs() < TOLERANCE);
+            prop_assert!(rotation_length_error(&q, &v) < TOLERANCE * v.abs().max(1.0));
+        }
+
+        #[test]
+        fn rigid_transform_translation(r in vector(), t in vector()) {
+            let d = DualQuaternion::from_rotation_and_translation(&r, &t);
+            prop_assert!((&d.translation() - &t).abs() < TOLERANCE * t.abs().max(1.0));
+        }
+
+        #[test]
+        fn rigid_transform_inverse(d in rigid_transform()) {
+            let i = &d * &d.inv();
+            let e = (&i.p - &Quaternion::from(1.0)).abs() + i.q.abs();
+            prop_assert!(e < TOLERANCE * d.q.abs().max(1.0));
+        }
+
+        #[test]
+        fn complex_division(a in complex(), b in nonzero_complex()) {
+            let c = a / b * b;
+            prop_assert!((c - a).abs() < TOLERANCE * a.abs().max(1.0));
+        }
+
+        #[test]
+        fn vector_scaling(v in vector(), s in nonzero_scalar()) {
+            let w = &v * s / s;
+            prop_assert!((&w - &v).abs() < TOLERANCE * v.abs().max(1.0));
+        }
+    }
+}
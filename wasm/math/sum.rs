@@ -0,0 +1,42 @@
+use crate::Float;
+
+/// Sums `values` with Neumaier's compensated summation.
+///
+/// The rounding error of each addition is accumulated separately and added back at the end, so
+/// small terms are kept even where large ones cancel each other.
+pub fn stable_sum(values: impl IntoIterator<Item = Float>) -> Float {
+    let mut sum: Float = 0.0;
+    let mut compensation: Float = 0.0;
+    for v in values {
+        let t = sum + v;
+        compensation += if sum.abs() >= v.abs() {
+            (sum - t) + v
+        } else {
+            (v - t) + sum
+        };
+        sum = t;
+    }
+    sum + compensation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(stable_sum([]), 0.0);
+    }
+
+    #[test]
+    fn cancellation() {
+        assert_eq!(stable_sum([1.0, 1.0e30, 1.0, -1.0e30]), 2.0);
+        assert_eq!(stable_sum([-1.0e30, 3.5, 1.0e30, -1.0]), 2.5);
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(stable_sum([0.1; 10]), 1.0);
+        assert_eq!(stable_sum((0..1000).map(|_| 0.001)), 1.0);
+    }
+}
@@ -2,12 +2,14 @@ pub trait Sin {
     fn sin(&self) -> Self;
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Sin for f32 {
     fn sin(&self) -> Self {
         (*self).sin()
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Sin for f64 {
     fn sin(&self) -> Self {
         (*self).sin()
@@ -18,12 +20,14 @@ pub trait Cos {
     fn cos(&self) -> Self;
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Cos for f32 {
     fn cos(&self) -> Self {
         (*self).cos()
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Cos for f64 {
     fn cos(&self) -> Self {
         (*self).cos()
@@ -34,12 +38,14 @@ pub trait Hypot {
     fn hypot(&self, other: &Self) -> Self;
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Hypot for f32 {
     fn hypot(&self, other: &Self) -> Self {
         (*self).hypot(*other)
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Hypot for f64 {
     fn hypot(&self, other: &Self) -> Self {
         (*self).hypot(*other)
@@ -50,12 +56,14 @@ pub trait Exp {
     fn exp(&self) -> Self;
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Exp for f32 {
     fn exp(&self) -> Self {
         (*self).exp()
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Exp for f64 {
     fn exp(&self) -> Self {
         (*self).exp()
@@ -66,12 +74,14 @@ pub trait Atan2 {
     fn atan2(&self, other: &Self) -> Self;
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Atan2 for f32 {
     fn atan2(&self, other: &Self) -> Self {
         (*self).atan2(*other)
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Atan2 for f64 {
     fn atan2(&self, other: &Self) -> Self {
         (*self).atan2(*other)
@@ -82,14 +92,126 @@ pub trait Ln {
     fn ln(&self) -> Self;
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Ln for f32 {
     fn ln(&self) -> Self {
         (*self).ln()
     }
 }
 
+#[cfg(not(feature = "num-traits"))]
 impl Ln for f64 {
     fn ln(&self) -> Self {
         (*self).ln()
     }
 }
+
+/// Whether the value is neither zero, subnormal, infinite nor NaN, i.e. safe to divide by.
+pub trait IsNormal {
+    fn is_normal(&self) -> bool;
+}
+
+#[cfg(not(feature = "num-traits"))]
+impl IsNormal for f32 {
+    fn is_normal(&self) -> bool {
+        (*self).is_normal()
+    }
+}
+
+#[cfg(not(feature = "num-traits"))]
+impl IsNormal for f64 {
+    fn is_normal(&self) -> bool {
+        (*self).is_normal()
+    }
+}
+
+/// Constants used by the generic constructors, so scalars only need these instead of `From<u8>`.
+pub trait Constants {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn two() -> Self;
+}
+
+macro_rules! impl_constants {
+    ($($t:ty),*) => {
+        $(
+            #[cfg(not(feature = "num-traits"))]
+            impl Constants for $t {
+                fn zero() -> Self {
+                    0 as $t
+                }
+                fn one() -> Self {
+                    1 as $t
+                }
+                fn two() -> Self {
+                    2 as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_constants!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Implements the scalar traits for every `num_traits::Float` and [`Constants`] for every
+/// `Zero + One`, so third-party scalar types work with the math types without implementing each
+/// trait by hand.
+#[cfg(feature = "num-traits")]
+mod num {
+    use super::*;
+    use num_traits::Float;
+
+    impl<T: Float> Sin for T {
+        fn sin(&self) -> Self {
+            Float::sin(*self)
+        }
+    }
+
+    impl<T: Float> Cos for T {
+        fn cos(&self) -> Self {
+            Float::cos(*self)
+        }
+    }
+
+    impl<T: Float> Hypot for T {
+        fn hypot(&self, other: &Self) -> Self {
+            Float::hypot(*self, *other)
+        }
+    }
+
+    impl<T: Float> Exp for T {
+        fn exp(&self) -> Self {
+            Float::exp(*self)
+        }
+    }
+
+    impl<T: Float> Atan2 for T {
+        fn atan2(&self, other: &Self) -> Self {
+            Float::atan2(*self, *other)
+        }
+    }
+
+    impl<T: Float> Ln for T {
+        fn ln(&self) -> Self {
+            Float::ln(*self)
+        }
+    }
+
+    impl<T: Float> IsNormal for T {
+        fn is_normal(&self) -> bool {
+            Float::is_normal(*self)
+        }
+    }
+
+    impl<T: num_traits::Zero + num_traits::One> Constants for T {
+        fn zero() -> Self {
+            T::zero()
+        }
+        fn one() -> Self {
+            T::one()
+        }
+        fn two() -> Self {
+            T::one() + T::one()
+        }
+    }
+}
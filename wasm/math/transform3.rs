@@ -0,0 +1,472 @@
+use super::{
+    dual_quaternion::DualQuaternion,
+    quaternion::Quaternion,
+    traits::{Constants, IsNormal},
+    vector::Vector,
+};
+use crate::Float;
+use std::{
+    array,
+    ops::{Add, Div, Mul, MulAssign, Neg, Sub},
+};
+
+/// Homogeneous transform of 3D space, the 4×4 matrix `m` in row-major order acting on column
+/// vectors, so that `m[i][3]` for `i < 3` is the translation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform3<T> {
+    pub m: [[T; 4]; 4],
+}
+
+impl<T> Transform3<T> {
+    pub const fn new(m: [[T; 4]; 4]) -> Self {
+        Self { m }
+    }
+}
+
+impl<T> Transform3<T>
+where
+    T: Constants,
+{
+    pub fn identity() -> Self {
+        Self::from_axes(
+            Vector::new(T::one(), T::zero(), T::zero()),
+            Vector::new(T::zero(), T::one(), T::zero()),
+            Vector::new(T::zero(), T::zero(), T::one()),
+            Vector::new(T::zero(), T::zero(), T::zero()),
+        )
+    }
+
+    pub fn from_translation(t: Vector<T>) -> Self {
+        Self::from_axes(
+            Vector::new(T::one(), T::zero(), T::zero()),
+            Vector::new(T::zero(), T::one(), T::zero()),
+            Vector::new(T::zero(), T::zero(), T::one()),
+            t,
+        )
+    }
+
+    /// Transform from a local coordinate system with the axes `x`, `y`, `z` and the origin
+    /// `origin` given in global coordinates, to the global system.
+    pub fn from_axes(x: Vector<T>, y: Vector<T>, z: Vector<T>, origin: Vector<T>) -> Self {
+        Self {
+            m: [
+                [x.x, y.x, z.x, origin.x],
+                [x.y, y.y, z.y, origin.y],
+                [x.z, y.z, z.z, origin.z],
+                [T::zero(), T::zero(), T::zero(), T::one()],
+            ],
+        }
+    }
+}
+
+impl<T> Transform3<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    /// Transforms a direction or displacement, which is unaffected by the translation.
+    pub fn apply_vector(&self, v: &Vector<T>) -> Vector<T> {
+        let row = |r: &[T; 4]| &(&(&r[0] * &v.x) + &(&r[1] * &v.y)) + &(&r[2] * &v.z);
+        Vector::new(row(&self.m[0]), row(&self.m[1]), row(&self.m[2]))
+    }
+}
+
+impl<T> Transform3<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    /// Transforms a point, dividing by the homogeneous coordinate for projective transforms.
+    pub fn apply(&self, p: &Vector<T>) -> Vector<T> {
+        let row = |r: &[T; 4]| &(&(&(&r[0] * &p.x) + &(&r[1] * &p.y)) + &(&r[2] * &p.z)) + &r[3];
+        let w = row(&self.m[3]);
+        Vector::new(
+            &row(&self.m[0]) / &w,
+            &row(&self.m[1]) / &w,
+            &row(&self.m[2]) / &w,
+        )
+    }
+}
+
+/// `a x - b y + c z`, the expansion of cofactors along a row.
+fn expand<T>(a: &T, x: &T, b: &T, y: &T, c: &T, z: &T) -> T
+where
+    for<'a> &'a T: Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    &(&(a * x) - &(b * y)) + &(c * z)
+}
+
+/// `a d - b c`.
+fn det2<T>(a: &T, b: &T, c: &T, d: &T) -> T
+where
+    for<'a> &'a T: Sub<Output = T> + Mul<Output = T>,
+{
+    &(a * d) - &(b * c)
+}
+
+impl<T> Transform3<T>
+where
+    for<'a> &'a T: Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// 2×2 minors of the upper two and lower two rows, from which the determinant and the
+    /// adjugate are expanded.
+    fn minors(&self) -> ([T; 6], [T; 6]) {
+        let [a0, a1, a2, a3] = &self.m;
+        let s = [
+            det2::<T>(&a0[0], &a0[1], &a1[0], &a1[1]),
+            det2::<T>(&a0[0], &a0[2], &a1[0], &a1[2]),
+            det2::<T>(&a0[0], &a0[3], &a1[0], &a1[3]),
+            det2::<T>(&a0[1], &a0[2], &a1[1], &a1[2]),
+            det2::<T>(&a0[1], &a0[3], &a1[1], &a1[3]),
+            det2::<T>(&a0[2], &a0[3], &a1[2], &a1[3]),
+        ];
+        let c = [
+            det2::<T>(&a2[0], &a2[1], &a3[0], &a3[1]),
+            det2::<T>(&a2[0], &a2[2], &a3[0], &a3[2]),
+            det2::<T>(&a2[0], &a2[3], &a3[0], &a3[3]),
+            det2::<T>(&a2[1], &a2[2], &a3[1], &a3[2]),
+            det2::<T>(&a2[1], &a2[3], &a3[1], &a3[3]),
+            det2::<T>(&a2[2], &a2[3], &a3[2], &a3[3]),
+        ];
+        (s, c)
+    }
+
+    fn determinant_of_minors(s: &[T; 6], c: &[T; 6]) -> T {
+        &expand::<T>(&s[0], &c[5], &s[1], &c[4], &s[2], &c[3])
+            + &expand::<T>(&s[3], &c[2], &s[4], &c[1], &s[5], &c[0])
+    }
+
+    pub fn determinant(&self) -> T {
+        let (s, c) = self.minors();
+        Self::determinant_of_minors(&s, &c)
+    }
+}
+
+impl<T> Transform3<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+{
+    /// Inverse by the adjugate, with NaNs or infinities if the matrix is singular.
+    pub fn inv(&self) -> Self {
+        let (s, c) = self.minors();
+        self.inv_with_minors(&s, &c, &Self::determinant_of_minors(&s, &c))
+    }
+
+    fn inv_with_minors(&self, s: &[T; 6], c: &[T; 6], d: &T) -> Self {
+        let [a0, a1, a2, a3] = &self.m;
+        let adjugate = [
+            [
+                expand::<T>(&a1[1], &c[5], &a1[2], &c[4], &a1[3], &c[3]),
+                -&expand::<T>(&a0[1], &c[5], &a0[2], &c[4], &a0[3], &c[3]),
+                expand::<T>(&a3[1], &s[5], &a3[2], &s[4], &a3[3], &s[3]),
+                -&expand::<T>(&a2[1], &s[5], &a2[2], &s[4], &a2[3], &s[3]),
+            ],
+            [
+                -&expand::<T>(&a1[0], &c[5], &a1[2], &c[2], &a1[3], &c[1]),
+                expand::<T>(&a0[0], &c[5], &a0[2], &c[2], &a0[3], &c[1]),
+                -&expand::<T>(&a3[0], &s[5], &a3[2], &s[2], &a3[3], &s[1]),
+                expand::<T>(&a2[0], &s[5], &a2[2], &s[2], &a2[3], &s[1]),
+            ],
+            [
+                expand::<T>(&a1[0], &c[4], &a1[1], &c[2], &a1[3], &c[0]),
+                -&expand::<T>(&a0[0], &c[4], &a0[1], &c[2], &a0[3], &c[0]),
+                expand::<T>(&a3[0], &s[4], &a3[1], &s[2], &a3[3], &s[0]),
+                -&expand::<T>(&a2[0], &s[4], &a2[1], &s[2], &a2[3], &s[0]),
+            ],
+            [
+                -&expand::<T>(&a1[0], &c[3], &a1[1], &c[1], &a1[2], &c[0]),
+                expand::<T>(&a0[0], &c[3], &a0[1], &c[1], &a0[2], &c[0]),
+                -&expand::<T>(&a3[0], &s[3], &a3[1], &s[1], &a3[2], &s[0]),
+                expand::<T>(&a2[0], &s[3], &a2[1], &s[1], &a2[2], &s[0]),
+            ],
+        ];
+        Self {
+            m: adjugate.map(|row| row.map(|v| &v / d)),
+        }
+    }
+}
+
+impl<T> Transform3<T>
+where
+    for<'a> &'a T:
+        Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Neg<Output = T>,
+    T: IsNormal,
+{
+    /// Returns `None` if the determinant is zero, subnormal or not finite.
+    pub fn checked_inv(&self) -> Option<Self> {
+        let (s, c) = self.minors();
+        let d = Self::determinant_of_minors(&s, &c);
+        d.is_normal().then(|| self.inv_with_minors(&s, &c, &d))
+    }
+}
+
+/// Rotation matrix of a unit quaternion.
+impl<T> From<&Quaternion<T>> for Transform3<T>
+where
+    T: Constants,
+    for<'a> &'a T: Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn from(q: &Quaternion<T>) -> Self {
+        let Vector { x, y, z } = &q.v;
+        let w = &q.w;
+        let two = T::two();
+        let twice = |a: &T, b: &T| &two * &(a * b);
+        let diagonal = |a: &T, b: &T| &T::one() - &(&twice(a, a) + &twice(b, b));
+        Self {
+            m: [
+                [
+                    diagonal(y, z),
+                    &twice(x, y) - &twice(z, w),
+                    &twice(x, z) + &twice(y, w),
+                    T::zero(),
+                ],
+                [
+                    &twice(x, y) + &twice(z, w),
+                    diagonal(x, z),
+                    &twice(y, z) - &twice(x, w),
+                    T::zero(),
+                ],
+                [
+                    &twice(x, z) - &twice(y, w),
+                    &twice(y, z) + &twice(x, w),
+                    diagonal(x, y),
+                    T::zero(),
+                ],
+                [T::zero(), T::zero(), T::zero(), T::one()],
+            ],
+        }
+    }
+}
+
+/// Rigid motion of a unit dual quaternion.
+impl<T> From<&DualQuaternion<T>> for Transform3<T>
+where
+    T: Constants + Clone,
+    for<'a> &'a T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Neg<Output = T>,
+{
+    fn from(dq: &DualQuaternion<T>) -> Self {
+        let mut a = Self::from(&dq.p);
+        let Vector { x, y, z } = dq.translation();
+        a.m[0][3] = x;
+        a.m[1][3] = y;
+        a.m[2][3] = z;
+        a
+    }
+}
+
+impl Transform3<Float> {
+    /// Whether the matrix is a rotation about the origin, up to rounding errors.
+    fn is_rotation(&self) -> bool {
+        let tolerance = Float::EPSILON * 1e3;
+        let orthonormal = (0..3).all(|i| {
+            (0..3).all(|j| {
+                let dot: Float = (0..3).map(|k| self.m[k][i] * self.m[k][j]).sum();
+                (dot - if i == j { 1.0 } else { 0.0 }).abs() < tolerance
+            })
+        });
+        orthonormal
+            && self.determinant() > 0.0
+            && self.m[3] == [0.0, 0.0, 0.0, 1.0]
+            && (0..3).all(|i| self.m[i][3] == 0.0)
+    }
+
+    /// Unit quaternion of a rotation matrix with a nonnegative scalar part, by Shepperd's method.
+    ///
+    /// Returns `None` if the matrix is not a rotation about the origin.
+    pub fn to_quaternion(&self) -> Option<Quaternion<Float>> {
+        self.is_rotation().then(|| {
+            let r = &self.m;
+            let trace = r[0][0] + r[1][1] + r[2][2];
+            // Dividing by the largest of the four candidate components keeps the result accurate.
+            let (x, y, z, w) = if trace > r[0][0].max(r[1][1]).max(r[2][2]) {
+                let s = (trace + 1.0).sqrt() * 2.0;
+                (
+                    (r[2][1] - r[1][2]) / s,
+                    (r[0][2] - r[2][0]) / s,
+                    (r[1][0] - r[0][1]) / s,
+                    s / 4.0,
+                )
+            } else if r[0][0] >= r[1][1] && r[0][0] >= r[2][2] {
+                let s = (1.0 + r[0][0] - r[1][1] - r[2][2]).sqrt() * 2.0;
+                (
+                    s / 4.0,
+                    (r[0][1] + r[1][0]) / s,
+                    (r[0][2] + r[2][0]) / s,
+                    (r[2][1] - r[1][2]) / s,
+                )
+            } else if r[1][1] >= r[2][2] {
+                let s = (1.0 + r[1][1] - r[0][0] - r[2][2]).sqrt() * 2.0;
+                (
+                    (r[0][1] + r[1][0]) / s,
+                    s / 4.0,
+                    (r[1][2] + r[2][1]) / s,
+                    (r[0][2] - r[2][0]) / s,
+                )
+            } else {
+                let s = (1.0 + r[2][2] - r[0][0] - r[1][1]).sqrt() * 2.0;
+                (
+                    (r[0][2] + r[2][0]) / s,
+                    (r[1][2] + r[2][1]) / s,
+                    s / 4.0,
+                    (r[1][0] - r[0][1]) / s,
+                )
+            };
+            let q = Quaternion::new(Vector::new(x, y, z), w);
+            if w < 0.0 {
+                -q
+            } else {
+                q
+            }
+        })
+    }
+
+    /// Unit dual quaternion of a rigid motion, the rotation followed by the translation.
+    ///
+    /// Returns `None` if the matrix is not a rigid motion.
+    pub fn to_dual_quaternion(&self) -> Option<DualQuaternion<Float>> {
+        let t = Vector::new(self.m[0][3], self.m[1][3], self.m[2][3]);
+        let mut rotation = *self;
+        (0..3).for_each(|i| rotation.m[i][3] = 0.0);
+        rotation.to_quaternion().map(|r| {
+            let q = &(&Quaternion::from_translation(&t) * &r) / 2.0;
+            DualQuaternion::new(r, q)
+        })
+    }
+}
+
+/// Composition, `(a * b).apply(p) == a.apply(&b.apply(p))`.
+impl<T> Mul for &Transform3<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Transform3<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            m: array::from_fn(|i| {
+                array::from_fn(|j| {
+                    (1..4).fold(&self.m[i][0] * &rhs.m[0][j], |s, k| {
+                        &s + &(&self.m[i][k] * &rhs.m[k][j])
+                    })
+                })
+            }),
+        }
+    }
+}
+
+/// Applies `rhs` first, `a *= b` equals `a = a * b`.
+impl<T> MulAssign<&Transform3<T>> for Transform3<T>
+where
+    for<'a> &'a T: Add<Output = T> + Mul<Output = T>,
+{
+    fn mul_assign(&mut self, rhs: &Transform3<T>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl_owned_binop!(Transform3, Transform3<T>, Mul::mul, MulAssign::mul_assign);
+
+#[cfg(test)]
+#[allow(clippy::op_ref)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn assert_matrix_eq(a: &Transform3<Float>, b: &Transform3<Float>) {
+        for (a, b) in a.m.iter().flatten().zip(b.m.iter().flatten()) {
+            assert!((a - b).abs() < Float::EPSILON * 1e2, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let a = Transform3::from_axes(
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(-1.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::new(1.0, 2.0, 3.0),
+        );
+        let p = Vector::new(1.0, 1.0, 1.0);
+        assert_eq!(a.apply(&p), Vector::new(0.0, 3.0, 4.0));
+        assert_eq!(a.apply_vector(&p), Vector::new(-1.0, 1.0, 1.0));
+        assert_eq!(Transform3::<Float>::identity().apply(&p), p);
+        assert_eq!(a.determinant(), 1.0);
+        let projective = Transform3::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.5, 0.0],
+        ]);
+        assert_eq!(projective.apply(&p), Vector::new(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn compose_and_invert() {
+        let a = Transform3::new([
+            [2.0, 1.0, 0.0, 1.0],
+            [0.0, 1.0, -1.0, 2.0],
+            [1.0, 0.0, 3.0, -1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let b = Transform3::from_translation(Vector::new(0.5, -1.0, 2.0));
+        let p = Vector::new(0.3, -0.7, 1.1);
+        assert_relative_eq!((a * b).apply(&p), a.apply(&b.apply(&p)));
+        let mut c = a;
+        c *= &b;
+        assert_eq!(c, &a * &b);
+        assert_eq!(a.determinant(), 5.0);
+        assert_matrix_eq(&(a * a.inv()), &Transform3::identity());
+        assert_matrix_eq(&a.checked_inv().unwrap(), &a.inv());
+        let mut singular = a;
+        singular.m[2] = singular.m[0];
+        assert_eq!(singular.checked_inv(), None);
+    }
+
+    #[test]
+    fn quaternion() {
+        let axis: Vector<Float> = Vector::new(0.3, -0.5, 0.8);
+        let q = Quaternion::from_rotation(&axis);
+        let r = Transform3::<Float>::from(&q);
+        let p = Vector::new(1.0, 2.0, -0.5);
+        let rotated = &(&q * &Quaternion::from_translation(&p)) * &q.conj();
+        assert_relative_eq!(r.apply(&p), rotated.v, epsilon = Float::EPSILON * 1e1);
+        assert_relative_eq!(
+            r.to_quaternion().unwrap(),
+            q,
+            epsilon = Float::EPSILON * 1e1
+        );
+        // Rotations close to a half turn, about each axis, take the other branches.
+        for axis in [
+            Vector::new(3.0, 0.1, 0.2),
+            Vector::new(-0.1, 3.0, 0.2),
+            Vector::new(0.2, 0.1, -3.0),
+        ] {
+            let q = Quaternion::from_rotation(&axis);
+            assert_relative_eq!(
+                Transform3::<Float>::from(&q).to_quaternion().unwrap(),
+                q,
+                epsilon = Float::EPSILON * 1e1
+            );
+        }
+        assert_eq!(Transform3::from_translation(p).to_quaternion(), None);
+        let mut scaled = Transform3::identity();
+        scaled.m[1][1] = 2.0;
+        assert_eq!(scaled.to_quaternion(), None);
+    }
+
+    #[test]
+    fn dual_quaternion() {
+        let dq = DualQuaternion::<Float>::from_rotation_and_translation(
+            &Vector::new(0.1, 0.2, -0.3),
+            &Vector::new(4.0, -1.0, 2.0),
+        );
+        let a = Transform3::<Float>::from(&dq);
+        let p = Vector::new(1.0, 2.0, 3.0);
+        let expected = &Transform3::<Float>::from(&dq.p).apply(&p) + &Vector::new(4.0, -1.0, 2.0);
+        assert_relative_eq!(a.apply(&p), expected, epsilon = Float::EPSILON * 1e2);
+        assert_relative_eq!(
+            a.to_dual_quaternion().unwrap(),
+            dq,
+            epsilon = Float::EPSILON * 1e2
+        );
+    }
+}
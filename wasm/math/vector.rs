@@ -1,8 +1,13 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{
+    fmt::{self, Display, Formatter},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
-use super::traits::Hypot;
+use super::traits::{Hypot, IsNormal};
 
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector<T> {
     pub x: T,
     pub y: T,
@@ -43,6 +48,44 @@ where
     }
 }
 
+impl<T> Vector<T>
+where
+    for<'a> &'a T: Div<Output = T>,
+    T: Hypot + IsNormal,
+{
+    /// Returns `None` instead of NaNs if the norm is zero, subnormal or not finite.
+    pub fn checked_normalized(&self) -> Option<Self> {
+        self.checked_div(&self.abs())
+    }
+}
+
+impl<T> Vector<T>
+where
+    for<'a> &'a T: Div<Output = T>,
+    T: IsNormal,
+{
+    /// Returns `None` if `s` is zero, subnormal or not finite.
+    pub fn checked_div(&self, s: &T) -> Option<Self> {
+        s.is_normal().then(|| self / s)
+    }
+}
+
+/// Formats as `(x, y, z)`, applying the formatter options to each component.
+impl<T> Display for Vector<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("(")?;
+        self.x.fmt(f)?;
+        f.write_str(", ")?;
+        self.y.fmt(f)?;
+        f.write_str(", ")?;
+        self.z.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
 impl<T> Add for &Vector<T>
 where
     for<'a> &'a T: Add<Output = T>,
@@ -190,7 +233,17 @@ where
     }
 }
 
+impl_scalar_mul!(Vector);
+impl_owned_binop!(Vector, Vector<T>, Add::add, AddAssign::add_assign);
+impl_owned_binop!(Vector, Vector<T>, Sub::sub, SubAssign::sub_assign);
+impl_owned_binop!(Vector, Vector<T>, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Vector, T, Mul::mul, MulAssign::mul_assign);
+impl_owned_binop!(Vector, T, Div::div, DivAssign::div_assign);
+impl_owned_neg!(Vector);
+impl_sum!(Vector);
+
 #[cfg(test)]
+#[allow(clippy::op_ref)]
 mod tests {
 
     use super::*;
@@ -447,9 +500,9 @@ mod tests {
         let a = Vector::new(-1.3, 0.15, -30.8);
         let b = Vector::new(-20.4, -3.8, 11.3);
         let c = Vector::new(511.35, -2.9, 99.2);
-        assert_eq!(a.abs(), (1.3 as f64).hypot(0.15).hypot(30.8));
-        assert_eq!(b.abs(), (20.4 as f32).hypot(3.8).hypot(11.3));
-        assert_eq!(c.abs(), (511.35 as f64).hypot(2.9).hypot(99.2));
+        assert_eq!(a.abs(), 1.3_f64.hypot(0.15).hypot(30.8));
+        assert_eq!(b.abs(), 20.4_f32.hypot(3.8).hypot(11.3));
+        assert_eq!(c.abs(), 511.35_f64.hypot(2.9).hypot(99.2));
     }
 
     #[test]
@@ -459,17 +512,17 @@ mod tests {
         assert_eq!(
             a.normalized(),
             Vector::new(
-                -1.3 / (1.3 as f64).hypot(0.15).hypot(30.8),
-                0.15 / (1.3 as f64).hypot(0.15).hypot(30.8),
-                -30.8 / (1.3 as f64).hypot(0.15).hypot(30.8)
+                -1.3 / 1.3_f64.hypot(0.15).hypot(30.8),
+                0.15 / 1.3_f64.hypot(0.15).hypot(30.8),
+                -30.8 / 1.3_f64.hypot(0.15).hypot(30.8)
             )
         );
         assert_eq!(
             b.normalized(),
             Vector::new(
-                -20.4 / (20.4 as f32).hypot(3.8).hypot(11.3),
-                -3.8 / (20.4 as f32).hypot(3.8).hypot(11.3),
-                11.3 / (20.4 as f32).hypot(3.8).hypot(11.3)
+                -20.4 / 20.4_f32.hypot(3.8).hypot(11.3),
+                -3.8 / 20.4_f32.hypot(3.8).hypot(11.3),
+                11.3 / 20.4_f32.hypot(3.8).hypot(11.3)
             )
         );
     }
@@ -479,4 +532,84 @@ mod tests {
         assert_eq!(Vector::default(), Vector::new(0.0, 0.0, 0.0));
         assert_eq!(Vector::default(), Vector::new(0, 0, 0));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let a = Vector::new(1.5, -2.0, 0.25);
+        let s = serde_json::to_string(&a).unwrap();
+        assert_eq!(s, r#"{"x":1.5,"y":-2.0,"z":0.25}"#);
+        assert_eq!(serde_json::from_str::<Vector<f64>>(&s).unwrap(), a);
+    }
+
+    #[test]
+    fn display() {
+        let a = Vector::new(-1.3, 0.15, 30.0);
+        assert_eq!(a.to_string(), "(-1.3, 0.15, 30)");
+        assert_eq!(format!("{:.2}", a), "(-1.30, 0.15, 30.00)");
+        assert_eq!(format!("{:+}", Vector::new(1, -2, 0)), "(+1, -2, +0)");
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let a = Vector::new(-1.3, 0.15, -30.8);
+        assert_eq!(&3.8 * &a, &a * &3.8);
+        assert_eq!(&3 * &Vector::new(-1, 15, -30), Vector::new(-3, 45, -90));
+    }
+
+    #[test]
+    fn checked_normalized() {
+        let a = Vector::new(-1.3, 0.15, -30.8);
+        assert_eq!(a.checked_normalized(), Some(a.normalized()));
+        assert_eq!(Vector::new(0.0, 0.0, 0.0).checked_normalized(), None);
+        assert_eq!(Vector::new(1.0e-320, 0.0, 0.0).checked_normalized(), None);
+        assert_eq!(Vector::new(f32::NAN, 0.0, 1.0).checked_normalized(), None);
+    }
+
+    #[test]
+    fn checked_div() {
+        let a = Vector::new(-1.3, 0.15, -30.8);
+        assert_eq!(a.checked_div(&3.8), Some(&a / &3.8));
+        assert_eq!(a.checked_div(&0.0), None);
+        assert_eq!(a.checked_div(&f64::INFINITY), None);
+    }
+
+    #[test]
+    fn owned_operators() {
+        let a = Vector::new(1.5, -2.0, 3.25);
+        let b = Vector::new(-0.5, 4.0, 1.5);
+        assert_eq!(a.clone() + b.clone(), &a + &b);
+        assert_eq!(a.clone() - &b, &a - &b);
+        assert_eq!(&a * b.clone(), &a * &b);
+        assert_eq!(a.clone() * 2.0, &a * &2.0);
+        assert_eq!(2.0 * a.clone(), &a * &2.0);
+        assert_eq!(a.clone() / 4.0, &a / &4.0);
+        assert_eq!(-a.clone(), -&a);
+        assert_eq!(
+            (a.clone() + b.clone()) * 2.0 - a.clone(),
+            &(&(&a + &b) * &2.0) - &a
+        );
+        let mut c = a.clone();
+        c += b.clone();
+        c *= 3.0;
+        assert_eq!(c, &(&a + &b) * &3.0);
+    }
+
+    #[test]
+    fn sum() {
+        let v = [
+            Vector::new(1.5, -2.0, 3.25),
+            Vector::new(-0.5, 4.0, 1.5),
+            Vector::new(2.0, 0.5, -1.0),
+        ];
+        assert_eq!(v.iter().sum::<Vector<f64>>(), Vector::new(3.0, 2.5, 3.75));
+        assert_eq!(
+            v.into_iter().sum::<Vector<f64>>(),
+            Vector::new(3.0, 2.5, 3.75)
+        );
+        assert_eq!(
+            Vec::<Vector<i32>>::new().into_iter().sum::<Vector<_>>(),
+            Vector::default()
+        );
+    }
 }
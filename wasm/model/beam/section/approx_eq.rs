@@ -0,0 +1,99 @@
+//! `approx` comparisons of the sections by their dimensions, so that sections built through
+//! different operations compare within the rounding errors. [`CombinedSection`] is left out, as its
+//! parts are opaque.
+//!
+//! [`CombinedSection`]: super::combined::CombinedSection
+
+use super::{
+    circle::CircleSection, rectangle::RectangleSection, rotated::RotatedSection,
+    translated::TranslatedSection, weighted::WeightedSection, Section,
+};
+use crate::Float;
+use ::approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+/// Implements the `approx` comparison traits component-wise over the given fields, the arrays
+/// marked by `[..]`, for sections optionally generic over the section they transform.
+macro_rules! impl_approx {
+    ($type:ident $(<$param:ident>)? { $($field:ident $([$range:tt])?),+ }) => {
+        impl$(<$param>)? AbsDiffEq for $type$(<$param>)?
+        $(where $param: Section + AbsDiffEq<Epsilon = Float>)?
+        {
+            type Epsilon = Float;
+
+            fn default_epsilon() -> Float {
+                Float::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Float) -> bool {
+                $(self.$field$([$range])?.abs_diff_eq(&other.$field$([$range])?, epsilon))&&+
+            }
+        }
+
+        impl$(<$param>)? RelativeEq for $type$(<$param>)?
+        $(where $param: Section + RelativeEq<Epsilon = Float>)?
+        {
+            fn default_max_relative() -> Float {
+                Float::default_max_relative()
+            }
+
+            fn relative_eq(&self, other: &Self, epsilon: Float, max_relative: Float) -> bool {
+                $(self.$field$([$range])?
+                    .relative_eq(&other.$field$([$range])?, epsilon, max_relative))&&+
+            }
+        }
+
+        impl$(<$param>)? UlpsEq for $type$(<$param>)?
+        $(where $param: Section + UlpsEq<Epsilon = Float>)?
+        {
+            fn default_max_ulps() -> u32 {
+                Float::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Float, max_ulps: u32) -> bool {
+                $(self.$field$([$range])?.ulps_eq(&other.$field$([$range])?, epsilon, max_ulps))&&+
+            }
+        }
+    };
+}
+
+impl_approx!(RectangleSection { size[..] });
+impl_approx!(CircleSection { radius });
+impl_approx!(RotatedSection<T> { origin, angle });
+impl_approx!(TranslatedSection<T> { origin, offset[..] });
+impl_approx!(WeightedSection<T> { section, weight });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    #[test]
+    fn rectangle() {
+        let a = RectangleSection::new([0.1 + 0.2, 1.0e4 + 0.3]);
+        assert_relative_eq!(a, RectangleSection::new([0.3, 1.0e4 + 0.1 + 0.2]));
+        assert_ulps_eq!(a, RectangleSection::new([0.3, 1.0e4 + 0.1 + 0.2]));
+        assert_relative_ne!(a, RectangleSection::new([0.3, 1.0e4 + 0.4]));
+    }
+
+    #[test]
+    fn circle() {
+        let a = CircleSection::new(0.1 + 0.2);
+        assert_abs_diff_eq!(a, CircleSection::new(0.3));
+        assert_abs_diff_eq!(a, CircleSection::new(0.31), epsilon = 0.02);
+    }
+
+    #[test]
+    fn transformed() {
+        let rectangle = || RectangleSection::new([2.0, 0.5]);
+        let a = TranslatedSection::new(RotatedSection::new(rectangle(), 0.1 + 0.2), [0.7, -1.1]);
+        let b = TranslatedSection::new(RotatedSection::new(rectangle(), 0.3), [0.7, -1.1]);
+        assert_relative_eq!(a, b);
+        assert_relative_ne!(
+            a,
+            TranslatedSection::new(RotatedSection::new(rectangle(), 0.3), [0.7, 1.1])
+        );
+        let w = WeightedSection::new(CircleSection::new(1.5), 0.1 + 0.2);
+        assert_relative_eq!(w, WeightedSection::new(CircleSection::new(1.5), 0.3));
+        assert_relative_ne!(w, WeightedSection::new(CircleSection::new(1.6), 0.3));
+    }
+}
@@ -1,7 +1,7 @@
 use super::Section;
 use crate::Float;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CircleSection {
     pub radius: Float,
 }
@@ -1,7 +1,7 @@
-use std::vec;
+use std::{array::from_fn, iter::Sum, vec};
 
 use super::Section;
-use crate::Float;
+use crate::{math::sum::stable_sum, Float};
 
 pub struct CombinedSection {
     pub sections: Vec<Box<dyn Section>>,
@@ -16,49 +16,47 @@ impl CombinedSection {
     }
 }
 
+impl Default for CombinedSection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Combines the sections into a [`CombinedSection`].
+impl<S: Section + 'static> Sum<S> for CombinedSection {
+    fn sum<I: Iterator<Item = S>>(iter: I) -> Self {
+        Self {
+            sections: iter.map(|s| Box::new(s) as Box<dyn Section>).collect(),
+        }
+    }
+}
+
 impl Section for CombinedSection {
     fn area(&self) -> Float {
-        let mut v: Vec<Float> = self.sections.iter().map(|s| s.area()).collect();
-        v.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-        v.iter().sum()
+        stable_sum(self.sections.iter().map(|s| s.area()))
     }
     fn centroid(&self) -> [Float; 2] {
-        let (mut va, vs): (Vec<Float>, Vec<[Float; 2]>) = self
+        let m: Vec<(Float, [Float; 2])> = self
             .sections
             .iter()
             .map(|s| {
                 let t = s.area();
                 (t, s.centroid().map(|c| c * t))
             })
-            .unzip();
-        va.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-        let a: Float = va.iter().sum();
-        let s: [Vec<Float>; 2] = vs.iter().map(|&i| i.into()).unzip().into();
-        s.map(|mut i| {
-            i.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-            i.iter().sum::<Float>() / a
-        })
+            .collect();
+        let a = stable_sum(m.iter().map(|&(a, _)| a));
+        from_fn(|i| stable_sum(m.iter().map(|&(_, s)| s[i])) / a)
     }
     fn moment_of_inertia(&self) -> [Float; 2] {
-        let j: [Vec<Float>; 2] = self
+        let j: Vec<[Float; 2]> = self
             .sections
             .iter()
-            .map(|s| s.moment_of_inertia().into())
-            .unzip()
-            .into();
-        j.map(|mut i| {
-            i.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-            i.iter().sum()
-        })
+            .map(|s| s.moment_of_inertia())
+            .collect();
+        from_fn(|i| stable_sum(j.iter().map(|j| j[i])))
     }
     fn product_of_inertia(&self) -> Float {
-        let mut v: Vec<Float> = self
-            .sections
-            .iter()
-            .map(|s| s.product_of_inertia())
-            .collect();
-        v.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-        v.iter().sum()
+        stable_sum(self.sections.iter().map(|s| s.product_of_inertia()))
     }
 }
 
@@ -181,5 +179,12 @@ mod tests {
             c.push(TestSectionD {});
             assert_eq!(c.product_of_inertia(), 0.0);
         }
+        #[test]
+        fn sum() {
+            let c: CombinedSection = [TestSectionA {}, TestSectionA {}].into_iter().sum();
+            assert_eq!(c.sections.len(), 2);
+            assert_eq!(c.area(), 60.0);
+            assert_eq!(c.centroid(), [2.5, 3.0]);
+        }
     }
 }
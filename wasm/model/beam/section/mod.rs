@@ -7,6 +7,21 @@ pub trait Section {
     fn product_of_inertia(&self) -> Float;
 }
 
+impl<T: Section + ?Sized> Section for &T {
+    fn area(&self) -> Float {
+        (**self).area()
+    }
+    fn centroid(&self) -> [Float; 2] {
+        (**self).centroid()
+    }
+    fn moment_of_inertia(&self) -> [Float; 2] {
+        (**self).moment_of_inertia()
+    }
+    fn product_of_inertia(&self) -> Float {
+        (**self).product_of_inertia()
+    }
+}
+
 /// Calculates the principal axis direction of the section.
 /// Returns the angle of axis in radians.
 pub fn principal_axis(section: impl Section) -> Float {
@@ -14,16 +29,21 @@ pub fn principal_axis(section: impl Section) -> Float {
     (section.product_of_inertia() * -2.0).atan2(jx - jy) * 0.5
 }
 
+#[cfg(any(test, feature = "approx"))]
+mod approx_eq;
 pub mod circle;
 pub mod combined;
 pub mod rectangle;
 pub mod rotated;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
 pub mod translated;
 pub mod weighted;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
     mod centered_rectangle {
         use super::*;
         // Mock a centered rectangle section.
@@ -53,7 +73,7 @@ mod tests {
         }
         #[test]
         fn principal_axis() {
-            assert_eq!(super::principal_axis(TestSection {}).to_degrees(), 13.0);
+            assert_relative_eq!(super::principal_axis(TestSection {}).to_degrees(), 13.0);
         }
     }
     mod first_quadrant_circle {
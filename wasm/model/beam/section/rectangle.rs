@@ -1,7 +1,7 @@
 use super::Section;
 use crate::Float;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RectangleSection {
     pub size: [Float; 2],
 }
@@ -32,6 +32,7 @@ impl Section for RectangleSection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
     mod area {
         use super::*;
         #[test]
@@ -83,51 +84,45 @@ mod tests {
         #[test]
         fn positive_width_positive_height() {
             let s = RectangleSection::new([3.3, 1.1]);
-            assert_eq!(
-                s.moment_of_inertia(),
+            assert_relative_eq!(
+                s.moment_of_inertia()[..],
                 [
                     1.1 * 3.3 * 3.3 * 3.3 / 12.0 + 3.3 * 1.1 * 3.3 * 0.5 * 3.3 * 0.5,
                     3.3 * 1.1 * 1.1 * 1.1 / 12.0 + 3.3 * 1.1 * 1.1 * 0.5 * 1.1 * 0.5,
-                ]
+                ][..]
             );
         }
         #[test]
         fn positive_width_negative_height() {
             let s = RectangleSection::new([3.3, -1.1]);
-            assert_eq!(
-                s.moment_of_inertia(),
+            assert_relative_eq!(
+                s.moment_of_inertia()[..],
                 [
                     1.1 * 3.3 * 3.3 * 3.3 / 12.0 + 3.3 * 1.1 * 3.3 * 0.5 * 3.3 * 0.5,
                     3.3 * 1.1 * 1.1 * 1.1 / 12.0 + 3.3 * 1.1 * 1.1 * 0.5 * 1.1 * 0.5,
-                ]
+                ][..]
             );
         }
         #[test]
         fn negative_width_positive_height() {
             let s = RectangleSection::new([-3.2, 1.1]);
-            assert_eq!(
-                format!("{:.14?}", s.moment_of_inertia()),
-                format!(
-                    "{:.14?}",
-                    [
-                        1.1 * 3.2 * 3.2 * 3.2 / 12.0 + 3.2 * 1.1 * 3.2 * 0.5 * 3.2 * 0.5,
-                        3.2 * 1.1 * 1.1 * 1.1 / 12.0 + 3.2 * 1.1 * 1.1 * 0.5 * 1.1 * 0.5,
-                    ]
-                )
+            assert_relative_eq!(
+                s.moment_of_inertia()[..],
+                [
+                    1.1 * 3.2 * 3.2 * 3.2 / 12.0 + 3.2 * 1.1 * 3.2 * 0.5 * 3.2 * 0.5,
+                    3.2 * 1.1 * 1.1 * 1.1 / 12.0 + 3.2 * 1.1 * 1.1 * 0.5 * 1.1 * 0.5,
+                ][..]
             );
         }
         #[test]
         fn negative_width_negative_height() {
             let s = RectangleSection::new([-3.2, -1.1]);
-            assert_eq!(
-                format!("{:.14?}", s.moment_of_inertia()),
-                format!(
-                    "{:.14?}",
-                    [
-                        1.1 * 3.2 * 3.2 * 3.2 / 12.0 + 3.2 * 1.1 * 3.2 * 0.5 * 3.2 * 0.5,
-                        3.2 * 1.1 * 1.1 * 1.1 / 12.0 + 3.2 * 1.1 * 1.1 * 0.5 * 1.1 * 0.5,
-                    ]
-                )
+            assert_relative_eq!(
+                s.moment_of_inertia()[..],
+                [
+                    1.1 * 3.2 * 3.2 * 3.2 / 12.0 + 3.2 * 1.1 * 3.2 * 0.5 * 3.2 * 0.5,
+                    3.2 * 1.1 * 1.1 * 1.1 / 12.0 + 3.2 * 1.1 * 1.1 * 0.5 * 1.1 * 0.5,
+                ][..]
             );
         }
     }
@@ -136,22 +131,22 @@ mod tests {
         #[test]
         fn positive_width_positive_height() {
             let s = RectangleSection::new([3.3, 4.5]);
-            assert_eq!(s.product_of_inertia(), 3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
+            assert_relative_eq!(s.product_of_inertia(), 3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
         }
         #[test]
         fn positive_width_negative_height() {
             let s = RectangleSection::new([3.3, -4.5]);
-            assert_eq!(s.product_of_inertia(), -3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
+            assert_relative_eq!(s.product_of_inertia(), -3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
         }
         #[test]
         fn negative_width_positive_height() {
             let s = RectangleSection::new([-3.3, 4.5]);
-            assert_eq!(s.product_of_inertia(), -3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
+            assert_relative_eq!(s.product_of_inertia(), -3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
         }
         #[test]
         fn negative_width_negative_height() {
             let s = RectangleSection::new([-3.3, -4.5]);
-            assert_eq!(s.product_of_inertia(), 3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
+            assert_relative_eq!(s.product_of_inertia(), 3.3 * 4.5 * 3.3 * 0.5 * 4.5 * 0.5);
         }
     }
 }
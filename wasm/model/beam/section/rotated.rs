@@ -1,10 +1,11 @@
 use super::Section;
-use crate::Float;
+use crate::{math::sum::stable_sum, Float};
 use std::array::from_fn;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct RotatedSection<T: Section> {
-    origin: T,
-    angle: Float,
+    pub(super) origin: T,
+    pub(super) angle: Float,
 }
 
 impl<T: Section> RotatedSection<T> {
@@ -42,9 +43,7 @@ impl<T: Section> Section for RotatedSection<T> {
         let jxy = self.origin.product_of_inertia();
         from_fn(|n| {
             let s = [-1.0].iter().cycle().take(n).product::<Float>();
-            let mut t = [(jy + jx) * 0.5, (jy - jx) * cos * s, jxy * sin * s];
-            t.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-            t.iter().sum()
+            stable_sum([(jy + jx) * 0.5, (jy - jx) * cos * s, jxy * sin * s])
         })
     }
     fn product_of_inertia(&self) -> Float {
@@ -57,6 +56,7 @@ impl<T: Section> Section for RotatedSection<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
     // Mock origin section representing a rectangle section.
     // width: 4.9; height: 8.1; centroid: [2.2, 3.1];
     struct Origin {}
@@ -87,7 +87,11 @@ mod tests {
         let s = RotatedSection::new(Origin {}, 0.92);
         let r = (2.2 as Float).hypot(3.1);
         let theta = (3.1 as Float).atan2(2.2) + 0.92;
-        assert_eq!(s.centroid(), [r * theta.cos(), r * theta.sin()]);
+        assert_relative_eq!(
+            s.centroid()[..],
+            [r * theta.cos(), r * theta.sin()][..],
+            max_relative = Float::EPSILON * 1e2
+        );
     }
     #[test]
     fn moment_of_inertia() {
@@ -96,8 +100,8 @@ mod tests {
         let theta = (3.1 as Float).atan2(2.2) + 0.67;
         let x = r * theta.cos();
         let y = r * theta.sin();
-        assert_eq!(
-            s.moment_of_inertia(),
+        assert_relative_eq!(
+            s.moment_of_inertia()[..],
             [
                 (8.1 * 4.9 * 4.9 * 4.9 + 4.9 * 8.1 * 8.1 * 8.1
                     - (4.9 * 8.1 * 8.1 * 8.1 - 8.1 * 4.9 * 4.9 * 4.9)
@@ -110,7 +114,8 @@ mod tests {
                         * (0.67 * 2.0 as Float).cos())
                     / 24.0
                     + y * y * 4.9 * 8.1,
-            ]
+            ][..],
+            max_relative = Float::EPSILON * 1e2
         );
     }
     #[test]
@@ -118,10 +123,11 @@ mod tests {
         let s = RotatedSection::new(Origin {}, 0.72);
         let r2 = 2.2 * 2.2 + 3.1 * 3.1;
         let theta = (3.1 as Float).atan2(2.2) + 0.72;
-        assert_eq!(
+        assert_relative_eq!(
             s.product_of_inertia(),
             -(4.9 * 8.1 * 8.1 * 8.1 - 8.1 * 4.9 * 4.9 * 4.9) / 24.0 * (1.44 as Float).sin()
-                + 4.9 * 8.1 * r2 * theta.cos() * theta.sin()
+                + 4.9 * 8.1 * r2 * theta.cos() * theta.sin(),
+            max_relative = Float::EPSILON * 1e2
         );
     }
 }
@@ -0,0 +1,85 @@
+//! `proptest` strategies generating valid sections. Available in tests and with the `proptest`
+//! feature.
+
+use super::{
+    circle::CircleSection, rectangle::RectangleSection, rotated::RotatedSection,
+    translated::TranslatedSection, Section,
+};
+use crate::{math::strategies::scalar, Float};
+use proptest::prelude::*;
+
+/// Lengths in `[0.1, 10]`.
+fn length() -> impl Strategy<Value = Float> {
+    0.1 as Float..10.0
+}
+
+pub fn rectangle() -> impl Strategy<Value = RectangleSection> {
+    (length(), length()).prop_map(|(w, h)| RectangleSection::new([w, h]))
+}
+
+pub fn circle() -> impl Strategy<Value = CircleSection> {
+    length().prop_map(CircleSection::new)
+}
+
+/// Rotates the sections generated by `section` by arbitrary angles.
+pub fn rotated<S: Section + std::fmt::Debug>(
+    section: impl Strategy<Value = S>,
+) -> impl Strategy<Value = RotatedSection<S>> {
+    let max = (180.0 as Float).to_radians();
+    (section, -max..max).prop_map(|(s, a)| RotatedSection::new(s, a))
+}
+
+/// Translates the sections generated by `section` by offsets in `[-100, 100]`.
+pub fn translated<S: Section + std::fmt::Debug>(
+    section: impl Strategy<Value = S>,
+) -> impl Strategy<Value = TranslatedSection<S>> {
+    (section, scalar(), scalar()).prop_map(|(s, x, y)| TranslatedSection::new(s, [x, y]))
+}
+
+/// Moments and product of inertia about the axes through the centroid.
+pub fn centroidal_inertia(section: &impl Section) -> [Float; 3] {
+    let a = section.area();
+    let [cx, cy] = section.centroid();
+    let [jy, jx] = section.moment_of_inertia();
+    [
+        jy - cx * cx * a,
+        jx - cy * cy * a,
+        section.product_of_inertia() - cx * cy * a,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::abs_diff_eq;
+
+    const TOLERANCE: Float = Float::EPSILON * 1.0e4;
+
+    proptest! {
+        #[test]
+        fn rotation_preserves_polar_moment(r in rectangle(), angle in scalar()) {
+            let s = RotatedSection::new(r.clone(), angle);
+            let [jy, jx] = s.moment_of_inertia();
+            let [oy, ox] = r.moment_of_inertia();
+            prop_assert!(abs_diff_eq!(s.area(), r.area(), epsilon = TOLERANCE * s.area()));
+            prop_assert!(abs_diff_eq!(jy + jx, oy + ox, epsilon = TOLERANCE * (oy + ox)));
+        }
+
+        #[test]
+        fn translation_preserves_centroidal_inertia(s in translated(rotated(rectangle()))) {
+            let t = centroidal_inertia(&s);
+            let o = centroidal_inertia(&s.origin);
+            let [jy, jx] = s.moment_of_inertia();
+            for (t, o) in t.into_iter().zip(o) {
+                prop_assert!(abs_diff_eq!(t, o, epsilon = TOLERANCE * (jy + jx)));
+            }
+        }
+
+        #[test]
+        fn circle_is_isotropic(s in rotated(circle())) {
+            let [jy, jx] = s.moment_of_inertia();
+            prop_assert!(abs_diff_eq!(jy, jx, epsilon = TOLERANCE * jy));
+            prop_assert!(abs_diff_eq!(s.product_of_inertia(), 0.0, epsilon = TOLERANCE * jy));
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use super::Section;
-use crate::Float;
+use crate::{math::sum::stable_sum, Float};
 use std::array::from_fn;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct TranslatedSection<T: Section> {
     pub origin: T,
     pub offset: [Float; 2],
@@ -32,21 +33,20 @@ impl<T: Section> Section for TranslatedSection<T> {
     }
     fn product_of_inertia(&self) -> Float {
         let c = self.origin.centroid();
-        let mut i = c
+        let t = c
             .iter()
             .rev()
             .zip(self.offset)
             .map(|(a, b)| a * b)
             .chain([self.offset.iter().product()]);
-        let mut t: [Float; 3] = from_fn(|_| i.next().unwrap());
-        t.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
-        self.origin.product_of_inertia() + t.iter().sum::<Float>() * self.origin.area()
+        self.origin.product_of_inertia() + stable_sum(t) * self.origin.area()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
     // Mock origin section representing a rectangle section.
     // width: 4.9; height: 8.1; centroid: [2.2, 3.1];
     struct Origin {}
@@ -80,17 +80,22 @@ mod tests {
     #[test]
     fn moment_of_inertia() {
         let s = TranslatedSection::new(Origin {}, [-3.4, -1.3]);
-        assert_eq!(
-            s.moment_of_inertia(),
+        assert_relative_eq!(
+            s.moment_of_inertia()[..],
             [
                 8.1 * 4.9 * 4.9 * 4.9 / 12.0 + 1.2 * 1.2 * 4.9 * 8.1,
                 4.9 * 8.1 * 8.1 * 8.1 / 12.0 + 1.8 * 1.8 * 4.9 * 8.1,
-            ]
+            ][..],
+            max_relative = Float::EPSILON * 1e2
         );
     }
     #[test]
     fn product_of_inertia() {
         let s = TranslatedSection::new(Origin {}, [-3.5, -1.4]);
-        assert_eq!(s.product_of_inertia(), -4.9 * 8.1 * 1.3 * 1.7);
+        assert_relative_eq!(
+            s.product_of_inertia(),
+            -4.9 * 8.1 * 1.3 * 1.7,
+            max_relative = Float::EPSILON * 1e2
+        );
     }
 }
@@ -1,9 +1,10 @@
 use super::Section;
 use crate::Float;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct WeightedSection<T: Section> {
-    weight: Float,
-    section: T,
+    pub(super) weight: Float,
+    pub(super) section: T,
 }
 
 impl<T: Section> WeightedSection<T> {
@@ -0,0 +1,339 @@
+//! Diagrams of the member forces and the deflected shape of a solved [`Model`] drawn as SVG.
+
+use super::{element, Model, Solution};
+use crate::{geometry::path::Path, math::complex::Complex, Float};
+use std::fmt::Write;
+
+/// Response drawn along the members by [`Model::diagram_svg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantity {
+    /// Axial force, positive in tension, drawn to the left of the members.
+    Axial,
+    /// Shear force drawn to the left of the members.
+    Shear,
+    /// Bending moment drawn on the tension side, to the right of the members when sagging.
+    Moment,
+    /// Displacement of the members, drawn as the deflected shape.
+    Deflection,
+}
+
+/// Controls of [`Model::diagram_svg`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagramOptions {
+    /// Drawn length per unit of the quantity, or `None` to draw the largest value at a tenth of
+    /// the extent of the model.
+    pub scale: Option<Float>,
+    /// Points sampled along each member, including its ends.
+    pub samples: usize,
+    /// Decimal places of the value labels.
+    pub precision: usize,
+}
+
+impl Default for DiagramOptions {
+    fn default() -> Self {
+        Self {
+            scale: None,
+            samples: 21,
+            precision: 2,
+        }
+    }
+}
+
+impl Model {
+    /// Displacements along and across the `member` in its local axes at the distance `x` from its
+    /// first node, the end displacements interpolated by the exact solution of a prismatic member
+    /// under its uniform load.
+    pub fn deflection(&self, solution: &Solution, member: usize, x: Float) -> [Float; 2] {
+        self.deflection_under(solution, member, self.member_load(member), x)
+    }
+
+    /// [`Self::deflection`] under the uniform `load` of the `member` in its local axes.
+    pub(super) fn deflection_under(
+        &self,
+        solution: &Solution,
+        member: usize,
+        [qx, qy]: [Float; 2],
+        x: Float,
+    ) -> [Float; 2] {
+        let m = &self.members[member];
+        let length = self.length(member);
+        let global = m.nodes.map(|n| solution.displacements[n]).concat();
+        let local = element::rotation(self.axis(member)).mul_vec(&global);
+        let [u1, v1, r1, u2, v2, r2] = [0, 1, 2, 3, 4, 5].map(|i| local[i]);
+        let s = x / length;
+        let (h1, h2, h3, h4) = (
+            1.0 - 3.0 * s * s + 2.0 * s * s * s,
+            (s - 2.0 * s * s + s * s * s) * length,
+            3.0 * s * s - 2.0 * s * s * s,
+            (s * s * s - s * s) * length,
+        );
+        let span = x * (length - x);
+        [
+            u1 + (u2 - u1) * s + qx * span / (2.0 * m.elasticity * m.area),
+            h1 * v1
+                + h2 * r1
+                + h3 * v2
+                + h4 * r2
+                + qy * span * span / (24.0 * m.elasticity * m.inertia),
+        ]
+    }
+
+    /// Value of the `quantity` at the distance `x` along the `member`, the transverse deflection
+    /// for [`Quantity::Deflection`].
+    pub fn quantity_at(
+        &self,
+        solution: &Solution,
+        quantity: Quantity,
+        member: usize,
+        x: Float,
+    ) -> Float {
+        let [n, v, m] = self.internal_forces(solution, member, x);
+        match quantity {
+            Quantity::Axial => n,
+            Quantity::Shear => v,
+            Quantity::Moment => m,
+            Quantity::Deflection => self.deflection(solution, member, x)[1],
+        }
+    }
+
+    /// SVG drawing of the members and the diagram of the `quantity` along them, hatched for the
+    /// forces, with the extreme values of each member labeled. The y axis points up as in the
+    /// model, and the lengths of the strokes and text follow the extent of the model.
+    pub fn diagram_svg(
+        &self,
+        solution: &Solution,
+        quantity: Quantity,
+        options: &DiagramOptions,
+    ) -> String {
+        let point = |n: usize| {
+            let [x, y] = self.nodes[n].position;
+            Complex::new(x, y)
+        };
+        let (min, max) = (0..self.nodes.len()).map(point).fold(
+            (
+                Complex::new(Float::INFINITY, Float::INFINITY),
+                Complex::new(Float::NEG_INFINITY, Float::NEG_INFINITY),
+            ),
+            |(min, max), p| {
+                (
+                    Complex::new(min.re.min(p.re), min.im.min(p.im)),
+                    Complex::new(max.re.max(p.re), max.im.max(p.im)),
+                )
+            },
+        );
+        let extent = if self.nodes.is_empty() {
+            1.0
+        } else {
+            (max.re - min.re).max(max.im - min.im)
+        };
+        let extent = if extent > 0.0 { extent } else { 1.0 };
+        let samples = options.samples.max(2);
+        // Positions and values of the samples of each member.
+        let values: Vec<Vec<(Float, Float)>> = (0..self.members.len())
+            .map(|member| {
+                let length = self.length(member);
+                (0..samples)
+                    .map(|i| {
+                        let x = length * i as Float / (samples - 1) as Float;
+                        (x, self.quantity_at(solution, quantity, member, x))
+                    })
+                    .collect()
+            })
+            .collect();
+        let largest = values
+            .iter()
+            .flatten()
+            .fold(0.0, |a: Float, &(_, v)| a.max(v.abs()));
+        let scale = options.scale.unwrap_or(if largest > 0.0 {
+            extent / 10.0 / largest
+        } else {
+            0.0
+        });
+        // Offsets of the moments are to the tension side.
+        let side = if quantity == Quantity::Moment {
+            -1.0
+        } else {
+            1.0
+        };
+        // To the y axis of SVG pointing down, without writing negative zeros.
+        let flip = |p: Complex<Float>| Complex::new(p.re, 0.0 - p.im);
+        let mut members = Path::<Complex<Float>, Float>::new();
+        let mut diagram = Path::<Complex<Float>, Float>::new();
+        let mut labels = vec![];
+        let mut bounds = (min, max);
+        for (member, values) in values.iter().enumerate() {
+            let [a, b] = self.members[member].nodes.map(point);
+            let along = (b - a) / (b - a).abs();
+            let left = Complex::new(-along.im, along.re);
+            members.move_to(flip(a)).line_to(flip(b));
+            let drawn: Vec<_> = values
+                .iter()
+                .map(|&(x, v)| match quantity {
+                    Quantity::Deflection => {
+                        let [u, v] = self.deflection(solution, member, x);
+                        a + along * (x + u * scale) + left * (v * scale)
+                    }
+                    _ => a + along * x + left * (v * scale * side),
+                })
+                .collect();
+            for p in &drawn {
+                bounds.0 = Complex::new(bounds.0.re.min(p.re), bounds.0.im.min(p.im));
+                bounds.1 = Complex::new(bounds.1.re.max(p.re), bounds.1.im.max(p.im));
+            }
+            if quantity == Quantity::Deflection {
+                diagram.move_to(flip(drawn[0]));
+            } else {
+                diagram.move_to(flip(a));
+                diagram.line_to(flip(drawn[0]));
+            }
+            for &p in &drawn[1..] {
+                diagram.line_to(flip(p));
+            }
+            if quantity != Quantity::Deflection {
+                diagram.line_to(flip(b)).close_path();
+            }
+            let extremes = [
+                values
+                    .iter()
+                    .enumerate()
+                    .max_by(|x, y| x.1 .1.total_cmp(&y.1 .1)),
+                values
+                    .iter()
+                    .enumerate()
+                    .min_by(|x, y| x.1 .1.total_cmp(&y.1 .1)),
+            ];
+            let mut labeled = vec![];
+            for (i, &(_, v)) in extremes.into_iter().flatten() {
+                if v.abs() > largest * 1e-9 && !labeled.contains(&i) {
+                    labeled.push(i);
+                    labels.push((flip(drawn[i]), v));
+                }
+            }
+        }
+        let stroke = extent / 500.0;
+        let hatch = extent / 100.0;
+        let font = extent / 40.0;
+        let margin = extent / 10.0;
+        let (top_left, bottom_right) = (
+            flip(Complex::new(bounds.0.re, bounds.1.im)),
+            flip(Complex::new(bounds.1.re, bounds.0.im)),
+        );
+        let mut svg = String::new();
+        write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            top_left.re - margin,
+            top_left.im - margin,
+            bottom_right.re - top_left.re + 2.0 * margin,
+            bottom_right.im - top_left.im + 2.0 * margin,
+        )
+        .unwrap();
+        write!(
+            svg,
+            r#"<defs><pattern id="hatch" patternUnits="userSpaceOnUse" width="{hatch}" height="{hatch}" patternTransform="rotate(45)"><line x1="0" y1="0" x2="0" y2="{hatch}" stroke="currentColor" stroke-width="{stroke}"/></pattern></defs>"#,
+        )
+        .unwrap();
+        write!(
+            svg,
+            r#"<path class="members" d="{members}" fill="none" stroke="currentColor" stroke-width="{}"/>"#,
+            stroke * 2.0
+        )
+        .unwrap();
+        let fill = if quantity == Quantity::Deflection {
+            "none"
+        } else {
+            "url(#hatch)"
+        };
+        write!(
+            svg,
+            r#"<path class="diagram" d="{diagram}" fill="{fill}" stroke="currentColor" stroke-width="{stroke}"/>"#,
+        )
+        .unwrap();
+        for (p, v) in labels {
+            write!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="{font}" text-anchor="middle">{:.*}</text>"#,
+                p.re, p.im, options.precision, v
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Load, Member, Support};
+    use super::*;
+
+    /// Simply supported beam of span 4 under a uniform load of 3 downward.
+    fn beam() -> (Model, Solution) {
+        let mut model = Model::new();
+        let a = model.add_node([0.0, 0.0]);
+        let b = model.add_node([4.0, 0.0]);
+        model.add_member(Member {
+            nodes: [a, b],
+            elasticity: 2.0,
+            area: 1.0,
+            inertia: 5.0,
+        });
+        model.add_support(Support {
+            node: a,
+            fixed: [true, true, false],
+        });
+        model.add_support(Support {
+            node: b,
+            fixed: [false, true, false],
+        });
+        model.add_load(Load::Member {
+            member: 0,
+            load: [0.0, -3.0],
+        });
+        let solution = model.solve().unwrap();
+        (model, solution)
+    }
+
+    #[test]
+    fn deflection() {
+        let (model, solution) = beam();
+        // 5 w L⁴ / 384 E I at midspan, and nothing at the supports.
+        let [u, v] = model.deflection(&solution, 0, 2.0);
+        assert!(u.abs() < 1e-6);
+        assert!((v + 5.0 * 3.0 * 256.0 / (384.0 * 10.0)).abs() < 1e-4);
+        assert!(model.deflection(&solution, 0, 0.0)[1].abs() < 1e-6);
+        assert!(model.deflection(&solution, 0, 4.0)[1].abs() < 1e-6);
+        assert_eq!(
+            model.quantity_at(&solution, Quantity::Deflection, 0, 2.0),
+            v
+        );
+    }
+
+    #[test]
+    fn diagram_svg() {
+        let (model, solution) = beam();
+        let options = DiagramOptions {
+            scale: Some(0.5),
+            samples: 5,
+            precision: 1,
+        };
+        let svg = model.diagram_svg(&solution, Quantity::Moment, &options);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert!(svg.contains(r#"<path class="members" d="M0,0 L4,0""#));
+        // The sagging moment of 6 drawn below the beam, at 3 in the y axis of SVG pointing down.
+        assert!(svg.contains(r#"text-anchor="middle">6.0</text>"#));
+        assert!(svg.contains(r#"<text x="2" y="3""#));
+        assert!(svg.contains(r#"fill="url(#hatch)""#));
+        // The shear is labeled at both ends.
+        let svg = model.diagram_svg(&solution, Quantity::Shear, &options);
+        assert!(svg.contains(">6.0</text>") && svg.contains(">-6.0</text>"));
+        // No labels without axial force.
+        let svg = model.diagram_svg(&solution, Quantity::Axial, &options);
+        assert!(!svg.contains("<text"));
+        let svg = model.diagram_svg(&solution, Quantity::Deflection, &options);
+        assert!(!svg.contains(r#"fill="url(#hatch)""#));
+        assert!(svg.contains(">-1.0</text>"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Matrices of a single member, with the degrees of freedom ordered as those of its first node
+//! then its second.
+
+use super::Member;
+use crate::{math::matrix::Matrix, Float};
+
+/// Stiffness in the local axes of an Euler–Bernoulli member of `length`.
+pub fn stiffness(member: &Member, length: Float) -> Matrix<Float> {
+    let e = member.elasticity;
+    let a = e * member.area / length;
+    let (b, c, d, f) = (
+        12.0 * e * member.inertia / length.powi(3),
+        6.0 * e * member.inertia / length.powi(2),
+        4.0 * e * member.inertia / length,
+        2.0 * e * member.inertia / length,
+    );
+    #[rustfmt::skip]
+    let k = vec![
+        a, 0.0, 0.0, -a, 0.0, 0.0,
+        0.0, b, c, 0.0, -b, c,
+        0.0, c, d, 0.0, -c, f,
+        -a, 0.0, 0.0, a, 0.0, 0.0,
+        0.0, -b, -c, 0.0, b, -c,
+        0.0, c, f, 0.0, -c, d,
+    ];
+    Matrix::new(6, 6, k)
+}
+
+/// Transformation from the global axes to the local ones of a member along `axis`.
+pub fn rotation(axis: [Float; 2]) -> Matrix<Float> {
+    let length = axis[0].hypot(axis[1]);
+    let (c, s) = (axis[0] / length, axis[1] / length);
+    Matrix::from_fn(6, 6, |i, j| match (i % 3, j % 3) {
+        _ if i / 3 != j / 3 => 0.0,
+        (0, 0) | (1, 1) => c,
+        (0, 1) => s,
+        (1, 0) => -s,
+        (2, 2) => 1.0,
+        _ => 0.0,
+    })
+}
+
+/// End forces in the local axes of a member of `length` with both ends fixed under the uniform
+/// `load` per length in its local axes, the forces the nodes exert on it.
+pub fn fixed_end_forces(load: [Float; 2], length: Float) -> [Float; 6] {
+    let [n, v] = load.map(|w| -w * length / 2.0);
+    let m = -load[1] * length * length / 12.0;
+    [n, v, m, n, v, -m]
+}
+
+/// `load` along the global axes in the local axes of a member along `axis`.
+pub fn local_load(load: [Float; 2], axis: [Float; 2]) -> [Float; 2] {
+    let length = axis[0].hypot(axis[1]);
+    let (c, s) = (axis[0] / length, axis[1] / length);
+    [c * load[0] + s * load[1], -s * load[0] + c * load[1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stiffness() {
+        let member = Member {
+            nodes: [0, 1],
+            elasticity: 2.0,
+            area: 3.0,
+            inertia: 4.0,
+        };
+        let k = super::stiffness(&member, 2.0);
+        assert_eq!(k, k.transpose());
+        assert_eq!(
+            (k[(0, 0)], k[(1, 1)], k[(2, 2)], k[(2, 5)]),
+            (3.0, 12.0, 16.0, 8.0)
+        );
+        // Rigid body motions are free of forces.
+        for u in [
+            [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            [0.0, -1.0, 1.0, 0.0, 1.0, 1.0],
+        ] {
+            assert!(k
+                .mul_vec(&u)
+                .iter()
+                .all(|&f| f.abs() < Float::EPSILON * 1e2));
+        }
+    }
+
+    #[test]
+    fn rotation() {
+        let t = super::rotation([0.0, 2.0]);
+        // The global y axis is the local x axis of a vertical member.
+        assert_eq!(
+            t.mul_vec(&[0.0, 1.0, 0.5, 1.0, 0.0, 0.0]),
+            [1.0, 0.0, 0.5, 0.0, -1.0, 0.0]
+        );
+        assert_eq!(local_load([0.0, -1.0], [0.0, 2.0]), [-1.0, 0.0]);
+    }
+
+    #[test]
+    fn fixed_end_forces() {
+        // A beam under gravity is held up with hogging end moments.
+        assert_eq!(
+            super::fixed_end_forces([0.0, -2.0], 6.0),
+            [0.0, 6.0, 6.0, 0.0, 6.0, -6.0]
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Extreme responses of the members over load combinations, with the governing combination and
+//! the forces concurrent with each extreme, as consumed by design checks.
+
+use super::{diagram::Quantity, solve, Load, LoadCase, Model, ModelError, Solution};
+use crate::Float;
+use std::ops::Range;
+
+/// Intervals sampled along a member for the extremes of the deflection, which unlike those of the
+/// forces are not found exactly.
+const SAMPLES: usize = 20;
+
+/// Analyzed load combination of [`Results`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combination {
+    pub name: String,
+    pub solution: Solution,
+    /// Uniform load of each member in its local axes.
+    member_loads: Vec<[Float; 2]>,
+}
+
+/// Solutions of a structure under each of its load combinations, queried for envelopes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Results {
+    model: Model,
+    combinations: Vec<Combination>,
+}
+
+/// Value of a quantity at a section of a member in a combination.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Extreme {
+    pub value: Float,
+    /// Index of the governing combination in [`Results::combinations`].
+    pub combination: usize,
+    /// Distance of the section from the first node of the member.
+    pub x: Float,
+    /// Axial force, shear force and bending moment at the section in the same combination, as
+    /// [`Model::internal_forces`].
+    pub concurrent: [Float; 3],
+}
+
+/// Largest and smallest values of a quantity along a member over all combinations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Envelope {
+    pub max: Extreme,
+    pub min: Extreme,
+}
+
+impl Results {
+    /// No combinations of the structure of the `model`, whose own loads are ignored.
+    pub fn new(model: Model) -> Self {
+        Self {
+            model: Model {
+                loads: vec![],
+                ..model
+            },
+            combinations: vec![],
+        }
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    pub fn combinations(&self) -> &[Combination] {
+        &self.combinations
+    }
+
+    /// Solves the structure under the `loads` and adds them as the combination `name`, returning
+    /// its index.
+    pub fn add(&mut self, name: impl Into<String>, loads: Vec<Load>) -> Result<usize, ModelError> {
+        let model = Model {
+            loads,
+            ..self.model.clone()
+        };
+        let solution = model.solve()?;
+        Ok(self.add_solved(name, &model.loads, solution))
+    }
+
+    /// Adds the `solution` of the structure under the `loads`, analyzed elsewhere, as the
+    /// combination `name` and returns its index.
+    pub fn add_solved(
+        &mut self,
+        name: impl Into<String>,
+        loads: &[Load],
+        solution: Solution,
+    ) -> usize {
+        self.combinations.push(Combination {
+            name: name.into(),
+            solution,
+            member_loads: self.model.member_loads(loads),
+        });
+        self.combinations.len() - 1
+    }
+
+    /// Solves the structure under all the load `cases` at once, as
+    /// [`Model::solve_static_many`], and adds them as combinations, returning their indices.
+    pub fn add_cases(&mut self, cases: &[LoadCase]) -> Result<Range<usize>, ModelError> {
+        let solutions = self.model.solve_static_many(cases)?;
+        let start = self.combinations.len();
+        for (case, solution) in cases.iter().zip(solutions) {
+            self.add_solved(case.name.clone(), &case.loads, solution);
+        }
+        Ok(start..self.combinations.len())
+    }
+
+    /// Sections of the `member` where the `quantity` may be extreme in a combination, its ends
+    /// and where the shear vanishes, the peak of the parabolic moment.
+    fn sections(&self, combination: &Combination, member: usize, quantity: Quantity) -> Vec<Float> {
+        let length = self.model.length(member);
+        if quantity == Quantity::Deflection {
+            return (0..=SAMPLES)
+                .map(|i| length * i as Float / SAMPLES as Float)
+                .collect();
+        }
+        let mut sections = vec![0.0, length];
+        let load = combination.member_loads[member];
+        let [_, v, _] =
+            solve::internal_forces(&combination.solution.member_forces[member], load, 0.0);
+        if load[1] != 0.0 {
+            let x = -v / load[1];
+            if x > 0.0 && x < length {
+                sections.push(x);
+            }
+        }
+        sections
+    }
+
+    /// Largest and smallest values of the `quantity` along the `member` over all combinations,
+    /// the transverse deflection for [`Quantity::Deflection`], or `None` without combinations.
+    /// Ties go to the first combination and section.
+    pub fn envelope(&self, member: usize, quantity: Quantity) -> Option<Envelope> {
+        let mut envelope: Option<Envelope> = None;
+        for (index, combination) in self.combinations.iter().enumerate() {
+            let load = combination.member_loads[member];
+            for x in self.sections(combination, member, quantity) {
+                let forces =
+                    solve::internal_forces(&combination.solution.member_forces[member], load, x);
+                let value = match quantity {
+                    Quantity::Axial => forces[0],
+                    Quantity::Shear => forces[1],
+                    Quantity::Moment => forces[2],
+                    Quantity::Deflection => {
+                        self.model
+                            .deflection_under(&combination.solution, member, load, x)[1]
+                    }
+                };
+                let extreme = Extreme {
+                    value,
+                    combination: index,
+                    x,
+                    concurrent: forces,
+                };
+                match &mut envelope {
+                    None => {
+                        envelope = Some(Envelope {
+                            max: extreme,
+                            min: extreme,
+                        })
+                    }
+                    Some(envelope) => {
+                        if value > envelope.max.value {
+                            envelope.max = extreme;
+                        }
+                        if value < envelope.min.value {
+                            envelope.min = extreme;
+                        }
+                    }
+                }
+            }
+        }
+        envelope
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{continuous, TOLERANCE};
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn uniform(members: &[usize], w: Float) -> Vec<Load> {
+        members
+            .iter()
+            .map(|&member| Load::Member {
+                member,
+                load: [0.0, -w],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn envelope() {
+        let mut results = Results::new(continuous(2, 4.0, 1));
+        assert_eq!(results.envelope(0, Quantity::Moment), None);
+        assert_eq!(results.add("both", uniform(&[0, 1], 2.0)), Ok(0));
+        assert_eq!(results.add("first", uniform(&[0], 2.0)), Ok(1));
+        assert_eq!(results.combinations()[1].name, "first");
+        let envelope = results.envelope(0, Quantity::Moment).unwrap();
+        // Loading the first span alone governs the sagging, 49/512 w L², at 7L/16, and loading
+        // both the hogging at the middle support, w L² / 8.
+        assert_eq!(envelope.max.combination, 1);
+        assert_relative_eq!(
+            envelope.max.value,
+            49.0 / 512.0 * 32.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            envelope.max.x,
+            1.75,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            envelope.max.concurrent[1],
+            0.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_eq!(envelope.max.concurrent[2], envelope.max.value);
+        assert_eq!(envelope.min.combination, 0);
+        assert_relative_eq!(
+            envelope.min.value,
+            -4.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_eq!(envelope.min.x, 4.0);
+        // The middle reaction, 5/4 w L, shared equally by the spans.
+        let shear = results.envelope(0, Quantity::Shear).unwrap();
+        assert_eq!((shear.min.combination, shear.min.x), (0, 4.0));
+        assert_relative_eq!(
+            shear.min.value,
+            -5.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        let axial = results.envelope(0, Quantity::Axial).unwrap();
+        assert_relative_eq!(
+            axial.max.value,
+            0.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        // The second span lifts when only the first is loaded.
+        let deflection = results.envelope(1, Quantity::Deflection).unwrap();
+        assert_eq!(deflection.max.combination, 1);
+        assert!(deflection.max.value > 0.0 && deflection.min.value < 0.0);
+        // Loads of the model are those of the combinations.
+        let mut model = results.model().clone();
+        model.loads = uniform(&[0], 2.0);
+        let solution = model.solve().unwrap();
+        assert_eq!(
+            results.add_solved("again", &model.loads, solution.clone()),
+            2
+        );
+        assert_eq!(results.combinations()[2].solution, solution);
+        assert_eq!(
+            results
+                .envelope(0, Quantity::Moment)
+                .unwrap()
+                .max
+                .combination,
+            1
+        );
+        let cases = [
+            LoadCase {
+                name: "second".into(),
+                loads: uniform(&[1], 2.0),
+            },
+            LoadCase {
+                name: "heavy".into(),
+                loads: uniform(&[0, 1], 3.0),
+            },
+        ];
+        assert_eq!(results.add_cases(&cases), Ok(3..5));
+        assert_eq!(results.combinations()[4].name, "heavy");
+        let envelope = results.envelope(1, Quantity::Moment).unwrap();
+        assert_eq!(envelope.min.combination, 4);
+        assert_relative_eq!(
+            envelope.min.value,
+            -6.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Models and comparisons shared by the tests of frames.
+
+use super::{Member, Model, Solution, Support};
+use crate::Float;
+use approx::assert_relative_eq;
+
+/// Error of the results of an analysis, relative to each and absolute about zero.
+pub const TOLERANCE: Float = Float::EPSILON * 1e4;
+
+/// Steel member between the `nodes` of an area of 1e-2 and a second moment of area of 1e-4, in kN
+/// and m.
+pub fn member(nodes: [usize; 2]) -> Member {
+    Member {
+        nodes,
+        elasticity: 2e8,
+        area: 1e-2,
+        inertia: 1e-4,
+    }
+}
+
+/// Horizontal [`member`] of the `length` fixed at the origin.
+pub fn cantilever(length: Float) -> Model {
+    let mut model = Model::new();
+    let nodes = model.extend_nodes([[0.0, 0.0], [length, 0.0]]);
+    model.add_member(member([nodes.start, nodes.start + 1]));
+    model.add_support(Support {
+        node: 0,
+        fixed: [true; 3],
+    });
+    model
+}
+
+/// Horizontal beam continuous over `spans` of the `length`, each of `members` [`member`]s, on
+/// pinned supports at the ends of the spans, the first also held along x.
+pub fn continuous(spans: usize, length: Float, members: usize) -> Model {
+    let mut model = Model::new();
+    let step = length / members as Float;
+    model.extend_nodes((0..=spans * members).map(|i| [step * i as Float, 0.0]));
+    for node in (0..=spans * members).step_by(members) {
+        model.add_support(Support {
+            node,
+            fixed: [node == 0, true, false],
+        });
+    }
+    model.extend_members((0..spans * members).map(|i| member([i, i + 1])));
+    model
+}
+
+/// Asserts the displacements, reactions and member forces of `actual` are those of `expected`
+/// within [`TOLERANCE`].
+pub fn assert_solution_eq(actual: &Solution, expected: &Solution) {
+    assert_relative_eq!(
+        actual.displacements.as_flattened(),
+        expected.displacements.as_flattened(),
+        epsilon = TOLERANCE,
+        max_relative = TOLERANCE
+    );
+    assert_relative_eq!(
+        actual.reactions.as_flattened(),
+        expected.reactions.as_flattened(),
+        epsilon = TOLERANCE,
+        max_relative = TOLERANCE
+    );
+    assert_relative_eq!(
+        actual.member_forces.as_flattened(),
+        expected.member_forces.as_flattened(),
+        epsilon = TOLERANCE,
+        max_relative = TOLERANCE
+    );
+}
@@ -0,0 +1,477 @@
+//! Complete models of common structures generated from a few dimensions, as templates to start
+//! from and as large inputs of known layout for measuring performance. The same parameters always
+//! produce the same model.
+
+use super::{Load, LoadCase, Member, Model, Support};
+use crate::Float;
+
+/// Material and section of a group of members.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Properties {
+    pub elasticity: Float,
+    pub area: Float,
+    pub inertia: Float,
+}
+
+impl Properties {
+    fn member(self, nodes: [usize; 2]) -> Member {
+        Member {
+            nodes,
+            elasticity: self.elasticity,
+            area: self.area,
+            inertia: self.inertia,
+        }
+    }
+}
+
+impl Default for Properties {
+    /// Steel in kN and m, a rolled section of about 200 mm deep.
+    fn default() -> Self {
+        Self {
+            elasticity: 2.05e8,
+            area: 6.4e-3,
+            inertia: 4.7e-5,
+        }
+    }
+}
+
+/// Magnitudes of the default load cases, downward per length of span for the gravity loads and
+/// to the right for the wind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Loading {
+    pub dead: Float,
+    pub live: Float,
+    /// Force at the windward top of the structure, or of each story.
+    pub wind: Float,
+}
+
+impl Default for Loading {
+    fn default() -> Self {
+        Self {
+            dead: 10.0,
+            live: 5.0,
+            wind: 20.0,
+        }
+    }
+}
+
+/// Generated model with its load cases `dead`, `live` and `wind`, in that order. The loads of the
+/// model are those of the dead case, so that it can be solved as it is.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Generated {
+    pub model: Model,
+    pub cases: Vec<LoadCase>,
+}
+
+impl Generated {
+    /// Cases of the `dead`, `live` and `wind` loads as listed by [`Loading`], each load scaled by
+    /// its magnitude.
+    fn new(model: Model, loading: Loading, gravity: Vec<Load>, wind: Vec<Load>) -> Self {
+        let scaled = |loads: &[Load], factor: Float| {
+            loads
+                .iter()
+                .map(|&load| match load {
+                    Load::Node { node, force } => Load::Node {
+                        node,
+                        force: force.map(|f| f * factor),
+                    },
+                    Load::Member { member, load } => Load::Member {
+                        member,
+                        load: load.map(|w| w * factor),
+                    },
+                })
+                .collect()
+        };
+        let cases = [
+            ("dead", scaled(&gravity, loading.dead)),
+            ("live", scaled(&gravity, loading.live)),
+            ("wind", scaled(&wind, loading.wind)),
+        ]
+        .map(|(name, loads)| LoadCase {
+            name: name.into(),
+            loads,
+        });
+        Self {
+            model: Model {
+                loads: cases[0].loads.clone(),
+                ..model
+            },
+            cases: cases.into(),
+        }
+    }
+}
+
+/// Single story frame of `bays` bays of beams on columns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Portal {
+    pub span: Float,
+    pub height: Float,
+    pub bays: usize,
+    /// Whether the column bases are pinned rather than fixed.
+    pub pinned: bool,
+    pub columns: Properties,
+    pub beams: Properties,
+    pub loading: Loading,
+}
+
+impl Default for Portal {
+    fn default() -> Self {
+        Self {
+            span: 6.0,
+            height: 4.0,
+            bays: 1,
+            pinned: false,
+            columns: Properties::default(),
+            beams: Properties::default(),
+            loading: Loading::default(),
+        }
+    }
+}
+
+impl Portal {
+    /// Numbered as by [`Grid::generate`], with the wind at the top of the leftmost column.
+    pub fn generate(&self) -> Generated {
+        Grid {
+            span: self.span,
+            height: self.height,
+            bays: self.bays,
+            stories: 1,
+            columns: self.columns,
+            beams: self.beams,
+            loading: self.loading,
+        }
+        .build(!self.pinned)
+    }
+}
+
+/// Frame of `stories` stories of beams on `bays + 1` lines of columns with fixed bases.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Grid {
+    pub span: Float,
+    /// Height of each story.
+    pub height: Float,
+    pub bays: usize,
+    pub stories: usize,
+    pub columns: Properties,
+    pub beams: Properties,
+    pub loading: Loading,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            span: 6.0,
+            height: 3.5,
+            bays: 3,
+            stories: 5,
+            columns: Properties::default(),
+            beams: Properties::default(),
+            loading: Loading::default(),
+        }
+    }
+}
+
+impl Grid {
+    /// Nodes of each column line from its base, numbered from the left, then the columns in the
+    /// same order and then the beams of each floor from the bottom, with the wind at the left end
+    /// of every floor.
+    pub fn generate(&self) -> Generated {
+        self.build(true)
+    }
+
+    fn build(&self, fixed: bool) -> Generated {
+        let levels = self.stories + 1;
+        let node = |column: usize, level: usize| column * levels + level;
+        let mut model = Model::with_capacity(
+            (self.bays + 1) * levels,
+            (self.bays + 1) * self.stories + self.bays * self.stories,
+        );
+        for column in 0..=self.bays {
+            let x = self.span * column as Float;
+            model.extend_nodes((0..levels).map(|level| [x, self.height * level as Float]));
+            model.add_support(Support {
+                node: node(column, 0),
+                fixed: [true, true, fixed],
+            });
+        }
+        for column in 0..=self.bays {
+            model.extend_members((1..levels).map(|level| {
+                self.columns
+                    .member([node(column, level - 1), node(column, level)])
+            }));
+        }
+        let beams = model.extend_members((1..levels).flat_map(|level| {
+            (0..self.bays)
+                .map(move |bay| self.beams.member([node(bay, level), node(bay + 1, level)]))
+        }));
+        let gravity = beams
+            .map(|member| Load::Member {
+                member,
+                load: [0.0, -1.0],
+            })
+            .collect();
+        let wind = (1..levels)
+            .map(|level| Load::Node {
+                node: node(0, level),
+                force: [1.0, 0.0, 0.0],
+            })
+            .collect();
+        Generated::new(model, self.loading, gravity, wind)
+    }
+}
+
+/// Pattern of the web members of a [`Truss`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Web {
+    /// Verticals at every panel point and diagonals sloping down toward the middle, in tension
+    /// under gravity.
+    Pratt,
+    /// Diagonals alternating in direction without verticals, with the top chord half a panel
+    /// shorter at each end.
+    Warren,
+}
+
+/// Truss of `panels` panels, pinned at the left end of its bottom chord and on a roller at the
+/// right. Its joints are rigid as for any frame, which adds little bending to slender members.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Truss {
+    pub web: Web,
+    pub span: Float,
+    pub depth: Float,
+    pub panels: usize,
+    pub chords: Properties,
+    pub webs: Properties,
+    pub loading: Loading,
+}
+
+impl Default for Truss {
+    fn default() -> Self {
+        Self {
+            web: Web::Pratt,
+            span: 12.0,
+            depth: 1.5,
+            panels: 6,
+            chords: Properties::default(),
+            webs: Properties {
+                area: 2.0e-3,
+                inertia: 5.0e-6,
+                ..Properties::default()
+            },
+            loading: Loading::default(),
+        }
+    }
+}
+
+impl Truss {
+    /// Nodes of the bottom chord from the left and then of the top chord, members of the bottom
+    /// chord, the top chord and then the web, with the gravity loads lumped at the top chord and
+    /// the wind at its left end.
+    pub fn generate(&self) -> Generated {
+        let panel = self.span / self.panels as Float;
+        let (offset, top) = match self.web {
+            Web::Pratt => (0.0, self.panels + 1),
+            Web::Warren => (0.5, self.panels),
+        };
+        let mut model = Model::with_capacity(self.panels + 1 + top, 4 * self.panels);
+        let bottom = model.extend_nodes((0..=self.panels).map(|i| [panel * i as Float, 0.0]));
+        let top = model.extend_nodes((0..top).map(|i| [panel * (i as Float + offset), self.depth]));
+        for (node, fixed) in [(bottom.start, true), (bottom.end - 1, false)] {
+            model.add_support(Support {
+                node,
+                fixed: [fixed, true, false],
+            });
+        }
+        let chord = |nodes: &std::ops::Range<usize>| {
+            let members = nodes.clone().zip(nodes.clone().skip(1));
+            members
+                .map(|(a, b)| self.chords.member([a, b]))
+                .collect::<Vec<_>>()
+        };
+        model.extend_members(chord(&bottom));
+        model.extend_members(chord(&top));
+        let (b, t) = (bottom.start, top.start);
+        let web: Vec<_> = match self.web {
+            Web::Pratt => {
+                let verticals = (0..=self.panels).map(|i| [b + i, t + i]);
+                let diagonals = (0..self.panels).map(|i| {
+                    if 2 * i < self.panels {
+                        [t + i, b + i + 1]
+                    } else {
+                        [b + i, t + i + 1]
+                    }
+                });
+                verticals.chain(diagonals).collect()
+            }
+            Web::Warren => (0..self.panels)
+                .flat_map(|i| [[b + i, t + i], [t + i, b + i + 1]])
+                .collect(),
+        };
+        model.extend_members(web.into_iter().map(|nodes| self.webs.member(nodes)));
+        let gravity = top
+            .clone()
+            .map(|node| {
+                let ends = self.web == Web::Pratt && (node == top.start || node == top.end - 1);
+                Load::Node {
+                    node,
+                    force: [0.0, -if ends { panel / 2.0 } else { panel }, 0.0],
+                }
+            })
+            .collect();
+        let wind = vec![Load::Node {
+            node: top.start,
+            force: [1.0, 0.0, 0.0],
+        }];
+        Generated::new(model, self.loading, gravity, wind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::TOLERANCE;
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Sum of the vertical reactions of the `case`.
+    fn lifted(generated: &Generated, case: usize) -> Float {
+        let model = Model {
+            loads: generated.cases[case].loads.clone(),
+            ..generated.model.clone()
+        };
+        let solution = model.solve().unwrap();
+        solution.reactions.iter().map(|r| r[1]).sum()
+    }
+
+    #[test]
+    fn portal() {
+        let generated = Portal {
+            bays: 2,
+            ..Portal::default()
+        }
+        .generate();
+        let model = &generated.model;
+        assert_eq!((model.nodes.len(), model.members.len()), (6, 5));
+        assert_eq!(model.supports.len(), 3);
+        assert!(model.supports.iter().all(|s| s.fixed == [true; 3]));
+        assert_eq!(model.loads, generated.cases[0].loads);
+        let names: Vec<_> = generated.cases.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["dead", "live", "wind"]);
+        assert_relative_eq!(
+            lifted(&generated, 0),
+            10.0 * 12.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            lifted(&generated, 1),
+            5.0 * 12.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        let pinned = Portal {
+            pinned: true,
+            ..Portal::default()
+        }
+        .generate();
+        assert_eq!(pinned.model.supports[1].fixed, [true, true, false]);
+        let solution = pinned.model.solve().unwrap();
+        assert_relative_eq!(
+            solution.reactions[0][2],
+            0.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn grid() {
+        let grid = Grid::default();
+        let generated = grid.generate();
+        assert_eq!(generated, grid.generate());
+        let model = &generated.model;
+        assert_eq!((model.nodes.len(), model.members.len()), (24, 35));
+        assert!(model.validate().is_ok());
+        // The top of the first column.
+        assert_eq!(model.nodes[5].position, [0.0, 17.5]);
+        assert_relative_eq!(
+            lifted(&generated, 0),
+            10.0 * 18.0 * 5.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        let wind = Model {
+            loads: generated.cases[2].loads.clone(),
+            ..model.clone()
+        };
+        let solution = wind.solve().unwrap();
+        let shear: Float = solution.reactions.iter().map(|r| r[0]).sum();
+        assert_relative_eq!(
+            shear,
+            -20.0 * 5.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert!(solution.displacements[5][0] > solution.displacements[1][0]);
+    }
+
+    #[test]
+    fn truss() {
+        for web in [Web::Pratt, Web::Warren] {
+            let generated = Truss {
+                web,
+                ..Truss::default()
+            }
+            .generate();
+            let model = &generated.model;
+            let members = match web {
+                Web::Pratt => 6 + 6 + 7 + 6,
+                Web::Warren => 6 + 5 + 12,
+            };
+            assert_eq!(model.members.len(), members);
+            assert!(model.validate().is_ok());
+            assert_relative_eq!(
+                lifted(&generated, 0),
+                10.0 * 12.0,
+                epsilon = TOLERANCE,
+                max_relative = TOLERANCE
+            );
+            let solution = model.solve().unwrap();
+            // The bottom chord in tension and the top in compression at midspan.
+            let forces = |member: usize| solution.member_forces[member];
+            assert!(forces(3)[0] < 0.0);
+            assert!(forces(6 + 2)[0] > 0.0);
+            if web == Web::Pratt {
+                // The end diagonals in tension, pulling the first node of the left one.
+                let diagonal = forces(6 + 6 + 7);
+                assert!(diagonal[0] < 0.0, "{diagonal:?}");
+            }
+        }
+    }
+}
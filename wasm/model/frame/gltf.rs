@@ -0,0 +1,292 @@
+//! Export of the members of a [`Model`] as a 3D mesh in glTF 2.0, with the deflected shape as a
+//! morph target.
+
+use super::{Model, Solution};
+use crate::{math::complex::Complex, Float};
+use std::fmt::Write;
+
+/// Controls of [`Model::gltf`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GltfOptions {
+    /// Convex outline of the members in the plane of their local y axis and the global z axis,
+    /// extruded along each member.
+    pub outline: Vec<Complex<Float>>,
+    /// Rings of the outline along each member, including its ends.
+    pub samples: usize,
+    /// Magnification of the displacements of the deflected shape.
+    pub scale: Float,
+}
+
+/// Encodes in base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [0, 1, 2].map(|i| chunk.get(i).copied().unwrap_or(0) as u32);
+        let n = b[0] << 16 | b[1] << 8 | b[2];
+        for i in 0..4 {
+            encoded.push(if i <= chunk.len() {
+                ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char
+            } else {
+                '='
+            });
+        }
+    }
+    encoded
+}
+
+/// Single precision of the components of glTF, a no-op with the `f32` feature.
+#[allow(clippy::unnecessary_cast)]
+fn single(value: Float) -> f32 {
+    value as f32
+}
+
+/// Componentwise minimum and maximum of the `vectors`, zero if there are none.
+fn bounds(vectors: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    if vectors.is_empty() {
+        return ([0.0; 3], [0.0; 3]);
+    }
+    vectors.iter().fold(
+        ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+        |(min, max), p| {
+            (
+                [0, 1, 2].map(|i| min[i].min(p[i])),
+                [0, 1, 2].map(|i| max[i].max(p[i])),
+            )
+        },
+    )
+}
+
+impl Model {
+    /// glTF asset of the members extruded along their axes, with the z axis of the model out of
+    /// its plane, as JSON with the data embedded.
+    ///
+    /// With a `solution`, the displacements of the deflected shape are the morph target of the
+    /// mesh, animated from the undeformed shape to the deformed one over a second.
+    pub fn gltf(&self, solution: Option<&Solution>, options: &GltfOptions) -> String {
+        let samples = options.samples.max(2);
+        let ring = options.outline.len();
+        let mut positions: Vec<[f32; 3]> = vec![];
+        let mut displacements: Vec<[f32; 3]> = vec![];
+        let mut indices: Vec<u32> = vec![];
+        for member in 0..self.members.len() {
+            let [a, b] = self.members[member].nodes.map(|n| {
+                let [x, y] = self.nodes[n].position;
+                Complex::new(x, y)
+            });
+            let length = self.length(member);
+            let along = (b - a) / length;
+            let left = Complex::new(-along.im, along.re);
+            let first = positions.len() as u32;
+            for i in 0..samples {
+                let x = length * i as Float / (samples - 1) as Float;
+                let d = solution.map_or(Complex::new(0.0, 0.0), |solution| {
+                    let [u, v] = self.deflection(solution, member, x);
+                    (along * u + left * v) * options.scale
+                });
+                for p in &options.outline {
+                    let q = a + along * x + left * p.re;
+                    positions.push([q.re, q.im, p.im].map(single));
+                    displacements.push([d.re, d.im, 0.0].map(single));
+                }
+            }
+            // Quadrilaterals between consecutive rings, counterclockwise seen from outside if the
+            // outline is counterclockwise.
+            let vertex = |i: usize, j: usize| first + (i * ring + j % ring) as u32;
+            for i in 0..samples - 1 {
+                for j in 0..ring {
+                    indices.extend([
+                        vertex(i, j),
+                        vertex(i + 1, j),
+                        vertex(i + 1, j + 1),
+                        vertex(i, j),
+                        vertex(i + 1, j + 1),
+                        vertex(i, j + 1),
+                    ]);
+                }
+            }
+            // Fans closing the ends.
+            for j in 1..ring.saturating_sub(1) {
+                indices.extend([vertex(0, 0), vertex(0, j + 1), vertex(0, j)]);
+                let last = samples - 1;
+                indices.extend([vertex(last, 0), vertex(last, j), vertex(last, j + 1)]);
+            }
+        }
+        let (min, max) = bounds(&positions);
+        let mut buffer: Vec<u8> = vec![];
+        let mut views = vec![];
+        let mut view = |buffer: &mut Vec<u8>, bytes: Vec<u8>, target: Option<u32>| {
+            views.push((buffer.len(), bytes.len(), target));
+            buffer.extend(bytes);
+            views.len() - 1
+        };
+        let floats = |values: &mut dyn Iterator<Item = f32>| -> Vec<u8> {
+            values.flat_map(f32::to_le_bytes).collect()
+        };
+        let position_view = view(
+            &mut buffer,
+            floats(&mut positions.iter().flatten().copied()),
+            Some(34962),
+        );
+        let index_view = view(
+            &mut buffer,
+            indices.iter().flat_map(|i| i.to_le_bytes()).collect(),
+            Some(34963),
+        );
+        let morph = solution.is_some().then(|| {
+            (
+                view(
+                    &mut buffer,
+                    floats(&mut displacements.iter().flatten().copied()),
+                    Some(34962),
+                ),
+                view(&mut buffer, floats(&mut [0.0, 1.0].into_iter()), None),
+                view(&mut buffer, floats(&mut [0.0, 1.0].into_iter()), None),
+            )
+        });
+        let vertices = positions.len();
+        let mut json = String::new();
+        json.push_str(r#"{"asset":{"version":"2.0","generator":"strust"},"scene":0,"#);
+        json.push_str(r#""scenes":[{"nodes":[0]}],"nodes":[{"mesh":0}],"#);
+        write!(
+            json,
+            r#""meshes":[{{"primitives":[{{"attributes":{{"POSITION":0}},"indices":1"#
+        )
+        .unwrap();
+        if morph.is_some() {
+            json.push_str(r#","targets":[{"POSITION":2}]}],"weights":[0]}],"#);
+        } else {
+            json.push_str("}]}],");
+        }
+        write!(
+            json,
+            r#""accessors":[{{"bufferView":{position_view},"componentType":5126,"count":{vertices},"type":"VEC3","min":{min:?},"max":{max:?}}},"#
+        )
+        .unwrap();
+        write!(
+            json,
+            r#"{{"bufferView":{index_view},"componentType":5125,"count":{},"type":"SCALAR"}}"#,
+            indices.len()
+        )
+        .unwrap();
+        if let Some((displacement_view, time_view, weight_view)) = morph {
+            let (min, max) = bounds(&displacements);
+            write!(
+                json,
+                r#",{{"bufferView":{displacement_view},"componentType":5126,"count":{vertices},"type":"VEC3","min":{min:?},"max":{max:?}}}"#
+            )
+            .unwrap();
+            write!(
+                json,
+                r#",{{"bufferView":{time_view},"componentType":5126,"count":2,"type":"SCALAR","min":[0.0],"max":[1.0]}}"#
+            )
+            .unwrap();
+            write!(
+                json,
+                r#",{{"bufferView":{weight_view},"componentType":5126,"count":2,"type":"SCALAR"}}"#
+            )
+            .unwrap();
+            json.push_str(r#"],"animations":[{"channels":[{"sampler":0,"target":{"node":0,"path":"weights"}}],"#);
+            json.push_str(r#""samplers":[{"input":3,"output":4}]}"#);
+        }
+        json.push_str(r#"],"bufferViews":["#);
+        for (i, (offset, length, target)) in views.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{length}"#
+            )
+            .unwrap();
+            if let Some(target) = target {
+                write!(json, r#","target":{target}"#).unwrap();
+            }
+            json.push('}');
+        }
+        write!(
+            json,
+            r#"],"buffers":[{{"byteLength":{},"uri":"data:application/octet-stream;base64,{}"}}]}}"#,
+            buffer.len(),
+            base64(&buffer)
+        )
+        .unwrap();
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Load, Member, Support};
+    use super::*;
+
+    #[test]
+    fn base64() {
+        for (bytes, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(super::base64(bytes.as_bytes()), encoded);
+        }
+    }
+
+    #[test]
+    fn gltf() {
+        let mut model = Model::new();
+        let a = model.add_node([0.0, 0.0]);
+        let b = model.add_node([2.0, 0.0]);
+        model.add_member(Member {
+            nodes: [a, b],
+            elasticity: 1.0,
+            area: 1.0,
+            inertia: 1.0,
+        });
+        model.add_support(Support {
+            node: a,
+            fixed: [true; 3],
+        });
+        model.add_load(Load::Node {
+            node: b,
+            force: [0.0, -1.0, 0.0],
+        });
+        let options = GltfOptions {
+            outline: vec![
+                Complex::new(-0.1, -0.1),
+                Complex::new(0.1, -0.1),
+                Complex::new(0.1, 0.1),
+                Complex::new(-0.1, 0.1),
+            ],
+            samples: 3,
+            scale: 1.0,
+        };
+        let undeformed = model.gltf(None, &options);
+        assert!(!undeformed.contains("targets"));
+        let solution = model.solve().unwrap();
+        let gltf: serde_json::Value =
+            serde_json::from_str(&model.gltf(Some(&solution), &options)).unwrap();
+        let accessors = &gltf["accessors"];
+        // Three rings of four vertices, with four quadrilaterals between each two rings and two
+        // triangles closing each end.
+        assert_eq!(accessors[0]["count"], 12);
+        assert_eq!(accessors[1]["count"], (2 * 4 * 2 + 2 * 2) * 3);
+        assert_eq!(accessors[0]["max"][0], 2.0);
+        assert_eq!(accessors[0]["min"][2], -0.1);
+        // The tip deflects by P L³ / 3 E I.
+        let min = accessors[2]["min"][1].as_f64().unwrap();
+        assert!((min + 8.0 / 3.0).abs() < 1e-4);
+        let buffer = &gltf["buffers"][0];
+        let uri = buffer["uri"].as_str().unwrap();
+        let length = buffer["byteLength"].as_u64().unwrap();
+        assert_eq!(
+            length,
+            (12 * 3 * 4 + 60 * 4 + 12 * 3 * 4 + 2 * 4 + 2 * 4) as u64
+        );
+        let encoded = uri.split(',').nth(1).unwrap();
+        assert_eq!(encoded.len() as u64, length.div_ceil(3) * 4);
+        assert_eq!(gltf["animations"][0]["samplers"][0]["output"], 4);
+    }
+}
@@ -0,0 +1,211 @@
+//! Edits of a [`Model`] as invertible commands, recorded to be undone and redone.
+
+use super::{Load, Member, Model};
+use crate::Float;
+
+/// Edit of a [`Model`], applied by [`Command::apply`] which returns the command undoing it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Command {
+    /// Appends the nodes, members, supports, loads and springs of a model.
+    Extend(Model),
+    /// Removes the nodes, members, supports, loads and springs past the counts, in that order.
+    Truncate {
+        nodes: usize,
+        members: usize,
+        supports: usize,
+        loads: usize,
+        springs: usize,
+    },
+    SetNode {
+        node: usize,
+        position: [Float; 2],
+    },
+    SetMember {
+        index: usize,
+        member: Member,
+    },
+    /// Replaces all the loads.
+    SetLoads(Vec<Load>),
+}
+
+impl Command {
+    /// Applies the command to the `model` and returns its inverse.
+    ///
+    /// # Panics
+    ///
+    /// If the node or member set does not exist.
+    pub fn apply(self, model: &mut Model) -> Command {
+        match self {
+            Command::Extend(items) => {
+                let inverse = Command::Truncate {
+                    nodes: model.nodes.len(),
+                    members: model.members.len(),
+                    supports: model.supports.len(),
+                    loads: model.loads.len(),
+                    springs: model.springs.len(),
+                };
+                model.nodes.extend(items.nodes);
+                model.members.extend(items.members);
+                model.supports.extend(items.supports);
+                model.loads.extend(items.loads);
+                model.springs.extend(items.springs);
+                inverse
+            }
+            Command::Truncate {
+                nodes,
+                members,
+                supports,
+                loads,
+                springs,
+            } => Command::Extend(Model {
+                nodes: model.nodes.split_off(nodes.min(model.nodes.len())),
+                members: model.members.split_off(members.min(model.members.len())),
+                supports: model.supports.split_off(supports.min(model.supports.len())),
+                loads: model.loads.split_off(loads.min(model.loads.len())),
+                springs: model.springs.split_off(springs.min(model.springs.len())),
+            }),
+            Command::SetNode { node, position } => Command::SetNode {
+                node,
+                position: std::mem::replace(&mut model.nodes[node].position, position),
+            },
+            Command::SetMember { index, member } => Command::SetMember {
+                index,
+                member: std::mem::replace(&mut model.members[index], member),
+            },
+            Command::SetLoads(loads) => {
+                Command::SetLoads(std::mem::replace(&mut model.loads, loads))
+            }
+        }
+    }
+}
+
+/// Commands applied to a [`Model`] through [`Self::apply`], to step back and forth through its
+/// edits.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct History {
+    /// Inverses of the commands applied, the last one first to undo.
+    undo: Vec<Command>,
+    /// Inverses of the commands undone, the last one first to redo.
+    redo: Vec<Command>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the `command` to the `model` and records it, so that the commands undone can no
+    /// longer be redone.
+    pub fn apply(&mut self, model: &mut Model, command: Command) {
+        self.undo.push(command.apply(model));
+        self.redo.clear();
+    }
+
+    /// Reverts the last command applied or redone to the `model`, returning whether there was
+    /// one.
+    pub fn undo(&mut self, model: &mut Model) -> bool {
+        let Some(command) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(command.apply(model));
+        true
+    }
+
+    /// Applies again the last command undone to the `model`, returning whether there was one.
+    pub fn redo(&mut self, model: &mut Model) -> bool {
+        let Some(command) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(command.apply(model));
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{fixtures::member, Node, Spring, Support};
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let mut model = Model::new();
+        let mut history = History::new();
+        assert!(!history.undo(&mut model));
+        history.apply(
+            &mut model,
+            Command::Extend(Model {
+                nodes: vec![
+                    Node {
+                        position: [0.0, 0.0],
+                    },
+                    Node {
+                        position: [4.0, 0.0],
+                    },
+                ],
+                members: vec![member([0, 1])],
+                supports: vec![Support {
+                    node: 0,
+                    fixed: [true; 3],
+                }],
+                loads: vec![],
+                springs: vec![Spring {
+                    node: 1,
+                    stiffness: [0.0, 1e3, 0.0],
+                }],
+            }),
+        );
+        let built = model.clone();
+        let load = Load::Node {
+            node: 1,
+            force: [0.0, -1.0, 0.0],
+        };
+        history.apply(&mut model, Command::SetLoads(vec![load]));
+        history.apply(
+            &mut model,
+            Command::SetNode {
+                node: 1,
+                position: [3.0, 0.0],
+            },
+        );
+        history.apply(
+            &mut model,
+            Command::SetMember {
+                index: 0,
+                member: Member {
+                    inertia: 2e-4,
+                    ..member([0, 1])
+                },
+            },
+        );
+        let edited = model.clone();
+        assert_eq!(model.length(0), 3.0);
+        assert!(history.undo(&mut model) && history.undo(&mut model));
+        assert_eq!(model.loads, [load]);
+        assert_eq!(model.nodes, built.nodes);
+        assert!(history.undo(&mut model));
+        assert_eq!(model, built);
+        assert!(history.undo(&mut model));
+        assert_eq!(model, Model::new());
+        assert!(!history.can_undo() && history.can_redo());
+        while history.redo(&mut model) {}
+        assert_eq!(model, edited);
+        // Applying a command drops those undone.
+        history.undo(&mut model);
+        history.apply(&mut model, Command::SetLoads(vec![]));
+        assert!(!history.can_redo());
+        assert!(history.undo(&mut model) && history.undo(&mut model));
+        assert_eq!(model.length(0), 4.0);
+    }
+}
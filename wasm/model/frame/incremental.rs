@@ -0,0 +1,420 @@
+//! Re-analysis of a [`Model`] under edits, reusing the factors of its stiffness. Edits of the
+//! loads only back-substitute, and edits of a few members correct the back-substitution by the
+//! Sherman-Morrison-Woodbury formula, so that what-if edits of a model take a fraction of a full
+//! analysis.
+
+use super::{
+    solve::{Factorized, Loads},
+    Load, Member, Model, ModelError, Solution,
+};
+use crate::{clock, math::matrix::Matrix, Float};
+use std::{cmp::Ordering, collections::BTreeSet};
+
+/// Degrees of freedom of the edited members beyond which they are reassembled and the stiffness
+/// factorized again, rather than corrected for at each solve.
+const RANK_LIMIT: usize = 48;
+
+/// How [`Incremental::solve`] reused the previous analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Reuse {
+    /// Assembled and factorized the stiffness, for the first analysis or after an edit of the
+    /// nodes, members or supports that changed the pattern of the stiffness.
+    None,
+    /// Back-substituted the loads through the factors of the stiffness unchanged.
+    Factors,
+    /// Corrected the back-substitution for the stiffness of the edited members, coupling `rank`
+    /// degrees of freedom.
+    LowRank { rank: usize },
+    /// Reassembled the stiffness of the edited `members` alone and factorized it again.
+    Reassembled { members: usize },
+}
+
+/// Correction of the factors for edited members, `(K + U D Uᵀ)⁻¹ = K⁻¹ - Z S⁻¹ D Uᵀ K⁻¹` with
+/// `Z = K⁻¹ U` and `S = I + D Uᵀ Z`, where the columns of `U` pick the degrees of freedom of the
+/// members in the reduced system.
+struct LowRank {
+    dofs: Vec<usize>,
+    d: Matrix<Float>,
+    /// `Z` by rows.
+    z: Vec<Float>,
+    s: Matrix<Float>,
+}
+
+impl LowRank {
+    /// Degrees of freedom of the `members` in the reduced system of the `factorized` stiffness.
+    fn dofs(factorized: &Factorized, members: &BTreeSet<usize>) -> Vec<usize> {
+        let free = factorized.free();
+        let dofs: BTreeSet<_> = members
+            .iter()
+            .flat_map(|&member| factorized.member_dofs(member))
+            .filter_map(|p| free[p])
+            .collect();
+        dofs.into_iter().collect()
+    }
+
+    /// Correction for the `members` of the `factorized` stiffness, `None` unless the edited
+    /// stiffness is safely positive definite, `G⁻¹ + D` with `G = Uᵀ K⁻¹ U` by Sylvester's law of
+    /// inertia, which is checked on the congruent `G + G D G`.
+    fn new(factorized: &Factorized, members: &BTreeSet<usize>, dofs: Vec<usize>) -> Option<Self> {
+        let free = factorized.free();
+        let m = dofs.len();
+        let mut d = Matrix::<Float>::zeros(m, m);
+        for &member in members {
+            let change = factorized.stiffness_change(member);
+            let local = (factorized.member_dofs(member))
+                .map(|p| free[p].map(|p| dofs.binary_search(&p).unwrap()));
+            for (i, p) in local.iter().enumerate() {
+                for (j, q) in local.iter().enumerate() {
+                    if let (Some(p), Some(q)) = (p, q) {
+                        d[(*p, *q)] += change[(i, j)];
+                    }
+                }
+            }
+        }
+        let mut u = vec![0.0; factorized.count() * m];
+        for (c, &p) in dofs.iter().enumerate() {
+            u[p * m + c] = 1.0;
+        }
+        let z = factorized.solve_block(&u, m);
+        let g = Matrix::<Float>::from_fn(m, m, |i, j| z[dofs[i] * m + j]);
+        let dg = &d * &g;
+        if !positive_definite(&(&g + &(&g * &dg))) {
+            return None;
+        }
+        let s = &Matrix::<Float>::identity(m) + &dg;
+        Some(Self { dofs, d, z, s })
+    }
+
+    /// Corrects the solution `x` of the reduced system for the factors to that for the edited
+    /// stiffness.
+    fn correct(&self, x: &mut [Float]) -> Result<(), ModelError> {
+        let m = self.dofs.len();
+        let at: Vec<_> = self.dofs.iter().map(|&p| x[p]).collect();
+        let w = Matrix::new(m, 1, self.d.mul_vec(&at));
+        let w = self.s.solve(&w).ok_or(ModelError::Unstable)?;
+        for (r, x) in x.iter_mut().enumerate() {
+            *x -= (0..m).map(|k| self.z[r * m + k] * w[(k, 0)]).sum::<Float>();
+        }
+        Ok(())
+    }
+}
+
+/// Whether the symmetric `a` has `L D Lᵀ` factors without pivoting whose pivots are positive and
+/// not much smaller than their diagonal elements.
+fn positive_definite(a: &Matrix<Float>) -> bool {
+    let n = a.rows();
+    let mut l = Matrix::<Float>::zeros(n, n);
+    let mut d = vec![0.0; n];
+    for k in 0..n {
+        for j in 0..=k {
+            let s = a[(k, j)] - (0..j).map(|i| l[(k, i)] * l[(j, i)] * d[i]).sum::<Float>();
+            if j < k {
+                l[(k, j)] = s / d[j];
+            } else {
+                d[k] = s;
+            }
+        }
+        // Also rejects NaN pivots.
+        if d[k].partial_cmp(&(Float::EPSILON.sqrt() * a[(k, k)].abs())) != Some(Ordering::Greater) {
+            return false;
+        }
+    }
+    true
+}
+
+/// [`Model`] tracking its edits since its last analysis, so that the next one redoes only the
+/// work they invalidate. Edits through [`Self::model_mut`] are assumed to change everything.
+///
+/// The [`Solution::diagnostics`] and [`Solution::stats`] are of the last factorization, which
+/// may precede edits of members.
+pub struct Incremental {
+    model: Model,
+    factorized: Option<Factorized>,
+    /// Members edited since the stiffness was assembled.
+    edited: BTreeSet<usize>,
+    /// Correction for the edited members, `None` until it is needed after they change.
+    low_rank: Option<LowRank>,
+    reuse: Option<Reuse>,
+}
+
+impl Incremental {
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            factorized: None,
+            edited: BTreeSet::new(),
+            low_rank: None,
+            reuse: None,
+        }
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// Model for edits of any kind, which discard the factors of the stiffness.
+    pub fn model_mut(&mut self) -> &mut Model {
+        self.discard();
+        &mut self.model
+    }
+
+    pub fn into_model(self) -> Model {
+        self.model
+    }
+
+    /// How the last [`Self::solve`] reused the analysis before it, `None` before solving.
+    pub fn reuse(&self) -> Option<Reuse> {
+        self.reuse
+    }
+
+    fn discard(&mut self) {
+        self.factorized = None;
+        self.edited.clear();
+        self.low_rank = None;
+    }
+
+    /// Marks the `member` as edited, invalidating the correction for the edited members.
+    fn edit(&mut self, member: usize) {
+        if self.factorized.is_some() {
+            self.edited.insert(member);
+            self.low_rank = None;
+        }
+    }
+
+    /// Replaces the loads, keeping the factors of the stiffness.
+    pub fn set_loads(&mut self, loads: Vec<Load>) {
+        self.model.loads = loads;
+    }
+
+    pub fn add_load(&mut self, load: Load) {
+        self.model.add_load(load);
+    }
+
+    /// Replaces the `index`th member, updating the factors for it unless its nodes change.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the member does not exist.
+    pub fn set_member(&mut self, index: usize, member: Member) {
+        let previous = std::mem::replace(&mut self.model.members[index], member);
+        if previous.nodes != member.nodes {
+            self.discard();
+        } else if previous != member {
+            self.edit(index);
+        }
+    }
+
+    /// Moves the `index`th node, updating the factors for the members connected to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn set_node(&mut self, index: usize, position: [Float; 2]) {
+        self.model.nodes[index].position = position;
+        for member in 0..self.model.members.len() {
+            if self.model.members[member].nodes.contains(&index) {
+                self.edit(member);
+            }
+        }
+    }
+
+    /// Linear static analysis of the model as [`Model::solve`], redoing only the work of the
+    /// edits since the last one.
+    pub fn solve(&mut self) -> Result<Solution, ModelError> {
+        self.model.validate()?;
+        let mut reuse = Reuse::Factors;
+        let factorized = match &mut self.factorized {
+            Some(factorized) => factorized,
+            None => {
+                reuse = Reuse::None;
+                self.factorized.insert(Factorized::new(&self.model)?)
+            }
+        };
+        let start = clock::now();
+        for &member in &self.edited {
+            factorized.edit(&self.model, member);
+        }
+        let loads: Loads = factorized.loads(&self.model, &self.model.loads);
+        let b = factorized.rhs(std::slice::from_ref(&loads));
+        let assembly = clock::now() - start;
+
+        let start = clock::now();
+        if !self.edited.is_empty() {
+            if self.low_rank.is_none() {
+                let dofs = LowRank::dofs(factorized, &self.edited);
+                if dofs.len() <= RANK_LIMIT {
+                    self.low_rank = LowRank::new(factorized, &self.edited, dofs);
+                }
+            }
+            match &self.low_rank {
+                Some(low_rank) => {
+                    reuse = Reuse::LowRank {
+                        rank: low_rank.dofs.len(),
+                    }
+                }
+                None => {
+                    let members: Vec<_> = self.edited.iter().copied().collect();
+                    if let Err(error) = factorized.reassemble(&members) {
+                        self.discard();
+                        return Err(error);
+                    }
+                    self.edited.clear();
+                    reuse = Reuse::Reassembled {
+                        members: members.len(),
+                    };
+                }
+            }
+        }
+        let factorized = self.factorized.as_ref().unwrap();
+        let mut x = factorized.solve_block(&b, 1);
+        if let Some(low_rank) = &self.low_rank {
+            low_rank.correct(&mut x)?;
+        }
+        let u = factorized.displacements(&x, 1, 0);
+        let solve = clock::now() - start;
+
+        let start = clock::now();
+        let mut solution = factorized.recover(&loads, &u);
+        let times = &mut solution.stats.times;
+        if reuse != Reuse::None {
+            *times = Default::default();
+        }
+        times.assembly += assembly;
+        times.factorization += solve;
+        times.recovery = clock::now() - start;
+        self.reuse = Some(reuse);
+        Ok(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        fixtures::{assert_solution_eq, member},
+        Support,
+    };
+    use super::*;
+
+    /// Portal frame with a floor beam and fixed column bases, one bay of `bays` wide.
+    fn frame(bays: usize) -> Model {
+        let mut model = Model::new();
+        for i in 0..=bays {
+            let x = 4.0 * i as Float;
+            let base = model.add_node([x, 0.0]);
+            let top = model.add_node([x, 3.0]);
+            model.add_support(Support {
+                node: base,
+                fixed: [true; 3],
+            });
+            model.add_member(member([base, top]));
+            if i > 0 {
+                model.add_member(Member {
+                    inertia: 2e-4,
+                    ..member([top - 2, top])
+                });
+            }
+        }
+        model.add_load(Load::Node {
+            node: 1,
+            force: [10.0, 0.0, 0.0],
+        });
+        model
+    }
+
+    #[test]
+    fn loads() {
+        let mut incremental = Incremental::new(frame(2));
+        assert_eq!(incremental.reuse(), None);
+        let solution = incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::None));
+        assert_solution_eq(&solution, &incremental.model().solve().unwrap());
+        let load = Load::Member {
+            member: 2,
+            load: [0.0, -5.0],
+        };
+        incremental.add_load(load);
+        let solution = incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::Factors));
+        assert_solution_eq(&solution, &incremental.model().solve().unwrap());
+        incremental.set_loads(vec![]);
+        let solution = incremental.solve().unwrap();
+        assert!(solution.displacements.iter().flatten().all(|&u| u == 0.0));
+    }
+
+    #[test]
+    fn members() {
+        let mut incremental = Incremental::new(frame(3));
+        incremental.solve().unwrap();
+        // Stiffer beams, corrected for by a low-rank update of their ends.
+        for member in [2, 4] {
+            let mut beam = incremental.model().members[member];
+            beam.inertia *= 3.0;
+            incremental.set_member(member, beam);
+        }
+        let solution = incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::LowRank { rank: 9 }));
+        assert_solution_eq(&solution, &incremental.model().solve().unwrap());
+        // The correction is kept for new loads.
+        incremental.add_load(Load::Node {
+            node: 5,
+            force: [0.0, -20.0, 0.0],
+        });
+        let solution = incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::LowRank { rank: 9 }));
+        assert_solution_eq(&solution, &incremental.model().solve().unwrap());
+        // A moved node edits the members connected to it.
+        incremental.set_node(7, [12.5, 3.5]);
+        let solution = incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::LowRank { rank: 12 }));
+        assert_solution_eq(&solution, &incremental.model().solve().unwrap());
+        // Reconnecting a member changes the pattern.
+        let mut member = incremental.model().members[2];
+        member.nodes = [1, 5];
+        incremental.set_member(2, member);
+        incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::None));
+    }
+
+    #[test]
+    fn reassembled() {
+        let mut incremental = Incremental::new(frame(20));
+        incremental.solve().unwrap();
+        let count = incremental.model().members.len();
+        for member in 0..count {
+            let mut m = incremental.model().members[member];
+            m.area *= 2.0;
+            incremental.set_member(member, m);
+        }
+        let solution = incremental.solve().unwrap();
+        assert_eq!(
+            incremental.reuse(),
+            Some(Reuse::Reassembled { members: count })
+        );
+        assert_solution_eq(&solution, &incremental.model().solve().unwrap());
+        // The edits are assembled for good.
+        incremental.solve().unwrap();
+        assert_eq!(incremental.reuse(), Some(Reuse::Factors));
+    }
+
+    #[test]
+    fn unstable() {
+        let mut incremental = Incremental::new(frame(1));
+        incremental.solve().unwrap();
+        incremental.model_mut().supports.clear();
+        assert_eq!(incremental.solve(), Err(ModelError::Unstable));
+        let mut incremental = Incremental::new(frame(1));
+        incremental.solve().unwrap();
+        // Columns without stiffness leave the frame a mechanism.
+        for member in [0, 1] {
+            incremental.set_member(
+                member,
+                Member {
+                    elasticity: 0.0,
+                    ..incremental.model().members[member]
+                },
+            );
+        }
+        assert_eq!(incremental.solve(), Err(ModelError::Unstable));
+    }
+}
@@ -0,0 +1,365 @@
+//! Linear elastic plane frames of prismatic members rigidly connected at nodes, with three degrees
+//! of freedom per node: the displacements along the global x and y axes and the rotation
+//! counterclockwise.
+
+pub mod diagram;
+pub mod element;
+pub mod envelope;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod generators;
+pub mod gltf;
+pub mod history;
+pub mod incremental;
+pub mod patterns;
+pub mod reliability;
+pub mod sensitivity;
+pub mod sizing;
+pub mod snapshot;
+mod solve;
+
+pub use solve::{Analysis, LoadCase, Phase, PhaseTimes, Progress, Requirements, Solution, Stats};
+
+use crate::{geometry::path::Path, math::complex::Complex, Float};
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Node {
+    pub position: [Float; 2],
+}
+
+/// Prismatic member from `nodes[0]` to `nodes[1]`, whose local x axis points from the first node
+/// to the second and local y axis is to its left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Member {
+    pub nodes: [usize; 2],
+    /// Young's modulus.
+    pub elasticity: Float,
+    pub area: Float,
+    /// Second moment of area about the axis of bending.
+    pub inertia: Float,
+}
+
+/// Restraints of the degrees of freedom of a node, the x and y displacements and the rotation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Support {
+    pub node: usize,
+    pub fixed: [bool; 3],
+}
+
+/// Elastic restraint of a node to the ground, of the stiffness of each of its degrees of freedom,
+/// the x and y displacements and the rotation, as of the soil under a footing or the piles under a
+/// cap. The reactions of the node are the forces of the spring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Spring {
+    pub node: usize,
+    pub stiffness: [Float; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Load {
+    /// Forces along the global axes and a moment counterclockwise at a node.
+    Node { node: usize, force: [Float; 3] },
+    /// Uniform load per length of a member along the global axes.
+    Member { member: usize, load: [Float; 2] },
+}
+
+/// Reason a [`Model`] cannot be analyzed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModelError {
+    /// A member, support, spring or load refers to a node that does not exist.
+    InvalidNode { node: usize },
+    /// A load refers to a member that does not exist.
+    InvalidMember { member: usize },
+    /// A member connects coincident nodes.
+    ZeroLength { member: usize },
+    /// The structure is a mechanism, with the stiffness singular.
+    Unstable,
+    /// The analysis was stopped through its [`CancelToken`](crate::cancel::CancelToken), at the
+    /// progress it had reached.
+    Cancelled { progress: Progress },
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ModelError::InvalidNode { node } => write!(f, "node {node} does not exist"),
+            ModelError::InvalidMember { member } => write!(f, "member {member} does not exist"),
+            ModelError::ZeroLength { member } => write!(f, "member {member} has no length"),
+            ModelError::Unstable => f.write_str("structure is unstable"),
+            ModelError::Cancelled { progress } => write!(
+                f,
+                "analysis cancelled during {} after {} of {}",
+                progress.phase, progress.done, progress.total
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+#[cfg_attr(feature = "serde", tsify(rename = "FrameModel"))]
+pub struct Model {
+    pub nodes: Vec<Node>,
+    pub members: Vec<Member>,
+    pub supports: Vec<Support>,
+    pub loads: Vec<Load>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub springs: Vec<Spring>,
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty model with room for `nodes` nodes and `members` members, and as many loads, so
+    /// that building a large one in batches does not reallocate.
+    pub fn with_capacity(nodes: usize, members: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(nodes),
+            members: Vec::with_capacity(members),
+            supports: vec![],
+            loads: Vec::with_capacity(members),
+            springs: vec![],
+        }
+    }
+
+    /// Adds a node and returns its index.
+    pub fn add_node(&mut self, position: [Float; 2]) -> usize {
+        self.nodes.push(Node { position });
+        self.nodes.len() - 1
+    }
+
+    /// Adds a member and returns its index.
+    pub fn add_member(&mut self, member: Member) -> usize {
+        self.members.push(member);
+        self.members.len() - 1
+    }
+
+    /// Adds nodes at the `positions` and returns the range of their indices.
+    pub fn extend_nodes(
+        &mut self,
+        positions: impl IntoIterator<Item = [Float; 2]>,
+    ) -> Range<usize> {
+        let start = self.nodes.len();
+        self.nodes
+            .extend(positions.into_iter().map(|position| Node { position }));
+        start..self.nodes.len()
+    }
+
+    /// Adds the `members` and returns the range of their indices.
+    pub fn extend_members(&mut self, members: impl IntoIterator<Item = Member>) -> Range<usize> {
+        let start = self.members.len();
+        self.members.extend(members);
+        start..self.members.len()
+    }
+
+    pub fn add_support(&mut self, support: Support) {
+        self.supports.push(support);
+    }
+
+    pub fn add_load(&mut self, load: Load) {
+        self.loads.push(load);
+    }
+
+    pub fn add_spring(&mut self, spring: Spring) {
+        self.springs.push(spring);
+    }
+
+    /// Checks that every reference is to an existing node or member and no member is degenerate.
+    pub fn validate(&self) -> Result<(), ModelError> {
+        self.validate_structure()?;
+        self.validate_loads(&self.loads)
+    }
+
+    /// [`Self::validate`] except for the loads.
+    fn validate_structure(&self) -> Result<(), ModelError> {
+        let node = |node: usize| {
+            (node < self.nodes.len())
+                .then_some(())
+                .ok_or(ModelError::InvalidNode { node })
+        };
+        for (i, member) in self.members.iter().enumerate() {
+            member.nodes.iter().try_for_each(|&n| node(n))?;
+            if self.length(i) == 0.0 {
+                return Err(ModelError::ZeroLength { member: i });
+            }
+        }
+        self.supports.iter().try_for_each(|s| node(s.node))?;
+        self.springs.iter().try_for_each(|s| node(s.node))
+    }
+
+    /// Checks that the `loads` are of existing nodes and members.
+    fn validate_loads(&self, loads: &[Load]) -> Result<(), ModelError> {
+        loads.iter().try_for_each(|load| match *load {
+            Load::Node { node, .. } if node >= self.nodes.len() => {
+                Err(ModelError::InvalidNode { node })
+            }
+            Load::Member { member, .. } if member >= self.members.len() => {
+                Err(ModelError::InvalidMember { member })
+            }
+            _ => Ok(()),
+        })
+    }
+
+    /// Vector from the first node of the member `i` to the second.
+    fn axis(&self, i: usize) -> [Float; 2] {
+        let [a, b] = self.members[i].nodes.map(|n| self.nodes[n].position);
+        [b[0] - a[0], b[1] - a[1]]
+    }
+
+    pub fn length(&self, member: usize) -> Float {
+        let [x, y] = self.axis(member);
+        x.hypot(y)
+    }
+
+    /// Lines along the members from their first nodes to their second.
+    pub fn wireframe(&self) -> Path<Complex<Float>, Float> {
+        let mut path = Path::new();
+        for member in &self.members {
+            let [a, b] = member.nodes.map(|n| {
+                let [x, y] = self.nodes[n].position;
+                Complex::new(x, y)
+            });
+            path.move_to(a).line_to(b);
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(nodes: [usize; 2]) -> Member {
+        Member {
+            nodes,
+            elasticity: 1.0,
+            area: 1.0,
+            inertia: 1.0,
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let mut model = Model::new();
+        let a = model.add_node([0.0, 0.0]);
+        let b = model.add_node([3.0, 4.0]);
+        let m = model.add_member(member([a, b]));
+        assert_eq!(model.length(m), 5.0);
+        assert_eq!(model.wireframe().to_string(), "M0,0 L3,4");
+        assert_eq!(model.validate(), Ok(()));
+        model.add_load(Load::Member {
+            member: 1,
+            load: [0.0, 1.0],
+        });
+        assert_eq!(
+            model.validate(),
+            Err(ModelError::InvalidMember { member: 1 })
+        );
+        model.loads.clear();
+        model.add_support(Support {
+            node: 2,
+            fixed: [true; 3],
+        });
+        assert_eq!(model.validate(), Err(ModelError::InvalidNode { node: 2 }));
+        model.supports.clear();
+        model.add_spring(Spring {
+            node: 3,
+            stiffness: [1.0; 3],
+        });
+        assert_eq!(model.validate(), Err(ModelError::InvalidNode { node: 3 }));
+        model.springs.clear();
+        model.add_member(member([b, b]));
+        assert_eq!(model.validate(), Err(ModelError::ZeroLength { member: 1 }));
+    }
+
+    #[test]
+    fn extend() {
+        let mut model = Model::with_capacity(4, 3);
+        assert_eq!(model.extend_nodes([[0.0, 0.0], [1.0, 0.0]]), 0..2);
+        assert_eq!(model.extend_nodes([[2.0, 0.0], [3.0, 0.0]]), 2..4);
+        assert_eq!(model.extend_nodes([]), 4..4);
+        let members = (0..3).map(|i| member([i, i + 1]));
+        assert_eq!(model.extend_members(members), 0..3);
+        assert_eq!(model.nodes[3].position, [3.0, 0.0]);
+        assert_eq!(model.members[2].nodes, [2, 3]);
+        assert_eq!(model.validate(), Ok(()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut model = Model::new();
+        let a = model.add_node([0.0, 0.0]);
+        let b = model.add_node([2.0, 0.0]);
+        model.add_member(member([a, b]));
+        model.add_support(Support {
+            node: a,
+            fixed: [true, true, false],
+        });
+        model.add_load(Load::Member {
+            member: 0,
+            load: [0.0, -1.0],
+        });
+        let s = serde_json::to_string(&model).unwrap();
+        assert_eq!(
+            s,
+            concat!(
+                r#"{"nodes":[{"position":[0.0,0.0]},{"position":[2.0,0.0]}],"#,
+                r#""members":[{"nodes":[0,1],"elasticity":1.0,"area":1.0,"inertia":1.0}],"#,
+                r#""supports":[{"node":0,"fixed":[true,true,false]}],"#,
+                r#""loads":[{"Member":{"member":0,"load":[0.0,-1.0]}}]}"#
+            )
+        );
+        assert_eq!(serde_json::from_str::<Model>(&s).unwrap(), model);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn typescript() {
+        use tsify::Tsify;
+        assert!(Model::DECL.starts_with("export interface FrameModel {\n    nodes: Node[];"));
+        assert_eq!(
+            Load::DECL,
+            concat!(
+                "export type Load = { Node: { node: number; force: [Float, Float, Float] } } | ",
+                "{ Member: { member: number; load: [Float, Float] } };"
+            )
+        );
+        assert!(Solution::DECL.contains("    stats: AnalysisStats;\n"));
+    }
+}
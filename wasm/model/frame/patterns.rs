@@ -0,0 +1,213 @@
+//! Arrangements of a variable load, such as live load or snow, over the spans of continuous beams
+//! and the floors of frames, whose envelope gives the moments that loading every span at once
+//! misses: alternate spans for the sagging at midspan and two adjacent spans for the hogging at
+//! the support between them, as EN 1992-1-1 5.1.3.
+
+use super::{Load, LoadCase, Model};
+use crate::Float;
+use std::collections::BTreeSet;
+
+/// Variable load over the spans of continuous beams, each span the members between two supports.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Patterns {
+    /// Spans of each beam, or floor of a frame, in order from one end, as [`Model::spans`].
+    pub beams: Vec<Vec<Vec<usize>>>,
+    /// Uniform load per length along the global axes on each member of a loaded span.
+    pub load: [Float; 2],
+    /// Loads in every case, such as the dead load.
+    pub permanent: Vec<Load>,
+}
+
+impl Patterns {
+    /// Cases of the load on every span, then on alternate spans in a checkerboard over the
+    /// beams, the first loading the first span of the first beam and the second the others, and
+    /// then on each two adjacent spans of a beam alone, skipping arrangements repeated or
+    /// without any span loaded. Each case is named after `name` and its arrangement, with the
+    /// spans and beams numbered from 0, and its results envelop as [`Results::add_cases`].
+    ///
+    /// [`Results::add_cases`]: super::envelope::Results::add_cases
+    pub fn cases(&self, name: &str) -> Vec<LoadCase> {
+        let every = || {
+            (self.beams.iter().enumerate())
+                .flat_map(|(beam, spans)| (0..spans.len()).map(move |span| (beam, span)))
+        };
+        let mut arrangements = vec![(format!("{name} all"), every().collect::<BTreeSet<_>>())];
+        for parity in 0..2 {
+            let loaded = every().filter(|(beam, span)| (beam + span) % 2 == parity);
+            arrangements.push((format!("{name} alternate {parity}"), loaded.collect()));
+        }
+        for (beam, spans) in self.beams.iter().enumerate() {
+            for span in 1..spans.len() {
+                arrangements.push((
+                    format!("{name} adjacent {beam}:{}-{span}", span - 1),
+                    BTreeSet::from([(beam, span - 1), (beam, span)]),
+                ));
+            }
+        }
+        let mut cases: Vec<LoadCase> = vec![];
+        let mut seen = vec![];
+        for (name, loaded) in arrangements {
+            if loaded.is_empty() || seen.contains(&loaded) {
+                continue;
+            }
+            let variable = loaded
+                .iter()
+                .flat_map(|&(beam, span)| &self.beams[beam][span])
+                .map(|&member| Load::Member {
+                    member,
+                    load: self.load,
+                });
+            cases.push(LoadCase {
+                name,
+                loads: self.permanent.iter().copied().chain(variable).collect(),
+            });
+            seen.push(loaded);
+        }
+        cases
+    }
+}
+
+impl Model {
+    /// Spans of the chain of `members` in order from one end, split where a node between two of
+    /// them is restrained against translation or joins another member, as at a column of a frame,
+    /// or where they do not meet.
+    pub fn spans(&self, members: &[usize]) -> Vec<Vec<usize>> {
+        let mut ends = vec![0; self.nodes.len()];
+        for member in &self.members {
+            member.nodes.iter().for_each(|&n| ends[n] += 1);
+        }
+        let supported = |node: usize| {
+            (self.supports.iter()).any(|s| s.node == node && (s.fixed[0] || s.fixed[1]))
+        };
+        let mut spans = vec![];
+        let mut span = vec![];
+        for (i, &member) in members.iter().enumerate() {
+            span.push(member);
+            let split = members.get(i + 1).is_none_or(|&next| {
+                let common = (self.members[member].nodes.into_iter())
+                    .find(|n| self.members[next].nodes.contains(n));
+                common.is_none_or(|n| supported(n) || ends[n] > 2)
+            });
+            if split {
+                spans.push(std::mem::take(&mut span));
+            }
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        diagram::Quantity,
+        envelope::{Extreme, Results},
+        fixtures::continuous,
+        generators::Grid,
+    };
+    use super::*;
+
+    #[test]
+    fn beam() {
+        let model = continuous(3, 4.0, 2);
+        let spans = model.spans(&[0, 1, 2, 3, 4, 5]);
+        assert_eq!(spans, [vec![0, 1], vec![2, 3], vec![4, 5]]);
+        let dead = vec![Load::Member {
+            member: 0,
+            load: [0.0, -1.0],
+        }];
+        let patterns = Patterns {
+            beams: vec![spans],
+            load: [0.0, -2.0],
+            permanent: dead.clone(),
+        };
+        let cases = patterns.cases("live");
+        let names: Vec<_> = cases.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "live all",
+                "live alternate 0",
+                "live alternate 1",
+                "live adjacent 0:0-1",
+                "live adjacent 0:1-2",
+            ]
+        );
+        assert_eq!(cases[1].loads.len(), 1 + 4);
+        assert_eq!(cases[2].loads[0], dead[0]);
+        assert_eq!(
+            cases[2].loads[1..],
+            [2, 3].map(|member| Load::Member {
+                member,
+                load: [0.0, -2.0],
+            })
+        );
+        let mut results = Results::new(model);
+        results.add_cases(&cases).unwrap();
+        let name = |extreme: &Extreme| results.combinations()[extreme.combination].name.as_str();
+        // The end spans sag most under the alternate spans, the middle one under the others.
+        let end = results.envelope(0, Quantity::Moment).unwrap();
+        assert_eq!(name(&end.max), "live alternate 0");
+        let middle = results.envelope(2, Quantity::Moment).unwrap();
+        assert_eq!(name(&middle.max), "live alternate 1");
+        assert!(middle.min.value < 0.0);
+        // The first inner support hogs most under its two spans, more than under all of them.
+        let support = results.envelope(1, Quantity::Moment).unwrap();
+        assert_eq!(name(&support.min), "live adjacent 0:0-1");
+        assert_eq!(support.min.x, 2.0);
+        let mut all = Results::new(results.model().clone());
+        all.add_cases(&cases[..1]).unwrap();
+        let all = all.envelope(1, Quantity::Moment).unwrap();
+        assert!(support.min.value < all.min.value);
+        // The two adjacent spans of a beam of two repeat all of them.
+        let two = Patterns {
+            beams: vec![vec![vec![0], vec![1]]],
+            load: [0.0, -1.0],
+            permanent: vec![],
+        };
+        assert_eq!(two.cases("q").len(), 3);
+    }
+
+    #[test]
+    fn frame() {
+        let grid = Grid {
+            bays: 3,
+            stories: 2,
+            ..Grid::default()
+        };
+        let model = grid.generate().model;
+        // The beams of each floor follow the 4 columns of 2 stories.
+        let floors: Vec<_> = (0..2)
+            .map(|floor| {
+                let start = 8 + 3 * floor;
+                model.spans(&(start..start + 3).collect::<Vec<_>>())
+            })
+            .collect();
+        assert_eq!(floors[1], [vec![11], vec![12], vec![13]]);
+        let cases = Patterns {
+            beams: floors,
+            load: [0.0, -5.0],
+            permanent: vec![],
+        }
+        .cases("snow");
+        assert_eq!(cases.len(), 3 + 2 * 2);
+        // A checkerboard, with the spans above the loaded ones empty.
+        let members = |case: &LoadCase| -> Vec<usize> {
+            (case.loads.iter())
+                .map(|load| match load {
+                    Load::Member { member, .. } => *member,
+                    Load::Node { .. } => unreachable!(),
+                })
+                .collect()
+        };
+        assert_eq!(members(&cases[1]), [8, 10, 12]);
+        assert_eq!(members(&cases[2]), [9, 11, 13]);
+        assert_eq!(members(&cases[6]), [12, 13]);
+        assert_eq!(cases[6].name, "snow adjacent 1:1-2");
+        // The columns split where the floor beams join them.
+        assert_eq!(model.spans(&[0, 1]), [vec![0], vec![1]]);
+    }
+}
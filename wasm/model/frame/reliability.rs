@@ -0,0 +1,628 @@
+//! Probabilities of exceeding limit states of a [`Model`] whose loads and member properties are
+//! random, estimated by sampling analyses.
+//!
+//! Each random variable multiplies an input of the model, so that its distribution is of a factor
+//! of the nominal value, of mean 1 for an unbiased one. While only the loads are random, the
+//! stiffness is factorized once for all the samples by [`Model::solve_static_many`].
+
+use super::{Load, LoadCase, Model, ModelError, Solution};
+use crate::{
+    math::random::{normal_quantile, Distribution, Rng},
+    Float,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// Samples analyzed at once when only the loads are random.
+const BATCH: usize = 256;
+
+/// Input of a [`Model`] multiplied by a [`Variable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Input {
+    /// The load of the index in [`Model::loads`].
+    Load {
+        load: usize,
+    },
+    Elasticity {
+        member: usize,
+    },
+    Area {
+        member: usize,
+    },
+    Inertia {
+        member: usize,
+    },
+    /// The depth of the section of the member, scaling its area in proportion and its second
+    /// moment of area by the cube, as of a rectangle.
+    Depth {
+        member: usize,
+    },
+}
+
+impl Input {
+    /// Member whose properties are the input, `None` for a load.
+    pub fn member(&self) -> Option<usize> {
+        match *self {
+            Input::Load { .. } => None,
+            Input::Elasticity { member }
+            | Input::Area { member }
+            | Input::Inertia { member }
+            | Input::Depth { member } => Some(member),
+        }
+    }
+}
+
+/// Random factor of an input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Variable {
+    pub input: Input,
+    pub distribution: Distribution,
+}
+
+/// Result of an analysis checked by a [`LimitState`], with the component 0, 1 or 2 of those of a
+/// node, or 0 to 5 of the end forces of a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Response {
+    Displacement {
+        node: usize,
+        component: usize,
+    },
+    Reaction {
+        node: usize,
+        component: usize,
+    },
+    /// As [`Solution::member_forces`].
+    MemberForce {
+        member: usize,
+        component: usize,
+    },
+}
+
+impl Response {
+    fn value(&self, solution: &Solution) -> Float {
+        match *self {
+            Response::Displacement { node, component } => solution.displacements[node][component],
+            Response::Reaction { node, component } => solution.reactions[node][component],
+            Response::MemberForce { member, component } => {
+                solution.member_forces[member][component]
+            }
+        }
+    }
+}
+
+/// Failure where the magnitude of a response, the demand, exceeds a capacity, itself random and
+/// independent of the variables.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct LimitState {
+    pub response: Response,
+    pub capacity: Distribution,
+}
+
+/// Way the samples cover the space of the variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Sampling {
+    /// Independent samples.
+    MonteCarlo,
+    /// One sample in each of as many equally probable strata of each variable as there are
+    /// samples, which estimates means with less scatter.
+    LatinHypercube,
+}
+
+/// Controls of [`Model::reliability`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct ReliabilityOptions {
+    pub samples: usize,
+    pub sampling: Sampling,
+    /// Seed of the generator, the same seed giving the same samples on every platform.
+    pub seed: u64,
+}
+
+impl Default for ReliabilityOptions {
+    fn default() -> Self {
+        Self {
+            samples: 1000,
+            sampling: Sampling::LatinHypercube,
+            seed: 0,
+        }
+    }
+}
+
+/// Sample statistics of a quantity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Statistics {
+    pub mean: Float,
+    /// Unbiased estimate, zero for a single sample.
+    pub std_dev: Float,
+    pub min: Float,
+    pub max: Float,
+}
+
+/// Failures of a limit state or of the system among the samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Failures {
+    pub count: usize,
+    /// Fraction of the samples failing.
+    pub probability: Float,
+    /// Reliability index β, the standard normal quantile of the probability of survival,
+    /// infinite without failures.
+    pub index: Float,
+}
+
+impl Failures {
+    fn new(count: usize, samples: usize) -> Self {
+        let probability = count as Float / samples as Float;
+        let index = match count {
+            0 => Float::INFINITY,
+            _ if count == samples => Float::NEG_INFINITY,
+            _ => -normal_quantile(probability),
+        };
+        Self {
+            count,
+            probability,
+            index,
+        }
+    }
+}
+
+/// Estimates of a [`LimitState`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Outcome {
+    pub demand: Statistics,
+    pub failures: Failures,
+}
+
+/// Result of [`Model::reliability`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Reliability {
+    pub samples: usize,
+    /// Of each limit state in order.
+    pub outcomes: Vec<Outcome>,
+    /// Of the system failing where any limit state does.
+    pub system: Failures,
+}
+
+/// Reason [`Model::reliability`] cannot run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReliabilityError {
+    /// The model or a sample of it cannot be analyzed, or a response refers to a node or member
+    /// that does not exist.
+    Model(ModelError),
+    /// A variable refers to a load that does not exist.
+    InvalidLoad {
+        load: usize,
+    },
+    /// A response has a component past those of its node or member.
+    InvalidComponent {
+        component: usize,
+    },
+    NoSamples,
+}
+
+impl Display for ReliabilityError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ReliabilityError::Model(error) => error.fmt(f),
+            ReliabilityError::InvalidLoad { load } => write!(f, "load {load} does not exist"),
+            ReliabilityError::InvalidComponent { component } => {
+                write!(f, "component {component} does not exist")
+            }
+            ReliabilityError::NoSamples => f.write_str("no samples requested"),
+        }
+    }
+}
+
+impl std::error::Error for ReliabilityError {}
+
+impl From<ModelError> for ReliabilityError {
+    fn from(error: ModelError) -> Self {
+        ReliabilityError::Model(error)
+    }
+}
+
+/// Running mean and variance by Welford's method, with the extremes.
+struct Accumulator {
+    count: usize,
+    mean: Float,
+    sum_squares: Float,
+    min: Float,
+    max: Float,
+}
+
+impl Accumulator {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            sum_squares: 0.0,
+            min: Float::INFINITY,
+            max: Float::NEG_INFINITY,
+        }
+    }
+
+    fn add(&mut self, value: Float) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as Float;
+        self.sum_squares += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics {
+            mean: self.mean,
+            std_dev: match self.count {
+                0 | 1 => 0.0,
+                n => (self.sum_squares / (n - 1) as Float).sqrt(),
+            },
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+/// Probabilities of the `samples` by `dimensions` drawn as the `sampling` requires, by sample.
+fn probabilities(
+    samples: usize,
+    dimensions: usize,
+    sampling: Sampling,
+    rng: &mut Rng,
+) -> Vec<Vec<Float>> {
+    let mut p = vec![vec![0.0; dimensions]; samples];
+    match sampling {
+        Sampling::MonteCarlo => p.iter_mut().flatten().for_each(|p| *p = rng.uniform()),
+        Sampling::LatinHypercube => {
+            let mut strata: Vec<_> = (0..samples).collect();
+            for d in 0..dimensions {
+                rng.shuffle(&mut strata);
+                for (p, &stratum) in p.iter_mut().zip(&strata) {
+                    p[d] = (stratum as Float + rng.uniform()) / samples as Float;
+                }
+            }
+        }
+    }
+    p
+}
+
+impl Model {
+    /// Sample of the model with each of the `variables` multiplying its input by its factor.
+    pub(super) fn sampled(&self, variables: &[Variable], factors: &[Float]) -> Model {
+        let mut model = self.clone();
+        for (variable, &factor) in variables.iter().zip(factors) {
+            match variable.input {
+                Input::Load { load } => match &mut model.loads[load] {
+                    Load::Node { force, .. } => force.iter_mut().for_each(|f| *f *= factor),
+                    Load::Member { load, .. } => load.iter_mut().for_each(|w| *w *= factor),
+                },
+                Input::Elasticity { member } => model.members[member].elasticity *= factor,
+                Input::Area { member } => model.members[member].area *= factor,
+                Input::Inertia { member } => model.members[member].inertia *= factor,
+                Input::Depth { member } => {
+                    let member = &mut model.members[member];
+                    member.area *= factor;
+                    member.inertia *= factor.powi(3);
+                }
+            }
+        }
+        model
+    }
+
+    /// Checks that the `inputs` are of existing loads and members.
+    pub(super) fn validate_inputs(
+        &self,
+        inputs: impl IntoIterator<Item = Input>,
+    ) -> Result<(), ReliabilityError> {
+        for input in inputs {
+            match (input, input.member()) {
+                (Input::Load { load }, _) if load >= self.loads.len() => {
+                    return Err(ReliabilityError::InvalidLoad { load })
+                }
+                (_, Some(member)) if member >= self.members.len() => {
+                    return Err(ModelError::InvalidMember { member }.into())
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the `variables` and `limit_states` refer to existing loads, nodes, members
+    /// and components.
+    fn validate_reliability(
+        &self,
+        variables: &[Variable],
+        limit_states: &[LimitState],
+    ) -> Result<(), ReliabilityError> {
+        self.validate()?;
+        self.validate_inputs(variables.iter().map(|variable| variable.input))?;
+        for limit_state in limit_states {
+            let (components, component) = match limit_state.response {
+                Response::Displacement { node, component }
+                | Response::Reaction { node, component } => {
+                    if node >= self.nodes.len() {
+                        return Err(ModelError::InvalidNode { node }.into());
+                    }
+                    (3, component)
+                }
+                Response::MemberForce { member, component } => {
+                    if member >= self.members.len() {
+                        return Err(ModelError::InvalidMember { member }.into());
+                    }
+                    (6, component)
+                }
+            };
+            if component >= components {
+                return Err(ReliabilityError::InvalidComponent { component });
+            }
+        }
+        Ok(())
+    }
+
+    /// Estimates the statistics of the demands of the `limit_states` and the probabilities of
+    /// exceeding them when the inputs are multiplied by the random `variables`, analyzing as
+    /// many samples as the `options` require.
+    pub fn reliability(
+        &self,
+        variables: &[Variable],
+        limit_states: &[LimitState],
+        options: &ReliabilityOptions,
+    ) -> Result<Reliability, ReliabilityError> {
+        if options.samples == 0 {
+            return Err(ReliabilityError::NoSamples);
+        }
+        self.validate_reliability(variables, limit_states)?;
+        let mut rng = Rng::new(options.seed);
+        let p = probabilities(
+            options.samples,
+            variables.len() + limit_states.len(),
+            options.sampling,
+            &mut rng,
+        );
+        let mut demands: Vec<_> = limit_states.iter().map(|_| Accumulator::new()).collect();
+        let mut failures = vec![0; limit_states.len()];
+        let mut system = 0;
+        let mut check = |solution: &Solution, p: &[Float]| {
+            let mut failed = false;
+            for (i, limit_state) in limit_states.iter().enumerate() {
+                let demand = limit_state.response.value(solution).abs();
+                demands[i].add(demand);
+                if demand > limit_state.capacity.quantile(p[i]) {
+                    failures[i] += 1;
+                    failed = true;
+                }
+            }
+            system += failed as usize;
+        };
+        let factors = |p: &[Float]| -> Vec<Float> {
+            variables
+                .iter()
+                .zip(p)
+                .map(|(variable, &p)| variable.distribution.quantile(p))
+                .collect()
+        };
+        let loads_only = variables
+            .iter()
+            .all(|variable| matches!(variable.input, Input::Load { .. }));
+        if loads_only {
+            for batch in p.chunks(BATCH) {
+                let cases: Vec<_> = batch
+                    .iter()
+                    .map(|p| LoadCase {
+                        name: String::new(),
+                        loads: self.sampled(variables, &factors(p)).loads,
+                    })
+                    .collect();
+                let solutions = self.solve_static_many(&cases)?;
+                for (solution, p) in solutions.iter().zip(batch) {
+                    check(solution, &p[variables.len()..]);
+                }
+            }
+        } else {
+            for p in &p {
+                let solution = self.sampled(variables, &factors(p)).solve()?;
+                check(&solution, &p[variables.len()..]);
+            }
+        }
+        let samples = options.samples;
+        Ok(Reliability {
+            samples,
+            outcomes: demands
+                .iter()
+                .zip(failures)
+                .map(|(demand, count)| Outcome {
+                    demand: demand.statistics(),
+                    failures: Failures::new(count, samples),
+                })
+                .collect(),
+            system: Failures::new(system, samples),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures;
+    use super::*;
+
+    /// Cantilever of length 2 with a unit tip load downward.
+    fn cantilever() -> Model {
+        let mut model = fixtures::cantilever(2.0);
+        model.add_load(Load::Node {
+            node: 1,
+            force: [0.0, -1.0, 0.0],
+        });
+        model
+    }
+
+    fn fixed(value: Float) -> Distribution {
+        Distribution::Normal {
+            mean: value,
+            std_dev: 0.0,
+        }
+    }
+
+    /// The root moment, twice the tip load.
+    const MOMENT: LimitState = LimitState {
+        response: Response::Reaction {
+            node: 0,
+            component: 2,
+        },
+        capacity: Distribution::Normal {
+            mean: 3.0,
+            std_dev: 0.0,
+        },
+    };
+
+    #[test]
+    fn loads() {
+        let model = cantilever();
+        let load = Variable {
+            input: Input::Load { load: 0 },
+            distribution: Distribution::Normal {
+                mean: 1.0,
+                std_dev: 0.2,
+            },
+        };
+        let options = ReliabilityOptions {
+            samples: 2000,
+            ..ReliabilityOptions::default()
+        };
+        let reliability = model.reliability(&[load], &[MOMENT], &options).unwrap();
+        assert_eq!(
+            reliability,
+            model.reliability(&[load], &[MOMENT], &options).unwrap()
+        );
+        let outcome = reliability.outcomes[0];
+        assert!((outcome.demand.mean - 2.0).abs() < 1e-3);
+        assert!((outcome.demand.std_dev - 0.4).abs() < 1e-2);
+        // Exceeding 1.5 times the mean, 2.5 standard deviations above it.
+        let expected = 1.0 - 0.993_790_3;
+        assert!((outcome.failures.probability - expected).abs() < 0.003);
+        assert!((outcome.failures.index - 2.5).abs() < 0.15);
+        assert_eq!(reliability.system, outcome.failures);
+        let monte_carlo = ReliabilityOptions {
+            sampling: Sampling::MonteCarlo,
+            seed: 1,
+            ..options
+        };
+        let scattered = model.reliability(&[load], &[MOMENT], &monte_carlo).unwrap();
+        assert!((scattered.outcomes[0].demand.mean - 2.0).abs() < 0.05);
+        assert_ne!(scattered, reliability);
+    }
+
+    #[test]
+    fn stiffness() {
+        let model = cantilever();
+        // The tip deflection P L³ / 3 E I, inversely proportional to the cube of the depth.
+        let deflection = 8.0 / (3.0 * 2e8 * 1e-4);
+        let variables = [Variable {
+            input: Input::Depth { member: 0 },
+            distribution: Distribution::Uniform { min: 0.5, max: 2.0 },
+        }];
+        let limit_states = [
+            LimitState {
+                response: Response::Displacement {
+                    node: 1,
+                    component: 1,
+                },
+                capacity: fixed(deflection),
+            },
+            MOMENT,
+        ];
+        let options = ReliabilityOptions {
+            samples: 300,
+            ..ReliabilityOptions::default()
+        };
+        let reliability = model
+            .reliability(&variables, &limit_states, &options)
+            .unwrap();
+        // Shallower than nominal in a third of the samples, and up to half as deep.
+        let outcome = reliability.outcomes[0];
+        assert!((outcome.failures.probability - 1.0 / 3.0).abs() < 0.01);
+        assert!(outcome.demand.max < 8.0 * deflection && outcome.demand.max > 7.5 * deflection);
+        let moment = reliability.outcomes[1];
+        assert_eq!(moment.failures.count, 0);
+        assert_eq!(moment.failures.index, Float::INFINITY);
+        assert!((moment.demand.std_dev).abs() < 1e-6);
+        assert_eq!(reliability.system, outcome.failures);
+    }
+
+    #[test]
+    fn invalid() {
+        let model = cantilever();
+        let options = ReliabilityOptions::default();
+        let variable = |input| Variable {
+            input,
+            distribution: fixed(1.0),
+        };
+        assert_eq!(
+            model.reliability(&[variable(Input::Load { load: 1 })], &[], &options),
+            Err(ReliabilityError::InvalidLoad { load: 1 })
+        );
+        assert_eq!(
+            model.reliability(&[variable(Input::Area { member: 1 })], &[], &options),
+            Err(ReliabilityError::Model(ModelError::InvalidMember {
+                member: 1
+            }))
+        );
+        let limit_state = LimitState {
+            response: Response::MemberForce {
+                member: 0,
+                component: 6,
+            },
+            ..MOMENT
+        };
+        assert_eq!(
+            model.reliability(&[], &[limit_state], &options),
+            Err(ReliabilityError::InvalidComponent { component: 6 })
+        );
+        let none = ReliabilityOptions {
+            samples: 0,
+            ..options
+        };
+        assert_eq!(
+            model.reliability(&[], &[], &none),
+            Err(ReliabilityError::NoSamples)
+        );
+    }
+}
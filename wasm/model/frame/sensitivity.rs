@@ -0,0 +1,371 @@
+//! Derivatives of the results of a [`Model`] with respect to factors of its loads and member
+//! properties, by direct differentiation of the equilibrium `K u = f`: the derivative of the
+//! displacements solves `K du = df - dK u` with the stiffness already factorized, so that any
+//! number of inputs costs one back-substitution each.
+//!
+//! The inputs are the [`Input`]s of the reliability analysis, each multiplied by a factor whose
+//! derivative is taken at 1, the derivative with respect to the logarithm of the input. Dividing
+//! it by the nominal value of the input gives the derivative with respect to the input itself.
+
+use super::{
+    element,
+    reliability::{Input, ReliabilityError},
+    solve::{self, Factorized, Loads},
+    Load, Member, Model, Solution,
+};
+use crate::Float;
+
+/// Derivatives of the results of a [`Solution`] with respect to the factor of an input.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Derivatives {
+    pub displacements: Vec<[Float; 3]>,
+    pub reactions: Vec<[Float; 3]>,
+    pub member_forces: Vec<[Float; 6]>,
+}
+
+/// Result of [`Model::sensitivities`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Sensitivities {
+    pub inputs: Vec<Input>,
+    pub solution: Solution,
+    /// Of each input in order.
+    pub derivatives: Vec<Derivatives>,
+}
+
+/// Member whose stiffness is the derivative of that of the `member` with respect to the factor
+/// of the `input`, as the stiffness is linear in the elasticity, the area and the inertia.
+fn stiffness_derivative(member: &Member, input: Input) -> Member {
+    let (area, inertia) = match input {
+        Input::Load { .. } => (0.0, 0.0),
+        Input::Elasticity { .. } => (member.area, member.inertia),
+        Input::Area { .. } => (member.area, 0.0),
+        Input::Inertia { .. } => (0.0, member.inertia),
+        Input::Depth { .. } => (member.area, 3.0 * member.inertia),
+    };
+    Member {
+        area,
+        inertia,
+        ..*member
+    }
+}
+
+impl Model {
+    /// Solution of the model and derivatives of its displacements, reactions and member forces
+    /// with respect to the factors of the `inputs`. Fails as [`Self::reliability`] for invalid
+    /// inputs.
+    pub fn sensitivities(&self, inputs: &[Input]) -> Result<Sensitivities, ReliabilityError> {
+        self.validate()?;
+        self.validate_inputs(inputs.iter().copied())?;
+        let factorized = Factorized::new(self)?;
+        let loads = factorized.loads(self, &self.loads);
+        let x = factorized.solve_block(&factorized.rhs(std::slice::from_ref(&loads)), 1);
+        let u = factorized.displacements(&x, 1, 0);
+        let solution = factorized.recover(&loads, &u);
+        if inputs.is_empty() {
+            return Ok(Sensitivities {
+                inputs: vec![],
+                solution,
+                derivatives: vec![],
+            });
+        }
+        // The derivatives of the loads, those of the inputs that are loads.
+        let pseudo: Vec<Loads> = inputs
+            .iter()
+            .map(|&input| match input {
+                Input::Load { load } => factorized.loads(self, &[self.loads[load]]),
+                _ => factorized.loads(self, &[]),
+            })
+            .collect();
+        let k = inputs.len();
+        let mut b = factorized.rhs(&pseudo);
+        // The end forces dK u of each member input in its local and global axes.
+        let changes: Vec<_> = inputs
+            .iter()
+            .map(|&input| {
+                let member = input.member()?;
+                let rotation = element::rotation(self.axis(member));
+                let dk = element::stiffness(
+                    &stiffness_derivative(&self.members[member], input),
+                    self.length(member),
+                );
+                let dofs = factorized.member_dofs(member);
+                let local = dk.mul_vec(&rotation.mul_vec(&dofs.map(|p| u[p])));
+                let global = rotation.transpose().mul_vec(&local);
+                Some((member, dofs, local, global))
+            })
+            .collect();
+        for (c, change) in changes.iter().enumerate() {
+            if let Some((_, dofs, _, global)) = change {
+                for (&p, f) in dofs.iter().zip(global) {
+                    if let Some(r) = factorized.free()[p] {
+                        b[r * k + c] -= f;
+                    }
+                }
+            }
+        }
+        let x = factorized.solve_block(&b, k);
+        let derivatives = (pseudo.iter().zip(&changes).enumerate())
+            .map(|(c, (loads, change))| {
+                let du = factorized.displacements(&x, k, c);
+                let mut derivative = factorized.recover(loads, &du);
+                if let Some((member, dofs, local, global)) = change {
+                    for (f, g) in derivative.member_forces[*member].iter_mut().zip(local) {
+                        *f += g;
+                    }
+                    for (&p, f) in dofs.iter().zip(global) {
+                        if factorized.free()[p].is_none() {
+                            derivative.reactions[p / 3][p % 3] += f;
+                        }
+                    }
+                }
+                Derivatives {
+                    displacements: derivative.displacements,
+                    reactions: derivative.reactions,
+                    member_forces: derivative.member_forces,
+                }
+            })
+            .collect();
+        Ok(Sensitivities {
+            inputs: inputs.to_vec(),
+            solution,
+            derivatives,
+        })
+    }
+
+    /// Derivative of the uniform load of the `member` in its local axes with respect to the
+    /// factor of the `input`.
+    fn member_load_derivative(&self, member: usize, input: Input) -> [Float; 2] {
+        match input {
+            Input::Load { load } => match self.loads[load] {
+                Load::Member { member: m, load } if m == member => {
+                    element::local_load(load, self.axis(member))
+                }
+                _ => [0.0; 2],
+            },
+            _ => [0.0; 2],
+        }
+    }
+
+    /// Internal forces of the `member` at `x` as [`Self::internal_forces`] and their derivatives
+    /// with respect to the factor of each input of the `sensitivities`.
+    pub fn internal_force_sensitivities(
+        &self,
+        sensitivities: &Sensitivities,
+        member: usize,
+        x: Float,
+    ) -> ([Float; 3], Vec<[Float; 3]>) {
+        let forces = self.internal_forces(&sensitivities.solution, member, x);
+        let derivatives = (sensitivities.inputs.iter())
+            .zip(&sensitivities.derivatives)
+            .map(|(&input, derivatives)| {
+                let load = self.member_load_derivative(member, input);
+                solve::internal_forces(&derivatives.member_forces[member], load, x)
+            })
+            .collect();
+        (forces, derivatives)
+    }
+
+    /// Normal stress `N / A - M y / I` in the `member` at `x`, at the distance `y` from its axis
+    /// along its local y axis, and its derivatives with respect to the factor of each input of
+    /// the `sensitivities`. The distance scales with the depth of the section as its input.
+    pub fn stress_sensitivities(
+        &self,
+        sensitivities: &Sensitivities,
+        member: usize,
+        x: Float,
+        y: Float,
+    ) -> (Float, Vec<Float>) {
+        let Member { area, inertia, .. } = self.members[member];
+        let ([n, _, m], forces) = self.internal_force_sensitivities(sensitivities, member, x);
+        let stress = n / area - m * y / inertia;
+        let derivatives = (sensitivities.inputs.iter().zip(forces))
+            .map(|(&input, [dn, _, dm])| {
+                let own = input.member() == Some(member);
+                let derivative = stiffness_derivative(&self.members[member], input);
+                let (da, di, dy) = match input {
+                    _ if !own => (0.0, 0.0, 0.0),
+                    Input::Depth { .. } => (derivative.area, derivative.inertia, y),
+                    Input::Area { .. } | Input::Inertia { .. } => {
+                        (derivative.area, derivative.inertia, 0.0)
+                    }
+                    _ => (0.0, 0.0, 0.0),
+                };
+                dn / area - n * da / (area * area) - (dm * y + m * dy) / inertia
+                    + m * y * di / (inertia * inertia)
+            })
+            .collect();
+        (stress, derivatives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{fixtures, reliability::Variable, ModelError, Support};
+    use super::*;
+    use crate::math::random::Distribution;
+    use approx::{assert_abs_diff_eq, assert_relative_eq};
+
+    /// Step of the central differences, balancing their truncation and rounding errors.
+    const H: Float = if cfg!(feature = "f32") { 5e-3 } else { 1e-5 };
+
+    /// Error of the central differences relative to the largest derivative.
+    const TOLERANCE: Float = if cfg!(feature = "f32") { 1e-2 } else { 1e-6 };
+
+    /// Error of the central differences of the `derivatives`, [`TOLERANCE`] of the largest.
+    fn epsilon(derivatives: &[Float]) -> Float {
+        TOLERANCE
+            * derivatives
+                .iter()
+                .fold(Float::EPSILON, |a, b| a.max(b.abs()))
+    }
+
+    /// Beam of length 4 fixed at the left and propped at the right, under a uniform load and a
+    /// horizontal force at the prop, statically indeterminate.
+    fn propped() -> Model {
+        let mut model = fixtures::cantilever(4.0);
+        model.add_support(Support {
+            node: 1,
+            fixed: [false, true, false],
+        });
+        model.add_load(Load::Member {
+            member: 0,
+            load: [0.0, -3.0],
+        });
+        model.add_load(Load::Node {
+            node: 1,
+            force: [5.0, 0.0, 1.0],
+        });
+        // A spring of a column to the prop, for the area to matter to the bending.
+        let top = model.add_node([4.0, 3.0]);
+        model.add_member(Member {
+            area: 1e-3,
+            inertia: 1e-5,
+            ..fixtures::member([1, top])
+        });
+        model.add_support(Support {
+            node: top,
+            fixed: [true, false, true],
+        });
+        model
+    }
+
+    const INPUTS: [Input; 7] = [
+        Input::Load { load: 0 },
+        Input::Load { load: 1 },
+        Input::Elasticity { member: 0 },
+        Input::Area { member: 0 },
+        Input::Inertia { member: 0 },
+        Input::Depth { member: 0 },
+        Input::Depth { member: 1 },
+    ];
+
+    /// The model with the factor of the `input` perturbed by `h`.
+    fn perturbed(model: &Model, input: Input, h: Float) -> Model {
+        let variable = Variable {
+            input,
+            distribution: Distribution::Uniform { min: 1.0, max: 1.0 },
+        };
+        model.sampled(&[variable], &[1.0 + h])
+    }
+
+    #[test]
+    fn finite_differences() {
+        let model = propped();
+        let sensitivities = model.sensitivities(&INPUTS).unwrap();
+        let solution = model.solve().unwrap();
+        assert_eq!(sensitivities.solution.displacements, solution.displacements);
+        for (&input, derivatives) in INPUTS.iter().zip(&sensitivities.derivatives) {
+            let [ahead, behind] = [H, -H].map(|h| perturbed(&model, input, h).solve().unwrap());
+            let central = |a: &[Float], b: &[Float], d: &[Float]| {
+                let differences: Vec<_> =
+                    a.iter().zip(b).map(|(a, b)| (a - b) / (2.0 * H)).collect();
+                assert_abs_diff_eq!(d, &differences[..], epsilon = epsilon(&differences));
+            };
+            central(
+                ahead.displacements.as_flattened(),
+                behind.displacements.as_flattened(),
+                derivatives.displacements.as_flattened(),
+            );
+            central(
+                ahead.reactions.as_flattened(),
+                behind.reactions.as_flattened(),
+                derivatives.reactions.as_flattened(),
+            );
+            central(
+                ahead.member_forces.as_flattened(),
+                behind.member_forces.as_flattened(),
+                derivatives.member_forces.as_flattened(),
+            );
+        }
+    }
+
+    #[test]
+    fn stresses() {
+        let model = propped();
+        let sensitivities = model.sensitivities(&INPUTS).unwrap();
+        let (x, y) = (1.5, 0.1);
+        let (stress, derivatives) = model.stress_sensitivities(&sensitivities, 0, x, y);
+        let stress_of = |model: &Model, y: Float| {
+            let solution = model.solve().unwrap();
+            let [n, _, m] = model.internal_forces(&solution, 0, x);
+            n / model.members[0].area - m * y / model.members[0].inertia
+        };
+        assert_relative_eq!(stress, stress_of(&model, y), max_relative = TOLERANCE);
+        let mut differences = vec![];
+        for &input in &INPUTS {
+            let scaled = |h: Float| match input {
+                Input::Depth { member: 0 } => y * (1.0 + h),
+                _ => y,
+            };
+            let [ahead, behind] =
+                [H, -H].map(|h| stress_of(&perturbed(&model, input, h), scaled(h)));
+            differences.push((ahead - behind) / (2.0 * H));
+        }
+        assert_abs_diff_eq!(
+            derivatives[..],
+            differences[..],
+            epsilon = epsilon(&differences)
+        );
+    }
+
+    #[test]
+    fn cantilever() {
+        // The tip deflection P L³ / 3 E I, proportional to the load and inversely to E I and the
+        // cube of the depth.
+        let mut model = propped();
+        model.members.truncate(1);
+        model.nodes.truncate(2);
+        model.supports.truncate(1);
+        model.loads = vec![Load::Node {
+            node: 1,
+            force: [0.0, -1.0, 0.0],
+        }];
+        let mut inputs = INPUTS[1..6].to_vec();
+        inputs[0] = Input::Load { load: 0 };
+        let sensitivities = model.sensitivities(&inputs).unwrap();
+        let deflection = sensitivities.solution.displacements[1][1];
+        let tip: Vec<_> = (sensitivities.derivatives.iter())
+            .map(|d| d.displacements[1][1] / deflection)
+            .collect();
+        assert_relative_eq!(
+            tip[..],
+            [1.0, -1.0, 0.0, -1.0, -3.0][..],
+            epsilon = fixtures::TOLERANCE,
+            max_relative = fixtures::TOLERANCE
+        );
+        assert_eq!(
+            model.sensitivities(&[Input::Area { member: 1 }]),
+            Err(ReliabilityError::Model(ModelError::InvalidMember {
+                member: 1
+            }))
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! Sizing of the members of a [`Model`] from a catalog of sections, resizing each group of members
+//! to the lightest section whose elastic stresses under the envelope of the load cases are within
+//! the strength, and reanalyzing until the sections settle, since the forces of an indeterminate
+//! structure follow the stiffness.
+
+use super::{diagram::Quantity, envelope::Results, LoadCase, Model, ModelError};
+use crate::Float;
+use std::fmt::{self, Display, Formatter};
+
+/// Section of a catalog for [`Model::size`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Profile {
+    pub name: String,
+    pub area: Float,
+    /// Second moment of area about the axis of bending.
+    pub inertia: Float,
+    /// Elastic section modulus about the axis of bending.
+    pub modulus: Float,
+}
+
+/// Controls of [`Model::size`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct SizingOptions {
+    /// Stress at which the utilization is 1.
+    pub strength: Float,
+    /// Largest utilization accepted.
+    pub limit: Float,
+    /// Weight per volume of the members.
+    pub density: Float,
+    /// Most analyses run.
+    pub iterations: usize,
+}
+
+impl Default for SizingOptions {
+    /// Steel of 235 MPa in kN and m, weighed in tonnes.
+    fn default() -> Self {
+        Self {
+            strength: 235e3,
+            limit: 1.0,
+            density: 7.85,
+            iterations: 20,
+        }
+    }
+}
+
+/// Result of [`Model::size`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Sizing {
+    /// The model with the members of the groups resized.
+    pub model: Model,
+    /// Index in the catalog of the section of each group.
+    pub sections: Vec<usize>,
+    /// Largest utilization of each group under the forces of the last analysis, over the limit
+    /// where even the largest section fails.
+    pub utilization: Vec<Float>,
+    /// Weight of the members of the groups before and after sizing.
+    pub initial_weight: Float,
+    pub weight: Float,
+    pub iterations: usize,
+    /// Whether the last analysis was of the sections chosen from it, rather than the sizing
+    /// running out of iterations or cycling between sections.
+    pub converged: bool,
+}
+
+/// Reason [`Model::size`] cannot run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SizingError {
+    /// The model cannot be analyzed, or a group refers to a member that does not exist.
+    Model(ModelError),
+    EmptyCatalog,
+}
+
+impl Display for SizingError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SizingError::Model(error) => error.fmt(f),
+            SizingError::EmptyCatalog => f.write_str("catalog has no sections"),
+        }
+    }
+}
+
+impl std::error::Error for SizingError {}
+
+impl From<ModelError> for SizingError {
+    fn from(error: ModelError) -> Self {
+        SizingError::Model(error)
+    }
+}
+
+/// Axial forces and bending moments of a member that may govern its stresses: the extremes of
+/// each with the other concurrent, over the combinations of the `results`.
+fn demands(results: &Results, member: usize) -> Vec<[Float; 2]> {
+    [Quantity::Moment, Quantity::Axial]
+        .into_iter()
+        .filter_map(|quantity| results.envelope(member, quantity))
+        .flat_map(|envelope| [envelope.max, envelope.min])
+        .map(|extreme| [extreme.concurrent[0], extreme.concurrent[2]])
+        .collect()
+}
+
+impl Model {
+    /// Sizes the members of each of the `groups` to a single section of the `catalog`, the
+    /// lightest whose utilization, the largest `|N| / A + |M| / W` over the envelope of the
+    /// `cases` divided by the strength, is within the limit, or the largest if none is. Members
+    /// outside the groups keep their properties, and the loads of the model are ignored.
+    ///
+    /// The first analysis is of the model as it is, and each after it of the sections chosen
+    /// from the one before, all the cases sharing a factorization as
+    /// [`Results::add_cases`], until the sections no longer change.
+    pub fn size(
+        &self,
+        cases: &[LoadCase],
+        groups: &[Vec<usize>],
+        catalog: &[Profile],
+        options: &SizingOptions,
+    ) -> Result<Sizing, SizingError> {
+        if catalog.is_empty() {
+            return Err(SizingError::EmptyCatalog);
+        }
+        if let Some(&member) = groups.iter().flatten().find(|&&m| m >= self.members.len()) {
+            return Err(ModelError::InvalidMember { member }.into());
+        }
+        let weight = |model: &Model| {
+            (groups.iter().flatten())
+                .map(|&m| model.members[m].area * model.length(m))
+                .sum::<Float>()
+                * options.density
+        };
+        let mut order: Vec<_> = (0..catalog.len()).collect();
+        order.sort_by(|&a, &b| catalog[a].area.total_cmp(&catalog[b].area));
+        let utilization = |profile: &Profile, demands: &[[Float; 2]]| {
+            demands
+                .iter()
+                .map(|[n, m]| n.abs() / profile.area + m.abs() / profile.modulus)
+                .fold(0.0, Float::max)
+                / options.strength
+        };
+        let mut model = self.clone();
+        let mut history: Vec<Vec<usize>> = vec![];
+        let mut sizing = Sizing {
+            model: self.clone(),
+            sections: vec![],
+            utilization: vec![],
+            initial_weight: weight(self),
+            weight: 0.0,
+            iterations: 0,
+            converged: false,
+        };
+        while sizing.iterations < options.iterations {
+            let mut results = Results::new(model.clone());
+            results.add_cases(cases)?;
+            sizing.iterations += 1;
+            let (sections, utilization): (Vec<_>, Vec<_>) = groups
+                .iter()
+                .map(|group| {
+                    let demands: Vec<_> = group.iter().map(|&m| demands(&results, m)).collect();
+                    let of = |section: usize| {
+                        (demands.iter())
+                            .map(|d| utilization(&catalog[section], d))
+                            .fold(0.0, Float::max)
+                    };
+                    let section = (order.iter().copied())
+                        .find(|&section| of(section) <= options.limit)
+                        .unwrap_or(order[order.len() - 1]);
+                    (section, of(section))
+                })
+                .unzip();
+            sizing.utilization = utilization;
+            if history.last() == Some(&sections) {
+                sizing.converged = true;
+                break;
+            }
+            let cycled = history.contains(&sections);
+            for (group, &section) in groups.iter().zip(&sections) {
+                let profile = &catalog[section];
+                for &member in group {
+                    model.members[member].area = profile.area;
+                    model.members[member].inertia = profile.inertia;
+                }
+            }
+            history.push(sections);
+            if cycled {
+                break;
+            }
+        }
+        sizing.sections = history.pop().unwrap_or_default();
+        sizing.weight = weight(&model);
+        sizing.model = model;
+        Ok(sizing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{generators::Grid, Load, Member, Support};
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// IPE sections from 160 to 300, out of order.
+    fn catalog() -> Vec<Profile> {
+        [
+            ("IPE 300", 53.8e-4, 8356e-8, 557e-6),
+            ("IPE 160", 20.1e-4, 869e-8, 109e-6),
+            ("IPE 180", 23.9e-4, 1317e-8, 146e-6),
+            ("IPE 200", 28.5e-4, 1943e-8, 194e-6),
+            ("IPE 220", 33.4e-4, 2772e-8, 252e-6),
+            ("IPE 240", 39.1e-4, 3892e-8, 324e-6),
+            ("IPE 270", 45.9e-4, 5790e-8, 429e-6),
+        ]
+        .map(|(name, area, inertia, modulus)| Profile {
+            name: name.into(),
+            area,
+            inertia,
+            modulus,
+        })
+        .into()
+    }
+
+    /// Simply supported beam of 6 in two members under the uniform load `w`.
+    fn simple(w: Float) -> (Model, Vec<LoadCase>) {
+        let mut model = Model::new();
+        model.extend_nodes([[0.0, 0.0], [3.0, 0.0], [6.0, 0.0]]);
+        model.add_support(Support {
+            node: 0,
+            fixed: [true, true, false],
+        });
+        model.add_support(Support {
+            node: 2,
+            fixed: [false, true, false],
+        });
+        model.extend_members([0, 1].map(|i| Member {
+            nodes: [i, i + 1],
+            elasticity: 2.05e8,
+            area: 53.8e-4,
+            inertia: 8356e-8,
+        }));
+        let loads = [0, 1]
+            .map(|member| Load::Member {
+                member,
+                load: [0.0, -w],
+            })
+            .into();
+        (
+            model,
+            vec![LoadCase {
+                name: "w".into(),
+                loads,
+            }],
+        )
+    }
+
+    #[test]
+    fn simple_beam() {
+        let (model, cases) = simple(10.0);
+        let options = SizingOptions::default();
+        let sizing = model
+            .size(&cases, &[vec![0, 1]], &catalog(), &options)
+            .unwrap();
+        // 45 kNm needs W of 191 cm³, just within the IPE 200.
+        assert_eq!(catalog()[sizing.sections[0]].name, "IPE 200");
+        assert_relative_eq!(
+            sizing.utilization[0],
+            45.0 / 194e-6 / 235e3,
+            max_relative = Float::EPSILON * 1e3
+        );
+        assert!(sizing.converged);
+        assert_eq!(sizing.iterations, 2);
+        assert_eq!(sizing.model.members[1].inertia, 1943e-8);
+        assert_relative_eq!(
+            sizing.initial_weight,
+            53.8e-4 * 6.0 * 7.85,
+            max_relative = Float::EPSILON * 1e1
+        );
+        assert_relative_eq!(
+            sizing.weight,
+            28.5e-4 * 6.0 * 7.85,
+            max_relative = Float::EPSILON * 1e1
+        );
+        // Too heavy for any, with the largest over the limit.
+        let (model, cases) = simple(30.0);
+        let sizing = model
+            .size(&cases, &[vec![0, 1]], &catalog(), &options)
+            .unwrap();
+        assert_eq!(catalog()[sizing.sections[0]].name, "IPE 300");
+        assert!(sizing.utilization[0] > 1.0 && sizing.converged);
+        assert_eq!(
+            model.size(&cases, &[vec![2]], &catalog(), &options),
+            Err(SizingError::Model(ModelError::InvalidMember { member: 2 }))
+        );
+        assert_eq!(
+            model.size(&cases, &[], &[], &options),
+            Err(SizingError::EmptyCatalog)
+        );
+    }
+
+    #[test]
+    fn frame() {
+        let generated = Grid {
+            bays: 2,
+            stories: 3,
+            ..Grid::default()
+        }
+        .generate();
+        // The columns together and the beams of each floor alike.
+        let groups = vec![
+            (0..9).collect(),
+            (9..11).collect(),
+            (11..13).collect(),
+            (13..15).collect(),
+        ];
+        let options = SizingOptions::default();
+        let sizing = generated
+            .model
+            .size(&generated.cases, &groups, &catalog(), &options)
+            .unwrap();
+        assert!(sizing.converged, "{sizing:?}");
+        assert!(sizing.weight < sizing.initial_weight);
+        // The sections hold under the forces of their own analysis.
+        let mut results = Results::new(sizing.model.clone());
+        results.add_cases(&generated.cases).unwrap();
+        for (group, &section) in groups.iter().zip(&sizing.sections) {
+            let profile = &catalog()[section];
+            for &member in group {
+                assert_eq!(sizing.model.members[member].area, profile.area);
+                for [n, m] in demands(&results, member) {
+                    let stress = n.abs() / profile.area + m.abs() / profile.modulus;
+                    assert!(stress <= options.strength * (1.0 + Float::EPSILON * 1e2));
+                }
+            }
+        }
+    }
+}
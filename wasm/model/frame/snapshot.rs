@@ -0,0 +1,342 @@
+//! Fingerprints and comparisons of [`Solution`]s, to detect numerical regressions across versions
+//! of the crate and across builds such as `f32` and `simd`.
+
+use super::Solution;
+use crate::Float;
+use std::fmt::{self, Display, Formatter};
+
+/// Results of a [`Solution`] fingerprinted and compared separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Quantity {
+    Displacements,
+    Reactions,
+    MemberForces,
+}
+
+impl Quantity {
+    pub const ALL: [Quantity; 3] = [
+        Quantity::Displacements,
+        Quantity::Reactions,
+        Quantity::MemberForces,
+    ];
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Quantity::Displacements => "displacements",
+            Quantity::Reactions => "reactions",
+            Quantity::MemberForces => "member forces",
+        })
+    }
+}
+
+/// Allowed difference of each value, `absolute + relative * scale` with the scale the largest
+/// magnitude of the expected component over all nodes or members.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Tolerance {
+    pub relative: Float,
+    pub absolute: Float,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            relative: 1e-4,
+            absolute: 1e-9,
+        }
+    }
+}
+
+/// Difference of a value from the expected one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Deviation {
+    pub quantity: Quantity,
+    /// Node or member.
+    pub index: usize,
+    /// Component of the value, such as 2 for the rotation of a node.
+    pub component: usize,
+    pub expected: Float,
+    pub actual: Float,
+    /// Largest difference within the [`Tolerance`].
+    pub allowed: Float,
+}
+
+impl Deviation {
+    pub fn difference(&self) -> Float {
+        (self.actual - self.expected).abs()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.difference() <= self.allowed
+    }
+
+    /// Difference relative to the allowed one, infinite if it is not a number.
+    fn excess(&self) -> Float {
+        let excess = self.difference() / self.allowed;
+        if excess.is_nan() {
+            Float::INFINITY
+        } else {
+            excess
+        }
+    }
+}
+
+/// Result of [`Solution::compare`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Comparison {
+    /// Deviation of each quantity with the largest difference relative to the allowed one, except
+    /// those of no nodes or members.
+    pub deviations: Vec<Deviation>,
+}
+
+impl Comparison {
+    pub fn passed(&self) -> bool {
+        self.deviations.iter().all(Deviation::passed)
+    }
+}
+
+/// Solutions compared with different numbers of nodes or members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShapeMismatch {
+    pub quantity: Quantity,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl Display for ShapeMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} items compared with {} expected",
+            self.quantity, self.actual, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ShapeMismatch {}
+
+/// 64-bit FNV-1a hash of the little-endian bytes of `words`, which unlike
+/// [`std::hash::DefaultHasher`] is stable across platforms and versions.
+fn fnv(words: &[u64]) -> u64 {
+    words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Double precision of a value, a no-op without the `f32` feature.
+#[allow(clippy::unnecessary_cast)]
+fn double(value: Float) -> f64 {
+    value as f64
+}
+
+impl Solution {
+    /// Values of the `quantity` of all nodes or members in a row each, and the length of a row.
+    fn values(&self, quantity: Quantity) -> (&[Float], usize) {
+        match quantity {
+            Quantity::Displacements => (self.displacements.as_flattened(), 3),
+            Quantity::Reactions => (self.reactions.as_flattened(), 3),
+            Quantity::MemberForces => (self.member_forces.as_flattened(), 6),
+        }
+    }
+
+    /// Hash of the displacements, reactions and member forces rounded to `digits` significant
+    /// digits of the largest magnitude of each of their components, the same for solutions that
+    /// agree to about that precision whatever their scalar type.
+    ///
+    /// It sums the hashes of the values, so it does not depend on the order they are visited.
+    /// Values close to halfway between two rounded ones may round either way, so builds expected
+    /// to agree to `n` digits are best compared with a couple fewer, or with [`Self::compare`].
+    pub fn fingerprint(&self, digits: u32) -> u64 {
+        let mut hash: u64 = 0;
+        for quantity in Quantity::ALL {
+            let (values, width) = self.values(quantity);
+            let id = quantity as u64;
+            hash = hash.wrapping_add(fnv(&[id, (values.len() / width) as u64]));
+            for component in 0..width {
+                let column = || {
+                    values
+                        .iter()
+                        .skip(component)
+                        .step_by(width)
+                        .map(|&v| double(v))
+                };
+                let max = column().fold(0.0, |max: f64, v| max.max(v.abs()));
+                let quantum = if max > 0.0 && max.is_finite() {
+                    10f64.powi(max.log10().ceil() as i32 - digits as i32)
+                } else {
+                    1.0
+                };
+                for (index, v) in column().enumerate() {
+                    let rounded = if v.is_finite() {
+                        (v / quantum).round() as i64 as u64
+                    } else {
+                        v.to_bits()
+                    };
+                    let words = [id, index as u64, component as u64, rounded];
+                    hash = hash.wrapping_add(fnv(&words));
+                }
+            }
+        }
+        hash
+    }
+
+    /// Largest deviation of each quantity from the `expected` solution, as a regression test of
+    /// results stored by an earlier version or another build.
+    ///
+    /// Returns an error if the solutions are of different numbers of nodes or members.
+    pub fn compare(
+        &self,
+        expected: &Solution,
+        tolerance: &Tolerance,
+    ) -> Result<Comparison, ShapeMismatch> {
+        let mut deviations = vec![];
+        for quantity in Quantity::ALL {
+            let (actual, width) = self.values(quantity);
+            let (expected, _) = expected.values(quantity);
+            if actual.len() != expected.len() {
+                return Err(ShapeMismatch {
+                    quantity,
+                    expected: expected.len() / width,
+                    actual: actual.len() / width,
+                });
+            }
+            let mut scales = vec![0.0 as Float; width];
+            for (i, v) in expected.iter().enumerate() {
+                scales[i % width] = scales[i % width].max(v.abs());
+            }
+            let largest = expected
+                .iter()
+                .zip(actual)
+                .enumerate()
+                .map(|(i, (&expected, &actual))| Deviation {
+                    quantity,
+                    index: i / width,
+                    component: i % width,
+                    expected,
+                    actual,
+                    allowed: tolerance.absolute + tolerance.relative * scales[i % width],
+                })
+                .reduce(|a, b| if b.excess() > a.excess() { b } else { a });
+            deviations.extend(largest);
+        }
+        Ok(Comparison { deviations })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::frame::{fixtures::member, Load, Model, Support};
+
+    fn portal() -> Solution {
+        let mut model = Model::new();
+        let nodes = model.extend_nodes([[0.0, 0.0], [0.0, 3.0], [4.0, 3.0], [4.0, 0.0]]);
+        model.extend_members(
+            nodes
+                .clone()
+                .zip(nodes.skip(1))
+                .map(|(a, b)| member([a, b])),
+        );
+        for node in [0, 3] {
+            model.add_support(Support {
+                node,
+                fixed: [true; 3],
+            });
+        }
+        model.add_load(Load::Node {
+            node: 1,
+            force: [10.0, 0.0, 0.0],
+        });
+        model.add_load(Load::Member {
+            member: 1,
+            load: [0.0, -5.0],
+        });
+        model.solve().unwrap()
+    }
+
+    #[test]
+    fn fingerprint() {
+        let solution = portal();
+        let digits = 6;
+        assert_eq!(
+            solution.fingerprint(digits),
+            solution.clone().fingerprint(digits)
+        );
+        // Rounding hides relative perturbations well below the digits kept.
+        let mut perturbed = solution.clone();
+        for u in perturbed.displacements.iter_mut().flatten() {
+            *u *= 1.0 + 1e-9;
+        }
+        perturbed.reactions[0][0] += 1e-12;
+        assert_eq!(perturbed.fingerprint(4), solution.fingerprint(4));
+        let mut changed = solution.clone();
+        changed.member_forces[1][2] *= 1.01;
+        assert_ne!(changed.fingerprint(digits), solution.fingerprint(digits));
+        // The order of the nodes matters.
+        let mut swapped = solution.clone();
+        swapped.displacements.swap(1, 2);
+        assert_ne!(swapped.fingerprint(digits), solution.fingerprint(digits));
+        let mut negative = solution.clone();
+        negative.reactions[1] = [-0.0; 3];
+        let mut positive = solution.clone();
+        positive.reactions[1] = [0.0; 3];
+        assert_eq!(negative.fingerprint(digits), positive.fingerprint(digits));
+    }
+
+    #[test]
+    fn compare() {
+        let expected = portal();
+        let tolerance = Tolerance::default();
+        let comparison = expected.compare(&expected, &tolerance).unwrap();
+        assert!(comparison.passed());
+        assert_eq!(comparison.deviations.len(), 3);
+        assert!(comparison.deviations.iter().all(|d| d.difference() == 0.0));
+        let mut actual = expected.clone();
+        let force = &mut actual.member_forces[2][4];
+        *force += 1e-2 * force.abs();
+        actual.displacements[1][0] *= 1.0 + 1e-6;
+        let comparison = actual.compare(&expected, &tolerance).unwrap();
+        assert!(!comparison.passed());
+        let [displacements, reactions, forces] = comparison.deviations[..] else {
+            panic!("{comparison:?}");
+        };
+        assert!(displacements.passed() && reactions.passed() && !forces.passed());
+        assert_eq!(
+            (forces.quantity, forces.index, forces.component),
+            (Quantity::MemberForces, 2, 4)
+        );
+        assert_eq!(forces.actual, actual.member_forces[2][4]);
+        actual.member_forces[0][0] = Float::NAN;
+        let comparison = actual.compare(&expected, &tolerance).unwrap();
+        assert_eq!(comparison.deviations[2].index, 0);
+        actual.reactions.pop();
+        assert_eq!(
+            actual.compare(&expected, &tolerance),
+            Err(ShapeMismatch {
+                quantity: Quantity::Reactions,
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+}
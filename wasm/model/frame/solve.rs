@@ -0,0 +1,1379 @@
+use super::{element, Load, Model, ModelError};
+use crate::{
+    cancel::CancelToken,
+    clock,
+    math::{
+        matrix::Matrix,
+        sparse::{
+            csr::CsrMatrix,
+            diagnostics::Diagnostics,
+            direct::{DirectSolver, Storage},
+        },
+    },
+    Float,
+};
+use std::{
+    borrow::Borrow,
+    fmt::{self, Display, Formatter},
+    mem,
+    ops::Range,
+};
+
+/// Members assembled or recovered in one step of an [`Analysis`].
+const CHUNK: usize = 256;
+
+/// Load cases back-substituted at once by [`Model::solve_static_many`].
+const BLOCK: usize = 8;
+
+/// Result of the linear static analysis of a [`Model`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Solution {
+    /// Displacements and rotation of each node.
+    pub displacements: Vec<[Float; 3]>,
+    /// Reactions of each node along the global axes, zero where it is not restrained.
+    pub reactions: Vec<[Float; 3]>,
+    /// End forces of each member in its local axes, exerted on it by its nodes.
+    pub member_forces: Vec<[Float; 6]>,
+    /// Diagnostics of the factorization of the stiffness, `None` if every degree of freedom is
+    /// restrained.
+    pub diagnostics: Option<Diagnostics>,
+    /// Size and cost of the analysis.
+    #[cfg_attr(feature = "serde", tsify(type = "AnalysisStats"))]
+    pub stats: Stats,
+}
+
+/// Size and cost of an [`Analysis`], to follow its performance across models and versions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+#[cfg_attr(feature = "serde", tsify(rename = "AnalysisStats"))]
+pub struct Stats {
+    /// Number of members.
+    pub elements: usize,
+    /// Number of unrestrained degrees of freedom, the dimension of the reduced stiffness.
+    pub dofs: usize,
+    /// Number of stored elements of the reduced stiffness.
+    pub nnz: usize,
+    /// Number of elements of the factor below the diagonal, as [`DirectSolver::nnz`].
+    pub factor_nnz: usize,
+    /// Estimate of the floating-point operations of the factorization.
+    pub flops: Float,
+    /// Estimate of the peak bytes allocated, counting the assembled triplets, the stiffness and
+    /// its factors as if they were alive at once.
+    pub memory: usize,
+    /// Wall times spent in the steps of each phase.
+    pub times: PhaseTimes,
+}
+
+/// Wall times of the phases of an [`Analysis`] in milliseconds, read from `performance.now()`
+/// in wasm.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct PhaseTimes {
+    pub assembly: f64,
+    pub factorization: f64,
+    pub recovery: f64,
+}
+
+/// Size and cost of an analysis estimated before running it, from [`Model::estimate_requirements`],
+/// to warn before a model exhausts the memory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Requirements {
+    /// Number of unrestrained degrees of freedom.
+    pub dofs: usize,
+    /// Number of stored elements of the reduced stiffness.
+    pub nnz: usize,
+    /// Storage the factorization would choose, `None` if every degree of freedom is restrained.
+    pub storage: Option<Storage>,
+    /// Number of elements of the factor below the diagonal, including the fill-in.
+    pub factor_nnz: usize,
+    /// Floating-point operations of the factorization.
+    pub flops: Float,
+    /// Peak bytes allocated, as [`Stats::memory`].
+    pub memory: usize,
+}
+
+/// Named set of loads of a structure, analyzed by [`Model::solve_static_many`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct LoadCase {
+    pub name: String,
+    pub loads: Vec<Load>,
+}
+
+/// Stiffness and transformation of a member.
+struct Element {
+    dofs: [usize; 6],
+    rotation: Matrix<Float>,
+    stiffness: Matrix<Float>,
+    /// Fixed end forces of the member loads in the local axes.
+    fixed: [Float; 6],
+}
+
+impl Element {
+    /// Stiffness in the global axes.
+    fn global_stiffness(&self) -> Matrix<Float> {
+        &(&self.rotation.transpose() * &self.stiffness) * &self.rotation
+    }
+
+    /// End forces in the global axes and in the local axes under the nodal `displacements`, and
+    /// the `fixed` end forces of the member loads.
+    fn end_forces(&self, displacements: &[Float], fixed: &[Float; 6]) -> (Vec<Float>, [Float; 6]) {
+        let local = self
+            .rotation
+            .mul_vec_simd(&self.dofs.map(|i| displacements[i]));
+        let mut forces = self.stiffness.mul_vec_simd(&local);
+        for (f, g) in forces.iter_mut().zip(fixed) {
+            *f += g;
+        }
+        let global = self.rotation.transpose().mul_vec_simd(&forces);
+        (global, [0, 1, 2, 3, 4, 5].map(|i| forces[i]))
+    }
+}
+
+impl Model {
+    /// Element of the member `i` under its uniform `load` in its local axes.
+    fn element(&self, i: usize, load: [Float; 2]) -> Element {
+        let [a, b] = self.members[i].nodes;
+        Element {
+            dofs: [3 * a, 3 * a + 1, 3 * a + 2, 3 * b, 3 * b + 1, 3 * b + 2],
+            rotation: element::rotation(self.axis(i)),
+            stiffness: element::stiffness(&self.members[i], self.length(i)),
+            fixed: element::fixed_end_forces(load, self.length(i)),
+        }
+    }
+
+    /// Total uniform load on the `member` in its local axes.
+    pub(super) fn member_load(&self, member: usize) -> [Float; 2] {
+        self.loads
+            .iter()
+            .filter_map(|load| match *load {
+                Load::Member { member: m, load } if m == member => {
+                    Some(element::local_load(load, self.axis(member)))
+                }
+                _ => None,
+            })
+            .fold([0.0; 2], |a, b| [a[0] + b[0], a[1] + b[1]])
+    }
+
+    /// Total uniform load on each member in its local axes among the `loads`.
+    pub(super) fn member_loads(&self, loads: &[Load]) -> Vec<[Float; 2]> {
+        let mut totals = vec![[0.0; 2]; self.members.len()];
+        for load in loads {
+            if let Load::Member { member, load } = *load {
+                let [x, y] = element::local_load(load, self.axis(member));
+                totals[member][0] += x;
+                totals[member][1] += y;
+            }
+        }
+        totals
+    }
+
+    /// Nodal forces of each degree of freedom among the `loads`.
+    fn applied(&self, loads: &[Load]) -> Vec<Float> {
+        let mut applied = vec![0.0; 3 * self.nodes.len()];
+        for load in loads {
+            if let Load::Node { node, force } = *load {
+                for (f, g) in applied[3 * node..].iter_mut().zip(force) {
+                    *f += g;
+                }
+            }
+        }
+        applied
+    }
+
+    /// Whether each degree of freedom is restrained by a support.
+    fn restraints(&self) -> Vec<bool> {
+        let mut fixed = vec![false; 3 * self.nodes.len()];
+        for support in &self.supports {
+            for (f, &s) in fixed[3 * support.node..].iter_mut().zip(&support.fixed) {
+                *f |= s;
+            }
+        }
+        fixed
+    }
+
+    /// Total stiffness of the springs of each degree of freedom.
+    fn spring_stiffness(&self) -> Vec<Float> {
+        let mut stiffness = vec![0.0; 3 * self.nodes.len()];
+        for spring in &self.springs {
+            for (k, s) in stiffness[3 * spring.node..]
+                .iter_mut()
+                .zip(spring.stiffness)
+            {
+                *k += s;
+            }
+        }
+        stiffness
+    }
+
+    /// Bytes of the nodal vectors, restraints and numbering, and the member forces of an
+    /// analysis.
+    fn vector_memory(&self) -> usize {
+        3 * self.nodes.len()
+            * (5 * size_of::<Float>() + size_of::<bool>() + size_of::<Option<usize>>())
+            + self.members.len() * size_of::<[Float; 6]>()
+    }
+
+    /// Size and cost of [`Self::solve`] from the pattern of the stiffness alone, without
+    /// computing it, so that a model too large for the memory can be rejected before it is
+    /// analyzed. The estimate of the factorization is exact unless it fails, and the peak memory
+    /// is that reported by [`Stats::memory`].
+    pub fn estimate_requirements(&self) -> Result<Requirements, ModelError> {
+        self.validate()?;
+        let (free, count) = numbering(&self.restraints());
+        let mut pattern = vec![];
+        scatter_springs(&mut pattern, &free, &self.spring_stiffness());
+        for member in &self.members {
+            let [a, b] = member.nodes;
+            let dofs = [3 * a, 3 * a + 1, 3 * a + 2, 3 * b, 3 * b + 1, 3 * b + 2];
+            for p in dofs.iter().filter_map(|&p| free[p]) {
+                pattern.extend(dofs.iter().filter_map(|&q| Some((p, free[q]?, 1.0))));
+            }
+        }
+        // Assembly grows the triplets by doubling from four.
+        let assembled = match pattern.len() {
+            0 => 0,
+            len => len.next_power_of_two().max(4) * size_of::<(usize, usize, Float)>(),
+        };
+        let mut requirements = Requirements {
+            dofs: count,
+            nnz: 0,
+            storage: None,
+            factor_nnz: 0,
+            flops: 0.0,
+            memory: self.vector_memory(),
+        };
+        if count > 0 {
+            let k = CsrMatrix::from_triplets(count, count, pattern);
+            let estimate = DirectSolver::estimate(&k);
+            requirements.nnz = k.nnz();
+            requirements.storage = Some(estimate.storage);
+            requirements.factor_nnz = estimate.nnz;
+            requirements.flops = estimate.flops;
+            requirements.memory += assembled + stiffness_memory(&k) + estimate.memory;
+        }
+        Ok(requirements)
+    }
+
+    /// Linear static analysis by the direct stiffness method, running an [`Analysis`] through.
+    pub fn solve(&self) -> Result<Solution, ModelError> {
+        self.solve_with_cancel(&CancelToken::new())
+    }
+
+    /// [`Self::solve`] stopping with [`ModelError::Cancelled`] once the `cancel` token is set,
+    /// as checked by [`Analysis::with_cancel`].
+    pub fn solve_with_cancel(&self, cancel: &CancelToken) -> Result<Solution, ModelError> {
+        let mut analysis = Analysis::with_cancel(self, cancel.clone())?;
+        loop {
+            if let Some(solution) = analysis.step()? {
+                return Ok(solution);
+            }
+        }
+    }
+
+    /// Linear static analyses of the structure under each of the load `cases`, whose loads replace
+    /// those of the model, assembling and factorizing the stiffness once and back-substituting
+    /// the cases several at a time. The solutions share the [`Stats`] of the whole run.
+    pub fn solve_static_many(&self, cases: &[LoadCase]) -> Result<Vec<Solution>, ModelError> {
+        self.solve_static_many_with_block(cases, BLOCK)
+    }
+
+    /// [`Self::solve_static_many`] back-substituting `block` cases per traversal of the factors,
+    /// holding as many load vectors of the reduced system at once.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "analysis", level = "info", skip_all, fields(nodes = self.nodes.len(), members = self.members.len(), cases = cases.len()))
+    )]
+    pub fn solve_static_many_with_block(
+        &self,
+        cases: &[LoadCase],
+        block: usize,
+    ) -> Result<Vec<Solution>, ModelError> {
+        self.validate_structure()?;
+        for case in cases {
+            self.validate_loads(&case.loads)?;
+        }
+        let mut factorized = Factorized::new(self)?;
+        let stats = &mut factorized.stats;
+        stats.memory += self.vector_memory() * cases.len().saturating_sub(1);
+
+        let start = clock::now();
+        let loads: Vec<_> = cases
+            .iter()
+            .map(|case| factorized.loads(self, &case.loads))
+            .collect();
+        factorized.stats.times.assembly += clock::now() - start;
+
+        let start = clock::now();
+        let mut displacements = Vec::with_capacity(cases.len());
+        for loads in loads.chunks(block.max(1)) {
+            let k = loads.len();
+            let x = factorized.solve_block(&factorized.rhs(loads), k);
+            displacements.extend((0..k).map(|c| factorized.displacements(&x, k, c)));
+        }
+        factorized.stats.times.factorization += clock::now() - start;
+
+        let start = clock::now();
+        let mut solutions: Vec<_> = (loads.iter().zip(&displacements))
+            .map(|(loads, u)| factorized.recover(loads, u))
+            .collect();
+        factorized.stats.times.recovery += clock::now() - start;
+        for solution in &mut solutions {
+            solution.stats = factorized.stats;
+        }
+        Ok(solutions)
+    }
+
+    /// Axial force, positive in tension, shear force and bending moment, positive sagging, at the
+    /// distance `x` along the `member` from its first node, with the shear the derivative of the
+    /// moment.
+    pub fn internal_forces(&self, solution: &Solution, member: usize, x: Float) -> [Float; 3] {
+        internal_forces(&solution.member_forces[member], self.member_load(member), x)
+    }
+}
+
+/// [`Model::internal_forces`] of a member of end `forces` under the uniform `load` in its local
+/// axes.
+pub(super) fn internal_forces(forces: &[Float; 6], [qx, qy]: [Float; 2], x: Float) -> [Float; 3] {
+    let [n, v, m, ..] = *forces;
+    [-n - qx * x, v + qy * x, -m + v * x + qy * x * x / 2.0]
+}
+
+/// Applied nodal forces of each degree of freedom and fixed end forces of each member of a set of
+/// loads, from [`Factorized::loads`].
+pub(super) type Loads = (Vec<Float>, Vec<[Float; 6]>);
+
+/// Stiffness of a structure assembled and factorized once and back-substituted for any number of
+/// sets of loads, as by [`Model::solve_static_many`], or updated for edited members.
+pub(super) struct Factorized {
+    restraints: Vec<bool>,
+    /// Stiffness of the springs of each degree of freedom.
+    springs: Vec<Float>,
+    free: Vec<Option<usize>>,
+    /// Unrestrained degrees of freedom in the order of the reduced system.
+    dofs: Vec<usize>,
+    /// Elements of the members as they are now, without their loads.
+    elements: Vec<Element>,
+    /// Stiffness of each member in the global axes as assembled into `k`, differing from that of
+    /// its element if it was edited since.
+    globals: Vec<Matrix<Float>>,
+    /// Reduced stiffness and its factors, `None` if every degree of freedom is restrained.
+    k: Option<(CsrMatrix, DirectSolver)>,
+    /// Stats of the assembly and factorization.
+    pub(super) stats: Stats,
+}
+
+impl Factorized {
+    /// Assembles and factorizes the stiffness of the structure of the `model`, whose loads are
+    /// ignored.
+    pub(super) fn new(model: &Model) -> Result<Self, ModelError> {
+        model.validate_structure()?;
+        let restraints = model.restraints();
+        let (free, count) = numbering(&restraints);
+        let start = clock::now();
+        let (elements, globals) = map_members(0..model.members.len(), |i| {
+            let e = model.element(i, [0.0; 2]);
+            let global = e.global_stiffness();
+            (e, global)
+        })
+        .into_iter()
+        .unzip();
+        let mut factorized = Self {
+            dofs: (0..restraints.len()).filter(|&p| !restraints[p]).collect(),
+            restraints,
+            springs: model.spring_stiffness(),
+            free,
+            elements,
+            globals,
+            k: None,
+            stats: Stats {
+                elements: model.members.len(),
+                dofs: count,
+                memory: model.vector_memory(),
+                ..Stats::default()
+            },
+        };
+        let mut triplets = vec![];
+        scatter_springs(&mut triplets, &factorized.free, &factorized.springs);
+        for (e, global) in factorized.elements.iter().zip(&factorized.globals) {
+            scatter(&mut triplets, &factorized.free, &e.dofs, global);
+        }
+        factorized.stats.memory += triplets.capacity() * size_of::<(usize, usize, Float)>();
+        factorized.stats.times.assembly = clock::now() - start;
+        let start = clock::now();
+        if count > 0 {
+            factorized.factorize(CsrMatrix::from_triplets(count, count, triplets))?;
+        }
+        factorized.stats.times.factorization = clock::now() - start;
+        Ok(factorized)
+    }
+
+    fn factorize(&mut self, k: CsrMatrix) -> Result<(), ModelError> {
+        let solver = DirectSolver::new(&k).ok_or(ModelError::Unstable)?;
+        if solver.diagnostics().pivots.negative > 0 {
+            return Err(ModelError::Unstable);
+        }
+        self.stats.nnz = k.nnz();
+        self.stats.factor_nnz = solver.nnz();
+        self.stats.flops = solver.flops();
+        self.stats.memory += stiffness_memory(&k) + solver.memory();
+        self.k = Some((k, solver));
+        Ok(())
+    }
+
+    /// Number of unrestrained degrees of freedom.
+    pub(super) fn count(&self) -> usize {
+        self.dofs.len()
+    }
+
+    /// Index in the reduced system of each unrestrained degree of freedom, `None` if restrained.
+    pub(super) fn free(&self) -> &[Option<usize>] {
+        &self.free
+    }
+
+    /// Degrees of freedom of the ends of the `member`.
+    pub(super) fn member_dofs(&self, member: usize) -> [usize; 6] {
+        self.elements[member].dofs
+    }
+
+    /// Change of the global stiffness of the `member` since it was assembled.
+    pub(super) fn stiffness_change(&self, member: usize) -> Matrix<Float> {
+        let (now, assembled) = (
+            self.elements[member].global_stiffness(),
+            &self.globals[member],
+        );
+        Matrix::from_fn(6, 6, |i, j| now[(i, j)] - assembled[(i, j)])
+    }
+
+    /// Replaces the element of the `member` by that of the `model`, leaving the assembled
+    /// stiffness as it was.
+    pub(super) fn edit(&mut self, model: &Model, member: usize) {
+        self.elements[member] = model.element(member, [0.0; 2]);
+    }
+
+    /// Adds the changes of the stiffness of the `members` to the assembled stiffness and
+    /// factorizes it again.
+    pub(super) fn reassemble(&mut self, members: &[usize]) -> Result<(), ModelError> {
+        let mut triplets = vec![];
+        for &member in members {
+            let change = self.stiffness_change(member);
+            scatter(
+                &mut triplets,
+                &self.free,
+                &self.elements[member].dofs,
+                &change,
+            );
+            self.globals[member] = self.elements[member].global_stiffness();
+        }
+        if let Some((k, solver)) = self.k.take() {
+            self.stats.memory -= stiffness_memory(&k) + solver.memory();
+            let count = self.count();
+            let change = CsrMatrix::from_triplets(count, count, triplets);
+            self.factorize(k.add_scaled(&change, 1.0))?;
+        }
+        Ok(())
+    }
+
+    /// Applied nodal forces and fixed end forces of the members of the `loads` on the `model`.
+    pub(super) fn loads(&self, model: &Model, loads: &[Load]) -> Loads {
+        let fixed = (model.member_loads(loads).into_iter().enumerate())
+            .map(|(i, load)| element::fixed_end_forces(load, model.length(i)))
+            .collect();
+        (model.applied(loads), fixed)
+    }
+
+    /// Right hand sides of the reduced system of each of the `loads` by rows, less the equivalent
+    /// nodal forces of the members.
+    pub(super) fn rhs(&self, loads: &[Loads]) -> Vec<Float> {
+        let (n, k) = (self.restraints.len(), loads.len());
+        let mut b = vec![0.0; n * k];
+        for (c, (applied, fixed)) in loads.iter().enumerate() {
+            for (p, &f) in applied.iter().enumerate() {
+                b[p * k + c] = f;
+            }
+            for (e, fixed) in self.elements.iter().zip(fixed) {
+                let equivalent = e.rotation.transpose().mul_vec_simd(fixed);
+                for (&p, f) in e.dofs.iter().zip(equivalent) {
+                    b[p * k + c] -= f;
+                }
+            }
+        }
+        (self.dofs.iter())
+            .flat_map(|&p| b[p * k..(p + 1) * k].iter().copied())
+            .collect()
+    }
+
+    /// Solves the assembled reduced system for the `k` columns of `b` stored by rows.
+    pub(super) fn solve_block(&self, b: &[Float], k: usize) -> Vec<Float> {
+        match &self.k {
+            Some((_, solver)) => solver.solve_block(b, k),
+            None => b.to_vec(),
+        }
+    }
+
+    /// Displacements of every degree of freedom of the column `c` of the `k` columns of the
+    /// solution `x` of the reduced system.
+    pub(super) fn displacements(&self, x: &[Float], k: usize, c: usize) -> Vec<Float> {
+        let mut u = vec![0.0; self.restraints.len()];
+        for (r, &p) in self.dofs.iter().enumerate() {
+            u[p] = x[r * k + c];
+        }
+        u
+    }
+
+    /// Solution of the nodal displacements `u` under the `loads`, with the member forces and
+    /// reactions of the elements as they are now.
+    pub(super) fn recover(&self, (applied, fixed): &Loads, u: &[Float]) -> Solution {
+        let forces = map_members(0..self.elements.len(), |i| {
+            self.elements[i].end_forces(u, &fixed[i])
+        });
+        let mut reactions = vec![0.0; u.len()];
+        for (e, (global, _)) in self.elements.iter().zip(&forces) {
+            for (&i, f) in e.dofs.iter().zip(global) {
+                reactions[i] += f;
+            }
+        }
+        let reactions = supported(&reactions, applied, &self.restraints, &self.springs, u);
+        Solution {
+            displacements: triples(u),
+            reactions: triples(&reactions),
+            member_forces: forces.into_iter().map(|(_, local)| local).collect(),
+            diagnostics: self.k.as_ref().map(|(_, solver)| *solver.diagnostics()),
+            stats: self.stats,
+        }
+    }
+}
+
+/// Stage of an [`Analysis`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    /// Adding the stiffness and loads of the members, a chunk of members per step.
+    Assembly,
+    /// Factorizing the stiffness and solving for the displacements in a single step.
+    Factorization,
+    /// Computing the member forces and reactions, a chunk of members per step.
+    Recovery,
+    Done,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Assembly => "assembly",
+            Phase::Factorization => "factorization",
+            Phase::Recovery => "recovery",
+            Phase::Done => "done",
+        })
+    }
+}
+
+/// Progress of an [`Analysis`] through its current phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Progress {
+    pub phase: Phase,
+    /// Units of work done in the phase, members or the factorization, out of `total`.
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Linear static analysis of the model `M`, owned or borrowed, in steps of bounded work, so that
+/// it can be interleaved with other work such as a user interface, and abandoned between steps.
+pub struct Analysis<M> {
+    model: M,
+    phase: Phase,
+    /// Next member to assemble or recover.
+    next: usize,
+    restraints: Vec<bool>,
+    /// Stiffness of the springs of each degree of freedom.
+    springs: Vec<Float>,
+    /// Index of each unrestrained degree of freedom in the reduced system.
+    free: Vec<Option<usize>>,
+    count: usize,
+    applied: Vec<Float>,
+    rhs: Vec<Float>,
+    triplets: Vec<(usize, usize, Float)>,
+    displacements: Vec<Float>,
+    reactions: Vec<Float>,
+    member_forces: Vec<[Float; 6]>,
+    diagnostics: Option<Diagnostics>,
+    stats: Stats,
+    cancel: CancelToken,
+    /// Spans of the whole analysis and of the current phase within it.
+    #[cfg(feature = "tracing")]
+    spans: (tracing::Span, tracing::Span),
+}
+
+impl<M: Borrow<Model>> Analysis<M> {
+    pub fn new(model: M) -> Result<Self, ModelError> {
+        Self::with_cancel(model, CancelToken::new())
+    }
+
+    /// Analysis checking the `cancel` token before each step and each row of the factorization,
+    /// failing with [`ModelError::Cancelled`] from then on once it is set.
+    pub fn with_cancel(model: M, cancel: CancelToken) -> Result<Self, ModelError> {
+        let m = model.borrow();
+        m.validate()?;
+        let n = 3 * m.nodes.len();
+        let restraints = m.restraints();
+        let (free, count) = numbering(&restraints);
+        let applied = m.applied(&m.loads);
+        let springs = m.spring_stiffness();
+        let mut triplets = vec![];
+        scatter_springs(&mut triplets, &free, &springs);
+        let stats = Stats {
+            elements: m.members.len(),
+            dofs: count,
+            memory: m.vector_memory(),
+            ..Stats::default()
+        };
+        #[cfg(feature = "tracing")]
+        let root =
+            tracing::info_span!("analysis", nodes = m.nodes.len(), members = m.members.len());
+        Ok(Self {
+            #[cfg(feature = "tracing")]
+            spans: (tracing::debug_span!(parent: &root, "assembly"), root),
+            rhs: applied.clone(),
+            member_forces: Vec::with_capacity(m.members.len()),
+            model,
+            phase: Phase::Assembly,
+            next: 0,
+            restraints,
+            springs,
+            free,
+            count,
+            applied,
+            triplets,
+            displacements: vec![0.0; n],
+            reactions: vec![0.0; n],
+            diagnostics: None,
+            stats,
+            cancel,
+        })
+    }
+
+    pub fn progress(&self) -> Progress {
+        let members = self.model.borrow().members.len();
+        let (done, total) = match self.phase {
+            Phase::Assembly | Phase::Recovery => (self.next, members),
+            Phase::Factorization => (0, 1),
+            Phase::Done => (1, 1),
+        };
+        Progress {
+            phase: self.phase,
+            done,
+            total,
+        }
+    }
+
+    /// Performs the next step, returning the solution after the last one. Steps after the last do
+    /// nothing.
+    pub fn step(&mut self) -> Result<Option<Solution>, ModelError> {
+        let (phase, start) = (self.phase, clock::now());
+        let mut result = self.work();
+        let elapsed = clock::now() - start;
+        let times = &mut self.stats.times;
+        match phase {
+            Phase::Assembly => times.assembly += elapsed,
+            Phase::Factorization => times.factorization += elapsed,
+            Phase::Recovery => times.recovery += elapsed,
+            Phase::Done => {}
+        }
+        if let Ok(Some(solution)) = &mut result {
+            solution.stats = self.stats;
+        }
+        result
+    }
+
+    fn work(&mut self) -> Result<Option<Solution>, ModelError> {
+        #[cfg(feature = "tracing")]
+        let _entered = self.spans.0.clone().entered();
+        if self.phase != Phase::Done && self.cancel.is_cancelled() {
+            return Err(ModelError::Cancelled {
+                progress: self.progress(),
+            });
+        }
+        let model = self.model.borrow();
+        let members = model.members.len();
+        match self.phase {
+            Phase::Assembly => {
+                let end = members.min(self.next + CHUNK);
+                let elements = map_members(self.next..end, |i| {
+                    let e = model.element(i, model.member_load(i));
+                    let equivalent = e.rotation.transpose().mul_vec_simd(&e.fixed);
+                    (e.dofs, e.global_stiffness(), equivalent)
+                });
+                for (dofs, global, equivalent) in elements {
+                    for (&p, f) in dofs.iter().zip(equivalent) {
+                        self.rhs[p] -= f;
+                    }
+                    scatter(&mut self.triplets, &self.free, &dofs, &global);
+                }
+                self.next = end;
+                if end == members {
+                    self.next = 0;
+                    self.advance(Phase::Factorization);
+                }
+            }
+            Phase::Factorization => {
+                if self.count > 0 {
+                    let triplets = mem::take(&mut self.triplets);
+                    let assembled = triplets.capacity() * size_of::<(usize, usize, Float)>();
+                    let k = CsrMatrix::from_triplets(self.count, self.count, triplets);
+                    let storage = DirectSolver::select_storage(&k);
+                    let solver = DirectSolver::with_cancel(&k, storage, &self.cancel)
+                        .map_err(|cancelled| ModelError::Cancelled {
+                            progress: Progress {
+                                phase: Phase::Factorization,
+                                done: cancelled.done,
+                                total: cancelled.total,
+                            },
+                        })?
+                        .ok_or(ModelError::Unstable)?;
+                    let stiffness = stiffness_memory(&k);
+                    self.stats.nnz = k.nnz();
+                    self.stats.factor_nnz = solver.nnz();
+                    self.stats.flops = solver.flops();
+                    self.stats.memory += assembled + stiffness + solver.memory();
+                    if solver.diagnostics().pivots.negative > 0 {
+                        return Err(ModelError::Unstable);
+                    }
+                    let b: Vec<_> = (self.rhs.iter().zip(&self.restraints))
+                        .filter(|(_, &fixed)| !fixed)
+                        .map(|(&b, _)| b)
+                        .collect();
+                    let x = solver.solve(&b);
+                    for (u, f) in self.displacements.iter_mut().zip(&self.free) {
+                        if let Some(f) = f {
+                            *u = x[*f];
+                        }
+                    }
+                    self.diagnostics = Some(*solver.diagnostics());
+                }
+                self.advance(Phase::Recovery);
+            }
+            Phase::Recovery => {
+                let end = members.min(self.next + CHUNK);
+                let displacements = &self.displacements;
+                let elements = map_members(self.next..end, |i| {
+                    let e = model.element(i, model.member_load(i));
+                    let (global, forces) = e.end_forces(displacements, &e.fixed);
+                    (e.dofs, global, forces)
+                });
+                for (dofs, global, forces) in elements {
+                    for (&i, f) in dofs.iter().zip(global) {
+                        self.reactions[i] += f;
+                    }
+                    self.member_forces.push(forces);
+                }
+                self.next = end;
+                if end == members {
+                    self.advance(Phase::Done);
+                    return Ok(Some(self.solution()));
+                }
+            }
+            Phase::Done => {}
+        }
+        Ok(None)
+    }
+
+    fn advance(&mut self, phase: Phase) {
+        self.phase = phase;
+        #[cfg(feature = "tracing")]
+        {
+            let root = &self.spans.1;
+            self.spans.0 = match phase {
+                Phase::Assembly => tracing::debug_span!(parent: root, "assembly"),
+                Phase::Factorization => tracing::debug_span!(parent: root, "factorization"),
+                Phase::Recovery => tracing::debug_span!(parent: root, "recovery"),
+                Phase::Done => tracing::Span::none(),
+            };
+        }
+    }
+
+    fn solution(&mut self) -> Solution {
+        let reactions = supported(
+            &self.reactions,
+            &self.applied,
+            &self.restraints,
+            &self.springs,
+            &self.displacements,
+        );
+        Solution {
+            displacements: triples(&self.displacements),
+            reactions: triples(&reactions),
+            member_forces: mem::take(&mut self.member_forces),
+            diagnostics: self.diagnostics,
+            stats: self.stats,
+        }
+    }
+}
+
+/// Adds the `global` stiffness of an element of the `dofs` to the `triplets` of the reduced
+/// stiffness, numbered by `free`.
+fn scatter(
+    triplets: &mut Vec<(usize, usize, Float)>,
+    free: &[Option<usize>],
+    dofs: &[usize; 6],
+    global: &Matrix<Float>,
+) {
+    for (i, &p) in dofs.iter().enumerate() {
+        for (j, &q) in dofs.iter().enumerate() {
+            if let (Some(p), Some(q)) = (free[p], free[q]) {
+                triplets.push((p, q, global[(i, j)]));
+            }
+        }
+    }
+}
+
+/// Adds the stiffness of the `springs` of each degree of freedom to the diagonal `triplets` of the
+/// reduced stiffness, numbered by `free`.
+fn scatter_springs(
+    triplets: &mut Vec<(usize, usize, Float)>,
+    free: &[Option<usize>],
+    springs: &[Float],
+) {
+    for (&p, &k) in free.iter().zip(springs) {
+        if let (Some(p), true) = (p, k != 0.0) {
+            triplets.push((p, p, k));
+        }
+    }
+}
+
+/// Reactions of each degree of freedom from the sums of the end `forces` of its members, less the
+/// `applied` forces where it is restrained, or the force of its spring under the displacements
+/// `u` elsewhere.
+fn supported(
+    forces: &[Float],
+    applied: &[Float],
+    restraints: &[bool],
+    springs: &[Float],
+    u: &[Float],
+) -> Vec<Float> {
+    (0..forces.len())
+        .map(|p| match (restraints[p], springs[p]) {
+            (true, _) => forces[p] - applied[p],
+            (false, 0.0) => 0.0,
+            (false, k) => -k * u[p],
+        })
+        .collect()
+}
+
+/// Index of each unrestrained degree of freedom in the reduced system, and their number.
+fn numbering(restraints: &[bool]) -> (Vec<Option<usize>>, usize) {
+    let mut count = 0;
+    let free = restraints
+        .iter()
+        .map(|&fixed| {
+            (!fixed).then(|| {
+                count += 1;
+                count - 1
+            })
+        })
+        .collect();
+    (free, count)
+}
+
+/// Bytes of the values, column indices and row offsets of the stiffness `k`.
+fn stiffness_memory(k: &CsrMatrix) -> usize {
+    k.nnz() * (size_of::<Float>() + size_of::<usize>()) + (k.rows() + 1) * size_of::<usize>()
+}
+
+/// Computes `f` for each member of the `range`, in parallel with the `rayon` feature.
+fn map_members<R: Send>(range: Range<usize>, f: impl Fn(usize) -> R + Send + Sync) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        range.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    range.map(f).collect()
+}
+
+/// Values of the degrees of freedom grouped by node.
+fn triples(values: &[Float]) -> Vec<[Float; 3]> {
+    values.chunks(3).map(|c| [c[0], c[1], c[2]]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        fixtures::{member, TOLERANCE},
+        Member, Spring, Support,
+    };
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Horizontal cantilever of `length` fixed at the origin.
+    fn cantilever(length: Float) -> Model {
+        let mut model = Model::new();
+        let a = model.add_node([0.0, 0.0]);
+        let b = model.add_node([length, 0.0]);
+        model.add_member(Member {
+            nodes: [a, b],
+            elasticity: 200.0,
+            area: 10.0,
+            inertia: 50.0,
+        });
+        model.add_support(Support {
+            node: a,
+            fixed: [true; 3],
+        });
+        model
+    }
+
+    #[test]
+    fn cantilever_tip_load() {
+        let mut model = cantilever(4.0);
+        model.add_load(Load::Node {
+            node: 1,
+            force: [3.0, -2.0, 0.0],
+        });
+        let solution = model.solve().unwrap();
+        // P L³ / 3 E I, P L² / 2 E I and the axial P L / E A.
+        assert_relative_eq!(
+            solution.displacements[1][..],
+            [
+                3.0 * 4.0 / 2000.0,
+                -2.0 * 64.0 / 30000.0,
+                -2.0 * 16.0 / 20000.0,
+            ][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            solution.reactions[0][..],
+            [-3.0, 2.0, 8.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_eq!(solution.reactions[1], [0.0; 3]);
+        // Tension and the hogging moment at the root.
+        assert_relative_eq!(
+            solution.member_forces[0][..],
+            [-3.0, 2.0, 8.0, 3.0, -2.0, 0.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            model.internal_forces(&solution, 0, 0.0)[..],
+            [3.0, 2.0, -8.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn fixed_beam_uniform_load() {
+        let mut model = cantilever(6.0);
+        model.add_support(Support {
+            node: 1,
+            fixed: [true; 3],
+        });
+        model.add_load(Load::Member {
+            member: 0,
+            load: [0.0, -2.0],
+        });
+        let solution = model.solve().unwrap();
+        assert_eq!(solution.diagnostics, None);
+        assert_relative_eq!(
+            solution.reactions[0][..],
+            [0.0, 6.0, 6.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            solution.reactions[1][..],
+            [0.0, 6.0, -6.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn spring() {
+        // Propped by a spring at the tip as stiff as the cantilever, 3 E I / L³, which takes half
+        // of the load.
+        let mut model = cantilever(4.0);
+        model.add_spring(Spring {
+            node: 1,
+            stiffness: [0.0, 3.0 * 200.0 * 50.0 / 64.0, 0.0],
+        });
+        model.add_load(Load::Node {
+            node: 1,
+            force: [0.0, -2.0, 0.0],
+        });
+        let solution = model.solve().unwrap();
+        assert_relative_eq!(
+            solution.displacements[1][1],
+            -64.0 / 30000.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            solution.reactions[1][..],
+            [0.0, 1.0, 0.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            solution.reactions[0][..],
+            [0.0, 1.0, 4.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        let case = LoadCase {
+            name: "tip".into(),
+            loads: model.loads.clone(),
+        };
+        let many = model.solve_static_many(&[case]).unwrap();
+        assert_relative_eq!(
+            many[0].reactions[1][..],
+            solution.reactions[1][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        // A spring restrains what no support does.
+        model.supports.clear();
+        for node in [0, 1] {
+            model.add_spring(Spring {
+                node,
+                stiffness: [1e3, 1e3, 0.0],
+            });
+        }
+        let solution = model.solve().unwrap();
+        let lifted: Float = solution.reactions.iter().map(|r| r[1]).sum();
+        assert_relative_eq!(lifted, 2.0, epsilon = TOLERANCE, max_relative = TOLERANCE);
+    }
+
+    #[test]
+    fn internal_forces() {
+        // Simply supported under a uniform load, with the moment wL²/8 at midspan.
+        let mut model = cantilever(4.0);
+        model.supports[0].fixed = [true, true, false];
+        model.add_support(Support {
+            node: 1,
+            fixed: [false, true, false],
+        });
+        model.add_load(Load::Member {
+            member: 0,
+            load: [0.0, -3.0],
+        });
+        let solution = model.solve().unwrap();
+        assert_relative_eq!(
+            model.internal_forces(&solution, 0, 0.0)[..],
+            [0.0, 6.0, 0.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            model.internal_forces(&solution, 0, 2.0)[..],
+            [0.0, 0.0, 6.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert_relative_eq!(
+            model.internal_forces(&solution, 0, 4.0)[..],
+            [0.0, -6.0, 0.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn portal() {
+        // Pinned columns with a horizontal load at the top, shared nearly evenly through the beam.
+        let mut model = Model::new();
+        let nodes = [[0.0, 0.0], [0.0, 3.0], [4.0, 3.0], [4.0, 0.0]].map(|p| model.add_node(p));
+        for pair in nodes.windows(2) {
+            model.add_member(Member {
+                nodes: [pair[0], pair[1]],
+                elasticity: 1e3,
+                area: 10.0,
+                inertia: 1.0,
+            });
+        }
+        for node in [nodes[0], nodes[3]] {
+            model.add_support(Support {
+                node,
+                fixed: [true, true, false],
+            });
+        }
+        model.add_load(Load::Node {
+            node: nodes[1],
+            force: [10.0, 0.0, 0.0],
+        });
+        let solution = model.solve().unwrap();
+        let (left, right) = (solution.reactions[0], solution.reactions[3]);
+        assert_relative_eq!(
+            [left[0] + right[0], left[1] + right[1]][..],
+            [-10.0, 0.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        // Overturning of 30 resisted by the couple of the vertical reactions over the span.
+        assert_relative_eq!(
+            right[1] * 4.0,
+            30.0,
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+        assert!((left[0] - right[0]).abs() < 0.1);
+        assert!(solution.diagnostics.unwrap().warning().is_none());
+    }
+
+    #[test]
+    fn analysis() {
+        // A chain of members longer than a chunk, assembled and recovered over several steps.
+        let mut model = Model::new();
+        let count = CHUNK + 10;
+        for i in 0..=count {
+            model.add_node([i as Float, 0.0]);
+        }
+        for i in 0..count {
+            model.add_member(Member {
+                nodes: [i, i + 1],
+                elasticity: 1.0,
+                area: 1.0,
+                inertia: 1.0,
+            });
+        }
+        model.add_support(Support {
+            node: 0,
+            fixed: [true; 3],
+        });
+        model.add_load(Load::Node {
+            node: count,
+            force: [1.0, 0.0, 0.0],
+        });
+        let mut analysis = Analysis::new(&model).unwrap();
+        let mut phases = vec![];
+        let solution = loop {
+            let progress = analysis.progress();
+            assert!(progress.done <= progress.total);
+            phases.push((progress.phase, progress.done));
+            if let Some(solution) = analysis.step().unwrap() {
+                break solution;
+            }
+        };
+        assert_eq!(
+            phases,
+            [
+                (Phase::Assembly, 0),
+                (Phase::Assembly, CHUNK),
+                (Phase::Factorization, 0),
+                (Phase::Recovery, 0),
+                (Phase::Recovery, CHUNK),
+            ]
+        );
+        assert_eq!(analysis.progress().phase, Phase::Done);
+        assert_eq!(analysis.step(), Ok(None));
+        // The same results by a single call, in other times.
+        let again = model.solve().unwrap();
+        assert_eq!(solution.displacements, again.displacements);
+        assert_eq!(solution.member_forces, again.member_forces);
+        let stats = solution.stats;
+        assert_eq!(
+            stats,
+            Stats {
+                times: stats.times,
+                ..again.stats
+            }
+        );
+        assert_eq!(stats.elements, model.members.len());
+        assert!(stats.nnz > stats.dofs && stats.factor_nnz > 0 && stats.flops > 0.0);
+        assert!(stats.memory > stats.nnz * size_of::<Float>());
+        assert!(stats.times.assembly >= 0.0 && stats.times.recovery >= 0.0);
+        assert_relative_eq!(
+            solution.displacements[count][..],
+            [count as Float, 0.0, 0.0][..],
+            epsilon = TOLERANCE,
+            max_relative = TOLERANCE
+        );
+    }
+
+    #[test]
+    fn requirements() {
+        // Frame of 6 bays and 8 storeys, and a cantilever restrained at both ends.
+        let mut grid = Model::new();
+        let (bays, storeys) = (6, 8);
+        for j in 0..=storeys {
+            for i in 0..=bays {
+                grid.add_node([i as Float * 4.0, j as Float * 3.0]);
+            }
+        }
+        let node = |i: usize, j: usize| j * (bays + 1) + i;
+        for j in 0..=storeys {
+            for i in 0..=bays {
+                let mut connect = |other| {
+                    grid.add_member(member([node(i, j), other]));
+                };
+                if i < bays && j > 0 {
+                    connect(node(i + 1, j));
+                }
+                if j < storeys {
+                    connect(node(i, j + 1));
+                }
+            }
+            grid.add_load(Load::Node {
+                node: node(0, j),
+                force: [1.0, 0.0, 0.0],
+            });
+        }
+        for i in 0..=bays {
+            grid.add_support(Support {
+                node: i,
+                fixed: [true; 3],
+            });
+        }
+        let mut fixed = cantilever(2.0);
+        fixed.add_support(Support {
+            node: 1,
+            fixed: [true; 3],
+        });
+        for model in [grid, cantilever(2.0), fixed] {
+            let requirements = model.estimate_requirements().unwrap();
+            let stats = model.solve().unwrap().stats;
+            assert_eq!(
+                requirements,
+                Requirements {
+                    dofs: stats.dofs,
+                    nnz: stats.nnz,
+                    storage: requirements.storage,
+                    factor_nnz: stats.factor_nnz,
+                    flops: stats.flops,
+                    memory: stats.memory,
+                }
+            );
+            assert_eq!(requirements.storage.is_some(), stats.dofs > 0);
+        }
+        let mut invalid = cantilever(2.0);
+        invalid.members[0].nodes[1] = 5;
+        assert_eq!(
+            invalid.estimate_requirements(),
+            Err(ModelError::InvalidNode { node: 5 })
+        );
+    }
+
+    #[test]
+    fn cancel() {
+        let model = cantilever(2.0);
+        let cancel = CancelToken::new();
+        let mut analysis = Analysis::with_cancel(&model, cancel.clone()).unwrap();
+        assert_eq!(analysis.step(), Ok(None));
+        cancel.cancel();
+        let cancelled = Err(ModelError::Cancelled {
+            progress: Progress {
+                phase: Phase::Factorization,
+                done: 0,
+                total: 1,
+            },
+        });
+        assert_eq!(analysis.step(), cancelled);
+        assert_eq!(analysis.step(), cancelled);
+        let error = model.solve_with_cancel(&cancel).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "analysis cancelled during assembly after 0 of 1"
+        );
+    }
+
+    #[test]
+    fn solve_static_many() {
+        let mut model = cantilever(4.0);
+        model.add_load(Load::Node {
+            node: 1,
+            force: [1.0, 1.0, 1.0],
+        });
+        let cases: Vec<_> = (0..5)
+            .map(|i| LoadCase {
+                name: format!("case {i}"),
+                loads: vec![
+                    Load::Node {
+                        node: 1,
+                        force: [i as Float, -2.0, 0.5],
+                    },
+                    Load::Member {
+                        member: 0,
+                        load: [0.0, -(i as Float)],
+                    },
+                ],
+            })
+            .collect();
+        let solutions = model.solve_static_many(&cases).unwrap();
+        assert_eq!(solutions.len(), cases.len());
+        // As analyzed one by one, whatever the loads of the model and the size of the blocks.
+        for (case, solution) in cases.iter().zip(&solutions) {
+            model.loads = case.loads.clone();
+            let expected = model.solve().unwrap();
+            assert_relative_eq!(
+                solution.displacements[1][..],
+                expected.displacements[1][..],
+                epsilon = TOLERANCE,
+                max_relative = TOLERANCE
+            );
+            assert_relative_eq!(
+                solution.reactions[0][..],
+                expected.reactions[0][..],
+                epsilon = TOLERANCE,
+                max_relative = TOLERANCE
+            );
+            assert_relative_eq!(
+                solution.member_forces[0][..],
+                expected.member_forces[0][..],
+                epsilon = TOLERANCE,
+                max_relative = TOLERANCE
+            );
+            assert_eq!(solution.diagnostics, expected.diagnostics);
+            assert_eq!(solution.stats.factor_nnz, expected.stats.factor_nnz);
+        }
+        for block in [0, 1, 2, 5] {
+            let blocked = model.solve_static_many_with_block(&cases, block).unwrap();
+            for (a, b) in blocked.iter().zip(&solutions) {
+                assert_eq!(
+                    (&a.displacements, &a.reactions, &a.member_forces),
+                    (&b.displacements, &b.reactions, &b.member_forces)
+                );
+            }
+        }
+        assert_eq!(model.solve_static_many(&[]), Ok(vec![]));
+        let invalid = LoadCase {
+            name: "invalid".into(),
+            loads: vec![Load::Member {
+                member: 1,
+                load: [0.0, 1.0],
+            }],
+        };
+        assert_eq!(
+            model.solve_static_many(&[invalid]),
+            Err(ModelError::InvalidMember { member: 1 })
+        );
+        model.supports[0].fixed = [true, true, false];
+        assert_eq!(model.solve_static_many(&cases), Err(ModelError::Unstable));
+    }
+
+    #[test]
+    fn unstable() {
+        let mut model = cantilever(2.0);
+        model.supports[0].fixed = [true, true, false];
+        assert_eq!(model.solve(), Err(ModelError::Unstable));
+    }
+}
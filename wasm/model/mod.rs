@@ -1 +1,5 @@
 pub mod beam;
+pub mod frame;
+pub mod soil;
+pub mod tributary;
+pub mod wind;
@@ -0,0 +1,272 @@
+//! Springs of the ground under a plane frame in elevation, with the x axis horizontal and the y
+//! axis up: Winkler foundations along footing beams and piles, from a modulus of subgrade
+//! reaction or a profile of soil layers, and the springs of pile caps from the stiffness of their
+//! piles.
+
+use super::frame::{Model, Spring};
+use crate::Float;
+use std::collections::BTreeMap;
+
+/// Layer of a [`SoilProfile`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Layer {
+    pub thickness: Float,
+    /// Modulus of subgrade reaction, the pressure per displacement.
+    pub modulus: Float,
+}
+
+/// Layers of soil from the ground surface down, the last extending below.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct SoilProfile {
+    /// y of the ground surface.
+    pub surface: Float,
+    pub layers: Vec<Layer>,
+}
+
+impl SoilProfile {
+    /// Modulus of subgrade reaction at the height `y`, zero above the surface.
+    pub fn modulus(&self, y: Float) -> Float {
+        let mut top = self.surface;
+        if y > top {
+            return 0.0;
+        }
+        for layer in &self.layers {
+            top -= layer.thickness;
+            if y > top {
+                return layer.modulus;
+            }
+        }
+        self.layers.last().map_or(0.0, |layer| layer.modulus)
+    }
+
+    /// Springs of the layers along the `members` of the `model` of the `width`, as a pile of
+    /// that diameter, lumped as [`subgrade`] but with the modulus of the soil at each depth.
+    pub fn springs(&self, model: &Model, members: &[usize], width: Float) -> Vec<Spring> {
+        let levels: Vec<_> = std::iter::once(self.surface)
+            .chain(self.layers.iter().scan(self.surface, |y, layer| {
+                *y -= layer.thickness;
+                Some(*y)
+            }))
+            .collect();
+        winkler(model, members, &levels, |y| self.modulus(y) * width)
+    }
+}
+
+/// Springs along the footing `members` of the `model` of the `width` on a soil of the modulus of
+/// subgrade reaction `modulus`, one per node. The foundation of each member is lumped at its ends
+/// as the consistent loads of a uniform pressure, half to each, and acts normal to the member,
+/// resolved along x and y by the squares of its direction cosines. The beam on the springs
+/// approaches the beam on the elastic foundation where the members are short compared with its
+/// characteristic length `(4 E I / (k B))^(1/4)`.
+pub fn subgrade(model: &Model, members: &[usize], modulus: Float, width: Float) -> Vec<Spring> {
+    winkler(model, members, &[], |_| modulus * width)
+}
+
+/// Springs lumping a Winkler foundation of the stiffness per length `stiffness(y)` at the height
+/// `y` along the `members`, constant between the heights `levels`.
+fn winkler(
+    model: &Model,
+    members: &[usize],
+    levels: &[Float],
+    stiffness: impl Fn(Float) -> Float,
+) -> Vec<Spring> {
+    let mut nodes = BTreeMap::new();
+    for &member in members {
+        let ends = model.members[member].nodes;
+        let [p, q] = ends.map(|n| model.nodes[n].position);
+        let length = model.length(member);
+        let (cos, sin) = ((q[0] - p[0]) / length, (q[1] - p[1]) / length);
+        // Fractions of the length where the stiffness may change.
+        let mut cuts: Vec<_> = (levels.iter())
+            .map(|&y| (y - p[1]) / (q[1] - p[1]))
+            .filter(|&t| t > 0.0 && t < 1.0)
+            .chain([0.0, 1.0])
+            .collect();
+        cuts.sort_by(Float::total_cmp);
+        let mut lumped = [0.0; 2];
+        for t in cuts.windows(2) {
+            let k = stiffness(p[1] + (q[1] - p[1]) * (t[0] + t[1]) / 2.0) * length;
+            let second = (t[1] * t[1] - t[0] * t[0]) / 2.0;
+            lumped[0] += k * (t[1] - t[0] - second);
+            lumped[1] += k * second;
+        }
+        for (node, k) in ends.into_iter().zip(lumped) {
+            let spring = nodes.entry(node).or_insert([0.0; 3]);
+            spring[0] += k * sin * sin;
+            spring[1] += k * cos * cos;
+        }
+    }
+    (nodes.into_iter())
+        .filter(|(_, stiffness)| *stiffness != [0.0; 3])
+        .map(|(node, stiffness)| Spring { node, stiffness })
+        .collect()
+}
+
+/// Stiffness of the head of a pile, as tabulated for its type or found by a load test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Pile {
+    /// Distance along x from the node of the cap.
+    pub offset: Float,
+    pub axial: Float,
+    pub lateral: Float,
+    /// Of the rotation of the head, zero if pinned to the cap.
+    pub rotational: Float,
+}
+
+/// Spring of the `node` of a rigid cap on the `piles`, of the sums of their lateral and axial
+/// stiffnesses and of their rotational ones with the axial by the squares of the offsets. The
+/// spring does not couple the settlement and the rotation, so the node is to be at the centroid
+/// of the axial stiffness of the piles.
+pub fn pile_cap(node: usize, piles: &[Pile]) -> Spring {
+    let stiffness = piles.iter().fold([0.0; 3], |[x, y, r], pile| {
+        [
+            x + pile.lateral,
+            y + pile.axial,
+            r + pile.rotational + pile.axial * pile.offset * pile.offset,
+        ]
+    });
+    Spring { node, stiffness }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::frame::{Load, Member, Support};
+    use super::*;
+    use approx::{assert_abs_diff_eq, assert_relative_eq};
+
+    /// Footing of `n` members of the `length` each along x, held along x at its left end.
+    fn footing(n: usize, length: Float) -> Model {
+        let mut model = Model::new();
+        model.extend_nodes((0..=n).map(|i| [length * i as Float, 0.0]));
+        model.extend_members((0..n).map(|i| Member {
+            nodes: [i, i + 1],
+            elasticity: 2.5e7,
+            area: 0.6,
+            inertia: 0.018,
+        }));
+        model.add_support(Support {
+            node: 0,
+            fixed: [true, false, false],
+        });
+        model
+    }
+
+    #[test]
+    fn uniform() {
+        let mut model = footing(4, 1.5);
+        let members: Vec<_> = (0..4).collect();
+        model.springs = subgrade(&model, &members, 2e4, 1.2);
+        assert_eq!(model.springs.len(), 5);
+        assert_eq!(model.springs[0].stiffness, [0.0, 2e4 * 1.2 * 0.75, 0.0]);
+        assert_eq!(model.springs[2].stiffness, [0.0, 2e4 * 1.2 * 1.5, 0.0]);
+        // Under loads in proportion to the springs, the footing settles uniformly by q / k B.
+        let settlement = 30.0 / 2.4e4;
+        model.loads = (model.springs.iter())
+            .map(|spring| Load::Node {
+                node: spring.node,
+                force: [0.0, -spring.stiffness[1] * settlement, 0.0],
+            })
+            .collect();
+        let solution = model.solve().unwrap();
+        let epsilon = settlement * Float::EPSILON * 1e2;
+        for u in &solution.displacements {
+            assert_relative_eq!(u[1], -settlement, max_relative = Float::EPSILON * 1e2);
+            // Without rotating over the members of 1.5 by more than the error of the settlement.
+            assert_abs_diff_eq!(u[2] * 1.5, 0.0, epsilon = epsilon);
+        }
+        let lifted: Float = solution.reactions.iter().map(|r| r[1]).sum();
+        assert_relative_eq!(lifted, 30.0 * 6.0, max_relative = Float::EPSILON * 1e2);
+    }
+
+    #[test]
+    fn point_load() {
+        // A long beam on an elastic foundation under a point load at the middle settles there
+        // by P λ / 2 k, with λ = (k / 4 E I)^(1/4), within the error of lumping the foundation.
+        // Members of 1/5 of 1 / λ keep the springs stiff enough against the bending for f32.
+        let mut model = footing(40, 0.5);
+        let members: Vec<_> = (0..40).collect();
+        model.springs = subgrade(&model, &members, 5e4, 1.0);
+        model.add_load(Load::Node {
+            node: 20,
+            force: [0.0, -100.0, 0.0],
+        });
+        let solution = model.solve().unwrap();
+        let lambda = (5e4 as Float / (4.0 * 2.5e7 * 0.018)).powf(0.25);
+        let expected = -100.0 * lambda / (2.0 * 5e4);
+        let settlement = solution.displacements[20][1];
+        assert_relative_eq!(settlement, expected, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn profile() {
+        // A pile 6 long in three members below a cap 1 above the ground.
+        let mut model = Model::new();
+        model.extend_nodes([1.0, 0.0, -2.0, -4.0, -6.0].map(|y| [0.0, y]));
+        model.extend_members((0..4).map(|i| Member {
+            nodes: [i, i + 1],
+            elasticity: 2.5e7,
+            area: 0.2,
+            inertia: 3e-3,
+        }));
+        let profile = SoilProfile {
+            surface: 0.0,
+            layers: vec![
+                Layer {
+                    thickness: 3.0,
+                    modulus: 1e3,
+                },
+                Layer {
+                    thickness: 1.0,
+                    modulus: 5e3,
+                },
+            ],
+        };
+        assert_eq!(profile.modulus(0.5), 0.0);
+        assert_eq!(profile.modulus(-1.0), 1e3);
+        assert_eq!(profile.modulus(-10.0), 5e3);
+        let springs = profile.springs(&model, &[0, 1, 2, 3], 0.5);
+        let nodes: Vec<_> = springs.iter().map(|s| s.node).collect();
+        assert_eq!(nodes, [1, 2, 3, 4]);
+        let lateral: Vec<_> = springs.iter().map(|s| s.stiffness[0]).collect();
+        let max_relative = Float::EPSILON * 1e1;
+        assert_relative_eq!(lateral[0], 500.0, max_relative = max_relative);
+        // Half in each layer, the stiffer at the lower end.
+        assert_relative_eq!(
+            lateral[1],
+            500.0 + 375.0 + 625.0,
+            max_relative = max_relative
+        );
+        let total: Float = lateral.iter().sum();
+        assert_relative_eq!(
+            total,
+            0.5 * (3.0 * 1e3 + 3.0 * 5e3),
+            max_relative = max_relative
+        );
+        assert!(springs.iter().all(|s| s.stiffness[1] == 0.0));
+    }
+
+    #[test]
+    fn cap() {
+        let piles = [-1.0, 1.0].map(|offset| Pile {
+            offset,
+            axial: 100.0,
+            lateral: 10.0,
+            rotational: 5.0,
+        });
+        let spring = pile_cap(3, &piles);
+        assert_eq!(spring.node, 3);
+        assert_eq!(spring.stiffness, [20.0, 200.0, 210.0]);
+    }
+}
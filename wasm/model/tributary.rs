@@ -0,0 +1,132 @@
+use crate::{
+    geometry::{clip::clip_by, polyline::signed_area},
+    math::complex::Complex,
+    Float,
+};
+
+/// Share of a floor panel carried by the beam along one of its edges, found by [`tributary`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tributary {
+    /// Index of the edge, from the vertex of the same index to the next one.
+    pub edge: usize,
+    /// The area carried, a convex polygon empty if the beam carries nothing.
+    pub area: Vec<Complex<Float>>,
+    /// Distributed load on the beam as pairs of the distance along the edge and the load per
+    /// length, linear between them.
+    pub load: Vec<[Float; 2]>,
+}
+
+/// Splits the convex floor panel with the vertices `panel` among the beams along the `edges`,
+/// each point going to the nearest beam, so the corners are split along their bisectors as by
+/// the usual 45° rule. Edges without beams carry nothing. Each share carries the `pressure`
+/// over its area as a distributed load on its beam.
+pub fn tributary(panel: &[Complex<Float>], edges: &[usize], pressure: Float) -> Vec<Tributary> {
+    let n = panel.len();
+    let sign = signed_area(panel).signum();
+    let beam = |i: usize| {
+        let (a, b) = (panel[i], panel[(i + 1) % n]);
+        (a, (b - a) / (b - a).abs(), (b - a).abs())
+    };
+    // Distance inside the panel from the line of an edge.
+    let distance = |i: usize, p: Complex<Float>| {
+        let (a, u, _) = beam(i);
+        (u.conj() * (p - a)).im * sign
+    };
+    edges
+        .iter()
+        .map(|&i| {
+            let area = edges
+                .iter()
+                .filter(|&&j| j != i)
+                .fold(panel.to_vec(), |area, &j| {
+                    clip_by(&area, |p| distance(i, p) - distance(j, p))
+                });
+            let (a, u, length) = beam(i);
+            let along = |p: Complex<Float>| ((p - a) * u.conj()).re.clamp(0.0, length);
+            let mut positions: Vec<_> = area.iter().map(|&p| along(p)).collect();
+            positions.sort_by(Float::total_cmp);
+            positions.dedup();
+            let m = area.len();
+            // The far side of the convex area at each position.
+            let depth = |s: Float| {
+                (0..m)
+                    .filter_map(|k| {
+                        let (p, q) = (area[k], area[(k + 1) % m]);
+                        let (sp, sq) = (along(p), along(q));
+                        let (dp, dq) = (distance(i, p), distance(i, q));
+                        if sp == sq {
+                            (sp == s).then_some(dp.max(dq))
+                        } else if sp.min(sq) <= s && s <= sp.max(sq) {
+                            Some(dp + (dq - dp) * (s - sp) / (sq - sp))
+                        } else {
+                            None
+                        }
+                    })
+                    .fold(0.0, Float::max)
+            };
+            let load = positions
+                .into_iter()
+                .map(|s| [s, depth(s) * pressure])
+                .collect();
+            Tributary {
+                edge: i,
+                area,
+                load,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle() -> Vec<Complex<Float>> {
+        vec![
+            Complex::new(0.0, 0.0),
+            Complex::new(6.0, 0.0),
+            Complex::new(6.0, 4.0),
+            Complex::new(0.0, 4.0),
+        ]
+    }
+
+    /// Total of a piecewise linear load.
+    fn total(load: &[[Float; 2]]) -> Float {
+        load.windows(2)
+            .map(|w| (w[1][0] - w[0][0]) * (w[0][1] + w[1][1]) / 2.0)
+            .sum()
+    }
+
+    #[test]
+    fn two_way() {
+        let shares = tributary(&rectangle(), &[0, 1, 2, 3], 5.0);
+        // Trapezoids on the long sides and triangles on the short ones.
+        let areas: Vec<_> = shares.iter().map(|t| signed_area(&t.area)).collect();
+        assert_eq!(areas, [8.0, 4.0, 8.0, 4.0]);
+        assert_eq!(
+            shares[0].load,
+            [[0.0, 0.0], [2.0, 10.0], [4.0, 10.0], [6.0, 0.0]]
+        );
+        assert_eq!(shares[1].load, [[0.0, 0.0], [2.0, 10.0], [4.0, 0.0]]);
+        for share in &shares {
+            let area = signed_area(&share.area);
+            assert!((total(&share.load) - area * 5.0).abs() < Float::EPSILON * 1e2);
+        }
+    }
+
+    #[test]
+    fn one_way() {
+        // Clockwise, with the beams on the long sides only.
+        let mut panel = rectangle();
+        panel.reverse();
+        let shares = tributary(&panel, &[0, 2], 1.0);
+        assert_eq!(shares[0].edge, 0);
+        assert_eq!(signed_area(&shares[0].area), -12.0);
+        assert_eq!(shares[0].load, [[0.0, 2.0], [6.0, 2.0]]);
+        assert_eq!(shares[1].load, [[0.0, 2.0], [6.0, 2.0]]);
+        // A beam on one side carries it all.
+        let shares = tributary(&panel, &[1], 1.0);
+        assert_eq!(shares[0].load, [[0.0, 6.0], [4.0, 6.0]]);
+    }
+}
@@ -0,0 +1,340 @@
+//! Wind pressures of EN 1991-1-4 on buildings of rectangular plan with vertical walls and flat
+//! roofs with sharp eaves, from the peak velocity pressure of flat terrain up to 200 m.
+//!
+//! Pressures are in the units of the air density times the square of the velocity, kN/m² with the
+//! default density in t/m³ and velocities in m/s.
+
+use super::{Envelope, Surface};
+use crate::{
+    model::frame::{Load, Model},
+    Float,
+};
+
+/// Terrain category of Table 4.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Terrain {
+    /// Category 0, sea or coastal area exposed to the open sea.
+    Sea,
+    /// Category I, lakes or flat areas with negligible vegetation and no obstacles.
+    Lakes,
+    /// Category II, low vegetation and isolated obstacles at least 20 times their height apart.
+    Open,
+    /// Category III, regular cover of vegetation or buildings, villages and suburbs.
+    Suburban,
+    /// Category IV, at least 15% of the surface covered by buildings over 15 m high.
+    Urban,
+}
+
+impl Terrain {
+    /// Roughness length z₀ and minimum height z_min.
+    fn parameters(self) -> (Float, Float) {
+        match self {
+            Terrain::Sea => (0.003, 1.0),
+            Terrain::Lakes => (0.01, 1.0),
+            Terrain::Open => (0.05, 2.0),
+            Terrain::Suburban => (0.3, 5.0),
+            Terrain::Urban => (1.0, 10.0),
+        }
+    }
+}
+
+/// Wind at the site of a building and the coefficients of its pressures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub struct Wind {
+    /// Basic wind velocity v_b, the fundamental value with the directional and seasonal factors.
+    pub basic_velocity: Float,
+    pub terrain: Terrain,
+    /// Orography factor c₀.
+    pub orography: Float,
+    /// Turbulence factor k_I.
+    pub turbulence: Float,
+    /// Air density ρ.
+    pub density: Float,
+    /// Structural factor c_s c_d, applied to the external pressures.
+    pub structural: Float,
+    /// Internal pressure coefficient c_pi, typically +0.2 or -0.3 without a dominant face.
+    pub internal: Float,
+}
+
+impl Default for Wind {
+    /// Open terrain at 25 m/s with no internal pressure, in kN/m².
+    fn default() -> Self {
+        Self {
+            basic_velocity: 25.0,
+            terrain: Terrain::Open,
+            orography: 1.0,
+            turbulence: 1.0,
+            density: 1.25e-3,
+            structural: 1.0,
+            internal: 0.0,
+        }
+    }
+}
+
+/// External pressure coefficients c_pe,10 of the windward and leeward walls, zones D and E of
+/// Table 7.1, at the ratios h/d of the height to the depth of the building.
+const WALLS: [(Float, Float, Float); 3] = [(0.25, 0.7, -0.3), (1.0, 0.8, -0.5), (5.0, 0.8, -0.7)];
+
+/// External pressure coefficients c_pe,10 of a flat roof with sharp eaves in the zones G, H and I
+/// of Table 7.2, which start from the windward edge at the fractions 0, 1/10 and 1/2 of e.
+const ROOF: [(Float, Float); 3] = [(0.0, -1.2), (0.1, -0.7), (0.5, -0.2)];
+
+impl Wind {
+    /// Peak velocity pressure q_p at the height `z` above the ground, by (4.8).
+    pub fn peak_pressure(&self, z: Float) -> Float {
+        let (z0, min) = self.terrain.parameters();
+        let log = (z.max(min) / z0).ln();
+        let roughness = 0.19 * (z0 / 0.05).powf(0.07) * log;
+        let mean = roughness * self.orography * self.basic_velocity;
+        let intensity = self.turbulence / (self.orography * log);
+        (1.0 + 7.0 * intensity) * 0.5 * self.density * mean * mean
+    }
+
+    /// Uniform loads on the members of the `envelope` of the `model` under the net pressures of
+    /// this wind, as [`Envelope::loads`].
+    ///
+    /// The windward wall is divided as Figure 7.4, each member at the reference height of its
+    /// top within the middle strips, and the other surfaces are at the height of the building.
+    /// The walls are scaled by the lack of correlation of (7.2.2(3)), and the roof carries the
+    /// zones across the middle of the breadth, averaged over each member.
+    pub fn loads(&self, model: &Model, envelope: &Envelope) -> Vec<Load> {
+        let extent = envelope.extent(model);
+        let (height, breadth) = (extent.height, envelope.breadth);
+        let ratio = height / extent.depth;
+        let interpolate = |points: &[(Float, Float)]| {
+            let r = ratio.clamp(points[0].0, points[points.len() - 1].0);
+            let i = points[1..]
+                .iter()
+                .position(|&(x, _)| r <= x)
+                .unwrap_or(points.len() - 2);
+            let ((x0, y0), (x1, y1)) = (points[i], points[i + 1]);
+            y0 + (y1 - y0) * (r - x0) / (x1 - x0)
+        };
+        let correlation = interpolate(&[(1.0, 0.85), (5.0, 1.0)]);
+        let windward = interpolate(&WALLS.map(|(r, d, _)| (r, d)));
+        let leeward = interpolate(&WALLS.map(|(r, _, e)| (r, e)));
+        let e = breadth.min(2.0 * height);
+        // Mean coefficient of the roof from the distances a to b from the windward edge.
+        let roof = |a: Float, b: Float| {
+            let (a, b) = (a.min(b), a.max(b));
+            if b == a {
+                return ROOF.iter().rev().find(|(s, _)| s * e <= a).unwrap().1;
+            }
+            let bounds = ROOF.map(|(s, _)| s * e);
+            (0..ROOF.len())
+                .map(|i| {
+                    let end = bounds.get(i + 1).copied().unwrap_or(Float::INFINITY);
+                    let overlap = b.min(end) - a.max(bounds[i]);
+                    ROOF[i].1 * overlap.max(0.0)
+                })
+                .sum::<Float>()
+                / (b - a)
+        };
+        let top = self.peak_pressure(height);
+        let internal = top * self.internal;
+        let position = |n: usize| model.nodes[n].position;
+        envelope.loads(model, |surface, member| {
+            let [p, q] = model.members[member].nodes.map(position);
+            let external = match surface {
+                Surface::Windward => {
+                    let z = p[1].max(q[1]) - extent.base;
+                    let reference = if height <= breadth || z > breadth.max(height - breadth) {
+                        height
+                    } else {
+                        z.max(breadth)
+                    };
+                    self.peak_pressure(reference) * windward * correlation
+                }
+                Surface::Leeward => top * leeward * correlation,
+                Surface::Roof => {
+                    let distance = |x: Float| (x - extent.windward) * extent.direction;
+                    top * roof(distance(p[0]), distance(q[0]))
+                }
+            };
+            self.structural * external - internal
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::frame::generators::{Grid, Portal};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn peak_pressure() {
+        let wind = Wind {
+            basic_velocity: 26.0,
+            density: 1.25,
+            ..Wind::default()
+        };
+        // Exposure factor of Figure 4.2 at 10 m in the category II, about 2.35.
+        let basic = 0.5 * 1.25 * 26.0 * 26.0;
+        let exposure = wind.peak_pressure(10.0) / basic;
+        assert_relative_eq!(exposure, 2.3523, max_relative = 1e-4);
+        // Constant below the minimum height and rougher terrain lower.
+        assert_eq!(wind.peak_pressure(0.5), wind.peak_pressure(2.0));
+        let urban = Wind {
+            terrain: Terrain::Urban,
+            ..wind
+        };
+        assert!(urban.peak_pressure(20.0) < wind.peak_pressure(20.0));
+    }
+
+    #[test]
+    fn portal() {
+        // A shed 6 high and 12 deep, as wide across the wind as it is deep.
+        let model = Portal {
+            span: 12.0,
+            height: 6.0,
+            ..Portal::default()
+        }
+        .generate()
+        .model;
+        let envelope = Envelope::of(&model, 5.0, 12.0);
+        assert_eq!(
+            (&envelope.windward, &envelope.leeward, &envelope.roof),
+            (&vec![0], &vec![1], &vec![2])
+        );
+        let wind = Wind::default();
+        let q = wind.peak_pressure(6.0);
+        let loads = wind.loads(&model, &envelope);
+        let load = |i: usize| match loads[i] {
+            Load::Member { load, .. } => load,
+            Load::Node { .. } => unreachable!(),
+        };
+        // h/d = 0.5, between the rows of Table 7.1, with the correlation at 0.85.
+        let d = 0.7 + 0.1 / 3.0;
+        let e = -0.3 - 0.2 / 3.0;
+        assert_relative_eq!(
+            load(0)[0],
+            q * d * 0.85 * 5.0,
+            max_relative = Float::EPSILON * 1e2
+        );
+        assert_eq!(load(0)[1], 0.0);
+        assert_relative_eq!(
+            load(1)[0],
+            -q * e * 0.85 * 5.0,
+            max_relative = Float::EPSILON * 1e2
+        );
+        // e = 12, so G over 1.2, H over 4.8 and I over 6 of the 12 of the roof.
+        let c = (-1.2 * 1.2 - 0.7 * 4.8 - 0.2 * 6.0) / 12.0;
+        assert_relative_eq!(
+            load(2)[1],
+            -q * c * 5.0,
+            max_relative = Float::EPSILON * 1e2
+        );
+        assert!(load(2)[1] > 0.0);
+        // Internal suction pushes the walls and the roof out.
+        let sucked = Wind {
+            internal: -0.3,
+            ..wind
+        }
+        .loads(&model, &envelope);
+        let Load::Member { load: roof, .. } = sucked[2] else {
+            unreachable!()
+        };
+        assert_relative_eq!(
+            roof[1],
+            -q * (c + 0.3) * 5.0,
+            max_relative = Float::EPSILON * 1e2
+        );
+        // Wind from the right mirrors the horizontal loads.
+        let mirrored = Envelope {
+            windward: envelope.leeward.clone(),
+            leeward: envelope.windward.clone(),
+            ..envelope
+        };
+        let Load::Member { load: right, .. } = wind.loads(&model, &mirrored)[0] else {
+            unreachable!()
+        };
+        assert_relative_eq!(right[0], -load(0)[0], max_relative = Float::EPSILON * 1e2);
+    }
+
+    #[test]
+    fn tall() {
+        // Tall and narrow, 17.5 high and 6 across the wind, with the windward strips.
+        let model = Grid {
+            bays: 1,
+            ..Grid::default()
+        }
+        .generate()
+        .model;
+        let envelope = Envelope::of(&model, 4.0, 6.0);
+        assert_eq!(envelope.windward, [0, 1, 2, 3, 4]);
+        let wind = Wind::default();
+        let loads = wind.loads(&model, &envelope);
+        let w: Vec<_> = loads[..5]
+            .iter()
+            .map(|load| match load {
+                Load::Member { load, .. } => load[0],
+                Load::Node { .. } => unreachable!(),
+            })
+            .collect();
+        // The lowest at q(b) up to b, the strips between at their tops and the top b at q(h).
+        let ratio = w[0] / wind.peak_pressure(6.0);
+        assert_relative_eq!(
+            w[1] / wind.peak_pressure(7.0),
+            ratio,
+            max_relative = Float::EPSILON * 1e2
+        );
+        assert_relative_eq!(
+            w[2] / wind.peak_pressure(10.5),
+            ratio,
+            max_relative = Float::EPSILON * 1e2
+        );
+        assert_relative_eq!(
+            w[3] / wind.peak_pressure(17.5),
+            ratio,
+            max_relative = Float::EPSILON * 1e2
+        );
+        assert_eq!(w[3], w[4]);
+        assert!(w.windows(2).all(|w| w[0] <= w[1]));
+        let solution = Model {
+            loads,
+            ..model.clone()
+        }
+        .solve()
+        .unwrap();
+        assert!(solution.displacements[5][0] > 0.0);
+    }
+
+    #[test]
+    fn intermediate() {
+        // 15 high and 10 across the wind, between b and 2b: q(b) up to b and q(h) above.
+        let model = Grid {
+            bays: 1,
+            height: 3.0,
+            ..Grid::default()
+        }
+        .generate()
+        .model;
+        let envelope = Envelope::of(&model, 4.0, 10.0);
+        assert_eq!(envelope.windward, [0, 1, 2, 3, 4]);
+        let wind = Wind::default();
+        let w: Vec<_> = wind.loads(&model, &envelope)[..5]
+            .iter()
+            .map(|load| match load {
+                Load::Member { load, .. } => load[0],
+                Load::Node { .. } => unreachable!(),
+            })
+            .collect();
+        assert_eq!(w[0], w[1]);
+        assert_eq!(w[1], w[2]);
+        assert_eq!(w[3], w[4]);
+        assert_relative_eq!(
+            w[3] / w[2],
+            wind.peak_pressure(15.0) / wind.peak_pressure(10.0),
+            max_relative = Float::EPSILON * 1e2
+        );
+    }
+}
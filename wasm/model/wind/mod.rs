@@ -0,0 +1,131 @@
+//! Wind loads on the envelope of a building carried by a plane frame in elevation, with the x
+//! axis horizontal and the y axis up, from the pressures of a code in its own module.
+
+pub mod en_1991_1_4;
+
+use super::frame::{Load, Model};
+use crate::Float;
+
+/// Part of the envelope of a building.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+pub enum Surface {
+    /// Wall facing the wind.
+    Windward,
+    /// Wall opposite the windward one.
+    Leeward,
+    Roof,
+}
+
+/// Members of a frame along the envelope of a building with a flat roof, which carry the wind on
+/// the width of the envelope between the frames.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize, tsify::Tsify)
+)]
+#[cfg_attr(feature = "serde", tsify(rename = "WindEnvelope"))]
+pub struct Envelope {
+    pub windward: Vec<usize>,
+    pub leeward: Vec<usize>,
+    pub roof: Vec<usize>,
+    /// Spacing of the frames, the width of the envelope carried by this one.
+    pub spacing: Float,
+    /// Dimension of the building across the wind, normal to the plane of the frame.
+    pub breadth: Float,
+}
+
+impl Envelope {
+    /// Envelope of the `model` with the wind from the left: its vertical members at the least x
+    /// facing the wind, those at the greatest x leeward, and the roof of the members with both
+    /// nodes at the greatest y. Swapping the walls gives the wind from the right.
+    pub fn of(model: &Model, spacing: Float, breadth: Float) -> Self {
+        let positions =
+            |member: usize| model.members[member].nodes.map(|n| model.nodes[n].position);
+        let (left, right, top) = model.nodes.iter().fold(
+            (Float::INFINITY, Float::NEG_INFINITY, Float::NEG_INFINITY),
+            |(left, right, top), node| {
+                let [x, y] = node.position;
+                (left.min(x), right.max(x), top.max(y))
+            },
+        );
+        let members = |f: &dyn Fn([[Float; 2]; 2]) -> bool| {
+            (0..model.members.len())
+                .filter(|&i| f(positions(i)))
+                .collect()
+        };
+        Self {
+            windward: members(&|[a, b]| a[0] == left && b[0] == left),
+            leeward: members(&|[a, b]| a[0] == right && b[0] == right),
+            roof: members(&|[a, b]| a[1] == top && b[1] == top),
+            spacing,
+            breadth,
+        }
+    }
+
+    /// Extent of the envelope of the `model`.
+    fn extent(&self, model: &Model) -> Extent {
+        let positions = |members: &[usize]| -> Vec<[Float; 2]> {
+            (members.iter().flat_map(|&i| model.members[i].nodes))
+                .map(|n| model.nodes[n].position)
+                .collect()
+        };
+        let mean = |positions: &[[Float; 2]]| {
+            positions.iter().map(|[x, _]| x).sum::<Float>() / positions.len().max(1) as Float
+        };
+        let (windward, leeward) = (positions(&self.windward), positions(&self.leeward));
+        let roof = positions(&self.roof);
+        let walls = windward.iter().chain(&leeward);
+        let base = walls.map(|[_, y]| *y).fold(Float::INFINITY, Float::min);
+        let top = (windward.iter().chain(&leeward).chain(&roof))
+            .map(|[_, y]| *y)
+            .fold(Float::NEG_INFINITY, Float::max);
+        let (start, end) = (mean(&windward), mean(&leeward));
+        Extent {
+            depth: (end - start).abs(),
+            height: top - base,
+            base,
+            windward: start,
+            direction: if end < start { -1.0 } else { 1.0 },
+        }
+    }
+
+    /// Uniform loads on the members of the envelope of the `model` under the net pressure
+    /// `pressure(surface, member)` on them, positive toward the surface.
+    pub fn loads(&self, model: &Model, pressure: impl Fn(Surface, usize) -> Float) -> Vec<Load> {
+        let direction = self.extent(model).direction;
+        let surfaces = [
+            (Surface::Windward, &self.windward, [direction, 0.0]),
+            (Surface::Leeward, &self.leeward, [-direction, 0.0]),
+            (Surface::Roof, &self.roof, [0.0, -1.0]),
+        ];
+        let mut loads = vec![];
+        for (surface, members, inward) in surfaces {
+            for &member in members {
+                let w = pressure(surface, member) * self.spacing;
+                loads.push(Load::Member {
+                    member,
+                    load: inward.map(|d| d * w),
+                });
+            }
+        }
+        loads
+    }
+}
+
+/// Dimensions of an [`Envelope`] along the wind.
+struct Extent {
+    /// Distance from the windward wall to the leeward one.
+    depth: Float,
+    /// Height from the lowest node of the walls to the highest of any surface.
+    height: Float,
+    /// y of the lowest node of the walls.
+    base: Float,
+    /// x of the windward wall.
+    windward: Float,
+    /// Sign of the direction of the wind along x.
+    direction: Float,
+}
@@ -0,0 +1,268 @@
+//! Minimal [`tracing`] subscriber writing a line per event and per closed span with its duration,
+//! indented by nesting, to the console in wasm and to standard error natively.
+//!
+//! The usual subscribers time spans with [`std::time::Instant`], which panics in wasm, so this
+//! one reads [`crate::clock`] instead.
+
+use crate::clock::now;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Write as _},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Metadata, Subscriber,
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn console_log(line: &str);
+}
+
+thread_local! {
+    /// Spans entered on this thread, innermost last.
+    static STACK: RefCell<Vec<u64>> = const { RefCell::new(vec![]) };
+}
+
+/// Fields formatted as ` name=value`, with the message of an event first and bare.
+#[derive(Default)]
+struct Fields {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+struct Span {
+    name: &'static str,
+    fields: String,
+    start: f64,
+    depth: usize,
+    /// Handles of the span, closed when the last is dropped.
+    references: usize,
+}
+
+/// Subscriber of the events and spans at `level` and more severe, passing each line to `write`.
+pub struct Logger<W> {
+    level: Level,
+    write: W,
+    next: AtomicU64,
+    spans: Mutex<HashMap<u64, Span>>,
+}
+
+impl<W: Fn(&str) + Send + Sync + 'static> Logger<W> {
+    pub fn new(level: Level, write: W) -> Self {
+        Self {
+            level,
+            write,
+            next: AtomicU64::new(1),
+            spans: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Depth of the children of the span `parent`.
+    fn depth(&self, parent: Option<u64>) -> usize {
+        parent
+            .and_then(|id| self.spans.lock().unwrap().get(&id).map(|s| s.depth + 1))
+            .unwrap_or(0)
+    }
+}
+
+/// Logger to the console in wasm and to standard error natively.
+pub fn console(level: Level) -> Logger<fn(&str)> {
+    let write: fn(&str) = if cfg!(target_arch = "wasm32") {
+        |line| console_log(line)
+    } else {
+        |line| eprintln!("{line}")
+    };
+    Logger::new(level, write)
+}
+
+impl<W: Fn(&str) + Send + Sync + 'static> Subscriber for Logger<W> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        *metadata.level() <= self.level
+    }
+
+    fn new_span(&self, attributes: &Attributes) -> Id {
+        let parent = match attributes.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attributes.is_contextual() => STACK.with(|s| s.borrow().last().copied()),
+            None => None,
+        };
+        let mut fields = Fields::default();
+        attributes.record(&mut fields);
+        let span = Span {
+            name: attributes.metadata().name(),
+            fields: fields.fields,
+            start: now(),
+            depth: self.depth(parent),
+            references: 1,
+        };
+        let id = self.next.fetch_add(1, Ordering::Relaxed);
+        self.spans.lock().unwrap().insert(id, span);
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record) {
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            let mut fields = Fields::default();
+            values.record(&mut fields);
+            span.fields.push_str(&fields.fields);
+        }
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let depth = self.depth(STACK.with(|s| s.borrow().last().copied()));
+        (self.write)(&format!(
+            "{:width$}{} {}{}",
+            "",
+            event.metadata().level(),
+            fields.message,
+            fields.fields,
+            width = 2 * depth
+        ));
+    }
+
+    fn enter(&self, span: &Id) {
+        STACK.with(|s| s.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        STACK.with(|s| {
+            let mut stack = s.borrow_mut();
+            if let Some(i) = stack.iter().rposition(|&id| id == span.into_u64()) {
+                stack.remove(i);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            span.references += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let mut spans = self.spans.lock().unwrap();
+        let Some(span) = spans.get_mut(&id.into_u64()) else {
+            return false;
+        };
+        span.references -= 1;
+        if span.references > 0 {
+            return false;
+        }
+        let span = spans.remove(&id.into_u64()).unwrap();
+        drop(spans);
+        (self.write)(&format!(
+            "{:width$}{}{} {:.3}ms",
+            "",
+            span.name,
+            span.fields,
+            now() - span.start,
+            width = 2 * span.depth
+        ));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::frame::{Load, Member, Model, Support};
+    use std::sync::Arc;
+
+    /// Lines logged while running `f`.
+    fn capture(level: Level, f: impl FnOnce()) -> Vec<String> {
+        let lines = Arc::new(Mutex::new(vec![]));
+        let sink = lines.clone();
+        let logger = Logger::new(level, move |line: &str| {
+            sink.lock().unwrap().push(line.to_owned())
+        });
+        tracing::subscriber::with_default(logger, f);
+        let lines = lines.lock().unwrap().clone();
+        lines
+    }
+
+    #[test]
+    fn spans() {
+        let lines = capture(Level::DEBUG, || {
+            let outer = tracing::info_span!("outer", n = 2);
+            let _entered = outer.enter();
+            tracing::info!(x = 1.5, "inside");
+            let inner = tracing::debug_span!("inner");
+            let clone = inner.clone();
+            drop(inner);
+            tracing::trace!("ignored");
+            drop(clone);
+        });
+        assert_eq!(lines.len(), 3, "{lines:?}");
+        assert_eq!(lines[0], "  INFO inside x=1.5");
+        assert!(lines[1].starts_with("  inner ") && lines[1].ends_with("ms"));
+        assert!(lines[2].starts_with("outer n=2 "));
+    }
+
+    #[test]
+    fn analysis() {
+        let mut model = Model::new();
+        let a = model.add_node([0.0, 0.0]);
+        let b = model.add_node([2.0, 0.0]);
+        model.add_member(Member {
+            nodes: [a, b],
+            elasticity: 1.0,
+            area: 1.0,
+            inertia: 1.0,
+        });
+        model.add_support(Support {
+            node: a,
+            fixed: [true; 3],
+        });
+        model.add_load(Load::Node {
+            node: b,
+            force: [0.0, -1.0, 0.0],
+        });
+        let lines = capture(Level::DEBUG, || {
+            model.solve().unwrap();
+        });
+        let names: Vec<_> = lines
+            .iter()
+            .map(|line| line.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "assembly",
+                "DEBUG",
+                "factorize",
+                "factorization",
+                "recovery",
+                "analysis"
+            ],
+            "{lines:?}"
+        );
+        assert!(lines[0].starts_with("  assembly "));
+        assert!(lines[1].starts_with("      DEBUG factorized condition="));
+        assert!(lines[2].starts_with("    factorize n=3 storage=Skyline "));
+        assert!(lines[5].starts_with("analysis nodes=2 members=1 "));
+    }
+}